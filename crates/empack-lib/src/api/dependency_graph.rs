@@ -8,7 +8,7 @@ use crate::application::session::{FileSystemProvider, LiveFileSystemProvider};
 use petgraph::algo::{is_cyclic_directed, toposort};
 use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::visit::EdgeRef;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 use toml::Value;
@@ -88,6 +88,23 @@ impl DependencyNode {
     }
 }
 
+/// Chain of dependents leading from a mod up to a mod nothing depends on.
+///
+/// Each entry is a dependent together with the edge type linking it to the
+/// previous mod in the chain.
+pub type DependentChain = Vec<(DependencyNode, DependencyType)>;
+
+/// Forward dependency tree rooted at one mod
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyTreeNode {
+    pub node: DependencyNode,
+    /// Edge type from the parent; `None` for the root
+    pub dep_type: Option<DependencyType>,
+    /// True when this subtree was already expanded elsewhere, or would cycle
+    pub repeated: bool,
+    pub children: Vec<DependencyTreeNode>,
+}
+
 /// Dependency graph for transitive dependency resolution
 pub struct DependencyGraph {
     /// Directed graph: nodes = mods, edges = dependencies
@@ -106,9 +123,15 @@ impl DependencyGraph {
     }
 
     /// Add a project to the graph (idempotent - won't duplicate if already exists)
+    ///
+    /// A placeholder created from another mod's `[deps]` entry is replaced once
+    /// the project's own `.pw.toml` is parsed.
     pub fn add_node(&mut self, node: DependencyNode) -> NodeIndex {
         if let Some(&idx) = self.node_map.get(&node.mod_id) {
             trace!("Node already exists: {}", node.mod_id);
+            if self.graph[idx].source_path.is_none() && node.source_path.is_some() {
+                self.graph[idx] = node;
+            }
             return idx;
         }
 
@@ -290,6 +313,190 @@ impl DependencyGraph {
         Some(dependents)
     }
 
+    /// Get all direct dependents of a mod with the type of each edge
+    pub fn get_dependents_with_type(
+        &self,
+        mod_id: &str,
+    ) -> Option<Vec<(DependencyNode, DependencyType)>> {
+        let node_idx = self.node_map.get(mod_id)?;
+
+        let dependents = self
+            .graph
+            .edges_directed(*node_idx, petgraph::Direction::Outgoing)
+            .map(|edge| (self.graph[edge.target()].clone(), *edge.weight()))
+            .collect();
+
+        Some(dependents)
+    }
+
+    /// Get every chain of dependents that pulls a mod in
+    ///
+    /// Chains end at a mod nothing depends on. A mod with no dependents yields
+    /// no chains. Cyclic edges are skipped so every chain is finite.
+    pub fn dependent_chains(&self, mod_id: &str) -> Option<Vec<DependentChain>> {
+        let node_idx = *self.node_map.get(mod_id)?;
+        let mut chains = Vec::new();
+        let mut path = Vec::new();
+        let mut on_path = vec![node_idx];
+
+        self.collect_dependent_chains(node_idx, &mut path, &mut on_path, &mut chains);
+        Some(chains)
+    }
+
+    fn collect_dependent_chains(
+        &self,
+        node: NodeIndex,
+        path: &mut Vec<(NodeIndex, DependencyType)>,
+        on_path: &mut Vec<NodeIndex>,
+        chains: &mut Vec<DependentChain>,
+    ) {
+        let mut extended = false;
+        for edge in self
+            .graph
+            .edges_directed(node, petgraph::Direction::Outgoing)
+        {
+            let dependent = edge.target();
+            if on_path.contains(&dependent) {
+                continue;
+            }
+            extended = true;
+            path.push((dependent, *edge.weight()));
+            on_path.push(dependent);
+            self.collect_dependent_chains(dependent, path, on_path, chains);
+            on_path.pop();
+            path.pop();
+        }
+
+        if !extended && !path.is_empty() {
+            chains.push(
+                path.iter()
+                    .map(|(idx, dep_type)| (self.graph[*idx].clone(), *dep_type))
+                    .collect(),
+            );
+        }
+    }
+
+    /// Get mods that nothing else depends on
+    pub fn root_nodes(&self) -> Vec<DependencyNode> {
+        self.graph
+            .node_indices()
+            .filter(|idx| {
+                self.graph
+                    .edges_directed(*idx, petgraph::Direction::Outgoing)
+                    .next()
+                    .is_none()
+            })
+            .map(|idx| self.graph[idx].clone())
+            .collect()
+    }
+
+    /// Build the forward dependency tree of a mod
+    ///
+    /// Each subtree is expanded once; later occurrences and cyclic edges are
+    /// marked `repeated` with no children.
+    pub fn dependency_tree(&self, mod_id: &str) -> Option<DependencyTreeNode> {
+        let node_idx = *self.node_map.get(mod_id)?;
+        let mut expanded = HashMap::new();
+        let mut ancestors = Vec::new();
+        Some(self.build_tree(node_idx, None, &mut expanded, &mut ancestors))
+    }
+
+    fn build_tree(
+        &self,
+        node: NodeIndex,
+        dep_type: Option<DependencyType>,
+        expanded: &mut HashMap<NodeIndex, bool>,
+        ancestors: &mut Vec<NodeIndex>,
+    ) -> DependencyTreeNode {
+        let has_dependencies = self
+            .graph
+            .edges_directed(node, petgraph::Direction::Incoming)
+            .next()
+            .is_some();
+        if has_dependencies && (ancestors.contains(&node) || expanded.contains_key(&node)) {
+            return DependencyTreeNode {
+                node: self.graph[node].clone(),
+                dep_type,
+                repeated: true,
+                children: Vec::new(),
+            };
+        }
+        expanded.insert(node, true);
+
+        let mut edges: Vec<_> = self
+            .graph
+            .edges_directed(node, petgraph::Direction::Incoming)
+            .map(|edge| (edge.source(), *edge.weight()))
+            .collect();
+        edges.sort_by(|a, b| self.graph[a.0].mod_id.cmp(&self.graph[b.0].mod_id));
+
+        ancestors.push(node);
+        let children = edges
+            .into_iter()
+            .map(|(dependency, edge_type)| {
+                self.build_tree(dependency, Some(edge_type), expanded, ancestors)
+            })
+            .collect();
+        ancestors.pop();
+
+        DependencyTreeNode {
+            node: self.graph[node].clone(),
+            dep_type,
+            repeated: false,
+            children,
+        }
+    }
+
+    /// Find installed dependencies left without dependents once `removed` are gone
+    ///
+    /// Walks outward from the removed mods: a dependency is orphaned when every
+    /// one of its dependents is being removed and `keep` does not protect it.
+    /// Placeholder nodes for mods that are not installed are never returned.
+    pub fn orphaned_by_removal(
+        &self,
+        removed: &[&str],
+        keep: impl Fn(&DependencyNode) -> bool,
+    ) -> Vec<DependencyNode> {
+        let mut gone: HashSet<NodeIndex> = removed
+            .iter()
+            .filter_map(|mod_id| self.node_map.get(*mod_id).copied())
+            .collect();
+        let mut queue: Vec<NodeIndex> = gone.iter().copied().collect();
+        let mut orphans = Vec::new();
+
+        while let Some(idx) = queue.pop() {
+            let dependencies: Vec<NodeIndex> = self
+                .graph
+                .edges_directed(idx, petgraph::Direction::Incoming)
+                .map(|edge| edge.source())
+                .collect();
+
+            for dependency in dependencies {
+                if gone.contains(&dependency) {
+                    continue;
+                }
+                let node = &self.graph[dependency];
+                if node.source_path.is_none() || keep(node) {
+                    continue;
+                }
+                let still_needed = self
+                    .graph
+                    .edges_directed(dependency, petgraph::Direction::Outgoing)
+                    .any(|edge| !gone.contains(&edge.target()));
+                if still_needed {
+                    continue;
+                }
+
+                gone.insert(dependency);
+                orphans.push(node.clone());
+                queue.push(dependency);
+            }
+        }
+
+        orphans.sort_by(|a, b| a.mod_id.cmp(&b.mod_id));
+        orphans
+    }
+
     /// Parse a packwiz `.pw.toml` file and extract dependencies using the live filesystem.
     /// Prefer `parse_packwiz_file_with` when a `FileSystemProvider` is available.
     pub fn parse_packwiz_file(
@@ -816,3 +816,221 @@ file-id = 5678901
     assert_eq!(node.platform, "curseforge");
     assert_eq!(node.version, Some("5678901".to_string()));
 }
+
+// ============================================================================
+// Dependent Chains and Trees
+// ============================================================================
+
+fn node(mod_id: &str) -> DependencyNode {
+    DependencyNode::new(
+        mod_id.to_string(),
+        mod_id.to_string(),
+        "modrinth".to_string(),
+        None,
+    )
+}
+
+/// lib <- api (required) <- mod-a (required)
+/// lib <- mod-b (optional)
+fn chain_graph() -> DependencyGraph {
+    let mut graph = DependencyGraph::new();
+    for id in ["lib", "api", "mod-a", "mod-b"] {
+        graph.add_node(node(id));
+    }
+    graph
+        .add_dependency("api", "lib", DependencyType::Required)
+        .unwrap();
+    graph
+        .add_dependency("mod-a", "api", DependencyType::Required)
+        .unwrap();
+    graph
+        .add_dependency("mod-b", "lib", DependencyType::Optional)
+        .unwrap();
+    graph
+}
+
+fn chain_ids(chain: &DependentChain) -> Vec<(&str, DependencyType)> {
+    chain
+        .iter()
+        .map(|(node, dep_type)| (node.mod_id.as_str(), *dep_type))
+        .collect()
+}
+
+#[test]
+fn test_dependent_chains_follow_each_path_to_a_root() {
+    let graph = chain_graph();
+
+    let mut chains = graph.dependent_chains("lib").unwrap();
+    chains.sort_by_key(|chain| chain.len());
+
+    assert_eq!(chains.len(), 2);
+    assert_eq!(
+        chain_ids(&chains[0]),
+        vec![("mod-b", DependencyType::Optional)]
+    );
+    assert_eq!(
+        chain_ids(&chains[1]),
+        vec![
+            ("api", DependencyType::Required),
+            ("mod-a", DependencyType::Required)
+        ]
+    );
+}
+
+#[test]
+fn test_dependent_chains_empty_for_root_and_none_for_unknown() {
+    let graph = chain_graph();
+    assert!(graph.dependent_chains("mod-a").unwrap().is_empty());
+    assert!(graph.dependent_chains("missing").is_none());
+}
+
+#[test]
+fn test_dependent_chains_terminate_on_cycles() {
+    let mut graph = DependencyGraph::new();
+    graph.add_node(node("a"));
+    graph.add_node(node("b"));
+    graph
+        .add_dependency("a", "b", DependencyType::Required)
+        .unwrap();
+    graph
+        .add_dependency("b", "a", DependencyType::Required)
+        .unwrap();
+
+    let chains = graph.dependent_chains("a").unwrap();
+    assert_eq!(chains.len(), 1);
+    assert_eq!(chain_ids(&chains[0]), vec![("b", DependencyType::Required)]);
+}
+
+#[test]
+fn test_get_dependents_with_type_reports_edge_types() {
+    let graph = chain_graph();
+    let mut dependents = graph.get_dependents_with_type("lib").unwrap();
+    dependents.sort_by(|a, b| a.0.mod_id.cmp(&b.0.mod_id));
+
+    assert_eq!(dependents.len(), 2);
+    assert_eq!(dependents[0].0.mod_id, "api");
+    assert_eq!(dependents[0].1, DependencyType::Required);
+    assert_eq!(dependents[1].0.mod_id, "mod-b");
+    assert_eq!(dependents[1].1, DependencyType::Optional);
+}
+
+#[test]
+fn test_root_nodes_are_mods_without_dependents() {
+    let graph = chain_graph();
+    let mut roots: Vec<String> = graph.root_nodes().into_iter().map(|n| n.mod_id).collect();
+    roots.sort();
+    assert_eq!(roots, vec!["mod-a".to_string(), "mod-b".to_string()]);
+}
+
+#[test]
+fn test_dependency_tree_marks_repeated_subtrees() {
+    let mut graph = chain_graph();
+    graph.add_node(node("pack"));
+    graph
+        .add_dependency("pack", "api", DependencyType::Required)
+        .unwrap();
+    graph
+        .add_dependency("pack", "mod-a", DependencyType::Required)
+        .unwrap();
+
+    let tree = graph.dependency_tree("pack").unwrap();
+    assert_eq!(tree.dep_type, None);
+    let children: Vec<&str> = tree
+        .children
+        .iter()
+        .map(|c| c.node.mod_id.as_str())
+        .collect();
+    assert_eq!(children, vec!["api", "mod-a"]);
+
+    let api = &tree.children[0];
+    assert!(!api.repeated);
+    assert_eq!(api.children[0].node.mod_id, "lib");
+
+    let mod_a = &tree.children[1];
+    assert_eq!(mod_a.children[0].node.mod_id, "api");
+    assert!(mod_a.children[0].repeated);
+    assert!(mod_a.children[0].children.is_empty());
+}
+
+#[test]
+fn test_placeholder_node_is_replaced_by_parsed_metafile() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut graph = DependencyGraph::new();
+    let fs = LiveFileSystemProvider;
+
+    let dependent = create_mod_with_deps(
+        temp_dir.path(),
+        "mod-menu",
+        "mOgUt4GM",
+        &[("P7dR8mSH", false)],
+    );
+    let library = create_simple_mod(temp_dir.path(), "fabric-api", "P7dR8mSH");
+    graph.parse_packwiz_file_with(&dependent, &fs).unwrap();
+    graph.parse_packwiz_file_with(&library, &fs).unwrap();
+
+    let node = graph.get_node("P7dR8mSH").unwrap();
+    assert_eq!(node.name, "fabric-api");
+    assert_eq!(node.source_path.as_deref(), Some(library.as_path()));
+    assert_eq!(graph.edge_count(), 1);
+}
+
+#[test]
+fn test_orphaned_by_removal_follows_transitive_libraries() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut graph = DependencyGraph::new();
+    let fs = LiveFileSystemProvider;
+
+    // target -> api -> lib, other -> shared, target -> shared
+    for path in [
+        create_mod_with_deps(
+            temp_dir.path(),
+            "target",
+            "target",
+            &[("api", false), ("shared", false)],
+        ),
+        create_mod_with_deps(temp_dir.path(), "api", "api", &[("lib", false)]),
+        create_simple_mod(temp_dir.path(), "lib", "lib"),
+        create_mod_with_deps(temp_dir.path(), "other", "other", &[("shared", false)]),
+        create_simple_mod(temp_dir.path(), "shared", "shared"),
+    ] {
+        graph.parse_packwiz_file_with(&path, &fs).unwrap();
+    }
+
+    let orphans: Vec<String> = graph
+        .orphaned_by_removal(&["target"], |_| false)
+        .into_iter()
+        .map(|node| node.mod_id)
+        .collect();
+    assert_eq!(orphans, vec!["api", "lib"]);
+}
+
+#[test]
+fn test_orphaned_by_removal_respects_keep_and_placeholders() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut graph = DependencyGraph::new();
+    let fs = LiveFileSystemProvider;
+
+    for path in [
+        create_mod_with_deps(
+            temp_dir.path(),
+            "target",
+            "target",
+            &[("api", false), ("missing", false)],
+        ),
+        create_mod_with_deps(temp_dir.path(), "api", "api", &[("lib", false)]),
+        create_simple_mod(temp_dir.path(), "lib", "lib"),
+    ] {
+        graph.parse_packwiz_file_with(&path, &fs).unwrap();
+    }
+
+    // Keeping api also keeps everything only api needs
+    let orphans = graph.orphaned_by_removal(&["target"], |node| node.mod_id == "api");
+    assert!(orphans.is_empty());
+
+    let orphans: Vec<String> = graph
+        .orphaned_by_removal(&["target"], |_| false)
+        .into_iter()
+        .map(|node| node.mod_id)
+        .collect();
+    assert_eq!(orphans, vec!["api", "lib"]);
+}
@@ -8,4 +8,6 @@
 
 pub mod dependency_graph;
 
-pub use dependency_graph::{DependencyGraph, DependencyGraphError, DependencyNode};
+pub use dependency_graph::{
+    DependencyGraph, DependencyGraphError, DependencyNode, DependencyTreeNode, DependentChain,
+};
@@ -167,7 +167,7 @@ pub struct InitArgs {
 pub struct BuildArgs {
     /// Build targets to execute
     #[arg(
-        help = "Build targets: mrpack, client, server, client-full, server-full, all",
+        help = "Build targets: mrpack, client, server, client-full, server-full, curseforge, prism, all",
         conflicts_with = "continue_build"
     )]
     pub targets: Vec<String>,
@@ -184,6 +184,10 @@ pub struct BuildArgs {
     #[arg(short, long, help = "Clean build directories before building")]
     pub clean: bool,
 
+    /// Rebuild targets whose inputs are unchanged
+    #[arg(short, long, help = "Rebuild targets even when they are up to date")]
+    pub force: bool,
+
     /// Archive format for distribution packages
     #[arg(
         long,
@@ -196,6 +200,76 @@ pub struct BuildArgs {
     /// Directory to scan for manually downloaded restricted mods
     #[arg(long, env = "EMPACK_DOWNLOADS_DIR")]
     pub downloads_dir: Option<String>,
+
+    /// Timestamp stored in archive entries, in seconds since the Unix epoch
+    #[arg(
+        long,
+        env = "SOURCE_DATE_EPOCH",
+        value_name = "SECONDS",
+        help = "Timestamp for archive entries (default: last commit time)"
+    )]
+    pub source_date_epoch: Option<u64>,
+}
+
+/// Arguments for the `search` subcommand.
+#[derive(Args, Debug, Clone)]
+pub struct SearchArgs {
+    /// Search terms
+    #[arg(required = true, help = "Project name or search terms")]
+    pub query: Vec<String>,
+
+    /// Platforms to search
+    #[arg(long, value_enum, default_value = "both")]
+    pub platform: SearchPlatform,
+
+    /// Project type to search for
+    #[arg(long = "type", value_enum, default_value = "mod")]
+    pub project_type: CliProjectType,
+
+    /// Only show projects in this category
+    #[arg(long, help = "Category slug, e.g. optimization or utility")]
+    pub category: Option<String>,
+
+    /// Maximum number of results to show
+    #[arg(long, default_value_t = 10)]
+    pub limit: usize,
+
+    /// Pick a result and add it to the pack
+    #[arg(long, help = "Select a result and add it to the pack")]
+    pub add: bool,
+}
+
+/// Arguments for the `migrate` subcommand.
+#[derive(Args, Debug, Clone)]
+pub struct MigrateArgs {
+    /// Target Minecraft version
+    #[arg(long, value_name = "VERSION", help = "Minecraft version to migrate to")]
+    pub mc_version: String,
+
+    /// Target mod loader (defaults to the current loader)
+    #[arg(
+        long,
+        help = "Mod loader to migrate to (default: keep the current loader)"
+    )]
+    pub loader: Option<String>,
+
+    /// Target loader version (defaults to the newest for the target)
+    #[arg(
+        long,
+        value_name = "VERSION",
+        help = "Loader version to use with --apply (default: newest available)"
+    )]
+    pub loader_version: Option<String>,
+
+    /// Rewrite the project for the target instead of only reporting
+    #[arg(long, help = "Rewrite empack.yml, pack.toml, and .pw.toml files")]
+    pub apply: bool,
+}
+
+impl SearchArgs {
+    pub fn query_text(&self) -> String {
+        self.query.join(" ")
+    }
 }
 
 impl Default for BuildArgs {
@@ -204,8 +278,10 @@ impl Default for BuildArgs {
             targets: Vec::new(),
             continue_build: false,
             clean: false,
+            force: false,
             format: CliArchiveFormat::Zip,
             downloads_dir: None,
+            source_date_epoch: None,
         }
     }
 }
@@ -223,7 +299,11 @@ pub enum Commands {
     Init(InitArgs),
 
     /// Synchronize empack.yml dependencies with pack.toml reality
-    Sync {},
+    Sync {
+        /// Install exactly the files recorded in empack.lock
+        #[arg(long)]
+        locked: bool,
+    },
 
     /// Build modpack targets
     Build(BuildArgs),
@@ -281,6 +361,106 @@ pub enum Commands {
         #[arg(help = "What to clean: builds, cache, all")]
         targets: Vec<String>,
     },
+
+    /// List dependencies declared in empack.yml
+    #[command(alias = "ls")]
+    List {
+        /// Only show dependencies of this project type
+        #[arg(long = "type", value_enum)]
+        project_type: Option<CliProjectType>,
+
+        /// Only show dependencies hosted on this platform
+        #[arg(long, value_enum)]
+        platform: Option<CliProjectPlatform>,
+
+        /// Sort order for the listing
+        #[arg(long, value_enum, default_value = "slug")]
+        sort: ListSort,
+    },
+
+    /// Show resolved dependencies with newer compatible files
+    Outdated {},
+
+    /// Update resolved dependencies to their newest compatible files
+    Update {
+        /// Dependency keys to update (all resolved dependencies when empty)
+        #[arg(help = "Dependency keys to update (default: all)")]
+        mods: Vec<String>,
+
+        /// Update entries with a pinned version as well
+        #[arg(
+            short,
+            long,
+            help = "Also update dependencies pinned to a specific version"
+        )]
+        force: bool,
+    },
+
+    /// Show every chain of dependents that pulls in a dependency
+    Why {
+        /// Dependency slug, project ID, or name
+        #[arg(help = "Dependency slug, project ID, or name")]
+        slug: String,
+    },
+
+    /// Show the forward dependency tree
+    Tree {
+        /// Only show the tree below this dependency
+        #[arg(help = "Dependency slug, project ID, or name (default: whole pack)")]
+        slug: Option<String>,
+    },
+
+    /// Search Modrinth and CurseForge for projects
+    Search(SearchArgs),
+
+    /// Check or move the pack to another Minecraft version or loader
+    Migrate(MigrateArgs),
+
+    /// List recorded changes to the project
+    History,
+
+    /// Restore the project to its state before a journal entry
+    Undo {
+        /// Journal entry to undo (the most recent when omitted)
+        #[arg(help = "Journal entry to undo (default: the most recent)")]
+        entry: Option<u32>,
+
+        /// Undo even if files changed since the entry was recorded
+        #[arg(
+            short,
+            long,
+            help = "Undo even if project files changed since the entry was recorded"
+        )]
+        force: bool,
+    },
+
+    /// Check an artifact's signature and its SHA256SUMS entry
+    Verify {
+        /// Artifact to check
+        #[arg(help = "Artifact to check, with <artifact>.minisig and SHA256SUMS beside it")]
+        artifact: String,
+
+        /// Minisign public key the artifact was signed with
+        #[arg(
+            long,
+            value_name = "KEY",
+            help = "Minisign public key file or base64 key"
+        )]
+        pubkey: String,
+    },
+
+    /// Manage the shared download cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheCommand,
+    },
+}
+
+/// Actions of the `cache` command
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum CacheCommand {
+    /// Evict content store entries no known project references
+    Gc,
 }
 
 /// Search platform preference for project resolution
@@ -339,6 +519,32 @@ impl CliProjectType {
     }
 }
 
+/// Project platform filter for the list command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum CliProjectPlatform {
+    Modrinth,
+    Curseforge,
+}
+
+impl CliProjectPlatform {
+    pub fn to_project_platform(&self) -> crate::primitives::ProjectPlatform {
+        match self {
+            CliProjectPlatform::Modrinth => crate::primitives::ProjectPlatform::Modrinth,
+            CliProjectPlatform::Curseforge => crate::primitives::ProjectPlatform::CurseForge,
+        }
+    }
+}
+
+/// Sort order for the list command.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ListSort {
+    /// Sort by dependency key (the .pw.toml filename stem)
+    #[default]
+    Slug,
+    /// Sort by display title
+    Title,
+}
+
 impl std::str::FromStr for SearchPlatform {
     type Err = String;
 
@@ -353,6 +559,56 @@ impl std::str::FromStr for SearchPlatform {
 }
 
 impl Commands {
+    /// Subcommand name as typed on the command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            Commands::Requirements => "requirements",
+            Commands::Version => "version",
+            Commands::Init(..) => "init",
+            Commands::Sync { .. } => "sync",
+            Commands::Build(..) => "build",
+            Commands::Add { .. } => "add",
+            Commands::Remove { .. } => "remove",
+            Commands::Clean { .. } => "clean",
+            Commands::List { .. } => "list",
+            Commands::Outdated { .. } => "outdated",
+            Commands::Update { .. } => "update",
+            Commands::Why { .. } => "why",
+            Commands::Tree { .. } => "tree",
+            Commands::Search(..) => "search",
+            Commands::Migrate(..) => "migrate",
+            Commands::History => "history",
+            Commands::Undo { .. } => "undo",
+            Commands::Verify { .. } => "verify",
+            Commands::Cache { .. } => "cache",
+        }
+    }
+
+    /// Check if command writes project files and must hold the project lock
+    pub fn mutates_project(&self) -> bool {
+        match self {
+            Commands::Sync { .. }
+            | Commands::Build(..)
+            | Commands::Add { .. }
+            | Commands::Remove { .. }
+            | Commands::Clean { .. }
+            | Commands::Update { .. }
+            | Commands::Undo { .. } => true,
+            Commands::Migrate(args) => args.apply,
+            Commands::Search(args) => args.add,
+            Commands::Requirements
+            | Commands::Version
+            | Commands::Init(..)
+            | Commands::List { .. }
+            | Commands::Outdated { .. }
+            | Commands::Why { .. }
+            | Commands::Tree { .. }
+            | Commands::History
+            | Commands::Verify { .. }
+            | Commands::Cache { .. } => false,
+        }
+    }
+
     /// Check if command requires an initialized modpack directory
     pub fn requires_modpack(&self) -> bool {
         match self {
@@ -364,6 +620,17 @@ impl Commands {
             Commands::Add { .. } => true,
             Commands::Remove { .. } => true,
             Commands::Clean { .. } => true,
+            Commands::List { .. } => true,
+            Commands::Outdated { .. } => true,
+            Commands::Update { .. } => true,
+            Commands::Why { .. } => true,
+            Commands::Tree { .. } => true,
+            Commands::Search(..) => false,
+            Commands::Migrate(..) => true,
+            Commands::History => true,
+            Commands::Undo { .. } => true,
+            Commands::Verify { .. } => false,
+            Commands::Cache { .. } => false,
         }
     }
 
@@ -374,9 +641,20 @@ impl Commands {
             Commands::Version => 0,
             Commands::Init(..) => 1,
             Commands::Clean { .. } => 2,
+            Commands::List { .. } => 3,
+            Commands::Outdated { .. } => 3,
+            Commands::Why { .. } => 3,
+            Commands::Tree { .. } => 3,
+            Commands::Search(..) => 3,
+            Commands::History => 3,
+            Commands::Verify { .. } => 3,
+            Commands::Cache { .. } => 3,
             Commands::Sync { .. } => 5,
             Commands::Add { .. } => 6,
             Commands::Remove { .. } => 7,
+            Commands::Update { .. } => 8,
+            Commands::Undo { .. } => 8,
+            Commands::Migrate(..) => 9,
             Commands::Build(..) => 10,
         }
     }
@@ -444,13 +722,13 @@ mod tests {
     fn commands_surface_metadata_matches_expected_values() {
         assert!(!Commands::Requirements.requires_modpack());
         assert!(!Commands::Version.requires_modpack());
-        assert!(Commands::Sync {}.requires_modpack());
+        assert!(Commands::Sync { locked: false }.requires_modpack());
         assert!(Commands::Build(BuildArgs::default()).requires_modpack());
         assert_eq!(Commands::Requirements.execution_order(), 0);
         assert_eq!(Commands::Version.execution_order(), 0);
         assert_eq!(Commands::Init(InitArgs::default()).execution_order(), 1);
         assert_eq!(Commands::Clean { targets: vec![] }.execution_order(), 2);
-        assert_eq!(Commands::Sync {}.execution_order(), 5);
+        assert_eq!(Commands::Sync { locked: false }.execution_order(), 5);
         assert_eq!(
             Commands::Add {
                 mods: vec![],
@@ -472,6 +750,40 @@ mod tests {
             7
         );
         assert_eq!(Commands::Build(BuildArgs::default()).execution_order(), 10);
+        let list = Commands::List {
+            project_type: None,
+            platform: None,
+            sort: ListSort::Slug,
+        };
+        assert!(list.requires_modpack());
+        assert_eq!(list.execution_order(), 3);
+        assert!(Commands::Outdated {}.requires_modpack());
+        assert_eq!(Commands::Outdated {}.execution_order(), 3);
+        let update = Commands::Update {
+            mods: vec![],
+            force: false,
+        };
+        assert!(update.requires_modpack());
+        assert_eq!(update.execution_order(), 8);
+        let why = Commands::Why {
+            slug: "sodium".to_string(),
+        };
+        assert!(why.requires_modpack());
+        assert_eq!(why.execution_order(), 3);
+        assert!(Commands::Tree { slug: None }.requires_modpack());
+        assert_eq!(Commands::Tree { slug: None }.execution_order(), 3);
+    }
+
+    #[test]
+    fn cli_project_platform_to_project_platform_maps_variants() {
+        assert_eq!(
+            CliProjectPlatform::Modrinth.to_project_platform(),
+            crate::primitives::ProjectPlatform::Modrinth
+        );
+        assert_eq!(
+            CliProjectPlatform::Curseforge.to_project_platform(),
+            crate::primitives::ProjectPlatform::CurseForge
+        );
     }
 
     #[test]
@@ -683,4 +995,206 @@ mod tests {
         assert_eq!(args.targets, vec!["client-full"]);
         assert_eq!(args.downloads_dir.as_deref(), Some("/tmp/from-env"));
     }
+
+    #[test]
+    fn cli_config_load_from_parses_list_filters_and_alias() {
+        let config = CliConfig::load_from([
+            "empack",
+            "ls",
+            "--type",
+            "resourcepack",
+            "--platform",
+            "curseforge",
+            "--sort",
+            "title",
+        ])
+        .expect("parse list");
+
+        let Some(Commands::List {
+            project_type,
+            platform,
+            sort,
+        }) = config.command
+        else {
+            panic!("expected list command");
+        };
+
+        assert_eq!(project_type, Some(CliProjectType::ResourcePack));
+        assert_eq!(platform, Some(CliProjectPlatform::Curseforge));
+        assert_eq!(sort, ListSort::Title);
+    }
+
+    #[test]
+    fn cli_config_load_from_defaults_list_sort_to_slug() {
+        let config = CliConfig::load_from(["empack", "list"]).expect("parse list");
+
+        let Some(Commands::List {
+            project_type,
+            platform,
+            sort,
+        }) = config.command
+        else {
+            panic!("expected list command");
+        };
+
+        assert_eq!(project_type, None);
+        assert_eq!(platform, None);
+        assert_eq!(sort, ListSort::Slug);
+    }
+
+    #[test]
+    fn cli_config_load_from_parses_update_slugs_and_force() {
+        let config = CliConfig::load_from(["empack", "update", "sodium", "jei", "--force"])
+            .expect("parse update");
+
+        let Some(Commands::Update { mods, force }) = config.command else {
+            panic!("expected update command");
+        };
+
+        assert_eq!(mods, vec!["sodium", "jei"]);
+        assert!(force);
+    }
+
+    #[test]
+    fn cli_config_load_from_parses_why_and_tree() {
+        let config = CliConfig::load_from(["empack", "why", "sodium"]).expect("parse why");
+        let Some(Commands::Why { slug }) = config.command else {
+            panic!("expected why command");
+        };
+        assert_eq!(slug, "sodium");
+
+        let config = CliConfig::load_from(["empack", "tree"]).expect("parse tree");
+        let Some(Commands::Tree { slug }) = config.command else {
+            panic!("expected tree command");
+        };
+        assert_eq!(slug, None);
+    }
+
+    #[test]
+    fn cli_config_load_from_parses_history_and_undo() {
+        let config = CliConfig::load_from(["empack", "history"]).expect("parse history");
+        assert!(matches!(config.command, Some(Commands::History)));
+
+        let config = CliConfig::load_from(["empack", "undo", "3", "--force"]).expect("parse undo");
+        let Some(Commands::Undo { entry, force }) = config.command else {
+            panic!("expected undo command");
+        };
+        assert_eq!(entry, Some(3));
+        assert!(force);
+        assert_eq!(
+            Commands::Undo {
+                entry: None,
+                force: false
+            }
+            .execution_order(),
+            8
+        );
+    }
+
+    #[test]
+    fn cli_config_load_from_parses_verify() {
+        let config = CliConfig::load_from([
+            "empack",
+            "verify",
+            "dist/Pack-v1.0.0-server.zip",
+            "--pubkey",
+            "empack.pub",
+        ])
+        .expect("parse verify");
+        let Some(Commands::Verify { artifact, pubkey }) = config.command else {
+            panic!("expected verify command");
+        };
+        assert_eq!(artifact, "dist/Pack-v1.0.0-server.zip");
+        assert_eq!(pubkey, "empack.pub");
+
+        assert!(CliConfig::load_from(["empack", "verify", "Pack.zip"]).is_err());
+    }
+
+    #[test]
+    fn cli_config_load_from_parses_cache_gc() {
+        let config = CliConfig::load_from(["empack", "cache", "gc"]).expect("parse cache gc");
+        let Some(command) = config.command else {
+            panic!("expected cache command");
+        };
+        assert!(matches!(
+            command,
+            Commands::Cache {
+                action: CacheCommand::Gc
+            }
+        ));
+        assert!(!command.requires_modpack());
+        assert!(!command.mutates_project());
+
+        assert!(CliConfig::load_from(["empack", "cache"]).is_err());
+    }
+
+    #[test]
+    fn cli_config_load_from_parses_search_filters() {
+        let config = CliConfig::load_from([
+            "empack",
+            "search",
+            "just",
+            "enough",
+            "items",
+            "--platform",
+            "curseforge",
+            "--type",
+            "resourcepack",
+            "--category",
+            "utility",
+            "--limit",
+            "5",
+            "--add",
+        ])
+        .expect("parse search");
+        let Some(Commands::Search(args)) = config.command else {
+            panic!("expected search command");
+        };
+
+        assert_eq!(args.query_text(), "just enough items");
+        assert_eq!(args.platform, SearchPlatform::Curseforge);
+        assert_eq!(args.project_type, CliProjectType::ResourcePack);
+        assert_eq!(args.category.as_deref(), Some("utility"));
+        assert_eq!(args.limit, 5);
+        assert!(args.add);
+
+        let config = CliConfig::load_from(["empack", "search", "sodium"]).expect("parse search");
+        let Some(Commands::Search(args)) = config.command else {
+            panic!("expected search command");
+        };
+        assert_eq!(args.platform, SearchPlatform::Both);
+        assert_eq!(args.project_type, CliProjectType::Mod);
+        assert_eq!(args.limit, 10);
+        assert!(!args.add);
+        assert!(CliConfig::load_from(["empack", "search"]).is_err());
+    }
+
+    #[test]
+    fn cli_config_load_from_parses_migrate_target() {
+        let config = CliConfig::load_from([
+            "empack",
+            "migrate",
+            "--mc-version",
+            "1.21.1",
+            "--loader",
+            "neoforge",
+            "--loader-version",
+            "21.1.77",
+            "--apply",
+        ])
+        .expect("parse migrate");
+        let Some(Commands::Migrate(args)) = config.command else {
+            panic!("expected migrate command");
+        };
+
+        assert_eq!(args.mc_version, "1.21.1");
+        assert_eq!(args.loader.as_deref(), Some("neoforge"));
+        assert_eq!(args.loader_version.as_deref(), Some("21.1.77"));
+        assert!(args.apply);
+
+        let migrate = Commands::Migrate(args);
+        assert!(migrate.requires_modpack());
+        assert_eq!(migrate.execution_order(), 9);
+        assert!(CliConfig::load_from(["empack", "migrate"]).is_err());
+    }
 }
@@ -4,7 +4,10 @@
 //! Implements the Session-Scoped Dependency Injection Pattern.
 
 use crate::Result;
-use crate::application::cli::{BuildArgs, CliProjectType, InitArgs, SearchPlatform};
+use crate::application::cli::{
+    BuildArgs, CacheCommand, CliProjectPlatform, CliProjectType, InitArgs, ListSort, MigrateArgs,
+    SearchArgs, SearchPlatform,
+};
 use crate::application::session::{
     CommandSession, FileSystemProvider, Session, execute_process_with_live_issues,
 };
@@ -12,18 +15,26 @@ use crate::application::sync::{
     AddContractError, AddResolution, SyncExecutionAction, SyncPlanAction, build_sync_plan,
     loader_arg, project_type_arg, resolve_add_contract, resolve_sync_action,
 };
-use crate::application::{CliConfig, Commands};
+use crate::application::{CliConfig, Commands, EmpackExitCode, classify_error};
 use crate::empack::config::{
     DependencyEntry, DependencyRecord, DependencySource, DependencyStatus, LocalDependencyRecord,
 };
-use crate::empack::content::{JarResolver, UrlKind};
+use crate::empack::content::{DependencySide, JarResolver, UrlKind};
 use crate::empack::import::{
     ImportConfig, ModpackManifest, SourceKind, execute_import,
-    parse_curseforge_zip_with_filesystem, parse_modrinth_mrpack_with_filesystem, resolve_manifest,
+    parse_curseforge_zip_with_filesystem, parse_modrinth_mrpack_with_filesystem,
+    parse_packwiz_directory_with_filesystem, parse_prism_instance_with_filesystem,
+    resolve_manifest,
 };
+use crate::empack::journal;
 use crate::empack::parsing::ModLoader;
-use crate::empack::search::SearchError;
-use crate::primitives::{BuildTarget, PackState, ProjectPlatform, ProjectType, StateTransition};
+use crate::empack::search::{CandidateFilters, ProjectResolverTrait, SearchError};
+use crate::empack::state::StateError;
+use crate::empack::transaction::{ProjectTransaction, capture_project_files};
+use crate::networking::endpoints::{Endpoints, Service};
+use crate::primitives::{
+    BuildTarget, OutputFormat, PackState, ProjectPlatform, ProjectType, StateTransition,
+};
 use anyhow::Context;
 use std::collections::HashSet;
 use std::path::{Component, Path, PathBuf};
@@ -31,8 +42,13 @@ use std::path::{Component, Path, PathBuf};
 use crate::empack::config::format_empack_yml;
 use tracing::instrument;
 
+/// How often `--wait-lock` retries a project lock held by another process
+const PROJECT_LOCK_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
+
 /// Execute CLI commands using the new session-based architecture
 pub async fn execute_command(config: CliConfig) -> Result<()> {
+    let output = config.app_config.output;
+
     // Create command session (owns all ephemeral state)
     let session = CommandSession::new_async(config.app_config).await;
 
@@ -51,12 +67,64 @@ pub async fn execute_command(config: CliConfig) -> Result<()> {
         }
     };
 
+    let command_name = command.name();
+
     // Dispatch to session-aware command handlers
-    execute_command_with_session(command, &session).await
+    let result = execute_command_with_session(command, &session).await;
+
+    if output == OutputFormat::Json {
+        let document = json_result_document(command_name, &result, session.display());
+        println!("{document}");
+    }
+
+    result
+}
+
+/// Assemble the single JSON document printed by `--output json`
+///
+/// `data` holds whatever the command recorded through
+/// [`DisplayProvider::record`](crate::display::DisplayProvider::record);
+/// failures add the error chain and its exit code classification.
+pub fn json_result_document(
+    command: &str,
+    result: &Result<()>,
+    display: &dyn crate::display::DisplayProvider,
+) -> serde_json::Value {
+    let exit_code = match result {
+        Ok(()) => EmpackExitCode::Success,
+        Err(error) => classify_error(error),
+    };
+    let mut document = serde_json::json!({
+        "command": command,
+        "success": result.is_ok(),
+        "exit_code": exit_code.as_i32(),
+        "data": display.take_records(),
+    });
+    if let Err(error) = result {
+        document["error"] = serde_json::json!({
+            "kind": exit_code.name(),
+            "message": format!("{error:#}"),
+        });
+    }
+    document
+}
+
+/// Record a command result for `--output json`
+fn record_json<T: serde::Serialize + ?Sized>(session: &dyn Session, key: &str, value: &T) {
+    match serde_json::to_value(value) {
+        Ok(value) => session.display().record(key, value),
+        Err(error) => tracing::debug!("Failed to serialize {key} for JSON output: {error}"),
+    }
 }
 
 /// Execute a specific command with a provided session (for testing)
 pub async fn execute_command_with_session(command: Commands, session: &dyn Session) -> Result<()> {
+    let _project_lock = if command.mutates_project() {
+        acquire_project_lock(session).await?
+    } else {
+        None
+    };
+
     match command {
         Commands::Requirements => handle_requirements(session).await,
         Commands::Version => handle_version(session).await,
@@ -69,24 +137,211 @@ pub async fn execute_command_with_session(command: Commands, session: &dyn Sessi
             version_id,
             file_id,
         } => {
-            handle_add(
+            run_mutating(
                 session,
-                mods,
-                force,
-                platform,
-                project_type,
-                version_id,
-                file_id,
+                handle_add(
+                    session,
+                    mods,
+                    force,
+                    platform,
+                    project_type,
+                    version_id,
+                    file_id,
+                ),
             )
             .await
         }
-        Commands::Remove { mods, deps } => handle_remove(session, mods, deps).await,
+        Commands::Remove { mods, deps } => {
+            run_mutating(session, handle_remove(session, mods, deps)).await
+        }
         Commands::Build(args) => handle_build(session, &args).await,
         Commands::Clean { targets } => handle_clean(session, targets).await,
-        Commands::Sync {} => handle_sync(session).await,
+        Commands::Sync { locked } => run_mutating(session, handle_sync(session, locked)).await,
+        Commands::List {
+            project_type,
+            platform,
+            sort,
+        } => handle_list(session, project_type, platform, sort).await,
+        Commands::Outdated {} => handle_outdated(session).await,
+        Commands::Update { mods, force } => {
+            run_mutating(session, handle_update(session, mods, force)).await
+        }
+        Commands::Why { slug } => handle_why(session, slug).await,
+        Commands::Tree { slug } => handle_tree(session, slug).await,
+        Commands::Search(args) => run_mutating(session, handle_search(session, &args)).await,
+        Commands::Migrate(args) => run_mutating(session, handle_migrate(session, &args)).await,
+        Commands::History => handle_history(session).await,
+        Commands::Undo { entry, force } => {
+            run_mutating(session, handle_undo(session, entry, force)).await
+        }
+        Commands::Verify { artifact, pubkey } => handle_verify(session, &artifact, &pubkey).await,
+        Commands::Cache { action } => match action {
+            CacheCommand::Gc => handle_cache_gc(session).await,
+        },
+    }
+}
+
+/// Take the project lock for a mutating command
+///
+/// Fails right away when another empack process holds it, or polls until it
+/// is released with `--wait-lock`. Directories without `empack.yml` are not
+/// locked, so commands outside a project still report their usual errors.
+async fn acquire_project_lock(
+    session: &dyn Session,
+) -> Result<Option<crate::application::session::FileLock>> {
+    let Ok(manager) = session.state() else {
+        return Ok(None);
+    };
+    if !session
+        .filesystem()
+        .exists(&manager.workdir.join("empack.yml"))
+    {
+        return Ok(None);
+    }
+
+    let mut announced = false;
+    loop {
+        match manager.lock_project() {
+            Ok(lock) => return Ok(Some(lock)),
+            Err(error @ StateError::Locked { .. }) if session.config().app_config().wait_lock => {
+                if !announced {
+                    session
+                        .display()
+                        .status()
+                        .info(&format!("{error}; waiting for it to finish"));
+                    announced = true;
+                }
+                if crate::interrupt_requested() {
+                    return Err(anyhow::anyhow!(
+                        "Interrupted while waiting for project lock"
+                    ));
+                }
+                tokio::time::sleep(PROJECT_LOCK_POLL_INTERVAL).await;
+            }
+            Err(error @ StateError::Locked { .. }) => {
+                session.display().status().error(&error.to_string(), "");
+                session
+                    .display()
+                    .status()
+                    .subtle("   Re-run with --wait-lock to wait for it to finish");
+                return Err(error.into());
+            }
+            Err(error) => return Err(error.into()),
+        }
+    }
+}
+
+/// Run a project-mutating command with rollback and journaling
+///
+/// A failure or an interrupt restores `empack.yml`,
+/// `empack.lock`, the pack index, and the `.pw.toml` metafiles to their state
+/// before the command; `--no-rollback` skips the snapshot. A successful run
+/// that changed any of those files is recorded in the operation journal.
+/// `--dry-run` runs the command directly.
+async fn run_mutating(
+    session: &dyn Session,
+    command: impl std::future::Future<Output = Result<()>>,
+) -> Result<()> {
+    let app_config = session.config().app_config();
+    if app_config.dry_run {
+        return command.await;
+    }
+    let workdir = match session.state() {
+        Ok(manager) => manager.workdir.clone(),
+        Err(_) => return command.await,
+    };
+    if !session.filesystem().exists(&workdir.join("empack.yml")) {
+        return command.await;
+    }
+
+    let transaction = if !app_config.no_rollback {
+        Some(ProjectTransaction::begin(session.filesystem(), &workdir)?)
+    } else {
+        None
+    };
+    let before = match &transaction {
+        Some(transaction) => transaction.files().clone(),
+        None => capture_project_files(session.filesystem(), &workdir)?,
+    };
+
+    let result = command.await;
+    let result = match result {
+        Ok(()) if crate::interrupt_requested() => Err(anyhow::anyhow!("Interrupted")),
+        other => other,
+    };
+
+    match result {
+        Ok(()) => {
+            if let Some(transaction) = transaction {
+                transaction.commit()?;
+            }
+            record_journal_entry(session, &workdir, &before);
+            Ok(())
+        }
+        Err(error) => {
+            let Some(transaction) = transaction else {
+                return Err(error);
+            };
+            match transaction.rollback() {
+                Ok(0) => {}
+                Ok(restored) => session.display().status().warning(&format!(
+                    "Rolled back {restored} project file(s); re-run with --no-rollback to keep partial changes"
+                )),
+                Err(rollback_error) => session
+                    .display()
+                    .status()
+                    .error("Rollback failed", &rollback_error.to_string()),
+            }
+            Err(error)
+        }
+    }
+}
+
+/// Journal the files a successful command changed
+///
+/// Journal failures are reported but never fail the command itself.
+fn record_journal_entry(
+    session: &dyn Session,
+    workdir: &Path,
+    before: &std::collections::BTreeMap<String, Option<String>>,
+) {
+    let recorded = capture_project_files(session.filesystem(), workdir)
+        .map_err(anyhow::Error::from)
+        .and_then(|after| {
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs());
+            journal::record(
+                session.filesystem(),
+                workdir,
+                &journal_command_line(),
+                timestamp,
+                journal::diff_files(before, &after),
+            )
+            .map_err(anyhow::Error::from)
+        });
+    if let Err(error) = recorded {
+        session
+            .display()
+            .status()
+            .warning(&format!("Failed to record journal entry: {error}"));
     }
 }
 
+/// The invoking command line, as recorded in the journal
+fn journal_command_line() -> String {
+    std::iter::once("empack".to_string())
+        .chain(std::env::args().skip(1).map(|arg| {
+            if arg.contains(char::is_whitespace) {
+                format!("\"{arg}\"")
+            } else {
+                arg
+            }
+        }))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 fn report_incomplete_project(session: &dyn Session, action: &str) -> Result<()> {
     session
         .display()
@@ -163,6 +418,20 @@ async fn handle_requirements(session: &dyn Session) -> Result<()> {
         .execute(packwiz_path, &["--help"], &cwd)
         .map(|output| output.success)
         .unwrap_or(false);
+    let java_path = session.process().find_program("java");
+    record_json(
+        session,
+        "requirements",
+        &serde_json::json!({
+            "packwiz": {
+                "available": packwiz_works,
+                "path": packwiz_path,
+                "version": crate::platform::packwiz_bin::PACKWIZ_TX_VERSION,
+            },
+            "java": { "available": java_path.is_some(), "path": java_path },
+            "archive": ["zip", "tar.gz", "7z"],
+        }),
+    );
     if packwiz_works {
         session.display().status().success(
             "packwiz-tx",
@@ -183,7 +452,7 @@ async fn handle_requirements(session: &dyn Session) -> Result<()> {
         ));
     }
 
-    match session.process().find_program("java") {
+    match java_path {
         Some(path) => {
             session.display().status().success("java", &path);
         }
@@ -222,6 +491,16 @@ async fn handle_version(session: &dyn Session) -> Result<()> {
         ("Build date", option_env!("BUILD_DATE").unwrap_or("unknown")),
         ("Target", std::env::consts::ARCH),
     ];
+    record_json(
+        session,
+        "version",
+        &serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "commit": build_info[0].1,
+            "build_date": build_info[1].1,
+            "target": build_info[2].1,
+        }),
+    );
 
     session.display().table().properties(&build_info);
 
@@ -773,6 +1052,22 @@ async fn handle_init(session: &dyn Session, args: &InitArgs) -> Result<()> {
             .map_err(|e| anyhow::anyhow!("failed to refresh index after writing options: {}", e))?;
     }
 
+    record_json(
+        session,
+        "init",
+        &serde_json::json!({
+            "project_dir": target_dir,
+            "name": modpack_name,
+            "author": author,
+            "version": version,
+            "minecraft_version": minecraft_version,
+            "loader": loader_str,
+            "loader_version": (loader_str != "none").then_some(&loader_version),
+            "datapack_folder": datapack_folder,
+            "game_versions": game_versions,
+        }),
+    );
+
     tracing::info!(
         command = "init",
         duration_ms = start.elapsed().as_millis() as u64,
@@ -1025,6 +1320,7 @@ async fn handle_init_from_source(
 
     let resolved = resolve_manifest(
         manifest,
+        session.filesystem(),
         modrinth_api,
         curseforge_api,
         cf_api_key.as_deref(),
@@ -1067,6 +1363,21 @@ async fn handle_init_from_source(
     };
 
     let result = execute_import(resolved, config, session).await?;
+    record_json(
+        session,
+        "import",
+        &serde_json::json!({
+            "project_dir": result.project_dir,
+            "platform_referenced": result.stats.platform_referenced,
+            "platform_failed": result.stats.platform_failed,
+            "platform_skipped": result.stats.platform_skipped,
+            "embedded_jars_identified": result.stats.embedded_jars_identified,
+            "embedded_jars_unidentified": result.stats.embedded_jars_unidentified,
+            "local_tracked": result.stats.local_tracked,
+            "local_failed": result.stats.local_failed,
+            "overrides_copied": result.stats.overrides_copied,
+        }),
+    );
 
     session.display().status().section("Import Summary");
     session.display().status().success(
@@ -1085,19 +1396,39 @@ async fn handle_init_from_source(
             result.stats.platform_skipped
         ));
     }
+    if result.stats.embedded_jars_identified > 0 {
+        session.display().status().info(&format!(
+            "Embedded JARs identified: {}",
+            result.stats.embedded_jars_identified
+        ));
+    }
     session.display().status().info(&format!(
         "Embedded files extracted: {} (unidentified)",
         result.stats.embedded_jars_unidentified
     ));
+    if result.stats.local_tracked > 0 {
+        session.display().status().info(&format!(
+            "Local dependencies tracked: {}",
+            result.stats.local_tracked
+        ));
+    }
+    if result.stats.local_failed > 0 {
+        session.display().status().error(
+            "Local dependencies failed",
+            &result.stats.local_failed.to_string(),
+        );
+    }
     session.display().status().info(&format!(
         "Override files copied: {}",
         result.stats.overrides_copied
     ));
 
-    if result.stats.platform_failed > 0 {
+    if result.stats.platform_failed > 0 || result.stats.local_failed > 0 {
         let total = result.stats.platform_failed
             + result.stats.platform_referenced
-            + result.stats.platform_skipped;
+            + result.stats.platform_skipped
+            + result.stats.local_tracked
+            + result.stats.local_failed;
 
         tracing::info!(
             command = "init_from_source",
@@ -1111,8 +1442,8 @@ async fn handle_init_from_source(
         );
 
         return Err(anyhow::anyhow!(
-            "import incomplete: {} of {} platform references failed to add",
-            result.stats.platform_failed,
+            "import incomplete: {} of {} content entries failed to add",
+            result.stats.platform_failed + result.stats.local_failed,
             total
         ));
     }
@@ -1157,10 +1488,14 @@ fn import_from_local(
             Ok((manifest, None, source_path))
         }
         SourceKind::PackwizDirectory => {
-            anyhow::bail!(
-                "packwiz directory import is not yet implemented; \
-                 initialize with empack init then use empack add for each mod"
-            );
+            let manifest =
+                parse_packwiz_directory_with_filesystem(session.filesystem(), &source_path)?;
+            Ok((manifest, None, source_path))
+        }
+        SourceKind::PrismInstance => {
+            let manifest =
+                parse_prism_instance_with_filesystem(session.filesystem(), &source_path)?;
+            Ok((manifest, None, source_path))
         }
         SourceKind::ModrinthRemote { .. } | SourceKind::CurseForgeRemote { .. } => {
             unreachable!("remote source kinds should not be returned by detect_local_source")
@@ -1195,14 +1530,11 @@ async fn download_modrinth_modpack(
     version_filter: Option<&str>,
 ) -> Result<(ModpackManifest, tempfile::TempDir, PathBuf)> {
     let client = session.network().http_client()?;
-    download_modrinth_modpack_with_client(
-        session,
-        &client,
-        slug,
-        version_filter,
-        "https://api.modrinth.com/v2",
-    )
-    .await
+    let api_base = format!(
+        "{}/v2",
+        session.network().endpoints().base(Service::ModrinthApi)
+    );
+    download_modrinth_modpack_with_client(session, &client, slug, version_filter, &api_base).await
 }
 
 async fn download_modrinth_modpack_with_client(
@@ -1219,9 +1551,10 @@ async fn download_modrinth_modpack_with_client(
 
     let version_url = format!("{api_base}/project/{slug}/version");
 
-    let response = client
-        .get(&version_url)
-        .send()
+    let response = session
+        .network()
+        .endpoints()
+        .send(&version_url, |url| client.get(url).send())
         .await
         .context("failed to fetch Modrinth version list")?;
 
@@ -1289,7 +1622,14 @@ async fn download_modrinth_modpack_with_client(
     let tmp_dir = tempfile::tempdir().context("failed to create temp directory")?;
     let dest_path = tmp_dir.path().join(filename);
 
-    download_file(session.filesystem(), client, download_url, &dest_path).await?;
+    download_file(
+        session.filesystem(),
+        session.network().endpoints(),
+        client,
+        download_url,
+        &dest_path,
+    )
+    .await?;
 
     let manifest = parse_modrinth_mrpack_with_filesystem(session.filesystem(), &dest_path)?;
     Ok((manifest, tmp_dir, dest_path))
@@ -1300,8 +1640,11 @@ async fn download_curseforge_modpack(
     slug: &str,
 ) -> Result<(ModpackManifest, tempfile::TempDir, PathBuf)> {
     let client = session.network().http_client()?;
-    download_curseforge_modpack_with_client(session, &client, slug, "https://api.curseforge.com/v1")
-        .await
+    let api_base = format!(
+        "{}/v1",
+        session.network().endpoints().base(Service::CurseForgeApi)
+    );
+    download_curseforge_modpack_with_client(session, &client, slug, &api_base).await
 }
 
 async fn download_curseforge_modpack_with_client(
@@ -1326,10 +1669,11 @@ async fn download_curseforge_modpack_with_client(
 
     // Resolve slug to project ID via search
     let search_url = format!("{api_base}/mods/search?gameId=432&classId=4471&slug={slug}",);
-    let search_resp = client
-        .get(&search_url)
-        .header("x-api-key", &api_key)
-        .send()
+    let endpoints = session.network().endpoints();
+    let search_resp = endpoints
+        .send(&search_url, |url| {
+            client.get(url).header("x-api-key", &api_key).send()
+        })
         .await
         .context("failed to search CurseForge for modpack")?;
 
@@ -1367,10 +1711,10 @@ async fn download_curseforge_modpack_with_client(
 
     // Get latest file
     let files_url = format!("{api_base}/mods/{}/files?pageSize=1", project.id);
-    let files_resp = client
-        .get(&files_url)
-        .header("x-api-key", &api_key)
-        .send()
+    let files_resp = endpoints
+        .send(&files_url, |url| {
+            client.get(url).header("x-api-key", &api_key).send()
+        })
         .await
         .context("failed to fetch CurseForge file list")?;
 
@@ -1409,10 +1753,10 @@ async fn download_curseforge_modpack_with_client(
             "{api_base}/mods/{}/files/{}/download-url",
             project.id, file.id
         );
-        let dl_resp = client
-            .get(&dl_endpoint)
-            .header("x-api-key", &api_key)
-            .send()
+        let dl_resp = endpoints
+            .send(&dl_endpoint, |url| {
+                client.get(url).header("x-api-key", &api_key).send()
+            })
             .await
             .context("failed to fetch CurseForge download URL")?;
 
@@ -1455,7 +1799,7 @@ async fn download_curseforge_modpack_with_client(
     let tmp_dir = tempfile::tempdir().context("failed to create temp directory")?;
     let dest_path = tmp_dir.path().join(filename);
 
-    download_file(session.filesystem(), client, &dl_url, &dest_path).await?;
+    download_file(session.filesystem(), endpoints, client, &dl_url, &dest_path).await?;
 
     let manifest = parse_curseforge_zip_with_filesystem(session.filesystem(), &dest_path)?;
     Ok((manifest, tmp_dir, dest_path))
@@ -1463,13 +1807,13 @@ async fn download_curseforge_modpack_with_client(
 
 async fn download_file(
     filesystem: &dyn FileSystemProvider,
+    endpoints: &Endpoints,
     client: &reqwest::Client,
     url: &str,
     dest: &std::path::Path,
 ) -> Result<()> {
-    let response = client
-        .get(url)
-        .send()
+    let response = endpoints
+        .send(url, |url| client.get(url).send())
         .await
         .with_context(|| format!("failed to download from {}", url))?;
 
@@ -1675,6 +2019,7 @@ async fn handle_add(
                     file_id.as_deref(),
                     project_type.as_ref().map(|pt| pt.to_project_type()),
                     resolver.as_ref(),
+                    session.network().endpoints(),
                 )
                 .await
                 {
@@ -1779,6 +2124,7 @@ async fn handle_add(
                                     project_id,
                                     project_type: resolution.project_type,
                                     version: None,
+                                    side: None,
                                 };
                                 if let Err(e) = config_manager.add_dependency(&dep_key, record) {
                                     session
@@ -1812,6 +2158,8 @@ async fn handle_add(
         }
     }
 
+    record_json(session, "resolutions", &resolved_mods);
+
     if session.config().app_config().dry_run {
         session.display().status().section("Planned Actions");
         for resolved in &resolved_mods {
@@ -1831,6 +2179,9 @@ async fn handle_add(
 
     let all_content_folders: &[&str] = &["mods", "resourcepacks", "shaderpacks", "datapacks"];
     for resolved in resolved_mods {
+        if crate::interrupt_requested() {
+            return Err(anyhow::anyhow!("Interrupted"));
+        }
         let (scan_folders, before_slugs) = match resolved.resolution.resolved_project_type {
             Some(pt) => {
                 let folder = content_folder_for_type(pt);
@@ -1931,16 +2282,39 @@ async fn handle_add(
                 }
 
                 {
+                    let dep_project_type = resolved
+                        .resolution
+                        .resolved_project_type
+                        .unwrap_or(ProjectType::Mod);
+                    // Keep a hand-written side override when re-adding
+                    let existing_side = match config_manager.find_dependency(&dep_key) {
+                        Ok(Some((_, DependencyEntry::Resolved(existing)))) => existing.side,
+                        _ => None,
+                    };
+                    let side = match existing_side {
+                        Some(side) => Some(side),
+                        None => {
+                            lookup_dependency_side(
+                                session,
+                                resolver.as_ref(),
+                                &workdir,
+                                &dep_key,
+                                resolved.resolution.resolved_platform,
+                                &resolved.resolution.resolved_project_id,
+                                dep_project_type,
+                                config_manager.datapack_folder().as_deref(),
+                            )
+                            .await
+                        }
+                    };
                     let record = DependencyRecord {
                         status: DependencyStatus::Resolved,
                         title: resolved.resolution.title.clone(),
                         platform: resolved.resolution.resolved_platform,
                         project_id: resolved.resolution.resolved_project_id.clone(),
-                        project_type: resolved
-                            .resolution
-                            .resolved_project_type
-                            .unwrap_or(ProjectType::Mod),
+                        project_type: dep_project_type,
                         version: None,
+                        side,
                     };
                     if let Err(e) = config_manager.add_dependency(&dep_key, record) {
                         session
@@ -1963,6 +2337,20 @@ async fn handle_add(
         }
     }
 
+    if !added_mods.is_empty() {
+        refresh_lockfile(session, &workdir);
+    }
+
+    record_json(session, "added", &added_mods);
+    record_json(
+        session,
+        "failed",
+        &failed_mods
+            .iter()
+            .map(|(query, error)| serde_json::json!({ "query": query, "error": error }))
+            .collect::<Vec<_>>(),
+    );
+
     session.display().status().section("Add Summary");
     session
         .display()
@@ -2227,8 +2615,10 @@ fn discover_dep_key(
 /// Holds a fully-resolved mod ready for the execute phase of handle_add.
 /// Separating resolution (network + user interaction) from execution (file writes)
 /// ensures that a Ctrl+C during the gather phase leaves no files modified.
+#[derive(serde::Serialize)]
 struct ResolvedMod {
     query: String,
+    #[serde(flatten)]
     resolution: AddResolution,
     dep_key: String,
 }
@@ -2273,6 +2663,7 @@ async fn resolve_curseforge_slug(
     file_id_override: Option<&str>,
     project_type: Option<ProjectType>,
     resolver: &dyn crate::empack::search::ProjectResolverTrait,
+    endpoints: &Endpoints,
 ) -> std::result::Result<AddResolution, anyhow::Error> {
     resolve_curseforge_slug_with_api_base(
         slug,
@@ -2284,7 +2675,8 @@ async fn resolve_curseforge_slug(
         file_id_override,
         project_type,
         resolver,
-        "https://api.curseforge.com/v1",
+        endpoints,
+        &format!("{}/v1", endpoints.base(Service::CurseForgeApi)),
     )
     .await
 }
@@ -2300,6 +2692,7 @@ async fn resolve_curseforge_slug_with_api_base(
     file_id_override: Option<&str>,
     project_type: Option<ProjectType>,
     resolver: &dyn crate::empack::search::ProjectResolverTrait,
+    endpoints: &Endpoints,
     api_base: &str,
 ) -> std::result::Result<AddResolution, anyhow::Error> {
     let api_key = curseforge_api_key
@@ -2307,10 +2700,10 @@ async fn resolve_curseforge_slug_with_api_base(
 
     let search_url = format!("{api_base}/mods/search?gameId=432&slug={slug}");
 
-    let response = client
-        .get(&search_url)
-        .header("x-api-key", api_key)
-        .send()
+    let response = endpoints
+        .send(&search_url, |url| {
+            client.get(url).header("x-api-key", api_key).send()
+        })
         .await?;
     if !response.status().is_success() {
         anyhow::bail!(
@@ -2390,7 +2783,14 @@ async fn handle_direct_download_jar_with_client_and_resolver<R: JarResolver>(
     let filename = url.rsplit('/').next().unwrap_or("download.jar");
     let dest_path = tmp_dir.path().join(filename);
 
-    download_file(session.filesystem(), client, url, &dest_path).await?;
+    download_file(
+        session.filesystem(),
+        session.network().endpoints(),
+        client,
+        url,
+        &dest_path,
+    )
+    .await?;
 
     let sha1 = {
         let bytes = session.filesystem().read_bytes(&dest_path)?;
@@ -2545,7 +2945,14 @@ async fn handle_direct_download_non_jar(
     let tmp_dir = tempfile::tempdir().context("failed to create temp directory")?;
     let filename = download_filename(url, "download.zip");
     let dest_path = tmp_dir.path().join(&filename);
-    download_file(session.filesystem(), &client, url, &dest_path).await?;
+    download_file(
+        session.filesystem(),
+        session.network().endpoints(),
+        &client,
+        url,
+        &dest_path,
+    )
+    .await?;
     let bytes = session.filesystem().read_bytes(&dest_path)?;
 
     build_tracked_local_dependency(session, &workdir, url, &filename, project_type, &bytes)
@@ -2584,6 +2991,11 @@ fn build_tracked_local_dependency(
                 dest_path.display()
             )
         })?;
+    if let Err(e) = crate::empack::store::ContentStore::open(session.filesystem())
+        .and_then(|store| store.admit_for_project(workdir, bytes, None, url))
+    {
+        tracing::warn!(url, error = %e, "failed to add local dependency to the content store");
+    }
 
     let title = filename.to_string();
     let record = LocalDependencyRecord {
@@ -2593,6 +3005,7 @@ fn build_tracked_local_dependency(
         path: relative_path,
         source_url: Some(url.to_string()),
         sha256: compute_sha256_hex_for_bytes(bytes),
+        side: None,
     };
 
     session.display().status().info(&format!(
@@ -2954,7 +3367,6 @@ async fn handle_remove(session: &dyn Session, mods: Vec<String>, deps: bool) ->
         .section(&format!("Removing {} mod(s) from modpack", mods.len()));
 
     let workdir = manager.workdir.clone();
-    let mods_dir = workdir.join("pack").join("mods");
     let config_manager = session.filesystem().config_manager(workdir.clone());
     let mut removed_mods = Vec::new();
     let mut failed_mods = Vec::new();
@@ -2974,6 +3386,21 @@ async fn handle_remove(session: &dyn Session, mods: Vec<String>, deps: bool) ->
         })
         .collect();
 
+    // Plan orphan pruning before touching anything so the full removal set
+    // can be confirmed up front
+    let orphan_plan = if deps {
+        plan_orphan_removal(session, &workdir)
+    } else {
+        None
+    };
+    let planned_orphans = orphan_plan
+        .as_ref()
+        .map(|plan| plan.orphans(&validated_mods))
+        .unwrap_or_default();
+    if let Some(plan) = &orphan_plan {
+        show_removal_set(session, plan, &validated_mods, &planned_orphans);
+    }
+
     if session.config().app_config().dry_run {
         session.display().status().section("Planned Actions");
         for mod_name in &validated_mods {
@@ -2982,6 +3409,12 @@ async fn handle_remove(session: &dyn Session, mods: Vec<String>, deps: bool) ->
                 .status()
                 .info(&format!("Would remove: {}", mod_name));
         }
+        for orphan in &planned_orphans {
+            session.display().status().info(&format!(
+                "Would remove orphaned dependency: {}",
+                dependency_node_slug(orphan)
+            ));
+        }
         session
             .display()
             .status()
@@ -2989,7 +3422,20 @@ async fn handle_remove(session: &dyn Session, mods: Vec<String>, deps: bool) ->
         return Ok(());
     }
 
+    let remove_orphans = !planned_orphans.is_empty()
+        && (session.config().app_config().yes
+            || session.interactive().confirm(
+                &format!(
+                    "Also remove {} orphaned dependencies?",
+                    planned_orphans.len()
+                ),
+                false,
+            )?);
+
     for mod_name in validated_mods {
+        if crate::interrupt_requested() {
+            return Err(anyhow::anyhow!("Interrupted"));
+        }
         session
             .display()
             .status()
@@ -3047,9 +3493,8 @@ async fn handle_remove(session: &dyn Session, mods: Vec<String>, deps: bool) ->
             continue;
         }
 
-        // Execute packwiz remove command
-        // Note: packwiz does not support --remove-deps flag
-        // Orphan detection must be implemented using DependencyGraph
+        // packwiz removes only the named mod; orphans are pruned below from
+        // the dependency graph planned before any removal
         let packwiz_args = vec!["remove", "-y", &mod_name];
 
         let result = session
@@ -3091,126 +3536,66 @@ async fn handle_remove(session: &dyn Session, mods: Vec<String>, deps: bool) ->
         }
     }
 
-    // Orphan detection: Find mods with no dependents (if --deps flag is set)
     let mut removed_orphans = Vec::new();
-    if deps && !removed_mods.is_empty() && session.filesystem().exists(&mods_dir) {
-        session
-            .display()
-            .status()
-            .section("Detecting orphaned dependencies");
-
-        let mut dep_graph = crate::api::dependency_graph::DependencyGraph::new();
-        if let Err(e) = dep_graph.build_from_directory_with(&mods_dir, session.filesystem()) {
+    if remove_orphans && let Some(plan) = &orphan_plan {
+        // Recompute from what was actually removed: a target that failed to
+        // remove still needs its dependencies
+        let orphans = plan.orphans(&removed_mods);
+        if !orphans.is_empty() {
             session
                 .display()
                 .status()
-                .warning(&format!("Failed to build dependency graph: {}", e));
-        } else {
-            // Load empack.yml to get top-level mods
-            let top_level_mods: std::collections::HashSet<String> =
-                match config_manager.create_project_plan() {
-                    Ok(plan) => {
-                        // Use the dependency key as the mod identifier
-                        plan.dependencies
-                            .iter()
-                            .map(|dep| dep.key.clone())
-                            .collect()
-                    }
-                    Err(_) => std::collections::HashSet::new(),
-                };
-
-            // Find orphans: mods not in top-level AND no dependents
-            let mut orphans = Vec::new();
-            for node in dep_graph.all_nodes() {
-                // Skip if mod is explicitly declared in empack.yml
-                if top_level_mods.contains(&node.mod_id) {
-                    continue;
-                }
-
-                // Check if any mods depend on this one
-                let has_dependents = dep_graph
-                    .get_dependents(&node.mod_id)
-                    .map(|deps| !deps.is_empty())
-                    .unwrap_or(false);
+                .section("Removing orphaned dependencies");
+        }
 
-                if !has_dependents {
-                    orphans.push(node.mod_id.clone());
-                }
-            }
+        for orphan in orphans {
+            let slug = dependency_node_slug(&orphan);
+            let result = session
+                .process()
+                .execute(
+                    session.packwiz_bin(),
+                    &["remove", "-y", &slug],
+                    &workdir.join("pack"),
+                )
+                .and_then(|output| {
+                    if output.success {
+                        Ok(())
+                    } else {
+                        Err(anyhow::anyhow!(
+                            "Packwiz command failed: {}",
+                            output.error_output()
+                        ))
+                    }
+                });
 
-            if !orphans.is_empty() {
-                session
-                    .display()
-                    .status()
-                    .info(&format!("Found {} orphaned dependencies:", orphans.len()));
-                for orphan in &orphans {
+            match result {
+                Ok(_) => {
                     session
                         .display()
                         .status()
-                        .subtle(&format!("  - {}", orphan));
+                        .success(&format!("Removed orphan: {}", slug), "");
+                    removed_orphans.push(slug);
                 }
-
-                let should_remove = session
-                    .interactive()
-                    .confirm("Remove orphaned dependencies?", false)?;
-
-                if should_remove {
-                    session.display().status().section("Removing orphans");
-
-                    for orphan in orphans {
-                        let result = session
-                            .process()
-                            .execute(
-                                session.packwiz_bin(),
-                                &["remove", "-y", &orphan],
-                                &workdir.join("pack"),
-                            )
-                            .and_then(|output| {
-                                if output.success {
-                                    Ok(())
-                                } else {
-                                    Err(anyhow::anyhow!(
-                                        "Packwiz command failed: {}",
-                                        output.error_output()
-                                    ))
-                                }
-                            });
-
-                        match result {
-                            Ok(_) => {
-                                // Atomically remove from empack.yml
-                                if let Err(e) = config_manager.remove_dependency(&orphan) {
-                                    session
-                                        .display()
-                                        .status()
-                                        .warning(&format!("Failed to update empack.yml: {}", e));
-                                }
-                                session
-                                    .display()
-                                    .status()
-                                    .success(&format!("Removed orphan: {}", orphan), "");
-                                removed_orphans.push(orphan);
-                            }
-                            Err(e) => {
-                                session.display().status().error(
-                                    &format!("Failed to remove orphan: {}", orphan),
-                                    &e.to_string(),
-                                );
-                            }
-                        }
-                    }
-                } else {
-                    session.display().status().info("Orphans not removed");
+                Err(e) => {
+                    session.display().status().error(
+                        &format!("Failed to remove orphan: {}", slug),
+                        &e.to_string(),
+                    );
+                    failed_mods.push((slug, e.to_string()));
                 }
-            } else {
-                session
-                    .display()
-                    .status()
-                    .info("No orphaned dependencies found");
             }
         }
+    } else if !planned_orphans.is_empty() {
+        session.display().status().info("Orphans not removed");
+    }
+
+    if !removed_mods.is_empty() || !removed_orphans.is_empty() {
+        refresh_lockfile(session, &workdir);
     }
 
+    record_json(session, "removed", &removed_mods);
+    record_json(session, "orphans_removed", &removed_orphans);
+
     session.display().status().section("Remove Summary");
     session
         .display()
@@ -3256,42 +3641,149 @@ async fn handle_remove(session: &dyn Session, mods: Vec<String>, deps: bool) ->
     Ok(())
 }
 
-/// Handle the `build` subcommand.
-#[instrument(skip_all, fields(targets = ?args.targets))]
-async fn handle_build(session: &dyn Session, args: &BuildArgs) -> Result<()> {
-    let start = std::time::Instant::now();
-    let manager = session.state()?;
-
-    // Verify we're in a configured state
-    let current_state = manager.discover_state()?;
-    ensure_configured_project(session, &manager.workdir, current_state, "building")?;
-
-    debug_assert!(
-        !args.continue_build || args.targets.is_empty(),
-        "clap should reject build --continue with positional targets"
-    );
-    debug_assert!(
-        !args.continue_build || !args.clean,
-        "clap should reject build --continue with --clean"
-    );
+/// Dependency graph and empack.yml declarations consulted when pruning orphans
+struct OrphanPlan {
+    graph: crate::api::dependency_graph::DependencyGraph,
+    declared: HashSet<String>,
+}
 
-    if args.continue_build {
-        return continue_pending_restricted_build(session, &manager.workdir, args, start).await;
+impl OrphanPlan {
+    /// Installed dependencies left without dependents once `targets` are removed.
+    /// Anything declared in empack.yml is never an orphan.
+    fn orphans(&self, targets: &[String]) -> Vec<crate::api::dependency_graph::DependencyNode> {
+        let target_ids: Vec<&str> = targets
+            .iter()
+            .filter_map(|target| find_dependency_node(&self.graph, target))
+            .map(|node| node.mod_id.as_str())
+            .collect();
+        self.graph.orphaned_by_removal(&target_ids, |node| {
+            self.declared.contains(&dependency_node_slug(node))
+                || self.declared.contains(&node.mod_id)
+        })
     }
+}
 
-    // Parse build targets
-    let build_targets = parse_build_targets(args.targets.clone())?;
-
-    validate_build_project_plan(session, &manager.workdir, &build_targets)?;
-
-    session
-        .display()
-        .status()
-        .section(&format!("Building targets: {:?}", build_targets));
-
-    if session.config().app_config().dry_run {
-        session.display().status().section("Planned Actions");
-        for target in &build_targets {
+fn plan_orphan_removal(session: &dyn Session, workdir: &Path) -> Option<OrphanPlan> {
+    let graph = match build_pack_dependency_graph(session, workdir) {
+        Ok(graph) => graph,
+        Err(e) => {
+            session
+                .display()
+                .status()
+                .warning(&format!("Skipping orphan detection: {}", e));
+            return None;
+        }
+    };
+    let declared = match session
+        .filesystem()
+        .config_manager(workdir.to_path_buf())
+        .load_empack_config()
+    {
+        // Match on project IDs too: the empack.yml key need not equal the
+        // .pw.toml slug (fabric_api vs fabric-api)
+        Ok(config) => config
+            .empack
+            .dependencies
+            .into_iter()
+            .flat_map(|(key, entry)| match entry {
+                DependencyEntry::Resolved(record) => vec![key, record.project_id],
+                _ => vec![key],
+            })
+            .collect(),
+        Err(e) => {
+            session
+                .display()
+                .status()
+                .warning(&format!("Skipping orphan detection: {}", e));
+            return None;
+        }
+    };
+    Some(OrphanPlan { graph, declared })
+}
+
+fn show_removal_set(
+    session: &dyn Session,
+    plan: &OrphanPlan,
+    targets: &[String],
+    orphans: &[crate::api::dependency_graph::DependencyNode],
+) {
+    session.display().status().section("Removal Set");
+    for target in targets {
+        session
+            .display()
+            .status()
+            .subtle(&format!("  - {}", target));
+    }
+    for orphan in orphans {
+        session.display().status().subtle(&format!(
+            "  - {} (orphaned dependency)",
+            dependency_node_slug(orphan)
+        ));
+    }
+    if orphans.is_empty() {
+        session
+            .display()
+            .status()
+            .info("No orphaned dependencies found");
+    }
+
+    let removing: HashSet<String> = targets
+        .iter()
+        .cloned()
+        .chain(orphans.iter().map(dependency_node_slug))
+        .collect();
+    for target in targets {
+        let Some(node) = find_dependency_node(&plan.graph, target) else {
+            continue;
+        };
+        for dependent in plan.graph.get_dependents(&node.mod_id).unwrap_or_default() {
+            let dependent_slug = dependency_node_slug(&dependent);
+            if dependent.source_path.is_some() && !removing.contains(&dependent_slug) {
+                session
+                    .display()
+                    .status()
+                    .warning(&format!("{} still depends on {}", dependent_slug, target));
+            }
+        }
+    }
+}
+
+/// Handle the `build` subcommand.
+#[instrument(skip_all, fields(targets = ?args.targets))]
+async fn handle_build(session: &dyn Session, args: &BuildArgs) -> Result<()> {
+    let start = std::time::Instant::now();
+    let manager = session.state()?;
+
+    // Verify we're in a configured state
+    let current_state = manager.discover_state()?;
+    ensure_configured_project(session, &manager.workdir, current_state, "building")?;
+
+    debug_assert!(
+        !args.continue_build || args.targets.is_empty(),
+        "clap should reject build --continue with positional targets"
+    );
+    debug_assert!(
+        !args.continue_build || !args.clean,
+        "clap should reject build --continue with --clean"
+    );
+
+    if args.continue_build {
+        return continue_pending_restricted_build(session, &manager.workdir, args, start).await;
+    }
+
+    // Parse build targets
+    let build_targets = parse_build_targets(args.targets.clone())?;
+
+    validate_build_project_plan(session, &manager.workdir, &build_targets)?;
+
+    session
+        .display()
+        .status()
+        .section(&format!("Building targets: {:?}", build_targets));
+
+    if session.config().app_config().dry_run {
+        session.display().status().section("Planned Actions");
+        for target in &build_targets {
             session
                 .display()
                 .status()
@@ -3323,7 +3815,15 @@ async fn handle_build(session: &dyn Session, args: &BuildArgs) -> Result<()> {
 
     ensure_build_runtime_assets(session, &build_targets).await?;
 
-    let results = run_build_pipeline(session, &build_targets, archive_format, false).await?;
+    let results = run_build_pipeline(
+        session,
+        &build_targets,
+        archive_format,
+        false,
+        args.force,
+        args.source_date_epoch,
+    )
+    .await?;
     let restricted_entries = collect_restricted_entries(&results);
 
     if !restricted_entries.is_empty() {
@@ -3418,6 +3918,12 @@ async fn handle_build(session: &dyn Session, args: &BuildArgs) -> Result<()> {
     crate::empack::restricted_build::clear_pending_build(session.filesystem(), &manager.workdir)
         .context("Failed to clear pending restricted build state")?;
 
+    for warning in results.iter().flat_map(|r| &r.warnings) {
+        session.display().status().warning(warning);
+    }
+    display_up_to_date_targets(session, &results);
+    display_side_exclusions(session, &results);
+
     session
         .display()
         .status()
@@ -3598,7 +4104,15 @@ async fn continue_pending_restricted_build_inner(
         ));
     }
 
-    let results = run_build_pipeline(session, &build_targets, archive_format, true).await?;
+    let results = run_build_pipeline(
+        session,
+        &build_targets,
+        archive_format,
+        true,
+        args.force,
+        args.source_date_epoch,
+    )
+    .await?;
     let restricted_entries = collect_restricted_entries(&results);
     if !restricted_entries.is_empty() {
         let cache_dir = pending.restricted_cache_path();
@@ -3643,6 +4157,12 @@ async fn continue_pending_restricted_build_inner(
     crate::empack::restricted_build::clear_pending_build(session.filesystem(), workdir)
         .context("Failed to clear pending restricted build state")?;
 
+    for warning in results.iter().flat_map(|r| &r.warnings) {
+        session.display().status().warning(warning);
+    }
+    display_up_to_date_targets(session, &results);
+    display_side_exclusions(session, &results);
+
     session
         .display()
         .status()
@@ -3668,6 +4188,10 @@ async fn ensure_build_runtime_assets(
     session: &dyn Session,
     build_targets: &[BuildTarget],
 ) -> Result<()> {
+    use crate::networking::download::{Download, DownloadEngine};
+
+    let mut downloads = Vec::new();
+
     let bootstrap_jar_path = session.packwiz().bootstrap_jar_cache_path()?;
     let needs_bootstrap_jar = build_targets.iter().any(|target| {
         matches!(
@@ -3676,27 +4200,18 @@ async fn ensure_build_runtime_assets(
                 | BuildTarget::Server
                 | BuildTarget::ClientFull
                 | BuildTarget::ServerFull
+                | BuildTarget::Prism
         )
     });
 
     if needs_bootstrap_jar && !session.filesystem().exists(&bootstrap_jar_path) {
-        session
-            .display()
-            .status()
-            .info("Downloading required component: packwiz-installer-bootstrap.jar...");
-
-        download_to_cache(
-            session,
-            "https://github.com/packwiz/packwiz-installer-bootstrap/releases/latest/download/packwiz-installer-bootstrap.jar",
-            &bootstrap_jar_path,
-            "packwiz-installer-bootstrap.jar",
-        )
-        .await?;
-
-        session
-            .display()
-            .status()
-            .complete("Downloaded packwiz-installer-bootstrap.jar");
+        downloads.push(
+            Download::new(
+                "https://github.com/packwiz/packwiz-installer-bootstrap/releases/latest/download/packwiz-installer-bootstrap.jar",
+                bootstrap_jar_path,
+            )
+            .with_label("packwiz-installer-bootstrap.jar"),
+        );
     }
 
     let installer_jar_path = session.packwiz().installer_jar_cache_path()?;
@@ -3705,23 +4220,46 @@ async fn ensure_build_runtime_assets(
         .any(|target| matches!(target, BuildTarget::ClientFull | BuildTarget::ServerFull));
 
     if needs_installer_jar && !session.filesystem().exists(&installer_jar_path) {
-        session
-            .display()
-            .status()
-            .info("Downloading required component: packwiz-installer.jar...");
+        downloads.push(
+            Download::new(
+                "https://github.com/packwiz/packwiz-installer/releases/latest/download/packwiz-installer.jar",
+                installer_jar_path,
+            )
+            .with_label("packwiz-installer.jar"),
+        );
+    }
 
-        download_to_cache(
-            session,
-            "https://github.com/packwiz/packwiz-installer/releases/latest/download/packwiz-installer.jar",
-            &installer_jar_path,
-            "packwiz-installer.jar",
-        )
-        .await?;
+    if downloads.is_empty() {
+        return Ok(());
+    }
+
+    if let Some(missing) = downloads.first()
+        && session.network().is_offline()
+    {
+        return Err(crate::networking::NetworkingError::Offline {
+            resource: format!("{} ({})", missing.label, missing.dest.display()),
+        }
+        .into());
+    }
+
+    for download in &downloads {
+        session.display().status().info(&format!(
+            "Downloading required component: {}...",
+            download.label
+        ));
+    }
+
+    let progress = session.display().progress();
+    let results = DownloadEngine::for_session(session)?
+        .fetch_all(&downloads, progress.as_ref())
+        .await;
 
+    for (download, result) in downloads.iter().zip(results) {
+        result.with_context(|| format!("Failed to download {}", download.label))?;
         session
             .display()
             .status()
-            .complete("Downloaded packwiz-installer.jar");
+            .complete(&format!("Downloaded {}", download.label));
     }
 
     Ok(())
@@ -3732,6 +4270,8 @@ async fn run_build_pipeline(
     build_targets: &[BuildTarget],
     archive_format: crate::empack::archive::ArchiveFormat,
     continue_full_builds: bool,
+    force_rebuild: bool,
+    source_date_epoch: Option<u64>,
 ) -> Result<Vec<crate::empack::builds::BuildResult>> {
     let mut build_orchestrator =
         crate::empack::builds::BuildOrchestrator::new(session, archive_format)
@@ -3739,10 +4279,17 @@ async fn run_build_pipeline(
     if continue_full_builds {
         build_orchestrator = build_orchestrator.continue_full_builds();
     }
+    if force_rebuild {
+        build_orchestrator = build_orchestrator.force_rebuild();
+    }
+    if let Some(epoch) = source_date_epoch {
+        build_orchestrator = build_orchestrator.source_date_epoch(epoch);
+    }
 
     build_orchestrator
         .execute_build_pipeline(build_targets)
         .await
+        .inspect(|results| record_json(session, "builds", results))
         .inspect_err(|_| {
             session
                 .display()
@@ -4076,22 +4623,59 @@ async fn maybe_open_and_wait_for_restricted_downloads(
     Ok(false)
 }
 
+/// List the dependencies each target left out for being on the other side.
+fn display_up_to_date_targets(session: &dyn Session, results: &[crate::empack::BuildResult]) {
+    for result in results.iter().filter(|r| r.up_to_date) {
+        session.display().status().info(&format!(
+            "{}: up to date (use --force to rebuild)",
+            result.target
+        ));
+    }
+}
+
+fn display_side_exclusions(session: &dyn Session, results: &[crate::empack::BuildResult]) {
+    for result in results.iter().filter(|r| !r.excluded.is_empty()) {
+        let other_side = match result.target {
+            BuildTarget::Server | BuildTarget::ServerFull => "client-only",
+            _ => "server-only",
+        };
+        session.display().status().info(&format!(
+            "{}: excluded {} {}: {}",
+            result.target,
+            other_side,
+            if result.excluded.len() == 1 {
+                "dependency"
+            } else {
+                "dependencies"
+            },
+            result.excluded.join(", ")
+        ));
+    }
+}
+
 #[instrument(skip_all, fields(targets = ?targets))]
 async fn handle_clean(session: &dyn Session, targets: Vec<String>) -> Result<()> {
     let manager = session.state()?;
+    let clean_builds = targets.is_empty()
+        || targets.contains(&"builds".to_string())
+        || targets.contains(&"all".to_string());
+    let clean_cache =
+        targets.contains(&"cache".to_string()) || targets.contains(&"all".to_string());
 
     if session.config().app_config().dry_run {
+        let plan: Vec<&str> = [("builds", clean_builds), ("cache", clean_cache)]
+            .into_iter()
+            .filter_map(|(target, planned)| planned.then_some(target))
+            .collect();
+        record_json(session, "plan", &plan);
         session.display().status().section("Planned Actions");
-        if targets.is_empty()
-            || targets.contains(&"builds".to_string())
-            || targets.contains(&"all".to_string())
-        {
+        if clean_builds {
             session
                 .display()
                 .status()
                 .info("Would clean build artifacts in dist/");
         }
-        if targets.contains(&"cache".to_string()) || targets.contains(&"all".to_string()) {
+        if clean_cache {
             session.display().status().info("Would clean cached data");
         }
         session
@@ -4101,10 +4685,9 @@ async fn handle_clean(session: &dyn Session, targets: Vec<String>) -> Result<()>
         return Ok(());
     }
 
-    if targets.is_empty()
-        || targets.contains(&"builds".to_string())
-        || targets.contains(&"all".to_string())
-    {
+    // Whether each requested target had anything to remove
+    let mut cleaned = serde_json::Map::new();
+    if clean_builds {
         let had_dist = session
             .filesystem()
             .is_directory(&crate::empack::state::artifact_root(&manager.workdir));
@@ -4130,16 +4713,20 @@ async fn handle_clean(session: &dyn Session, targets: Vec<String>) -> Result<()>
             "No build artifacts to clean"
         };
         session.display().status().complete(message);
+        cleaned.insert("builds".into(), had_dist.into());
     }
 
-    if targets.contains(&"cache".to_string()) || targets.contains(&"all".to_string()) {
+    if clean_cache {
         session.display().status().checking("Cleaning cache");
-        if clean_empack_cache(session.filesystem())? {
+        let removed = clean_empack_cache(session.filesystem())?;
+        if removed {
             session.display().status().complete("Cache cleaned");
         } else {
             session.display().status().info("No cache data to clean");
         }
+        cleaned.insert("cache".into(), removed.into());
     }
+    record_json(session, "cleaned", &cleaned);
 
     Ok(())
 }
@@ -4180,8 +4767,8 @@ fn clean_empack_cache(filesystem: &dyn FileSystemProvider) -> Result<bool> {
     Ok(removed_any)
 }
 
-#[instrument(skip_all)]
-async fn handle_sync(session: &dyn Session) -> Result<()> {
+#[instrument(skip_all, fields(locked))]
+async fn handle_sync(session: &dyn Session, locked: bool) -> Result<()> {
     let start = std::time::Instant::now();
     let manager = session.state()?;
 
@@ -4192,6 +4779,13 @@ async fn handle_sync(session: &dyn Session) -> Result<()> {
     let workdir = manager.workdir.clone();
     let config_manager = session.filesystem().config_manager(workdir.clone());
 
+    // A locked sync must not resolve anything new, so reject a stale lock up front
+    let lock = if locked {
+        Some(load_agreeing_lockfile(session, &workdir)?)
+    } else {
+        None
+    };
+
     let client = session.network().http_client()?;
     let curseforge_api_key = session
         .config()
@@ -4282,6 +4876,7 @@ async fn handle_sync(session: &dyn Session) -> Result<()> {
                         project_id: project_info.project_id.clone(),
                         project_type: search.project_type.unwrap_or(resolved_project_type),
                         version: None,
+                        side: None,
                     };
 
                     // Remove old search entry if slug differs from resolved slug
@@ -4315,10 +4910,20 @@ async fn handle_sync(session: &dyn Session) -> Result<()> {
     }
 
     // Phase 2: Build project plan (now all resolvable entries are Resolved)
-    let project_plan = config_manager
+    let mut project_plan = config_manager
         .create_project_plan()
         .context("Failed to load empack.yml configuration")?;
 
+    if let Some(lock) = &lock {
+        for dep_spec in &mut project_plan.dependencies {
+            if let DependencySource::Platform { version_pin, .. } = &mut dep_spec.source
+                && let Some(locked_dep) = lock.dependencies.get(&dep_spec.key)
+            {
+                version_pin.clone_from(&locked_dep.version_id);
+            }
+        }
+    }
+
     let local_dependency_issues =
         validate_local_dependencies(session.filesystem(), &workdir, &project_plan);
     if !local_dependency_issues.is_empty() {
@@ -4350,7 +4955,7 @@ async fn handle_sync(session: &dyn Session) -> Result<()> {
     ));
 
     // Get currently installed mods
-    let installed_mods = match session.packwiz().get_installed_mods(&workdir) {
+    let mut installed_mods = match session.packwiz().get_installed_mods(&workdir) {
         Ok(mods) => {
             session
                 .display()
@@ -4371,6 +4976,61 @@ async fn handle_sync(session: &dyn Session) -> Result<()> {
         }
     };
 
+    // Installed files that drifted from the lock are reinstalled at the locked version
+    if let Some(lock) = &lock {
+        let datapack_folder = config_manager.datapack_folder();
+        for dep_spec in &project_plan.dependencies {
+            if !installed_mods.contains(&dep_spec.key) {
+                continue;
+            }
+            let (
+                DependencySource::Platform {
+                    project_id,
+                    project_platform,
+                    version_pin,
+                },
+                Some(locked_dep),
+            ) = (&dep_spec.source, lock.dependencies.get(&dep_spec.key))
+            else {
+                continue;
+            };
+            let record = DependencyRecord {
+                status: DependencyStatus::Resolved,
+                title: dep_spec.search_query.clone(),
+                platform: *project_platform,
+                project_id: project_id.clone(),
+                project_type: dep_spec.project_type,
+                version: version_pin.clone(),
+                side: None,
+            };
+            let drift = find_dependency_metafile(
+                session.filesystem(),
+                &workdir,
+                &dep_spec.key,
+                Some(dep_spec.project_type),
+                datapack_folder.as_deref(),
+            )
+            .and_then(|path| {
+                crate::empack::lockfile::LockedDependency::from_metafile(
+                    session.filesystem(),
+                    &record,
+                    &path,
+                )
+            })
+            .map_or_else(
+                || Some("unreadable .pw.toml".to_string()),
+                |installed| locked_dep.file_drift(&installed),
+            );
+            if let Some(drift) = drift {
+                session.display().status().info(&format!(
+                    "Reinstalling {} from empack.lock: {}",
+                    dep_spec.key, drift
+                ));
+                installed_mods.remove(&dep_spec.key);
+            }
+        }
+    }
+
     let sync_plan = build_sync_plan(&project_plan, &installed_mods);
 
     // Protect installed mods whose Search entries failed resolution from removal
@@ -4445,6 +5105,8 @@ async fn handle_sync(session: &dyn Session) -> Result<()> {
         }
     }
 
+    record_json(session, "plan", &planned_actions);
+
     // Show planned actions
     if planned_actions.is_empty() {
         if planning_failure_count > 0 {
@@ -4468,6 +5130,7 @@ async fn handle_sync(session: &dyn Session) -> Result<()> {
                 .status()
                 .complete("Dry run complete - tracked local dependency drift reported");
         } else {
+            finish_sync_lockfile(session, &workdir, lock.as_ref())?;
             session
                 .display()
                 .status()
@@ -4543,13 +5206,16 @@ async fn handle_sync(session: &dyn Session) -> Result<()> {
     }
 
     for action in planned_actions {
+        if crate::interrupt_requested() {
+            return Err(anyhow::anyhow!("Interrupted"));
+        }
         match action {
             SyncExecutionAction::Add {
-                key: _,
+                key,
                 title,
                 commands,
-                resolved_project_id: _,
-                resolved_platform: _,
+                resolved_project_id,
+                resolved_platform,
             } => {
                 session
                     .display()
@@ -4592,6 +5258,31 @@ async fn handle_sync(session: &dyn Session) -> Result<()> {
                     Ok(_) => {
                         session.display().status().success("Added", "successfully");
                         success_count += 1;
+
+                        if let Ok(Some((found_key, DependencyEntry::Resolved(mut record)))) =
+                            config_manager.find_dependency(&key)
+                            && record.side.is_none()
+                        {
+                            record.side = lookup_dependency_side(
+                                session,
+                                resolver.as_ref(),
+                                &workdir,
+                                &found_key,
+                                resolved_platform,
+                                &resolved_project_id,
+                                record.project_type,
+                                config_manager.datapack_folder().as_deref(),
+                            )
+                            .await;
+                            if record.side.is_some()
+                                && let Err(e) = config_manager.add_dependency(&found_key, record)
+                            {
+                                session.display().status().warning(&format!(
+                                    "Failed to record side for '{}': {}",
+                                    found_key, e
+                                ));
+                            }
+                        }
                     }
                     Err(_) => {
                         let e = last_error
@@ -4658,6 +5349,18 @@ async fn handle_sync(session: &dyn Session) -> Result<()> {
         }
     }
 
+    if failure_count == 0 {
+        finish_sync_lockfile(session, &workdir, lock.as_ref())?;
+    } else if lock.is_none() {
+        refresh_lockfile(session, &workdir);
+    }
+
+    record_json(
+        session,
+        "summary",
+        &serde_json::json!({ "succeeded": success_count, "failed": failure_count }),
+    );
+
     session.display().status().section("Sync Summary");
     session
         .display()
@@ -4695,68 +5398,2183 @@ async fn handle_sync(session: &dyn Session) -> Result<()> {
     }
 }
 
-async fn download_to_cache(
+/// Load empack.lock for `sync --locked` and reject it when it disagrees with empack.yml.
+fn load_agreeing_lockfile(
     session: &dyn Session,
-    url: &str,
-    dest: &std::path::Path,
-    label: &str,
-) -> Result<()> {
-    if let Some(parent) = dest.parent() {
-        session.filesystem().create_dir_all(parent)?;
-    }
+    workdir: &Path,
+) -> Result<crate::empack::lockfile::EmpackLock> {
+    use crate::empack::lockfile::{LockfileError, check_agreement, load_lock};
 
-    let client = session.network().http_client()?;
-    let max_attempts: u32 = 3;
-    let mut last_error = None;
+    let lock = load_lock(session.filesystem(), workdir)?.ok_or(LockfileError::Missing)?;
+    let empack_config = session
+        .filesystem()
+        .config_manager(workdir.to_path_buf())
+        .load_empack_config()
+        .context("Failed to load empack.yml configuration")?;
 
-    for attempt in 0..max_attempts {
-        if attempt > 0 {
-            tokio::time::sleep(std::time::Duration::from_secs(1 << (attempt - 1))).await;
+    let mismatches = check_agreement(&empack_config.empack.dependencies, &lock);
+    if !mismatches.is_empty() {
+        session
+            .display()
+            .status()
+            .section("empack.lock does not match empack.yml");
+        for mismatch in &mismatches {
+            session.display().status().error(mismatch, "");
+        }
+        session
+            .display()
+            .status()
+            .subtle("   Run 'empack sync' without --locked to update empack.lock");
+        return Err(LockfileError::OutOfDate {
+            details: mismatches.join("; "),
         }
+        .into());
+    }
 
-        match client
-            .get(url)
-            .timeout(std::time::Duration::from_secs(
-                session.config().app_config().net_timeout,
-            ))
-            .send()
-            .await
-        {
-            Ok(resp) if resp.status().is_success() => match resp.bytes().await {
-                Ok(bytes) => {
-                    session
-                        .filesystem()
-                        .write_bytes(dest, bytes.as_ref())
-                        .context(format!("Failed to write {} to cache", label))?;
-                    return Ok(());
-                }
-                Err(e) => {
-                    last_error = Some(format!("Failed to read response body for {}: {}", label, e));
-                    continue;
-                }
-            },
-            Ok(resp) => {
-                let status = resp.status();
-                last_error = Some(format!("HTTP {} for {}", status, label));
-                if status.is_client_error() {
-                    break;
-                }
-                continue;
-            }
-            Err(e) => {
-                last_error = Some(format!("Failed to download {}: {}", label, e));
-                continue;
+    Ok(lock)
+}
+
+/// Lock entries for every resolved and local dependency whose file is present.
+fn collect_locked_dependencies(
+    session: &dyn Session,
+    workdir: &Path,
+) -> Result<crate::empack::lockfile::EmpackLock> {
+    use crate::empack::lockfile::{EmpackLock, LockedDependency};
+
+    let config_manager = session.filesystem().config_manager(workdir.to_path_buf());
+    let empack_config = config_manager
+        .load_empack_config()
+        .context("Failed to load empack.yml configuration")?;
+    let datapack_folder = config_manager.datapack_folder();
+
+    let mut lock = EmpackLock::default();
+    for (key, entry) in &empack_config.empack.dependencies {
+        let locked = match entry {
+            DependencyEntry::Resolved(record) => find_dependency_metafile(
+                session.filesystem(),
+                workdir,
+                key,
+                Some(record.project_type),
+                datapack_folder.as_deref(),
+            )
+            .and_then(|path| LockedDependency::from_metafile(session.filesystem(), record, &path)),
+            DependencyEntry::Local(record) => {
+                LockedDependency::from_local(session.filesystem(), workdir, record).ok()
             }
+            DependencyEntry::Search(_) => None,
+        };
+        if let Some(locked) = locked {
+            lock.dependencies.insert(key.clone(), locked);
         }
     }
 
-    Err(anyhow::anyhow!(
-        "{}",
-        last_error.unwrap_or_else(|| format!(
-            "Failed to download {} after {} attempts",
-            label, max_attempts
-        ))
-    ))
+    Ok(lock)
+}
+
+/// Rewrite empack.lock from the current project state.
+///
+/// Failures are reported as warnings: the command that changed the project
+/// has already succeeded by the time the lock is refreshed.
+fn refresh_lockfile(session: &dyn Session, workdir: &Path) {
+    let result = collect_locked_dependencies(session, workdir)
+        .and_then(|lock| crate::empack::lockfile::write_lock(session.filesystem(), workdir, &lock));
+    if let Err(e) = result {
+        session
+            .display()
+            .status()
+            .warning(&format!("Failed to update empack.lock: {}", e));
+    }
+}
+
+/// Refresh empack.lock, or with `--locked` verify the installed files match it.
+fn finish_sync_lockfile(
+    session: &dyn Session,
+    workdir: &Path,
+    lock: Option<&crate::empack::lockfile::EmpackLock>,
+) -> Result<()> {
+    let Some(lock) = lock else {
+        refresh_lockfile(session, workdir);
+        return Ok(());
+    };
+
+    let installed = collect_locked_dependencies(session, workdir)?;
+    let drift: Vec<String> = lock
+        .dependencies
+        .iter()
+        .filter_map(|(key, locked)| match installed.dependencies.get(key) {
+            None => Some(format!("{key}: file missing")),
+            Some(actual) => locked
+                .file_drift(actual)
+                .map(|reason| format!("{key}: {reason}")),
+        })
+        .collect();
+
+    if drift.is_empty() {
+        session
+            .display()
+            .status()
+            .success("Installed files match empack.lock", "");
+        return Ok(());
+    }
+
+    session
+        .display()
+        .status()
+        .section("Installed files differ from empack.lock");
+    for entry in &drift {
+        session.display().status().error(entry, "");
+    }
+    Err(crate::empack::lockfile::LockfileError::Drift {
+        details: drift.join("; "),
+    }
+    .into())
+}
+
+/// One row of `empack list` output, derived from an empack.yml dependency entry.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+struct DependencyListRow {
+    key: String,
+    title: String,
+    status: &'static str,
+    platform: Option<ProjectPlatform>,
+    project_id: Option<String>,
+    version: Option<String>,
+    project_type: Option<ProjectType>,
+    metafile_present: Option<bool>,
+}
+
+fn find_dependency_metafile(
+    filesystem: &dyn FileSystemProvider,
+    workdir: &Path,
+    key: &str,
+    project_type: Option<ProjectType>,
+    datapack_folder: Option<&str>,
+) -> Option<PathBuf> {
+    let folder_for = |pt: ProjectType| match pt {
+        ProjectType::Datapack => {
+            datapack_folder.unwrap_or(content_folder_for_type(ProjectType::Datapack))
+        }
+        other => content_folder_for_type(other),
+    };
+    let folders: Vec<&str> = match project_type {
+        Some(pt) => vec![folder_for(pt)],
+        None => [
+            ProjectType::Mod,
+            ProjectType::ResourcePack,
+            ProjectType::Shader,
+            ProjectType::Datapack,
+        ]
+        .into_iter()
+        .map(folder_for)
+        .collect(),
+    };
+    folders
+        .into_iter()
+        .map(|folder| {
+            workdir
+                .join("pack")
+                .join(folder)
+                .join(format!("{key}.pw.toml"))
+        })
+        .find(|path| filesystem.exists(path))
+}
+
+/// Ask the platform which side a freshly added dependency belongs to.
+///
+/// Returns `None` when the dependency installs on both sides or the lookup
+/// fails, so `empack.yml` only records real restrictions.
+#[allow(clippy::too_many_arguments)]
+async fn lookup_dependency_side(
+    session: &dyn Session,
+    resolver: &dyn ProjectResolverTrait,
+    workdir: &Path,
+    key: &str,
+    platform: ProjectPlatform,
+    project_id: &str,
+    project_type: ProjectType,
+    datapack_folder: Option<&str>,
+) -> Option<DependencySide> {
+    let file_id = find_dependency_metafile(
+        session.filesystem(),
+        workdir,
+        key,
+        Some(project_type),
+        datapack_folder,
+    )
+    .and_then(|path| crate::empack::updates::read_installed_file(session.filesystem(), &path))
+    .and_then(|installed| installed.version_id);
+
+    match resolver
+        .fetch_side_env(platform, project_id, file_id.as_deref())
+        .await
+    {
+        Ok(env) => env.side().restriction(),
+        Err(e) => {
+            tracing::debug!("side lookup failed for {}: {}", key, e);
+            None
+        }
+    }
+}
+
+fn collect_dependency_list_rows(
+    filesystem: &dyn FileSystemProvider,
+    workdir: &Path,
+    project: &crate::empack::config::EmpackProjectConfig,
+    project_type: Option<ProjectType>,
+    platform: Option<ProjectPlatform>,
+    sort: ListSort,
+) -> Vec<DependencyListRow> {
+    let datapack_folder = project.datapack_folder.as_deref();
+    let mut rows: Vec<DependencyListRow> = project
+        .dependencies
+        .iter()
+        .map(|(key, entry)| match entry {
+            DependencyEntry::Resolved(record) => DependencyListRow {
+                key: key.clone(),
+                title: record.title.clone(),
+                status: "resolved",
+                platform: Some(record.platform),
+                project_id: Some(record.project_id.clone()),
+                version: record.version.clone(),
+                project_type: Some(record.project_type),
+                metafile_present: Some(
+                    find_dependency_metafile(
+                        filesystem,
+                        workdir,
+                        key,
+                        Some(record.project_type),
+                        datapack_folder,
+                    )
+                    .is_some(),
+                ),
+            },
+            DependencyEntry::Local(record) => DependencyListRow {
+                key: key.clone(),
+                title: record.title.clone(),
+                status: "local",
+                platform: None,
+                project_id: None,
+                version: None,
+                project_type: Some(record.project_type),
+                metafile_present: None,
+            },
+            DependencyEntry::Search(search) => DependencyListRow {
+                key: key.clone(),
+                title: search.title.clone(),
+                status: "search",
+                platform: search.platform,
+                project_id: None,
+                version: None,
+                project_type: search.project_type,
+                metafile_present: Some(
+                    find_dependency_metafile(
+                        filesystem,
+                        workdir,
+                        key,
+                        search.project_type,
+                        datapack_folder,
+                    )
+                    .is_some(),
+                ),
+            },
+        })
+        .filter(|row| project_type.is_none() || row.project_type == project_type)
+        .filter(|row| platform.is_none() || row.platform == platform)
+        .collect();
+
+    if sort == ListSort::Title {
+        rows.sort_by(|a, b| {
+            a.title
+                .to_lowercase()
+                .cmp(&b.title.to_lowercase())
+                .then_with(|| a.key.cmp(&b.key))
+        });
+    }
+
+    rows
+}
+
+#[instrument(skip_all)]
+async fn handle_list(
+    session: &dyn Session,
+    project_type: Option<CliProjectType>,
+    platform: Option<CliProjectPlatform>,
+    sort: ListSort,
+) -> Result<()> {
+    let start = std::time::Instant::now();
+    let manager = session.state()?;
+
+    let current_state = manager.discover_state()?;
+    ensure_configured_project(
+        session,
+        &manager.workdir,
+        current_state,
+        "listing dependencies",
+    )?;
+
+    let workdir = manager.workdir.clone();
+    let config_manager = session.filesystem().config_manager(workdir.clone());
+    let empack_config = config_manager
+        .load_empack_config()
+        .context("Failed to load empack.yml configuration")?;
+
+    let rows = collect_dependency_list_rows(
+        session.filesystem(),
+        &workdir,
+        &empack_config.empack,
+        project_type.as_ref().map(|pt| pt.to_project_type()),
+        platform.map(|p| p.to_project_platform()),
+        sort,
+    );
+    record_json(session, "dependencies", &rows);
+
+    if rows.is_empty() {
+        if empack_config.empack.dependencies.is_empty() {
+            session
+                .display()
+                .status()
+                .info("No dependencies declared in empack.yml");
+        } else {
+            session
+                .display()
+                .status()
+                .info("No dependencies match the given filters");
+        }
+    } else {
+        session
+            .display()
+            .status()
+            .section(&format!("Dependencies ({})", rows.len()));
+
+        let cells: Vec<Vec<String>> = rows
+            .iter()
+            .map(|row| {
+                vec![
+                    row.key.clone(),
+                    row.title.clone(),
+                    row.status.to_string(),
+                    row.platform
+                        .map(|p| p.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                    row.project_id.clone().unwrap_or_else(|| "-".to_string()),
+                    row.version.clone().unwrap_or_else(|| "-".to_string()),
+                    row.project_type
+                        .map(|pt| crate::application::sync::project_type_arg(pt).to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                    match row.metafile_present {
+                        Some(true) => "yes".to_string(),
+                        Some(false) => "missing".to_string(),
+                        None => "-".to_string(),
+                    },
+                ]
+            })
+            .collect();
+        let table_rows: Vec<Vec<&str>> = cells
+            .iter()
+            .map(|row| row.iter().map(String::as_str).collect())
+            .collect();
+        session.display().table().table(
+            &[
+                "Slug",
+                "Title",
+                "Status",
+                "Platform",
+                "Project ID",
+                "Version",
+                "Type",
+                ".pw.toml",
+            ],
+            &table_rows,
+        );
+
+        let missing = rows
+            .iter()
+            .filter(|row| row.metafile_present == Some(false))
+            .count();
+        if missing > 0 {
+            session.display().status().warning(&format!(
+                "{} dependenc{} without a .pw.toml; run 'empack sync' to reconcile",
+                missing,
+                if missing == 1 { "y" } else { "ies" }
+            ));
+        }
+    }
+
+    tracing::info!(
+        command = "list",
+        duration_ms = start.elapsed().as_millis() as u64,
+        dependency_count = rows.len(),
+        exit_code = 0,
+        "command complete"
+    );
+
+    Ok(())
+}
+
+/// Newer-file check for one resolved dependency.
+struct DependencyUpdateCheck {
+    key: String,
+    record: DependencyRecord,
+    installed: Option<crate::empack::updates::InstalledFile>,
+    latest: std::result::Result<Option<crate::empack::updates::LatestFile>, String>,
+}
+
+impl DependencyUpdateCheck {
+    fn current_version(&self) -> Option<&str> {
+        self.installed
+            .as_ref()
+            .and_then(|installed| installed.version_id.as_deref())
+            .or(self.record.version.as_deref())
+    }
+
+    fn available_update(&self) -> Option<&crate::empack::updates::LatestFile> {
+        match &self.latest {
+            Ok(Some(latest)) if Some(latest.version_id.as_str()) != self.current_version() => {
+                Some(latest)
+            }
+            _ => None,
+        }
+    }
+}
+
+async fn check_dependency_updates(
+    session: &dyn Session,
+    workdir: &Path,
+    selected: &[(String, DependencyRecord)],
+    api_bases: &crate::empack::updates::UpdateApiBases,
+) -> Result<Vec<DependencyUpdateCheck>> {
+    let config_manager = session.filesystem().config_manager(workdir.to_path_buf());
+    let project_plan = config_manager
+        .create_project_plan()
+        .context("Failed to load empack.yml configuration")?;
+    let datapack_folder = config_manager.datapack_folder();
+    let mut minecraft_versions = vec![project_plan.minecraft_version.clone()];
+    for version in config_manager
+        .acceptable_game_versions()
+        .unwrap_or_default()
+    {
+        if !minecraft_versions.contains(&version) {
+            minecraft_versions.push(version);
+        }
+    }
+
+    let client = session.network().http_client()?;
+    let curseforge_api_key = session
+        .config()
+        .app_config()
+        .curseforge_api_client_key
+        .clone();
+
+    let progress = session.display().progress().bar(selected.len() as u64);
+    progress.set_message("Checking for updates");
+
+    let mut checks = Vec::with_capacity(selected.len());
+    for (key, record) in selected {
+        progress.tick(&record.title);
+        let installed = find_dependency_metafile(
+            session.filesystem(),
+            workdir,
+            key,
+            Some(record.project_type),
+            datapack_folder.as_deref(),
+        )
+        .and_then(|path| crate::empack::updates::read_installed_file(session.filesystem(), &path));
+        let latest = crate::empack::updates::fetch_latest_file(
+            &client,
+            api_bases,
+            curseforge_api_key.as_deref(),
+            record.platform,
+            &record.project_id,
+            crate::empack::updates::UpdateFilter {
+                minecraft_versions: &minecraft_versions,
+                loader: project_plan.loader,
+                project_type: record.project_type,
+            },
+        )
+        .await
+        .map_err(|e| e.to_string());
+        checks.push(DependencyUpdateCheck {
+            key: key.clone(),
+            record: record.clone(),
+            installed,
+            latest,
+        });
+        progress.inc();
+    }
+    progress.finish_clear();
+
+    Ok(checks)
+}
+
+fn resolved_dependencies(
+    empack_config: &crate::empack::config::EmpackConfig,
+) -> Vec<(String, DependencyRecord)> {
+    empack_config
+        .empack
+        .dependencies
+        .iter()
+        .filter_map(|(key, entry)| match entry {
+            DependencyEntry::Resolved(record) => Some((key.clone(), record.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+#[instrument(skip_all)]
+async fn handle_outdated(session: &dyn Session) -> Result<()> {
+    handle_outdated_with_api_bases(
+        session,
+        &crate::empack::updates::UpdateApiBases::production(),
+    )
+    .await
+}
+
+async fn handle_outdated_with_api_bases(
+    session: &dyn Session,
+    api_bases: &crate::empack::updates::UpdateApiBases,
+) -> Result<()> {
+    let start = std::time::Instant::now();
+    let manager = session.state()?;
+
+    let current_state = manager.discover_state()?;
+    ensure_configured_project(
+        session,
+        &manager.workdir,
+        current_state,
+        "checking for updates",
+    )?;
+
+    let workdir = manager.workdir.clone();
+    let config_manager = session.filesystem().config_manager(workdir.clone());
+    let empack_config = config_manager
+        .load_empack_config()
+        .context("Failed to load empack.yml configuration")?;
+    let selected = resolved_dependencies(&empack_config);
+
+    if selected.is_empty() {
+        session
+            .display()
+            .status()
+            .info("No resolved dependencies to check");
+        return Ok(());
+    }
+
+    let checks = check_dependency_updates(session, &workdir, &selected, api_bases).await?;
+
+    let cells: Vec<Vec<String>> = checks
+        .iter()
+        .map(|check| {
+            let (latest, state) = match &check.latest {
+                Ok(Some(latest)) if check.available_update().is_some() => (
+                    latest.version_id.clone(),
+                    if check.record.version.is_some() {
+                        "pinned".to_string()
+                    } else {
+                        "outdated".to_string()
+                    },
+                ),
+                Ok(Some(latest)) => (latest.version_id.clone(), "up to date".to_string()),
+                Ok(None) => ("-".to_string(), "no compatible file".to_string()),
+                Err(_) => ("-".to_string(), "check failed".to_string()),
+            };
+            vec![
+                check.key.clone(),
+                check.record.title.clone(),
+                check.record.platform.to_string(),
+                check.current_version().unwrap_or("-").to_string(),
+                latest,
+                state,
+            ]
+        })
+        .collect();
+    record_json(
+        session,
+        "updates",
+        &cells
+            .iter()
+            .map(|row| {
+                serde_json::json!({
+                    "key": row[0],
+                    "title": row[1],
+                    "platform": row[2],
+                    "current": row[3],
+                    "latest": row[4],
+                    "state": row[5],
+                })
+            })
+            .collect::<Vec<_>>(),
+    );
+    let table_rows: Vec<Vec<&str>> = cells
+        .iter()
+        .map(|row| row.iter().map(String::as_str).collect())
+        .collect();
+
+    session.display().status().section("Dependency Updates");
+    session.display().table().table(
+        &["Slug", "Title", "Platform", "Current", "Latest", "State"],
+        &table_rows,
+    );
+
+    let outdated_count = checks
+        .iter()
+        .filter(|check| check.available_update().is_some())
+        .count();
+    let failures: Vec<(&str, &str)> = checks
+        .iter()
+        .filter_map(|check| match &check.latest {
+            Err(e) => Some((check.key.as_str(), e.as_str())),
+            _ => None,
+        })
+        .collect();
+
+    if outdated_count == 0 {
+        session
+            .display()
+            .status()
+            .success("All checked dependencies are up to date", "");
+    } else {
+        session.display().status().info(&format!(
+            "{} dependenc{} with newer compatible files; run 'empack update' to apply",
+            outdated_count,
+            if outdated_count == 1 { "y" } else { "ies" }
+        ));
+    }
+
+    if !failures.is_empty() {
+        session.display().status().section("Failed checks");
+        for (key, error) in &failures {
+            session.display().status().error(key, error);
+        }
+        anyhow::bail!(
+            "{} dependenc{} could not be checked for updates",
+            failures.len(),
+            if failures.len() == 1 { "y" } else { "ies" }
+        );
+    }
+
+    tracing::info!(
+        command = "outdated",
+        duration_ms = start.elapsed().as_millis() as u64,
+        checked_count = checks.len(),
+        outdated_count,
+        exit_code = 0,
+        "command complete"
+    );
+
+    Ok(())
+}
+
+#[instrument(skip_all, fields(mod_count = mods.len()))]
+async fn handle_update(session: &dyn Session, mods: Vec<String>, force: bool) -> Result<()> {
+    handle_update_with_api_bases(
+        session,
+        mods,
+        force,
+        &crate::empack::updates::UpdateApiBases::production(),
+    )
+    .await
+}
+
+async fn handle_update_with_api_bases(
+    session: &dyn Session,
+    mods: Vec<String>,
+    force: bool,
+    api_bases: &crate::empack::updates::UpdateApiBases,
+) -> Result<()> {
+    let start = std::time::Instant::now();
+    let manager = session.state()?;
+
+    let current_state = manager.discover_state()?;
+    ensure_configured_project(
+        session,
+        &manager.workdir,
+        current_state,
+        "updating dependencies",
+    )?;
+
+    let workdir = manager.workdir.clone();
+    let config_manager = session.filesystem().config_manager(workdir.clone());
+    let empack_config = config_manager
+        .load_empack_config()
+        .context("Failed to load empack.yml configuration")?;
+    let resolved = resolved_dependencies(&empack_config);
+
+    let selected: Vec<(String, DependencyRecord)> = if mods.is_empty() {
+        resolved
+    } else {
+        let mut selected = Vec::new();
+        let mut unknown = Vec::new();
+        for name in &mods {
+            match config_manager
+                .find_dependency(name)
+                .with_context(|| format!("failed to inspect dependency '{name}'"))?
+            {
+                Some((key, DependencyEntry::Resolved(record))) => {
+                    if !selected.iter().any(|(k, _)| k == &key) {
+                        selected.push((key, record));
+                    }
+                }
+                Some((key, _)) => {
+                    session.display().status().warning(&format!(
+                        "Skipping {}: only resolved platform dependencies can be updated",
+                        key
+                    ));
+                }
+                None => unknown.push(name.clone()),
+            }
+        }
+        if !unknown.is_empty() {
+            session
+                .display()
+                .status()
+                .error("Unknown dependencies", &unknown.join(", "));
+            anyhow::bail!(
+                "No dependency named {} in empack.yml",
+                unknown
+                    .iter()
+                    .map(|name| format!("'{name}'"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        selected
+    };
+
+    if selected.is_empty() {
+        session
+            .display()
+            .status()
+            .info("No resolved dependencies to update");
+        return Ok(());
+    }
+
+    let checks = check_dependency_updates(session, &workdir, &selected, api_bases).await?;
+
+    let mut failed = Vec::new();
+    let mut planned = Vec::new();
+    for check in &checks {
+        if let Err(e) = &check.latest {
+            failed.push((check.key.clone(), e.clone()));
+            continue;
+        }
+        let Some(latest) = check.available_update() else {
+            continue;
+        };
+        if check.installed.is_none() {
+            session.display().status().warning(&format!(
+                "Skipping {}: no .pw.toml found (run 'empack sync' first)",
+                check.key
+            ));
+            continue;
+        }
+        if let Some(pin) = &check.record.version
+            && !force
+        {
+            session.display().status().info(&format!(
+                "Skipping {}: pinned to {} (use --force to update)",
+                check.key, pin
+            ));
+            continue;
+        }
+        planned.push((check, latest));
+    }
+
+    if session.config().app_config().dry_run {
+        session.display().status().section("Planned Actions");
+        for (check, latest) in &planned {
+            session.display().status().info(&format!(
+                "Would update: {} ({} -> {})",
+                check.key,
+                check.current_version().unwrap_or("-"),
+                latest.version_id
+            ));
+        }
+        session
+            .display()
+            .status()
+            .complete("Dry run complete - no changes applied");
+        return Ok(());
+    }
+
+    if planned.is_empty() && failed.is_empty() {
+        session
+            .display()
+            .status()
+            .success("All selected dependencies are up to date", "");
+        return Ok(());
+    }
+
+    session.display().status().section(&format!(
+        "Updating {} dependenc{}",
+        planned.len(),
+        if planned.len() == 1 { "y" } else { "ies" }
+    ));
+
+    let use_no_refresh = planned.len() > 1;
+    let mut updated = Vec::new();
+    for (check, latest) in &planned {
+        session.display().status().checking(&format!(
+            "Updating {}: {} -> {}",
+            check.key,
+            check.current_version().unwrap_or("-"),
+            latest.version_id
+        ));
+
+        let commands = match crate::application::sync::build_packwiz_add_commands(
+            &check.record.project_id,
+            check.record.platform,
+            Some(&latest.version_id),
+        ) {
+            Ok(commands) => commands,
+            Err(e) => {
+                failed.push((check.key.clone(), e.to_string()));
+                continue;
+            }
+        };
+
+        let mut result = Ok(());
+        for command in &commands {
+            let mut args: Vec<String> = Vec::new();
+            if use_no_refresh {
+                args.push("--no-refresh".to_string());
+            }
+            args.extend(command.iter().cloned());
+            let arg_refs = args.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+            result = match execute_process_with_live_issues(
+                session,
+                session.packwiz_bin(),
+                &arg_refs,
+                &workdir.join("pack"),
+            ) {
+                Ok(output) if output.success => Ok(()),
+                Ok(output) => Err(anyhow::anyhow!(
+                    "Packwiz command failed: {}",
+                    output.error_output()
+                )),
+                Err(error) => Err(anyhow::anyhow!(error)),
+            };
+            if result.is_err() {
+                break;
+            }
+        }
+
+        if let Err(e) = result {
+            session
+                .display()
+                .status()
+                .error(&format!("Failed to update {}", check.key), &e.to_string());
+            failed.push((check.key.clone(), e.to_string()));
+            continue;
+        }
+
+        if check.record.version.is_some() {
+            let record = DependencyRecord {
+                version: Some(latest.version_id.clone()),
+                ..check.record.clone()
+            };
+            if let Err(e) = config_manager.add_dependency(&check.key, record) {
+                let detail = format!(
+                    "{} was updated, but its empack.yml pin could not be rewritten: {}",
+                    check.key, e
+                );
+                session
+                    .display()
+                    .status()
+                    .error("Failed to update empack.yml", &detail);
+                failed.push((check.key.clone(), detail));
+                continue;
+            }
+        }
+
+        session.display().status().success(
+            "Updated",
+            &format!("{} ({})", check.key, latest.version_name),
+        );
+        updated.push(check.key.clone());
+    }
+
+    if use_no_refresh {
+        let refresh_output = execute_process_with_live_issues(
+            session,
+            session.packwiz_bin(),
+            &["refresh"],
+            &workdir.join("pack"),
+        )?;
+        if !refresh_output.success {
+            anyhow::bail!("packwiz refresh failed: {}", refresh_output.error_output());
+        }
+    }
+
+    if !updated.is_empty() {
+        refresh_lockfile(session, &workdir);
+    }
+
+    session.display().status().section("Update Summary");
+    session
+        .display()
+        .status()
+        .success("Successfully updated", &updated.len().to_string());
+    session
+        .display()
+        .status()
+        .info(&format!("Failed: {}", failed.len()));
+
+    if !failed.is_empty() {
+        session.display().status().section("Failed updates");
+        for (key, error) in &failed {
+            session.display().status().error(key, error);
+        }
+        let summary = failed
+            .iter()
+            .map(|(key, err)| format!("{}: {}", key, err))
+            .collect::<Vec<_>>()
+            .join("; ");
+        return Err(anyhow::anyhow!(
+            "{} dependenc{} failed to update: {}",
+            failed.len(),
+            if failed.len() == 1 { "y" } else { "ies" },
+            summary
+        ));
+    }
+
+    tracing::info!(
+        command = "update",
+        duration_ms = start.elapsed().as_millis() as u64,
+        updated_count = updated.len(),
+        exit_code = 0,
+        "command complete"
+    );
+
+    Ok(())
+}
+
+/// Migration check result for one resolved dependency.
+#[derive(serde::Serialize)]
+struct MigrationRow {
+    key: String,
+    #[serde(skip)]
+    record: DependencyRecord,
+    title: String,
+    platform: ProjectPlatform,
+    project_id: String,
+    #[serde(flatten)]
+    status: crate::empack::migrate::MigrationStatus,
+}
+
+#[instrument(skip_all)]
+async fn handle_migrate(session: &dyn Session, args: &MigrateArgs) -> Result<()> {
+    handle_migrate_with_api_bases(
+        session,
+        args,
+        &crate::empack::updates::UpdateApiBases::production(),
+    )
+    .await
+}
+
+async fn handle_migrate_with_api_bases(
+    session: &dyn Session,
+    args: &MigrateArgs,
+    api_bases: &crate::empack::updates::UpdateApiBases,
+) -> Result<()> {
+    use crate::empack::migrate::{MigrationStatus, MigrationTarget, check_dependency_migration};
+
+    let start = std::time::Instant::now();
+    let manager = session.state()?;
+
+    let current_state = manager.discover_state()?;
+    ensure_configured_project(
+        session,
+        &manager.workdir,
+        current_state,
+        "migrating the pack",
+    )?;
+
+    let workdir = manager.workdir.clone();
+    let config_manager = session.filesystem().config_manager(workdir.clone());
+    let project_plan = config_manager
+        .create_project_plan()
+        .context("Failed to load empack.yml configuration")?;
+    let loader = match &args.loader {
+        Some(name) => Some(ModLoader::parse(name)?),
+        None => project_plan.loader,
+    };
+    let target = MigrationTarget {
+        minecraft_version: args.mc_version.clone(),
+        loader,
+    };
+    let describe = |version: &str, loader: Option<ModLoader>| match loader {
+        Some(loader) => format!("{} {}", version, loader_arg(loader)),
+        None => format!("{} vanilla", version),
+    };
+
+    let empack_config = config_manager
+        .load_empack_config()
+        .context("Failed to load empack.yml configuration")?;
+    let selected = resolved_dependencies(&empack_config);
+    let local_count = empack_config
+        .empack
+        .dependencies
+        .values()
+        .filter(|entry| matches!(entry, DependencyEntry::Local(_)))
+        .count();
+
+    session.display().status().section(&format!(
+        "Migration: {} -> {}",
+        describe(&project_plan.minecraft_version, project_plan.loader),
+        describe(&target.minecraft_version, target.loader)
+    ));
+
+    let client = session.network().http_client()?;
+    let curseforge_api_key = session
+        .config()
+        .app_config()
+        .curseforge_api_client_key
+        .clone();
+    let resolver = session
+        .network()
+        .project_resolver(client.clone(), curseforge_api_key.clone());
+
+    let progress = session.display().progress().bar(selected.len() as u64);
+    progress.set_message("Checking target compatibility");
+    let mut rows = Vec::with_capacity(selected.len());
+    for (key, record) in selected {
+        progress.tick(&record.title);
+        let status = check_dependency_migration(
+            &client,
+            api_bases,
+            curseforge_api_key.as_deref(),
+            resolver.as_ref(),
+            &record,
+            &target,
+        )
+        .await;
+        rows.push(MigrationRow {
+            key,
+            title: record.title.clone(),
+            platform: record.platform,
+            project_id: record.project_id.clone(),
+            record,
+            status,
+        });
+        progress.inc();
+    }
+    progress.finish_clear();
+    record_json(session, "migration", &rows);
+
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            let target_file = match &row.status {
+                MigrationStatus::Ready { version_name, .. } => version_name.clone(),
+                MigrationStatus::OtherPlatform {
+                    platform,
+                    project_id,
+                    ..
+                } => format!("{} {}", platform, project_id),
+                MigrationStatus::Missing | MigrationStatus::Failed { .. } => "-".to_string(),
+            };
+            vec![
+                row.key.clone(),
+                row.title.clone(),
+                row.platform.to_string(),
+                target_file,
+                row.status.label().to_string(),
+            ]
+        })
+        .collect();
+    let table_rows: Vec<Vec<&str>> = cells
+        .iter()
+        .map(|row| row.iter().map(String::as_str).collect())
+        .collect();
+    if !table_rows.is_empty() {
+        session.display().table().table(
+            &["Slug", "Title", "Platform", "Target file", "State"],
+            &table_rows,
+        );
+    }
+
+    let count = |label: &str| {
+        rows.iter()
+            .filter(|row| row.status.label() == label)
+            .count()
+    };
+    session.display().status().info(&format!(
+        "Ready: {}, other platform only: {}, missing: {}",
+        count("ready"),
+        count("other platform"),
+        count("missing")
+    ));
+    if local_count > 0 {
+        session.display().status().warning(&format!(
+            "{} local dependenc{} not checked; verify {} by hand",
+            local_count,
+            if local_count == 1 { "y is" } else { "ies are" },
+            if local_count == 1 { "it" } else { "them" }
+        ));
+    }
+
+    let failures: Vec<(&str, &str)> = rows
+        .iter()
+        .filter_map(|row| match &row.status {
+            MigrationStatus::Failed { error } => Some((row.key.as_str(), error.as_str())),
+            _ => None,
+        })
+        .collect();
+    if !failures.is_empty() {
+        session.display().status().section("Failed checks");
+        for (key, error) in &failures {
+            session.display().status().error(key, error);
+        }
+        anyhow::bail!(
+            "{} dependenc{} could not be checked for migration",
+            failures.len(),
+            if failures.len() == 1 { "y" } else { "ies" }
+        );
+    }
+
+    if !args.apply {
+        session.display().status().subtle(&format!(
+            "   Run 'empack migrate --mc-version {} --apply' to rewrite the project",
+            target.minecraft_version
+        ));
+        tracing::info!(
+            command = "migrate",
+            duration_ms = start.elapsed().as_millis() as u64,
+            checked_count = rows.len(),
+            exit_code = 0,
+            "command complete"
+        );
+        return Ok(());
+    }
+
+    let loader_version = match target.loader {
+        Some(loader) => {
+            let loader_str = loader_arg(loader);
+            let version = match &args.loader_version {
+                Some(version) => version.clone(),
+                None => {
+                    let version_fetcher = crate::empack::versions::VersionFetcher::new(
+                        session.network(),
+                        session.filesystem(),
+                    )?;
+                    fetch_loader_versions(
+                        session,
+                        &version_fetcher,
+                        &loader.into(),
+                        loader_str,
+                        &target.minecraft_version,
+                    )
+                    .await
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "No {} versions available for Minecraft {}",
+                            loader_str,
+                            target.minecraft_version
+                        )
+                    })?
+                }
+            };
+            Some(normalize_selected_loader_version(
+                loader_str,
+                &target.minecraft_version,
+                &version,
+            ))
+        }
+        None => None,
+    };
+
+    if session.config().app_config().dry_run {
+        session.display().status().section("Planned Actions");
+        session.display().status().info(&format!(
+            "Would retarget empack.yml and pack.toml to {}{}",
+            describe(&target.minecraft_version, target.loader),
+            loader_version
+                .as_deref()
+                .map(|version| format!(" {}", version))
+                .unwrap_or_default()
+        ));
+        for row in &rows {
+            match &row.status {
+                MigrationStatus::Ready { version_name, .. } => session
+                    .display()
+                    .status()
+                    .info(&format!("Would update: {} ({})", row.key, version_name)),
+                _ => session.display().status().info(&format!(
+                    "Would turn {} into a search entry for sync",
+                    row.key
+                )),
+            }
+        }
+        session
+            .display()
+            .status()
+            .complete("Dry run complete - no changes applied");
+        return Ok(());
+    }
+
+    config_manager
+        .set_platform_target(
+            &target.minecraft_version,
+            target.loader,
+            loader_version.as_deref(),
+        )
+        .context("Failed to update empack.yml")?;
+    crate::empack::packwiz::retarget_pack_toml(
+        &workdir.join("pack").join("pack.toml"),
+        &target.minecraft_version,
+        target.loader.map(loader_arg).zip(loader_version.as_deref()),
+        session.filesystem(),
+    )
+    .context("Failed to update pack.toml")?;
+    session.display().status().success(
+        "Retargeted",
+        &describe(&target.minecraft_version, target.loader),
+    );
+
+    let run_packwiz = |args: Vec<String>| -> Result<()> {
+        let arg_refs = args.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+        let output = execute_process_with_live_issues(
+            session,
+            session.packwiz_bin(),
+            &arg_refs,
+            &workdir.join("pack"),
+        )?;
+        if output.success {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(
+                "Packwiz command failed: {}",
+                output.error_output()
+            ))
+        }
+    };
+
+    let mut migrated = Vec::new();
+    let mut stubbed = Vec::new();
+    let mut failed = Vec::new();
+    for row in &rows {
+        let result = match &row.status {
+            MigrationStatus::Ready { version_id, .. } => {
+                session
+                    .display()
+                    .status()
+                    .checking(&format!("Migrating {}", row.key));
+                crate::application::sync::build_packwiz_add_commands(
+                    &row.project_id,
+                    row.platform,
+                    Some(version_id),
+                )
+                .map_err(anyhow::Error::from)
+                .and_then(|commands| {
+                    commands.into_iter().try_for_each(|command| {
+                        let mut args = vec!["--no-refresh".to_string()];
+                        args.extend(command);
+                        run_packwiz(args)
+                    })
+                })
+                .and_then(|()| {
+                    if row.record.version.is_none() {
+                        return Ok(());
+                    }
+                    let record = DependencyRecord {
+                        version: Some(version_id.clone()),
+                        ..row.record.clone()
+                    };
+                    config_manager
+                        .add_dependency(&row.key, record)
+                        .context("failed to rewrite the empack.yml pin")
+                })
+                .map(|()| migrated.push(row.key.clone()))
+            }
+            status => {
+                session
+                    .display()
+                    .status()
+                    .checking(&format!("Leaving {} for sync", row.key));
+                let platform = match status {
+                    MigrationStatus::OtherPlatform { platform, .. } => Some(*platform),
+                    _ => None,
+                };
+                let metafile = find_dependency_metafile(
+                    session.filesystem(),
+                    &workdir,
+                    &row.key,
+                    Some(row.record.project_type),
+                    config_manager.datapack_folder().as_deref(),
+                );
+                let removal = match metafile {
+                    Some(_) => run_packwiz(vec![
+                        "--no-refresh".to_string(),
+                        "remove".to_string(),
+                        "-y".to_string(),
+                        row.key.clone(),
+                    ]),
+                    None => Ok(()),
+                };
+                removal
+                    .and_then(|()| {
+                        config_manager
+                            .add_dependency_entry(
+                                &row.key,
+                                DependencyEntry::Search(crate::empack::config::DependencySearch {
+                                    title: row.title.clone(),
+                                    project_type: Some(row.record.project_type),
+                                    platform,
+                                }),
+                            )
+                            .context("failed to write the search entry to empack.yml")
+                    })
+                    .map(|()| stubbed.push(row.key.clone()))
+            }
+        };
+
+        if let Err(e) = result {
+            session
+                .display()
+                .status()
+                .error(&format!("Failed to migrate {}", row.key), &e.to_string());
+            failed.push((row.key.clone(), e.to_string()));
+        }
+    }
+
+    let refresh_output = execute_process_with_live_issues(
+        session,
+        session.packwiz_bin(),
+        &["refresh"],
+        &workdir.join("pack"),
+    )?;
+    if !refresh_output.success {
+        anyhow::bail!("packwiz refresh failed: {}", refresh_output.error_output());
+    }
+    refresh_lockfile(session, &workdir);
+
+    session.display().status().section("Migration Summary");
+    session
+        .display()
+        .status()
+        .success("Migrated", &migrated.len().to_string());
+    if !stubbed.is_empty() {
+        session.display().status().info(&format!(
+            "Left as search entries: {} (run 'empack sync' once they support {})",
+            stubbed.join(", "),
+            describe(&target.minecraft_version, target.loader)
+        ));
+    }
+
+    if !failed.is_empty() {
+        session.display().status().section("Failed migrations");
+        for (key, error) in &failed {
+            session.display().status().error(key, error);
+        }
+        let summary = failed
+            .iter()
+            .map(|(key, err)| format!("{}: {}", key, err))
+            .collect::<Vec<_>>()
+            .join("; ");
+        return Err(anyhow::anyhow!(
+            "{} dependenc{} failed to migrate: {}",
+            failed.len(),
+            if failed.len() == 1 { "y" } else { "ies" },
+            summary
+        ));
+    }
+
+    tracing::info!(
+        command = "migrate",
+        duration_ms = start.elapsed().as_millis() as u64,
+        migrated_count = migrated.len(),
+        stubbed_count = stubbed.len(),
+        exit_code = 0,
+        "command complete"
+    );
+
+    Ok(())
+}
+
+/// Build the dependency graph from every content folder under `pack/`.
+fn build_pack_dependency_graph(
+    session: &dyn Session,
+    workdir: &Path,
+) -> Result<crate::api::dependency_graph::DependencyGraph> {
+    let config_manager = session.filesystem().config_manager(workdir.to_path_buf());
+    let datapack_folder = config_manager.datapack_folder();
+    let mut folders = vec![
+        content_folder_for_type(ProjectType::Mod),
+        content_folder_for_type(ProjectType::ResourcePack),
+        content_folder_for_type(ProjectType::Shader),
+    ];
+    folders.push(
+        datapack_folder
+            .as_deref()
+            .unwrap_or(content_folder_for_type(ProjectType::Datapack)),
+    );
+
+    let mut graph = crate::api::dependency_graph::DependencyGraph::new();
+    for folder in folders {
+        let dir = workdir.join("pack").join(folder);
+        if session.filesystem().is_directory(&dir) {
+            graph
+                .build_from_directory_with(&dir, session.filesystem())
+                .with_context(|| {
+                    format!("Failed to build dependency graph from {}", dir.display())
+                })?;
+        }
+    }
+    Ok(graph)
+}
+
+/// Slug of an installed graph node (its `.pw.toml` stem), or the raw ID for
+/// dependencies that are referenced but not installed.
+fn dependency_node_slug(node: &crate::api::dependency_graph::DependencyNode) -> String {
+    node.source_path
+        .as_deref()
+        .and_then(|path| path.file_name())
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.strip_suffix(".pw.toml"))
+        .map(|slug| slug.to_string())
+        .unwrap_or_else(|| node.mod_id.clone())
+}
+
+/// Find a graph node by slug, project ID, or display name.
+fn find_dependency_node<'a>(
+    graph: &'a crate::api::dependency_graph::DependencyGraph,
+    query: &str,
+) -> Option<&'a crate::api::dependency_graph::DependencyNode> {
+    graph
+        .all_nodes()
+        .find(|node| node.source_path.is_some() && dependency_node_slug(node) == query)
+        .or_else(|| graph.get_node(query))
+        .or_else(|| {
+            graph
+                .all_nodes()
+                .find(|node| node.source_path.is_some() && node.name.eq_ignore_ascii_case(query))
+        })
+}
+
+fn dependency_type_name(dep_type: crate::api::dependency_graph::DependencyType) -> &'static str {
+    match dep_type {
+        crate::api::dependency_graph::DependencyType::Required => "required",
+        crate::api::dependency_graph::DependencyType::Optional => "optional",
+    }
+}
+
+fn render_dependent_chain(
+    target: &crate::api::dependency_graph::DependencyNode,
+    chain: &crate::api::dependency_graph::DependentChain,
+) -> String {
+    let mut line = dependency_node_slug(target);
+    for (dependent, dep_type) in chain {
+        line.push_str(&format!(
+            " <- {} ({})",
+            dependency_node_slug(dependent),
+            dependency_type_name(*dep_type)
+        ));
+    }
+    line
+}
+
+/// One link of an `empack why` chain.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+struct DependentRow {
+    slug: String,
+    dependency: &'static str,
+}
+
+fn dependent_chain_rows(chain: &crate::api::dependency_graph::DependentChain) -> Vec<DependentRow> {
+    chain
+        .iter()
+        .map(|(dependent, dep_type)| DependentRow {
+            slug: dependency_node_slug(dependent),
+            dependency: dependency_type_name(*dep_type),
+        })
+        .collect()
+}
+
+/// One mod of `empack tree` output, with its dependencies.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+struct DependencyTreeRow {
+    slug: String,
+    name: String,
+    optional: bool,
+    installed: bool,
+    repeated: bool,
+    dependencies: Vec<DependencyTreeRow>,
+}
+
+impl From<&crate::api::dependency_graph::DependencyTreeNode> for DependencyTreeRow {
+    fn from(tree: &crate::api::dependency_graph::DependencyTreeNode) -> Self {
+        Self {
+            slug: dependency_node_slug(&tree.node),
+            name: tree.node.name.clone(),
+            optional: tree.dep_type == Some(crate::api::dependency_graph::DependencyType::Optional),
+            installed: tree.node.source_path.is_some(),
+            repeated: tree.repeated,
+            dependencies: tree.children.iter().map(Self::from).collect(),
+        }
+    }
+}
+
+/// Render a forward dependency tree as indented lines.
+fn render_dependency_tree_lines(
+    tree: &crate::api::dependency_graph::DependencyTreeNode,
+) -> Vec<String> {
+    fn label(tree: &crate::api::dependency_graph::DependencyTreeNode) -> String {
+        let mut label = dependency_node_slug(&tree.node);
+        if tree.dep_type == Some(crate::api::dependency_graph::DependencyType::Optional) {
+            label.push_str(" (optional)");
+        }
+        if tree.node.source_path.is_none() {
+            label.push_str(" (not installed)");
+        }
+        if tree.repeated {
+            label.push_str(" (*)");
+        }
+        label
+    }
+
+    fn walk(
+        tree: &crate::api::dependency_graph::DependencyTreeNode,
+        prefix: &str,
+        lines: &mut Vec<String>,
+    ) {
+        for (i, child) in tree.children.iter().enumerate() {
+            let last = i + 1 == tree.children.len();
+            let (branch, indent) = if last {
+                ("`-- ", "    ")
+            } else {
+                ("|-- ", "|   ")
+            };
+            lines.push(format!("{prefix}{branch}{}", label(child)));
+            walk(child, &format!("{prefix}{indent}"), lines);
+        }
+    }
+
+    let mut lines = vec![label(tree)];
+    walk(tree, "", &mut lines);
+    lines
+}
+
+/// One `empack history` row for `--output json`
+#[derive(Debug, Clone, serde::Serialize)]
+struct HistoryRow {
+    id: u32,
+    command: String,
+    timestamp: u64,
+    files: Vec<String>,
+}
+
+#[instrument(skip_all)]
+async fn handle_history(session: &dyn Session) -> Result<()> {
+    let start = std::time::Instant::now();
+    let manager = session.state()?;
+
+    let current_state = manager.discover_state()?;
+    ensure_configured_project(session, &manager.workdir, current_state, "showing history")?;
+
+    let entries = journal::load_entries(session.filesystem(), &manager.workdir)?;
+    let rows: Vec<HistoryRow> = entries
+        .iter()
+        .map(|entry| HistoryRow {
+            id: entry.id,
+            command: entry.command.clone(),
+            timestamp: entry.timestamp,
+            files: entry.changes.iter().map(|c| c.path.clone()).collect(),
+        })
+        .collect();
+    record_json(session, "history", &rows);
+
+    if rows.is_empty() {
+        session
+            .display()
+            .status()
+            .info("No recorded changes in this project");
+    }
+    for row in rows.iter().rev() {
+        session.display().status().message(&format!(
+            "{:>4}  {}  {}",
+            row.id,
+            journal::format_timestamp(row.timestamp),
+            row.command
+        ));
+        session
+            .display()
+            .status()
+            .subtle(&format!("      {}", row.files.join(", ")));
+    }
+
+    tracing::info!(
+        command = "history",
+        duration_ms = start.elapsed().as_millis() as u64,
+        entries = rows.len(),
+        exit_code = 0,
+        "command complete"
+    );
+
+    Ok(())
+}
+
+/// Handle the `verify` subcommand.
+///
+/// Reads `<artifact>.minisig` and `SHA256SUMS` from the artifact's directory.
+/// `pubkey` is a minisign public key file, or the base64 key itself.
+#[instrument(skip_all, fields(artifact = %artifact))]
+async fn handle_verify(session: &dyn Session, artifact: &str, pubkey: &str) -> Result<()> {
+    use crate::empack::builds::CHECKSUMS_FILE;
+    use crate::empack::signing::{self, VerifyingKey};
+
+    let start = std::time::Instant::now();
+    let filesystem = session.filesystem();
+    let artifact_path = Path::new(artifact);
+    let file_name = artifact_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .with_context(|| format!("Not an artifact file: {artifact}"))?;
+
+    let pubkey_path = Path::new(pubkey);
+    let key_text = if filesystem.exists(pubkey_path) {
+        filesystem
+            .read_to_string(pubkey_path)
+            .with_context(|| format!("Failed to read public key {pubkey}"))?
+    } else {
+        pubkey.to_string()
+    };
+    let key = VerifyingKey::parse(&key_text)?;
+
+    let data = filesystem
+        .read_bytes(artifact_path)
+        .with_context(|| format!("Failed to read {artifact}"))?;
+    let signature_path = signing::signature_path(artifact_path);
+    let signature = filesystem
+        .read_to_string(&signature_path)
+        .with_context(|| format!("Failed to read signature {}", signature_path.display()))?;
+    let checksums_path = artifact_path.with_file_name(CHECKSUMS_FILE);
+    let checksums = filesystem
+        .read_to_string(&checksums_path)
+        .with_context(|| format!("Failed to read {}", checksums_path.display()))?;
+
+    let checks = key
+        .verify(&data, &signature, &file_name)
+        .and_then(|()| signing::verify_checksum(&checksums, &data, &file_name));
+    if let Err(error) = checks {
+        session.display().status().error(
+            &format!("{file_name} failed verification"),
+            &error.to_string(),
+        );
+        return Err(error.into());
+    }
+
+    record_json(
+        session,
+        "verify",
+        &serde_json::json!({ "artifact": artifact, "signature": true, "checksum": true }),
+    );
+    session.display().status().success(
+        &format!("Verified {file_name}"),
+        "signature and SHA256SUMS entry match",
+    );
+
+    tracing::info!(
+        command = "verify",
+        duration_ms = start.elapsed().as_millis() as u64,
+        exit_code = 0,
+        "command complete"
+    );
+
+    Ok(())
+}
+
+#[instrument(skip_all)]
+async fn handle_cache_gc(session: &dyn Session) -> Result<()> {
+    let start = std::time::Instant::now();
+    let dry_run = session.config().app_config().dry_run;
+    let store = crate::empack::store::ContentStore::open(session.filesystem())?;
+    let report = store.gc(dry_run)?;
+
+    record_json(session, "cache_gc", &report);
+    let summary = format!(
+        "{} objects ({} bytes); kept {} objects used by {} projects",
+        report.removed_objects, report.removed_bytes, report.kept_objects, report.projects
+    );
+    if dry_run {
+        session
+            .display()
+            .status()
+            .info(&format!("Would evict {summary}"));
+        session
+            .display()
+            .status()
+            .complete("Dry run complete - no changes applied");
+    } else {
+        session.display().status().success("Evicted", &summary);
+    }
+    if report.forgotten_projects > 0 {
+        session.display().status().subtle(&format!(
+            "      Forgot {} projects that no longer exist",
+            report.forgotten_projects
+        ));
+    }
+
+    tracing::info!(
+        command = "cache",
+        duration_ms = start.elapsed().as_millis() as u64,
+        exit_code = 0,
+        "command complete"
+    );
+
+    Ok(())
+}
+
+#[instrument(skip_all)]
+async fn handle_undo(session: &dyn Session, entry: Option<u32>, force: bool) -> Result<()> {
+    let start = std::time::Instant::now();
+    let manager = session.state()?;
+
+    let current_state = manager.discover_state()?;
+    ensure_configured_project(session, &manager.workdir, current_state, "undoing changes")?;
+
+    let (id, restored) = match journal::undo(session.filesystem(), &manager.workdir, entry, force) {
+        Ok(undone) => undone,
+        Err(error @ journal::JournalError::Diverged { .. }) => {
+            session
+                .display()
+                .status()
+                .error("Project files changed since the entry was recorded", "");
+            session
+                .display()
+                .status()
+                .subtle("   Re-run with --force to overwrite them");
+            return Err(error.into());
+        }
+        Err(error) => return Err(error.into()),
+    };
+    let paths: Vec<&str> = restored.iter().map(|c| c.path.as_str()).collect();
+    record_json(
+        session,
+        "undo",
+        &serde_json::json!({ "entry": id, "restored": paths }),
+    );
+
+    if restored.is_empty() {
+        session.display().status().info(&format!(
+            "Project already matches the state before entry {id}"
+        ));
+    } else {
+        session.display().status().success(
+            &format!("Restored the project to before entry {id}"),
+            &format!("{} file(s)", restored.len()),
+        );
+        session.display().status().list(&paths);
+    }
+
+    tracing::info!(
+        command = "undo",
+        duration_ms = start.elapsed().as_millis() as u64,
+        entry = id,
+        restored = restored.len(),
+        exit_code = 0,
+        "command complete"
+    );
+
+    Ok(())
+}
+
+#[instrument(skip_all, fields(slug = %slug))]
+async fn handle_why(session: &dyn Session, slug: String) -> Result<()> {
+    use crate::api::dependency_graph::DependencyType;
+
+    let start = std::time::Instant::now();
+    let manager = session.state()?;
+
+    let current_state = manager.discover_state()?;
+    ensure_configured_project(
+        session,
+        &manager.workdir,
+        current_state,
+        "explaining dependencies",
+    )?;
+
+    let workdir = manager.workdir.clone();
+    let graph = build_pack_dependency_graph(session, &workdir)?;
+    let Some(node) = find_dependency_node(&graph, &slug) else {
+        anyhow::bail!("No dependency named '{}' in pack/", slug);
+    };
+    let node_slug = dependency_node_slug(node);
+
+    let empack_config = session
+        .filesystem()
+        .config_manager(workdir.clone())
+        .load_empack_config()
+        .context("Failed to load empack.yml configuration")?;
+    let declared = empack_config.empack.dependencies.contains_key(&node_slug);
+
+    let mut chains = graph.dependent_chains(&node.mod_id).unwrap_or_default();
+    chains.sort_by_cached_key(|chain| render_dependent_chain(node, chain));
+    let (required, optional): (Vec<_>, Vec<_>) = chains.iter().partition(|chain| {
+        chain
+            .iter()
+            .all(|(_, dep_type)| *dep_type == DependencyType::Required)
+    });
+    record_json(
+        session,
+        "why",
+        &serde_json::json!({
+            "slug": node_slug,
+            "name": node.name,
+            "declared": declared,
+            "required_by": required.iter().map(|chain| dependent_chain_rows(chain)).collect::<Vec<_>>(),
+            "optional_for": optional.iter().map(|chain| dependent_chain_rows(chain)).collect::<Vec<_>>(),
+        }),
+    );
+
+    session
+        .display()
+        .status()
+        .section(&format!("Why {}", node_slug));
+    if node.name != node_slug {
+        session
+            .display()
+            .status()
+            .subtle(&format!("   {}", node.name));
+    }
+    if declared {
+        session
+            .display()
+            .status()
+            .info("Declared directly in empack.yml");
+    }
+
+    if !required.is_empty() {
+        session.display().status().section("Required by");
+        for chain in &required {
+            session
+                .display()
+                .status()
+                .message(&render_dependent_chain(node, chain));
+        }
+    }
+    if !optional.is_empty() {
+        session.display().status().section("Optional for");
+        for chain in &optional {
+            session
+                .display()
+                .status()
+                .message(&render_dependent_chain(node, chain));
+        }
+    }
+
+    if chains.is_empty() {
+        if declared {
+            session
+                .display()
+                .status()
+                .info("No other dependency depends on it");
+        } else {
+            session.display().status().warning(&format!(
+                "Nothing depends on {} and it is not declared in empack.yml",
+                node_slug
+            ));
+        }
+    }
+
+    tracing::info!(
+        command = "why",
+        duration_ms = start.elapsed().as_millis() as u64,
+        required_chains = required.len(),
+        optional_chains = optional.len(),
+        exit_code = 0,
+        "command complete"
+    );
+
+    Ok(())
+}
+
+#[instrument(skip_all)]
+async fn handle_tree(session: &dyn Session, slug: Option<String>) -> Result<()> {
+    let start = std::time::Instant::now();
+    let manager = session.state()?;
+
+    let current_state = manager.discover_state()?;
+    ensure_configured_project(
+        session,
+        &manager.workdir,
+        current_state,
+        "showing the dependency tree",
+    )?;
+
+    let workdir = manager.workdir.clone();
+    let graph = build_pack_dependency_graph(session, &workdir)?;
+
+    let roots: Vec<String> = match &slug {
+        Some(slug) => {
+            let Some(node) = find_dependency_node(&graph, slug) else {
+                anyhow::bail!("No dependency named '{}' in pack/", slug);
+            };
+            vec![node.mod_id.clone()]
+        }
+        None => {
+            let mut roots = graph.root_nodes();
+            // A pack made only of cycles has no roots; show every mod instead
+            if roots.is_empty() {
+                roots = graph.all_nodes().cloned().collect();
+            }
+            roots.sort_by_cached_key(dependency_node_slug);
+            roots.into_iter().map(|node| node.mod_id).collect()
+        }
+    };
+
+    let trees: Vec<_> = roots
+        .iter()
+        .filter_map(|root| graph.dependency_tree(root))
+        .collect();
+    record_json(
+        session,
+        "tree",
+        &trees
+            .iter()
+            .map(DependencyTreeRow::from)
+            .collect::<Vec<_>>(),
+    );
+
+    if roots.is_empty() {
+        session
+            .display()
+            .status()
+            .info("No .pw.toml files found under pack/");
+        return Ok(());
+    }
+
+    session.display().status().section("Dependency Tree");
+    let mut repeated = false;
+    for tree in &trees {
+        for line in render_dependency_tree_lines(tree) {
+            repeated |= line.ends_with(" (*)");
+            session.display().status().message(&line);
+        }
+    }
+    if repeated {
+        session
+            .display()
+            .status()
+            .subtle("(*) dependencies already shown above");
+    }
+
+    tracing::info!(
+        command = "tree",
+        duration_ms = start.elapsed().as_millis() as u64,
+        root_count = roots.len(),
+        exit_code = 0,
+        "command complete"
+    );
+
+    Ok(())
+}
+
+/// One row of `empack search` output.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+struct SearchResultRow {
+    title: String,
+    platform: ProjectPlatform,
+    project_id: String,
+    downloads: u64,
+    confidence: u8,
+    installed: bool,
+}
+
+fn search_platforms(platform: &SearchPlatform) -> Vec<ProjectPlatform> {
+    match platform {
+        SearchPlatform::Modrinth => vec![ProjectPlatform::Modrinth],
+        SearchPlatform::Curseforge => vec![ProjectPlatform::CurseForge],
+        SearchPlatform::Both => vec![ProjectPlatform::Modrinth, ProjectPlatform::CurseForge],
+    }
+}
+
+/// Project IDs already in the pack, from empack.yml and the installed `.pw.toml` files.
+fn installed_project_ids(session: &dyn Session, workdir: &Path) -> HashSet<String> {
+    let mut installed = HashSet::new();
+
+    if let Ok(config) = session
+        .filesystem()
+        .config_manager(workdir.to_path_buf())
+        .load_empack_config()
+    {
+        for entry in config.empack.dependencies.values() {
+            if let DependencyEntry::Resolved(record) = entry {
+                installed.insert(record.project_id.clone());
+            }
+        }
+    }
+
+    if let Ok(graph) = build_pack_dependency_graph(session, workdir) {
+        installed.extend(graph.all_nodes().map(|node| node.mod_id.clone()));
+    }
+
+    installed
+}
+
+#[instrument(skip_all, fields(query = %args.query_text()))]
+async fn handle_search(session: &dyn Session, args: &SearchArgs) -> Result<()> {
+    let start = std::time::Instant::now();
+    let query = args.query_text();
+    let manager = session.state()?;
+    let workdir = manager.workdir.clone();
+
+    if args.add {
+        let current_state = manager.discover_state()?;
+        ensure_configured_project(session, &workdir, current_state, "adding dependencies")?;
+    }
+
+    // Outside a pack there is no version or loader to filter by
+    let project_plan = session
+        .filesystem()
+        .config_manager(workdir.clone())
+        .create_project_plan()
+        .ok();
+    let installed = installed_project_ids(session, &workdir);
+
+    let project_type = args.project_type.to_project_type();
+    let filters = CandidateFilters {
+        minecraft_version: project_plan.as_ref().map(|p| p.minecraft_version.clone()),
+        mod_loader: plan_loader(project_plan.as_ref()).map(|loader| loader_arg(loader).to_string()),
+        category: args.category.clone(),
+        platforms: search_platforms(&args.platform),
+    };
+
+    let client = session.network().http_client()?;
+    let curseforge_api_key = session
+        .config()
+        .app_config()
+        .curseforge_api_client_key
+        .clone();
+    let resolver = session
+        .network()
+        .project_resolver(client, curseforge_api_key);
+
+    session
+        .display()
+        .status()
+        .checking(&format!("Searching for '{}'", query));
+    let mut candidates = match resolver
+        .search_candidates(&query, project_type_arg(project_type), &filters, 0)
+        .await
+    {
+        Ok(candidates) => candidates,
+        Err(error) => {
+            record_json(session, "results", &Vec::<SearchResultRow>::new());
+            session
+                .display()
+                .status()
+                .error("Search failed", &error.to_string());
+            return Err(error.into());
+        }
+    };
+    candidates.truncate(args.limit);
+
+    let rows: Vec<SearchResultRow> = candidates
+        .into_iter()
+        .map(|candidate| SearchResultRow {
+            installed: installed.contains(&candidate.project_id),
+            title: candidate.title,
+            platform: candidate.platform,
+            project_id: candidate.project_id,
+            downloads: candidate.downloads,
+            confidence: candidate.confidence,
+        })
+        .collect();
+    record_json(session, "results", &rows);
+
+    let mut filter_notes = vec![format!("type {}", project_type_arg(project_type))];
+    if let Some(version) = &filters.minecraft_version {
+        filter_notes.push(format!("Minecraft {}", version));
+    }
+    if let Some(loader) = &filters.mod_loader {
+        filter_notes.push(format!("loader {}", loader));
+    }
+    if let Some(category) = &filters.category {
+        filter_notes.push(format!("category {}", category));
+    }
+
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .enumerate()
+        .map(|(index, row)| {
+            vec![
+                (index + 1).to_string(),
+                row.title.clone(),
+                row.platform.to_string(),
+                format_downloads(row.downloads),
+                format!("{}%", row.confidence),
+                if row.installed { "yes" } else { "" }.to_string(),
+                row.project_id.clone(),
+            ]
+        })
+        .collect();
+    let table_rows: Vec<Vec<&str>> = cells
+        .iter()
+        .map(|row| row.iter().map(String::as_str).collect())
+        .collect();
+
+    session
+        .display()
+        .status()
+        .section(&format!("Search results for '{}'", query));
+    session
+        .display()
+        .status()
+        .subtle(&format!("Filters: {}", filter_notes.join(", ")));
+    session.display().table().table(
+        &[
+            "#",
+            "Title",
+            "Platform",
+            "Downloads",
+            "Confidence",
+            "In pack",
+            "ID",
+        ],
+        &table_rows,
+    );
+
+    tracing::info!(
+        command = "search",
+        duration_ms = start.elapsed().as_millis() as u64,
+        result_count = rows.len(),
+        exit_code = 0,
+        "command complete"
+    );
+
+    if !args.add {
+        return Ok(());
+    }
+
+    let options: Vec<String> = rows
+        .iter()
+        .map(|row| format!("{} ({}, {})", row.title, row.platform, row.project_id))
+        .collect();
+    let Some(index) = session
+        .interactive()
+        .fuzzy_select("Project to add", &options)?
+    else {
+        session.display().status().info("No project selected");
+        return Ok(());
+    };
+    let selected = &rows[index];
+    let platform = match selected.platform {
+        ProjectPlatform::Modrinth => SearchPlatform::Modrinth,
+        ProjectPlatform::CurseForge => SearchPlatform::Curseforge,
+    };
+
+    handle_add(
+        session,
+        vec![selected.project_id.clone()],
+        false,
+        Some(platform),
+        Some(args.project_type.clone()),
+        None,
+        None,
+    )
+    .await
 }
 
 /// Fetch loader versions with fallback on network failure.
@@ -4832,6 +7650,8 @@ fn parse_build_targets(targets: Vec<String>) -> Result<Vec<BuildTarget>> {
                     BuildTarget::Server,
                     BuildTarget::ClientFull,
                     BuildTarget::ServerFull,
+                    BuildTarget::Curseforge,
+                    BuildTarget::Prism,
                 ]);
             }
             "mrpack" => build_targets.push(BuildTarget::Mrpack),
@@ -4839,6 +7659,8 @@ fn parse_build_targets(targets: Vec<String>) -> Result<Vec<BuildTarget>> {
             "server" => build_targets.push(BuildTarget::Server),
             "client-full" => build_targets.push(BuildTarget::ClientFull),
             "server-full" => build_targets.push(BuildTarget::ServerFull),
+            "curseforge" => build_targets.push(BuildTarget::Curseforge),
+            "prism" => build_targets.push(BuildTarget::Prism),
             _ => return Err(anyhow::anyhow!("Unknown build target: {}", target)),
         }
     }
@@ -1291,16 +1291,65 @@ mod handle_init_from_source_tests {
     }
 
     #[test]
-    fn import_from_local_rejects_packwiz_directory() {
+    fn import_from_local_parses_prism_instance_zip() {
+        use std::io::Write;
+
+        let archive = tempfile::NamedTempFile::with_suffix(".zip").unwrap();
+        let mut zip = zip::ZipWriter::new(archive.reopen().unwrap());
+        for (name, content) in [
+            ("instance.cfg", "name=Player Pack\n"),
+            (
+                "mmc-pack.json",
+                r#"{"components":[{"uid":"net.minecraft","version":"1.20.1"},{"uid":"net.minecraftforge","version":"47.3.0"}]}"#,
+            ),
+            (".minecraft/mods/jei.jar", "jei"),
+        ] {
+            zip.start_file::<&str, ()>(name, zip::write::FileOptions::default())
+                .unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let archive_bytes = std::fs::read(archive.path()).expect("prism zip bytes");
+        let session = MockCommandSession::new().with_filesystem(
+            MockFileSystemProvider::new().with_binary_file(archive.path().to_path_buf(), archive_bytes),
+        );
+
+        let (manifest, tmp_dir, source_path) =
+            import_from_local(&session, &archive.path().to_string_lossy())
+                .expect("prism instance import");
+
+        assert!(tmp_dir.is_none(), "local import should not allocate a temp dir");
+        assert_eq!(source_path, archive.path());
+        assert_eq!(manifest.identity.name, "Player Pack");
+        assert_eq!(manifest.target.loader, crate::empack::parsing::ModLoader::Forge);
+        assert_eq!(manifest.content.len(), 1);
+    }
+
+    #[test]
+    fn import_from_local_parses_packwiz_directory() {
         let root = tempfile::TempDir::new().expect("temp dir");
-        std::fs::write(root.path().join("pack.toml"), "name = \"pack\"").expect("pack.toml");
-        std::fs::write(root.path().join("index.toml"), "").expect("index.toml");
+        let pack_toml = "name = \"Old Pack\"\nauthor = \"Someone\"\nversion = \"2.1.0\"\n\n[index]\nfile = \"index.toml\"\n\n[versions]\nminecraft = \"1.21.1\"\nfabric = \"0.16.5\"\n";
+        let index_toml = "hash-format = \"sha256\"\n\n[[files]]\nfile = \"mods/sodium.pw.toml\"\nmetafile = true\n";
+        let sodium = "name = \"Sodium\"\nfilename = \"sodium.jar\"\n\n[download]\nurl = \"https://cdn.modrinth.com/sodium.jar\"\nhash-format = \"sha512\"\nhash = \"abcd\"\n\n[update.modrinth]\nmod-id = \"AANobbMI\"\nversion = \"v1\"\n";
+        std::fs::write(root.path().join("pack.toml"), pack_toml).expect("pack.toml");
 
-        let session = MockCommandSession::new();
-        let err = import_from_local(&session, &root.path().to_string_lossy())
-            .expect_err("packwiz directory should be rejected");
+        let session = MockCommandSession::new().with_filesystem(
+            MockFileSystemProvider::new()
+                .with_file(root.path().join("pack.toml"), pack_toml.to_string())
+                .with_file(root.path().join("index.toml"), index_toml.to_string())
+                .with_file(root.path().join("mods/sodium.pw.toml"), sodium.to_string()),
+        );
+        let (manifest, tmp_dir, source_path) =
+            import_from_local(&session, &root.path().to_string_lossy())
+                .expect("packwiz directory import");
 
-        assert!(err.to_string().contains("packwiz directory import is not yet implemented"));
+        assert!(tmp_dir.is_none(), "local import should not allocate a temp dir");
+        assert_eq!(source_path, root.path());
+        assert_eq!(manifest.identity.name, "Old Pack");
+        assert_eq!(manifest.target.loader_version, "0.16.5");
+        assert_eq!(manifest.content.len(), 1);
+        assert_eq!(manifest.source_platform, ProjectPlatform::Modrinth);
     }
 
     #[tokio::test]
@@ -1328,6 +1377,7 @@ mod handle_init_from_source_tests {
         let dest = mock_root().join("downloads").join("artifact.bin");
         download_file(
             &filesystem,
+            Endpoints::upstream(),
             &test_http_client(),
             &format!("{}/artifact.bin", server.url()),
             &dest,
@@ -1351,6 +1401,7 @@ mod handle_init_from_source_tests {
         let dest = mock_root().join("downloads").join("missing.bin");
         let err = download_file(
             &filesystem,
+            Endpoints::upstream(),
             &test_http_client(),
             &format!("{}/missing.bin", server.url()),
             &dest,
@@ -2043,6 +2094,7 @@ mod resolve_curseforge_slug_tests {
             None,
             Some(ProjectType::Mod),
             &resolver,
+            Endpoints::upstream(),
         )
         .await
         .expect_err("missing api key should error");
@@ -2063,6 +2115,7 @@ mod resolve_curseforge_slug_tests {
             None,
             Some(ProjectType::Mod),
             &resolver,
+            Endpoints::upstream(),
             "https://example.invalid/v1",
         )
         .await
@@ -2095,6 +2148,7 @@ mod resolve_curseforge_slug_tests {
             None,
             Some(ProjectType::Mod),
             &resolver,
+            Endpoints::upstream(),
             &format!("{}/v1", server.url()),
         )
         .await
@@ -2128,6 +2182,7 @@ mod resolve_curseforge_slug_tests {
             None,
             Some(ProjectType::Mod),
             &resolver,
+            Endpoints::upstream(),
             &format!("{}/v1", server.url()),
         )
         .await
@@ -2161,6 +2216,7 @@ mod resolve_curseforge_slug_tests {
             None,
             Some(ProjectType::Mod),
             &resolver,
+            Endpoints::upstream(),
             &format!("{}/v1", server.url()),
         )
         .await
@@ -2586,6 +2642,7 @@ mod handle_direct_download_jar_tests {
                     path: "pack/resourcepacks/my-mod.zip".to_string(),
                     source_url: Some("https://example.com/my-mod.zip".to_string()),
                     sha256: "deadbeef".to_string(),
+                    side: None,
                 }),
             )
             .expect("add existing local dependency");
@@ -2658,6 +2715,7 @@ mod handle_direct_download_jar_tests {
                     project_id: "AANobbMI".to_string(),
                     project_type: ProjectType::Mod,
                     version: None,
+                    side: None,
                 },
             )
             .expect("add existing resolved dependency");
@@ -2726,6 +2784,7 @@ mod handle_direct_download_jar_tests {
                     path: "pack/resourcepacks/pack.zip".to_string(),
                     source_url: Some("https://example.com/pack.zip".to_string()),
                     sha256: "deadbeef".to_string(),
+                    side: None,
                 }),
             )
             .expect("add existing local dependency");
@@ -3159,6 +3218,204 @@ mod handle_add_tests {
         );
     }
 
+    #[tokio::test]
+    async fn it_records_client_only_side_reported_by_platform() {
+        let workdir = mock_root().join("configured-project");
+        let mock_project = modrinth_project("zoomify-id", "Zoomify");
+
+        let session = configured_session(&workdir)
+            .with_network(
+                MockNetworkProvider::new()
+                    .with_project_response("zoomify".to_string(), mock_project)
+                    .with_side_env(
+                        "zoomify-id",
+                        crate::empack::content::SideEnv {
+                            client: crate::empack::content::SideRequirement::Required,
+                            server: crate::empack::content::SideRequirement::Unsupported,
+                        },
+                    ),
+            )
+            .with_process(MockProcessProvider::new().with_packwiz_result(
+                vec![
+                    "modrinth".to_string(),
+                    "add".to_string(),
+                    "--project-id".to_string(),
+                    "zoomify-id".to_string(),
+                    "-y".to_string(),
+                ],
+                Ok(ProcessOutput {
+                    stdout: String::new(),
+                    stderr: String::new(),
+                    success: true,
+                }),
+            ));
+
+        let result = handle_add(&session, vec!["zoomify".to_string()], false, None, None, None, None).await;
+        assert!(result.is_ok(), "handle_add should succeed: {result:?}");
+
+        let config = session
+            .filesystem()
+            .config_manager(workdir.clone())
+            .load_empack_config()
+            .unwrap();
+        match config.empack.dependencies.get("zoomify") {
+            Some(DependencyEntry::Resolved(record)) => {
+                assert_eq!(record.side, Some(crate::empack::content::DependencySide::Client));
+            }
+            other => panic!("expected resolved zoomify entry, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_rolls_back_partial_multi_mod_add() {
+        let workdir = mock_root().join("configured-project");
+        let add_args = |project_id: &str| {
+            vec![
+                "modrinth".to_string(),
+                "add".to_string(),
+                "--project-id".to_string(),
+                project_id.to_string(),
+                "-y".to_string(),
+            ]
+        };
+
+        let session = configured_session(&workdir)
+            .with_network(
+                MockNetworkProvider::new()
+                    .with_project_response("sodium".to_string(), modrinth_project("AANobbMI", "Sodium"))
+                    .with_project_response("broken".to_string(), modrinth_project("broken-id", "Broken")),
+            )
+            .with_process(
+                MockProcessProvider::new()
+                    .with_packwiz_result(
+                        add_args("AANobbMI"),
+                        Ok(ProcessOutput {
+                            stdout: String::new(),
+                            stderr: String::new(),
+                            success: true,
+                        }),
+                    )
+                    .with_packwiz_result(
+                        add_args("broken-id"),
+                        Ok(ProcessOutput {
+                            stdout: String::new(),
+                            stderr: "download failed".to_string(),
+                            success: false,
+                        }),
+                    )
+                    .with_packwiz_add_slug("AANobbMI".to_string(), "sodium".to_string()),
+            );
+        let empack_yml_before = session
+            .filesystem()
+            .read_to_string(&workdir.join("empack.yml"))
+            .unwrap();
+
+        let result = execute_command_with_session(
+            Commands::Add {
+                mods: vec!["sodium".to_string(), "broken".to_string()],
+                force: false,
+                platform: None,
+                project_type: None,
+                version_id: None,
+                file_id: None,
+            },
+            &session,
+        )
+        .await;
+
+        assert!(result.is_err(), "partial add should fail");
+        assert_eq!(
+            session
+                .filesystem()
+                .read_to_string(&workdir.join("empack.yml"))
+                .unwrap(),
+            empack_yml_before
+        );
+        assert!(!session.filesystem().exists(&workdir.join("pack/mods/sodium.pw.toml")));
+        assert!(
+            !session
+                .filesystem()
+                .exists(&workdir.join(crate::empack::transaction::ROLLBACK_FILE))
+        );
+    }
+
+    #[tokio::test]
+    async fn it_keeps_partial_add_with_no_rollback() {
+        let workdir = mock_root().join("configured-project");
+        let mut session = configured_session(&workdir)
+            .with_network(
+                MockNetworkProvider::new()
+                    .with_project_response("sodium".to_string(), modrinth_project("AANobbMI", "Sodium")),
+            )
+            .with_process(
+                MockProcessProvider::new()
+                    .with_packwiz_result(
+                        vec![
+                            "modrinth".to_string(),
+                            "add".to_string(),
+                            "--project-id".to_string(),
+                            "AANobbMI".to_string(),
+                            "-y".to_string(),
+                        ],
+                        Ok(ProcessOutput {
+                            stdout: String::new(),
+                            stderr: String::new(),
+                            success: true,
+                        }),
+                    )
+                    .with_packwiz_add_slug("AANobbMI".to_string(), "sodium".to_string()),
+            );
+        session.config_provider.app_config.no_rollback = true;
+
+        let result = execute_command_with_session(
+            Commands::Add {
+                mods: vec!["sodium".to_string(), "missing".to_string()],
+                force: false,
+                platform: None,
+                project_type: None,
+                version_id: None,
+                file_id: None,
+            },
+            &session,
+        )
+        .await;
+
+        assert!(result.is_err(), "unresolved mod should fail the add");
+        assert!(session.filesystem().exists(&workdir.join("pack/mods/sodium.pw.toml")));
+        assert!(
+            !session
+                .filesystem()
+                .exists(&workdir.join(crate::empack::transaction::ROLLBACK_FILE))
+        );
+    }
+
+    #[tokio::test]
+    async fn it_rolls_back_any_failed_mutating_command() {
+        let workdir = mock_root().join("configured-project");
+        let session = configured_session(&workdir);
+        let empack_yml = workdir.join("empack.yml");
+        let empack_yml_before = session.filesystem().read_to_string(&empack_yml).unwrap();
+
+        let result = run_mutating(&session, async {
+            session
+                .filesystem()
+                .write_file(&empack_yml, "half-migrated: true\n")?;
+            Err(anyhow::anyhow!("migration failed"))
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            session.filesystem().read_to_string(&empack_yml).unwrap(),
+            empack_yml_before
+        );
+        assert!(
+            !session
+                .filesystem()
+                .exists(&workdir.join(crate::empack::transaction::ROLLBACK_FILE))
+        );
+    }
+
     #[tokio::test]
     async fn it_falls_back_to_input_key_when_no_new_pw_toml_detected() {
         // When packwiz doesn't create a new .pw.toml (edge case), fall back to input-derived key.
@@ -3487,107 +3744,1020 @@ mod handle_remove_tests {
             err_msg
         );
     }
-}
-
-// ===== HANDLE_SYNC TESTS =====
-
-mod handle_sync_tests {
-    use super::*;
-
-    #[tokio::test]
-    async fn it_adds_missing_mod() {
-        let installed_mods = HashSet::new();
 
-        let workdir = mock_root().join("configured-project");
-        let session = MockCommandSession::new()
+    fn orphan_session(workdir: &Path, confirm: bool) -> MockCommandSession {
+        let mods = workdir.join("pack").join("mods");
+        let metafile = |name: &str, mod_id: &str, deps: &str| {
+            format!(
+                "name = \"{name}\"\nfilename = \"{mod_id}.jar\"\n\n[update.modrinth]\nmod-id = \"{mod_id}\"\nversion = \"v1\"\n{deps}"
+            )
+        };
+        MockCommandSession::new()
             .with_filesystem(
                 MockFileSystemProvider::new()
-                    .with_current_dir(workdir.clone())
-                    .with_configured_project(workdir.clone())
-                    .with_installed_mods(installed_mods),
-            )
-            .with_network(MockNetworkProvider::new())
-            .with_process(
-                MockProcessProvider::new()
-                    .with_packwiz_result(
-                        vec![
-                            "--no-refresh".to_string(),
-                            "modrinth".to_string(),
-                            "add".to_string(),
-                            "--project-id".to_string(),
-                            "P7dR8mSH".to_string(),
-                            "-y".to_string(),
-                        ],
-                        Ok(ProcessOutput {
-                            stdout: String::new(),
-                            stderr: String::new(),
-                            success: true,
-                        }),
+                    .with_current_dir(workdir.to_path_buf())
+                    .with_configured_project(workdir.to_path_buf())
+                    .with_file(
+                        mods.join("sodium.pw.toml"),
+                        metafile(
+                            "Sodium",
+                            "AANobbMI",
+                            "\n[deps]\nLIBA = \"*\"\nP7dR8mSH = \"*\"\n",
+                        ),
                     )
-                    .with_packwiz_result(
-                        vec![
-                            "--no-refresh".to_string(),
-                            "modrinth".to_string(),
-                            "add".to_string(),
-                            "--project-id".to_string(),
-                            "AANobbMI".to_string(),
-                            "-y".to_string(),
-                        ],
-                        Ok(ProcessOutput {
-                            stdout: String::new(),
-                            stderr: String::new(),
-                            success: true,
-                        }),
+                    .with_file(
+                        mods.join("lib-a.pw.toml"),
+                        metafile("Lib A", "LIBA", "\n[deps]\nLIBB = \"*\"\n"),
                     )
-                    .with_packwiz_result(
-                        vec!["refresh".to_string()],
-                        Ok(ProcessOutput {
-                            stdout: String::new(),
-                            stderr: String::new(),
-                            success: true,
-                        }),
+                    .with_file(mods.join("lib-b.pw.toml"), metafile("Lib B", "LIBB", ""))
+                    .with_file(
+                        mods.join("fabric-api.pw.toml"),
+                        metafile("Fabric API", "P7dR8mSH", ""),
                     ),
-            );
+            )
+            .with_interactive(MockInteractiveProvider::new().queue_confirm(confirm))
+            .with_process(MockProcessProvider::new())
+    }
 
-        let result = handle_sync(&session).await;
+    fn removed_slugs(session: &MockCommandSession) -> Vec<String> {
+        session
+            .process_provider
+            .get_calls()
+            .into_iter()
+            .filter(|call| call.args.first().map(String::as_str) == Some("remove"))
+            .filter_map(|call| call.args.last().cloned())
+            .collect()
+    }
 
-        assert!(result.is_ok());
+    #[tokio::test]
+    async fn it_prunes_transitive_orphans_by_slug() {
+        let workdir = mock_root().join("orphan-project");
+        let session = orphan_session(&workdir, true);
 
-        let calls = session.process_provider.get_calls();
-        assert_eq!(calls.len(), 3);
-        assert!(session.process_provider.verify_call(
-            crate::empack::packwiz::PACKWIZ_BIN,
-            &["--no-refresh", "modrinth", "add", "--project-id", "P7dR8mSH", "-y"],
-            &workdir.join("pack")
-        ));
-        assert!(session.process_provider.verify_call(
-            crate::empack::packwiz::PACKWIZ_BIN,
-            &["--no-refresh", "modrinth", "add", "--project-id", "AANobbMI", "-y"],
-            &workdir.join("pack")
-        ));
-        assert!(session.process_provider.verify_call(
-            crate::empack::packwiz::PACKWIZ_BIN,
-            &["refresh"],
-            &workdir.join("pack")
-        ));
+        handle_remove(&session, vec!["sodium".to_string()], true)
+            .await
+            .expect("remove --deps succeeds");
+
+        // fabric-api is declared in empack.yml (as fabric_api) and must survive
+        assert_eq!(removed_slugs(&session), vec!["sodium", "lib-a", "lib-b"]);
+        assert_eq!(session.interactive_provider.get_confirm_calls().len(), 1);
     }
 
     #[tokio::test]
-    async fn it_removes_extra_mod() {
-        let mut installed_mods = HashSet::new();
-        installed_mods.insert("fabric_api".to_string());
-        installed_mods.insert("sodium".to_string());
-        installed_mods.insert("extra_mod".to_string());
+    async fn it_keeps_orphans_when_confirmation_is_declined() {
+        let workdir = mock_root().join("orphan-project");
+        let session = orphan_session(&workdir, false);
 
-        let workdir = mock_root().join("configured-project");
-        let session = MockCommandSession::new()
-            .with_filesystem(
-                MockFileSystemProvider::new()
-                    .with_current_dir(workdir.clone())
-                    .with_configured_project(workdir.clone())
-                    .with_installed_mods(installed_mods),
-            )
-            .with_process(MockProcessProvider::new().with_packwiz_result(
+        handle_remove(&session, vec!["sodium".to_string()], true)
+            .await
+            .expect("remove succeeds");
+
+        assert_eq!(removed_slugs(&session), vec!["sodium"]);
+    }
+
+    #[tokio::test]
+    async fn it_skips_orphans_of_targets_that_failed_to_remove() {
+        let workdir = mock_root().join("orphan-project");
+        let session = orphan_session(&workdir, true).with_process(
+            MockProcessProvider::new().with_packwiz_result(
+                vec!["remove".to_string(), "-y".to_string(), "sodium".to_string()],
+                Ok(ProcessOutput {
+                    stdout: String::new(),
+                    stderr: "boom".to_string(),
+                    success: false,
+                }),
+            ),
+        );
+
+        let result = handle_remove(&session, vec!["sodium".to_string()], true).await;
+
+        assert!(result.is_err());
+        assert_eq!(removed_slugs(&session), vec!["sodium"]);
+    }
+
+    #[tokio::test]
+    async fn it_plans_orphans_in_dry_run_without_prompting() {
+        let workdir = mock_root().join("orphan-project");
+        let mut session = orphan_session(&workdir, true);
+        session.config_provider.app_config.dry_run = true;
+
+        handle_remove(&session, vec!["sodium".to_string()], true)
+            .await
+            .expect("dry run succeeds");
+
+        assert!(removed_slugs(&session).is_empty());
+        assert!(session.interactive_provider.get_confirm_calls().is_empty());
+    }
+}
+
+// ===== HANDLE_LIST TESTS =====
+
+mod handle_list_tests {
+    use super::*;
+
+    const MIXED_EMPACK_YML: &str = r#"empack:
+  dependencies:
+    sodium:
+      status: resolved
+      title: Sodium
+      platform: modrinth
+      project_id: AANobbMI
+      type: mod
+      version: mc1.21.1-0.6.0
+    jei:
+      status: resolved
+      title: Just Enough Items
+      platform: curseforge
+      project_id: "238222"
+      type: mod
+    faithful:
+      status: resolved
+      title: Faithful
+      platform: modrinth
+      project_id: faith123
+      type: resourcepack
+    custom-tweaks:
+      status: local
+      title: Custom Tweaks
+      type: mod
+      path: pack/mods/custom-tweaks.jar
+      sha256: abc123
+    appleskin:
+      title: AppleSkin
+  minecraft_version: "1.21.1"
+  loader: fabric
+  name: "Test Pack"
+  author: "Test Author"
+  version: "1.0.0"
+"#;
+
+    fn mixed_session(workdir: &Path) -> MockCommandSession {
+        MockCommandSession::new().with_filesystem(
+            MockFileSystemProvider::new()
+                .with_current_dir(workdir.to_path_buf())
+                .with_configured_project(workdir.to_path_buf())
+                .with_file(workdir.join("empack.yml"), MIXED_EMPACK_YML.to_string())
+                .with_file(
+                    workdir.join("pack").join("mods").join("sodium.pw.toml"),
+                    "name = \"Sodium\"\n".to_string(),
+                )
+                .with_file(
+                    workdir.join("pack").join("mods").join("appleskin.pw.toml"),
+                    "name = \"AppleSkin\"\n".to_string(),
+                ),
+        )
+    }
+
+    fn rows_for(
+        session: &MockCommandSession,
+        workdir: &Path,
+        project_type: Option<ProjectType>,
+        platform: Option<ProjectPlatform>,
+        sort: ListSort,
+    ) -> Vec<DependencyListRow> {
+        let config = session
+            .filesystem()
+            .config_manager(workdir.to_path_buf())
+            .load_empack_config()
+            .expect("load empack.yml");
+        collect_dependency_list_rows(
+            session.filesystem(),
+            workdir,
+            &config.empack,
+            project_type,
+            platform,
+            sort,
+        )
+    }
+
+    #[tokio::test]
+    async fn it_lists_configured_project_dependencies() {
+        let workdir = mock_root().join("configured-project");
+        let session = configured_session(&workdir);
+
+        let result = handle_list(&session, None, None, ListSort::Slug).await;
+
+        assert!(result.is_ok(), "list failed: {result:?}");
+        assert!(session.process_provider.get_calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_rejects_uninitialized_project() {
+        let session = MockCommandSession::new().with_filesystem(
+            MockFileSystemProvider::new()
+                .with_current_dir(mock_root().join("uninitialized-project")),
+        );
+
+        let result = handle_list(&session, None, None, ListSort::Slug).await;
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn it_reports_each_variant_and_metafile_presence() {
+        let workdir = mock_root().join("list-project");
+        let session = mixed_session(&workdir);
+
+        let rows = rows_for(&session, &workdir, None, None, ListSort::Slug);
+        let keys: Vec<&str> = rows.iter().map(|row| row.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec!["appleskin", "custom-tweaks", "faithful", "jei", "sodium"]
+        );
+
+        let sodium = rows.iter().find(|row| row.key == "sodium").unwrap();
+        assert_eq!(sodium.status, "resolved");
+        assert_eq!(sodium.platform, Some(ProjectPlatform::Modrinth));
+        assert_eq!(sodium.project_id.as_deref(), Some("AANobbMI"));
+        assert_eq!(sodium.version.as_deref(), Some("mc1.21.1-0.6.0"));
+        assert_eq!(sodium.metafile_present, Some(true));
+
+        let jei = rows.iter().find(|row| row.key == "jei").unwrap();
+        assert_eq!(jei.platform, Some(ProjectPlatform::CurseForge));
+        assert_eq!(jei.version, None);
+        assert_eq!(jei.metafile_present, Some(false));
+
+        let local = rows.iter().find(|row| row.key == "custom-tweaks").unwrap();
+        assert_eq!(local.status, "local");
+        assert_eq!(local.platform, None);
+        assert_eq!(local.metafile_present, None);
+
+        let search = rows.iter().find(|row| row.key == "appleskin").unwrap();
+        assert_eq!(search.status, "search");
+        assert_eq!(search.project_type, None);
+        assert_eq!(search.metafile_present, Some(true));
+    }
+
+    #[test]
+    fn it_filters_by_type_and_platform() {
+        let workdir = mock_root().join("list-project");
+        let session = mixed_session(&workdir);
+
+        let resourcepacks = rows_for(
+            &session,
+            &workdir,
+            Some(ProjectType::ResourcePack),
+            None,
+            ListSort::Slug,
+        );
+        assert_eq!(resourcepacks.len(), 1);
+        assert_eq!(resourcepacks[0].key, "faithful");
+
+        let curseforge_mods = rows_for(
+            &session,
+            &workdir,
+            Some(ProjectType::Mod),
+            Some(ProjectPlatform::CurseForge),
+            ListSort::Slug,
+        );
+        assert_eq!(curseforge_mods.len(), 1);
+        assert_eq!(curseforge_mods[0].key, "jei");
+    }
+
+    #[test]
+    fn it_sorts_by_title_case_insensitively() {
+        let workdir = mock_root().join("list-project");
+        let session = mixed_session(&workdir);
+
+        let rows = rows_for(&session, &workdir, None, None, ListSort::Title);
+        let titles: Vec<&str> = rows.iter().map(|row| row.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "AppleSkin",
+                "Custom Tweaks",
+                "Faithful",
+                "Just Enough Items",
+                "Sodium"
+            ]
+        );
+    }
+}
+
+mod handle_update_tests {
+    use super::*;
+    use crate::empack::updates::UpdateApiBases;
+
+    const UPDATE_EMPACK_YML: &str = r#"empack:
+  dependencies:
+    sodium:
+      status: resolved
+      title: Sodium
+      platform: modrinth
+      project_id: AANobbMI
+      type: mod
+    lithium:
+      status: resolved
+      title: Lithium
+      platform: modrinth
+      project_id: gvQqBUqZ
+      type: mod
+      version: lith-old
+  minecraft_version: "1.21.1"
+  loader: fabric
+  name: "Test Pack"
+  author: "Test Author"
+  version: "1.0.0"
+"#;
+
+    fn update_session(workdir: &Path) -> MockCommandSession {
+        MockCommandSession::new().with_filesystem(
+            MockFileSystemProvider::new()
+                .with_current_dir(workdir.to_path_buf())
+                .with_configured_project(workdir.to_path_buf())
+                .with_file(workdir.join("empack.yml"), UPDATE_EMPACK_YML.to_string())
+                .with_file(
+                    workdir.join("pack").join("mods").join("sodium.pw.toml"),
+                    "name = \"Sodium\"\nfilename = \"sodium-old.jar\"\n\n[update.modrinth]\nmod-id = \"AANobbMI\"\nversion = \"sodium-old\"\n".to_string(),
+                )
+                .with_file(
+                    workdir.join("pack").join("mods").join("lithium.pw.toml"),
+                    "name = \"Lithium\"\nfilename = \"lithium-old.jar\"\n\n[update.modrinth]\nmod-id = \"gvQqBUqZ\"\nversion = \"lith-old\"\n".to_string(),
+                ),
+        )
+    }
+
+    fn api_bases(server: &mockito::ServerGuard) -> UpdateApiBases {
+        UpdateApiBases {
+            modrinth: vec![server.url()],
+            curseforge: vec![server.url()],
+        }
+    }
+
+    async fn mock_latest(
+        server: &mut mockito::ServerGuard,
+        project_id: &str,
+        version_id: &str,
+    ) -> mockito::Mock {
+        server
+            .mock("GET", format!("/v2/project/{project_id}/version").as_str())
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(format!(
+                r#"[{{"id":"{version_id}","version_number":"{version_id}","date_published":"2024-06-01T00:00:00Z","files":[{{"filename":"{version_id}.jar","primary":true}}]}}]"#
+            ))
+            .create_async()
+            .await
+    }
+
+    #[tokio::test]
+    async fn outdated_reports_without_running_packwiz() {
+        let workdir = mock_root().join("configured-project");
+        let mut server = mockito::Server::new_async().await;
+        let sodium = mock_latest(&mut server, "AANobbMI", "sodium-new").await;
+        let lithium = mock_latest(&mut server, "gvQqBUqZ", "lith-old").await;
+        let session = update_session(&workdir);
+
+        handle_outdated_with_api_bases(&session, &api_bases(&server))
+            .await
+            .expect("outdated succeeds");
+
+        sodium.assert_async().await;
+        lithium.assert_async().await;
+        assert!(session.process_provider.get_calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn outdated_fails_when_a_lookup_fails() {
+        let workdir = mock_root().join("configured-project");
+        let mut server = mockito::Server::new_async().await;
+        let _sodium = mock_latest(&mut server, "AANobbMI", "sodium-new").await;
+        let _lithium = server
+            .mock("GET", "/v2/project/gvQqBUqZ/version")
+            .match_query(mockito::Matcher::Any)
+            .with_status(500)
+            .create_async()
+            .await;
+        let session = update_session(&workdir);
+
+        let err = handle_outdated_with_api_bases(&session, &api_bases(&server))
+            .await
+            .expect_err("failed lookup should surface");
+        assert!(err.to_string().contains("could not be checked"));
+    }
+
+    #[tokio::test]
+    async fn update_applies_newer_file_and_skips_pinned_entries() {
+        let workdir = mock_root().join("configured-project");
+        let mut server = mockito::Server::new_async().await;
+        let _sodium = mock_latest(&mut server, "AANobbMI", "sodium-new").await;
+        let _lithium = mock_latest(&mut server, "gvQqBUqZ", "lith-new").await;
+        let session = update_session(&workdir);
+
+        handle_update_with_api_bases(&session, vec![], false, &api_bases(&server))
+            .await
+            .expect("update succeeds");
+
+        let calls = session.process_provider.get_calls();
+        assert_eq!(calls.len(), 1);
+        assert!(session.process_provider.verify_call(
+            crate::empack::packwiz::PACKWIZ_BIN,
+            &[
+                "modrinth",
+                "add",
+                "--project-id",
+                "AANobbMI",
+                "--version-id",
+                "sodium-new",
+                "-y"
+            ],
+            &workdir.join("pack")
+        ));
+
+        let config = session
+            .filesystem()
+            .read_to_string(&workdir.join("empack.yml"))
+            .expect("empack.yml");
+        assert!(config.contains("version: lith-old"));
+    }
+
+    #[tokio::test]
+    async fn update_force_rewrites_pinned_version() {
+        let workdir = mock_root().join("configured-project");
+        let mut server = mockito::Server::new_async().await;
+        let _lithium = mock_latest(&mut server, "gvQqBUqZ", "lith-new").await;
+        let session = update_session(&workdir);
+
+        handle_update_with_api_bases(
+            &session,
+            vec!["lithium".to_string()],
+            true,
+            &api_bases(&server),
+        )
+        .await
+        .expect("forced update succeeds");
+
+        assert!(session.process_provider.verify_call(
+            crate::empack::packwiz::PACKWIZ_BIN,
+            &[
+                "modrinth",
+                "add",
+                "--project-id",
+                "gvQqBUqZ",
+                "--version-id",
+                "lith-new",
+                "-y"
+            ],
+            &workdir.join("pack")
+        ));
+
+        let config = session
+            .filesystem()
+            .config_manager(workdir.clone())
+            .load_empack_config()
+            .expect("empack.yml");
+        match config.empack.dependencies.get("lithium") {
+            Some(DependencyEntry::Resolved(record)) => {
+                assert_eq!(record.version.as_deref(), Some("lith-new"));
+            }
+            other => panic!("unexpected lithium entry: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn update_batches_with_no_refresh_and_refreshes_once() {
+        let workdir = mock_root().join("configured-project");
+        let mut server = mockito::Server::new_async().await;
+        let _sodium = mock_latest(&mut server, "AANobbMI", "sodium-new").await;
+        let _lithium = mock_latest(&mut server, "gvQqBUqZ", "lith-new").await;
+        let session = update_session(&workdir);
+
+        handle_update_with_api_bases(&session, vec![], true, &api_bases(&server))
+            .await
+            .expect("update succeeds");
+
+        let calls = session.process_provider.get_calls();
+        assert_eq!(calls.len(), 3);
+        assert!(calls[..2].iter().all(|call| call.args[0] == "--no-refresh"));
+        assert!(session.process_provider.verify_call(
+            crate::empack::packwiz::PACKWIZ_BIN,
+            &["refresh"],
+            &workdir.join("pack")
+        ));
+    }
+
+    #[tokio::test]
+    async fn update_dry_run_skips_side_effects() {
+        let workdir = mock_root().join("configured-project");
+        let mut server = mockito::Server::new_async().await;
+        let _sodium = mock_latest(&mut server, "AANobbMI", "sodium-new").await;
+        let mut session = update_session(&workdir);
+        session.config_provider.app_config.dry_run = true;
+
+        handle_update_with_api_bases(
+            &session,
+            vec!["sodium".to_string()],
+            false,
+            &api_bases(&server),
+        )
+        .await
+        .expect("dry run succeeds");
+
+        assert!(session.process_provider.get_calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn update_rejects_unknown_dependency() {
+        let workdir = mock_root().join("configured-project");
+        let server = mockito::Server::new_async().await;
+        let session = update_session(&workdir);
+
+        let err = handle_update_with_api_bases(
+            &session,
+            vec!["missing".to_string()],
+            false,
+            &api_bases(&server),
+        )
+        .await
+        .expect_err("unknown dependency should fail");
+
+        assert!(err.to_string().contains("'missing'"));
+        assert!(session.process_provider.get_calls().is_empty());
+    }
+}
+
+mod handle_why_tree_tests {
+    use super::*;
+    use crate::api::dependency_graph::DependencyType;
+
+    fn metafile(name: &str, mod_id: &str, deps: &str) -> String {
+        format!(
+            "name = \"{name}\"\nfilename = \"{mod_id}.jar\"\n\n[update.modrinth]\nmod-id = \"{mod_id}\"\nversion = \"v1\"\n{deps}"
+        )
+    }
+
+    fn graph_session(workdir: &Path) -> MockCommandSession {
+        let mods = workdir.join("pack").join("mods");
+        MockCommandSession::new().with_filesystem(
+            MockFileSystemProvider::new()
+                .with_current_dir(workdir.to_path_buf())
+                .with_configured_project(workdir.to_path_buf())
+                .with_file(
+                    mods.join("fabric-api.pw.toml"),
+                    metafile("Fabric API", "P7dR8mSH", ""),
+                )
+                .with_file(
+                    mods.join("sodium.pw.toml"),
+                    metafile("Sodium", "AANobbMI", "\n[deps]\nP7dR8mSH = \"*\"\n"),
+                )
+                .with_file(
+                    mods.join("indium.pw.toml"),
+                    metafile("Indium", "Orvt0mRa", "\n[deps]\nAANobbMI = \"*\"\n"),
+                )
+                .with_file(
+                    mods.join("iris.pw.toml"),
+                    metafile(
+                        "Iris Shaders",
+                        "YL57xq9U",
+                        "\n[deps]\nAANobbMI = \"*\"\nOrvt0mRa = { optional = true }\nP7dR8mSH = { optional = true }\n",
+                    ),
+                ),
+        )
+    }
+
+    #[test]
+    fn it_finds_nodes_by_slug_id_or_name() {
+        let workdir = mock_root().join("graph-project");
+        let session = graph_session(&workdir);
+        let graph = build_pack_dependency_graph(&session, &workdir).expect("graph");
+
+        let by_slug = find_dependency_node(&graph, "iris").expect("slug");
+        assert_eq!(by_slug.mod_id, "YL57xq9U");
+        let by_id = find_dependency_node(&graph, "AANobbMI").expect("id");
+        assert_eq!(dependency_node_slug(by_id), "sodium");
+        let by_name = find_dependency_node(&graph, "fabric api").expect("name");
+        assert_eq!(dependency_node_slug(by_name), "fabric-api");
+        assert!(find_dependency_node(&graph, "lithium").is_none());
+    }
+
+    #[test]
+    fn it_renders_every_dependent_chain() {
+        let workdir = mock_root().join("graph-project");
+        let session = graph_session(&workdir);
+        let graph = build_pack_dependency_graph(&session, &workdir).expect("graph");
+        let target = find_dependency_node(&graph, "fabric-api").expect("node");
+
+        let mut chains: Vec<String> = graph
+            .dependent_chains(&target.mod_id)
+            .expect("chains")
+            .iter()
+            .map(|chain| render_dependent_chain(target, chain))
+            .collect();
+        chains.sort();
+
+        assert_eq!(
+            chains,
+            vec![
+                "fabric-api <- iris (optional)",
+                "fabric-api <- sodium (required) <- indium (required) <- iris (optional)",
+                "fabric-api <- sodium (required) <- iris (required)",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_renders_tree_with_repeated_subtrees_marked() {
+        let workdir = mock_root().join("graph-project");
+        let session = graph_session(&workdir);
+        let graph = build_pack_dependency_graph(&session, &workdir).expect("graph");
+
+        let roots = graph.root_nodes();
+        assert_eq!(roots.len(), 1);
+        let tree = graph.dependency_tree(&roots[0].mod_id).expect("tree");
+        assert_eq!(tree.dep_type, None);
+        assert_eq!(
+            tree.children
+                .iter()
+                .map(|child| child.dep_type)
+                .collect::<Vec<_>>(),
+            vec![
+                Some(DependencyType::Required),
+                Some(DependencyType::Optional),
+                Some(DependencyType::Optional)
+            ]
+        );
+
+        assert_eq!(
+            render_dependency_tree_lines(&tree),
+            vec![
+                "iris",
+                "|-- sodium",
+                "|   `-- fabric-api",
+                "|-- indium (optional)",
+                "|   `-- sodium (*)",
+                "`-- fabric-api (optional)",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_rejects_unknown_dependency() {
+        let workdir = mock_root().join("graph-project");
+        let session = graph_session(&workdir);
+
+        let err = handle_why(&session, "lithium".to_string())
+            .await
+            .expect_err("unknown slug should fail");
+        assert!(err.to_string().contains("No dependency named 'lithium'"));
+
+        let err = handle_tree(&session, Some("lithium".to_string()))
+            .await
+            .expect_err("unknown slug should fail");
+        assert!(err.to_string().contains("No dependency named 'lithium'"));
+    }
+
+    #[tokio::test]
+    async fn it_explains_and_prints_installed_dependencies() {
+        let workdir = mock_root().join("graph-project");
+        let session = graph_session(&workdir);
+
+        handle_why(&session, "sodium".to_string())
+            .await
+            .expect("why succeeds");
+        handle_tree(&session, None).await.expect("tree succeeds");
+    }
+
+    #[tokio::test]
+    async fn why_records_required_and_optional_chains() {
+        let workdir = mock_root().join("graph-project");
+        let session = graph_session(&workdir);
+
+        let result = execute_command_with_session(
+            Commands::Why {
+                slug: "sodium".to_string(),
+            },
+            &session,
+        )
+        .await;
+        let document = json_result_document("why", &result, session.display());
+
+        let why = &document["data"]["why"];
+        assert_eq!(why["slug"], "sodium");
+        assert_eq!(why["name"], "Sodium");
+        assert_eq!(why["declared"], true);
+        assert_eq!(
+            why["required_by"],
+            serde_json::json!([[{ "slug": "iris", "dependency": "required" }]])
+        );
+        assert_eq!(
+            why["optional_for"],
+            serde_json::json!([[
+                { "slug": "indium", "dependency": "required" },
+                { "slug": "iris", "dependency": "optional" }
+            ]])
+        );
+    }
+
+    #[tokio::test]
+    async fn tree_records_nested_dependencies() {
+        let workdir = mock_root().join("graph-project");
+        let session = graph_session(&workdir);
+
+        let result = execute_command_with_session(Commands::Tree { slug: None }, &session).await;
+        let document = json_result_document("tree", &result, session.display());
+
+        let tree = &document["data"]["tree"];
+        assert_eq!(tree.as_array().map(Vec::len), Some(1));
+        let iris = &tree[0];
+        assert_eq!(iris["slug"], "iris");
+        assert_eq!(iris["name"], "Iris Shaders");
+        assert_eq!(iris["installed"], true);
+        let children: Vec<_> = iris["dependencies"]
+            .as_array()
+            .expect("dependencies")
+            .iter()
+            .map(|child| (child["slug"].clone(), child["optional"].clone()))
+            .collect();
+        assert_eq!(
+            children,
+            vec![
+                ("sodium".into(), false.into()),
+                ("indium".into(), true.into()),
+                ("fabric-api".into(), true.into()),
+            ]
+        );
+        assert_eq!(iris["dependencies"][0]["dependencies"][0]["slug"], "fabric-api");
+        let repeated = &iris["dependencies"][1]["dependencies"][0];
+        assert_eq!(repeated["slug"], "sodium");
+        assert_eq!(repeated["repeated"], true);
+        assert_eq!(repeated["dependencies"], serde_json::json!([]));
+    }
+}
+
+mod handle_history_undo_tests {
+    use super::*;
+    use crate::empack::journal;
+
+    fn sodium_add_session(workdir: &Path) -> MockCommandSession {
+        configured_session(workdir)
+            .with_network(MockNetworkProvider::new().with_project_response(
+                "sodium".to_string(),
+                modrinth_project("AANobbMI", "Sodium"),
+            ))
+            .with_process(
+                MockProcessProvider::new()
+                    .with_packwiz_result(
+                        vec![
+                            "modrinth".to_string(),
+                            "add".to_string(),
+                            "--project-id".to_string(),
+                            "AANobbMI".to_string(),
+                            "-y".to_string(),
+                        ],
+                        Ok(ProcessOutput {
+                            stdout: String::new(),
+                            stderr: String::new(),
+                            success: true,
+                        }),
+                    )
+                    .with_packwiz_add_slug("AANobbMI".to_string(), "sodium".to_string()),
+            )
+    }
+
+    fn add_sodium() -> Commands {
+        Commands::Add {
+            mods: vec!["sodium".to_string()],
+            force: false,
+            platform: None,
+            project_type: None,
+            version_id: None,
+            file_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn it_journals_add_and_undoes_it() {
+        let workdir = mock_root().join("configured-project");
+        let session = sodium_add_session(&workdir);
+        let empack_yml_before = session
+            .filesystem()
+            .read_to_string(&workdir.join("empack.yml"))
+            .unwrap();
+
+        execute_command_with_session(add_sodium(), &session)
+            .await
+            .expect("add succeeds");
+
+        let entries = journal::load_entries(session.filesystem(), &workdir).unwrap();
+        assert_eq!(entries.len(), 1);
+        let paths: Vec<&str> = entries[0].changes.iter().map(|c| c.path.as_str()).collect();
+        assert!(paths.contains(&"empack.yml"), "journaled paths: {paths:?}");
+        assert!(paths.contains(&"pack/mods/sodium.pw.toml"), "journaled paths: {paths:?}");
+
+        execute_command_with_session(Commands::History, &session)
+            .await
+            .expect("history succeeds");
+        execute_command_with_session(Commands::Undo { entry: None, force: false }, &session)
+            .await
+            .expect("undo succeeds");
+
+        assert_eq!(
+            session
+                .filesystem()
+                .read_to_string(&workdir.join("empack.yml"))
+                .unwrap(),
+            empack_yml_before
+        );
+        assert!(!session.filesystem().exists(&workdir.join("pack/mods/sodium.pw.toml")));
+        let entries = journal::load_entries(session.filesystem(), &workdir).unwrap();
+        assert_eq!(entries.len(), 2, "undo is journaled as its own entry");
+    }
+
+    #[tokio::test]
+    async fn it_refuses_undo_after_manual_edits_without_force() {
+        let workdir = mock_root().join("configured-project");
+        let session = sodium_add_session(&workdir);
+
+        execute_command_with_session(add_sodium(), &session)
+            .await
+            .expect("add succeeds");
+        session
+            .filesystem()
+            .write_file(&workdir.join("pack/mods/sodium.pw.toml"), "edited by hand\n")
+            .unwrap();
+
+        let err = execute_command_with_session(
+            Commands::Undo {
+                entry: Some(1),
+                force: false,
+            },
+            &session,
+        )
+        .await
+        .expect_err("diverged files should block undo");
+        assert!(err.to_string().contains("pack/mods/sodium.pw.toml"), "{err}");
+        assert!(session.filesystem().exists(&workdir.join("pack/mods/sodium.pw.toml")));
+
+        execute_command_with_session(Commands::Undo { entry: Some(1), force: true }, &session)
+            .await
+            .expect("forced undo succeeds");
+        assert!(!session.filesystem().exists(&workdir.join("pack/mods/sodium.pw.toml")));
+    }
+}
+
+mod project_lock_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn it_fails_fast_when_another_process_holds_the_lock() {
+        let workdir = mock_root().join("configured-project");
+        let session = MockCommandSession::new().with_filesystem(
+            MockFileSystemProvider::new()
+                .with_current_dir(workdir.clone())
+                .with_configured_project(workdir.clone())
+                .with_held_lock(workdir.join(".empack/lock"), "4242"),
+        );
+
+        let err = execute_command_with_session(Commands::Sync { locked: false }, &session)
+            .await
+            .expect_err("locked project should refuse sync");
+
+        assert_eq!(err.to_string(), "Project locked by PID 4242");
+        assert!(session.process_provider.get_calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_skips_the_lock_for_read_only_commands() {
+        let workdir = mock_root().join("configured-project");
+        let session = MockCommandSession::new().with_filesystem(
+            MockFileSystemProvider::new()
+                .with_current_dir(workdir.clone())
+                .with_configured_project(workdir.clone())
+                .with_held_lock(workdir.join(".empack/lock"), "4242"),
+        );
+
+        execute_command_with_session(Commands::History, &session)
+            .await
+            .expect("history does not need the lock");
+    }
+
+    #[tokio::test]
+    async fn it_waits_for_the_lock_with_wait_lock() {
+        let workdir = mock_root().join("configured-project");
+        let lock_path = workdir.join(".empack/lock");
+        let filesystem = MockFileSystemProvider::new()
+            .with_current_dir(workdir.clone())
+            .with_configured_project(workdir.clone())
+            .with_held_lock(lock_path.clone(), "4242");
+        let locks = filesystem.file_locks.clone();
+        let mut session = MockCommandSession::new().with_filesystem(filesystem);
+        session.config_provider.app_config.wait_lock = true;
+
+        let release = tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+            locks.lock().unwrap().remove(&lock_path);
+        });
+        execute_command_with_session(Commands::Clean { targets: vec![] }, &session)
+            .await
+            .expect("clean runs once the lock is released");
+        release.await.unwrap();
+
+        assert!(
+            session.filesystem_provider.file_locks.lock().unwrap().is_empty(),
+            "the lock is released when the command finishes"
+        );
+    }
+}
+
+// ===== HANDLE_SYNC TESTS =====
+
+mod handle_sync_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn it_adds_missing_mod() {
+        let installed_mods = HashSet::new();
+
+        let workdir = mock_root().join("configured-project");
+        let session = MockCommandSession::new()
+            .with_filesystem(
+                MockFileSystemProvider::new()
+                    .with_current_dir(workdir.clone())
+                    .with_configured_project(workdir.clone())
+                    .with_installed_mods(installed_mods),
+            )
+            .with_network(MockNetworkProvider::new())
+            .with_process(
+                MockProcessProvider::new()
+                    .with_packwiz_result(
+                        vec![
+                            "--no-refresh".to_string(),
+                            "modrinth".to_string(),
+                            "add".to_string(),
+                            "--project-id".to_string(),
+                            "P7dR8mSH".to_string(),
+                            "-y".to_string(),
+                        ],
+                        Ok(ProcessOutput {
+                            stdout: String::new(),
+                            stderr: String::new(),
+                            success: true,
+                        }),
+                    )
+                    .with_packwiz_result(
+                        vec![
+                            "--no-refresh".to_string(),
+                            "modrinth".to_string(),
+                            "add".to_string(),
+                            "--project-id".to_string(),
+                            "AANobbMI".to_string(),
+                            "-y".to_string(),
+                        ],
+                        Ok(ProcessOutput {
+                            stdout: String::new(),
+                            stderr: String::new(),
+                            success: true,
+                        }),
+                    )
+                    .with_packwiz_result(
+                        vec!["refresh".to_string()],
+                        Ok(ProcessOutput {
+                            stdout: String::new(),
+                            stderr: String::new(),
+                            success: true,
+                        }),
+                    ),
+            );
+
+        let result = handle_sync(&session, false).await;
+
+        assert!(result.is_ok());
+
+        let calls = session.process_provider.get_calls();
+        assert_eq!(calls.len(), 3);
+        assert!(session.process_provider.verify_call(
+            crate::empack::packwiz::PACKWIZ_BIN,
+            &["--no-refresh", "modrinth", "add", "--project-id", "P7dR8mSH", "-y"],
+            &workdir.join("pack")
+        ));
+        assert!(session.process_provider.verify_call(
+            crate::empack::packwiz::PACKWIZ_BIN,
+            &["--no-refresh", "modrinth", "add", "--project-id", "AANobbMI", "-y"],
+            &workdir.join("pack")
+        ));
+        assert!(session.process_provider.verify_call(
+            crate::empack::packwiz::PACKWIZ_BIN,
+            &["refresh"],
+            &workdir.join("pack")
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_removes_extra_mod() {
+        let mut installed_mods = HashSet::new();
+        installed_mods.insert("fabric_api".to_string());
+        installed_mods.insert("sodium".to_string());
+        installed_mods.insert("extra_mod".to_string());
+
+        let workdir = mock_root().join("configured-project");
+        let session = MockCommandSession::new()
+            .with_filesystem(
+                MockFileSystemProvider::new()
+                    .with_current_dir(workdir.clone())
+                    .with_configured_project(workdir.clone())
+                    .with_installed_mods(installed_mods),
+            )
+            .with_process(MockProcessProvider::new().with_packwiz_result(
                 vec!["remove".to_string(), "-y".to_string(), "extra_mod".to_string()],
                 Ok(ProcessOutput {
                     stdout: String::new(),
@@ -3596,7 +4766,7 @@ mod handle_sync_tests {
                 }),
             ));
 
-        let result = handle_sync(&session).await;
+        let result = handle_sync(&session, false).await;
 
         assert!(result.is_ok());
 
@@ -3623,7 +4793,7 @@ mod handle_sync_tests {
                 .with_installed_mods(installed_mods),
         );
 
-        let result = handle_sync(&session).await;
+        let result = handle_sync(&session, false).await;
 
         assert!(result.is_ok());
         assert!(session.process_provider.get_calls().is_empty());
@@ -3655,7 +4825,7 @@ mod handle_sync_tests {
             );
         session.config_provider.app_config.dry_run = true; // Enable dry-run mode
 
-        let result = handle_sync(&session).await;
+        let result = handle_sync(&session, false).await;
 
         assert!(result.is_ok());
         assert!(
@@ -3720,7 +4890,7 @@ forge = "47.3.0"
                 }),
             ));
 
-        let result = handle_sync(&session).await;
+        let result = handle_sync(&session, false).await;
 
         assert!(result.is_ok());
         assert!(session.process_provider.verify_call(
@@ -3794,7 +4964,7 @@ fabric = "0.16.0"
                 }),
             ));
 
-        let result = handle_sync(&session).await;
+        let result = handle_sync(&session, false).await;
 
         assert!(result.is_ok());
         let calls = session.process_provider.get_calls();
@@ -3821,7 +4991,7 @@ fabric = "0.16.0"
                 .with_current_dir(mock_root().join("uninitialized-project")),
         );
 
-        let result = handle_sync(&session).await;
+        let result = handle_sync(&session, false).await;
 
         assert!(result.is_err());
 
@@ -3842,7 +5012,7 @@ fabric = "0.16.0"
                 ),
         );
 
-        let result = handle_sync(&session).await;
+        let result = handle_sync(&session, false).await;
 
         assert!(result.is_err());
         assert!(
@@ -3910,7 +5080,7 @@ fabric = "0.15.0"
                     .with_error_response("Mod B".to_string(), "network timeout".to_string()),
             );
 
-        let result = handle_sync(&session).await;
+        let result = handle_sync(&session, false).await;
 
         assert!(result.is_err(), "handle_sync should fail when all resolutions fail");
         let err_msg = result.unwrap_err().to_string();
@@ -3999,7 +5169,7 @@ fabric = "0.15.0"
                 ),
             );
 
-        let result = handle_sync(&session).await;
+        let result = handle_sync(&session, false).await;
 
         assert!(result.is_ok(), "handle_sync should succeed for partial failure: {result:?}");
         // Only the successful resolution should have been executed
@@ -4012,70 +5182,277 @@ fabric = "0.15.0"
         );
         assert!(session.process_provider.verify_call(
             crate::empack::packwiz::PACKWIZ_BIN,
-            &["modrinth", "add", "--project-id", "BBNobbMI", "-y"],
+            &["modrinth", "add", "--project-id", "BBNobbMI", "-y"],
+            &workdir.join("pack")
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_succeeds_normally_when_no_planning_resolutions_fail() {
+        let installed_mods = HashSet::new();
+        let workdir = mock_root().join("configured-project");
+        let session = MockCommandSession::new()
+            .with_filesystem(
+                MockFileSystemProvider::new()
+                    .with_current_dir(workdir.clone())
+                    .with_configured_project(workdir.clone())
+                    .with_installed_mods(installed_mods),
+            )
+            .with_network(MockNetworkProvider::new())
+            .with_process(
+                MockProcessProvider::new()
+                    .with_packwiz_result(
+                        vec![
+                            "--no-refresh".to_string(),
+                            "modrinth".to_string(),
+                            "add".to_string(),
+                            "--project-id".to_string(),
+                            "P7dR8mSH".to_string(),
+                            "-y".to_string(),
+                        ],
+                        Ok(ProcessOutput {
+                            stdout: String::new(),
+                            stderr: String::new(),
+                            success: true,
+                        }),
+                    )
+                    .with_packwiz_result(
+                        vec![
+                            "--no-refresh".to_string(),
+                            "modrinth".to_string(),
+                            "add".to_string(),
+                            "--project-id".to_string(),
+                            "AANobbMI".to_string(),
+                            "-y".to_string(),
+                        ],
+                        Ok(ProcessOutput {
+                            stdout: String::new(),
+                            stderr: String::new(),
+                            success: true,
+                        }),
+                    )
+                    .with_packwiz_result(
+                        vec!["refresh".to_string()],
+                        Ok(ProcessOutput {
+                            stdout: String::new(),
+                            stderr: String::new(),
+                            success: true,
+                        }),
+                    ),
+            );
+
+        let result = handle_sync(&session, false).await;
+
+        assert!(result.is_ok(), "handle_sync should succeed when all resolutions pass: {result:?}");
+        let calls = session.process_provider.get_calls();
+        assert_eq!(calls.len(), 3, "Both resolved actions + final refresh should execute");
+    }
+
+    const LOCKED_EMPACK_LOCK: &str = r#"version = 1
+
+[dependencies.fabric_api]
+platform = "modrinth"
+project_id = "P7dR8mSH"
+version_id = "fapi-1"
+type = "mod"
+filename = "fabric_api.jar"
+
+[dependencies.sodium]
+platform = "modrinth"
+project_id = "AANobbMI"
+version_id = "sod-1"
+type = "mod"
+filename = "sodium.jar"
+"#;
+
+    fn modrinth_pw_toml(slug: &str, project_id: &str, version: &str) -> String {
+        format!(
+            "name = \"{slug}\"\nfilename = \"{slug}.jar\"\n\n[update.modrinth]\nmod-id = \"{project_id}\"\nversion = \"{version}\"\n"
+        )
+    }
+
+    #[tokio::test]
+    async fn it_writes_lockfile_after_sync() {
+        let installed_mods: HashSet<String> = ["fabric_api", "sodium"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let workdir = mock_root().join("configured-project");
+        let session = MockCommandSession::new().with_filesystem(
+            MockFileSystemProvider::new()
+                .with_current_dir(workdir.clone())
+                .with_configured_project(workdir.clone())
+                .with_installed_mods(installed_mods)
+                .with_file(
+                    workdir.join("pack/mods/fabric_api.pw.toml"),
+                    modrinth_pw_toml("fabric_api", "P7dR8mSH", "fapi-1"),
+                )
+                .with_file(
+                    workdir.join("pack/mods/sodium.pw.toml"),
+                    modrinth_pw_toml("sodium", "AANobbMI", "sod-1"),
+                ),
+        );
+
+        handle_sync(&session, false).await.expect("sync succeeds");
+
+        let lock = crate::empack::lockfile::load_lock(session.filesystem(), &workdir)
+            .expect("load lock")
+            .expect("lock written");
+        assert_eq!(lock.dependencies.len(), 2);
+        assert_eq!(
+            lock.dependencies["sodium"].version_id.as_deref(),
+            Some("sod-1")
+        );
+        assert_eq!(lock.dependencies["fabric_api"].filename, "fabric_api.jar");
+    }
+
+    #[tokio::test]
+    async fn locked_sync_requires_lockfile() {
+        let workdir = mock_root().join("configured-project");
+        let session = configured_session(&workdir);
+
+        let err = handle_sync(&session, true)
+            .await
+            .expect_err("missing lock should fail");
+
+        assert!(matches!(
+            err.downcast_ref::<crate::empack::lockfile::LockfileError>(),
+            Some(crate::empack::lockfile::LockfileError::Missing)
+        ));
+        assert!(session.process_provider.get_calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn locked_sync_rejects_lock_that_disagrees_with_empack_yml() {
+        let workdir = mock_root().join("configured-project");
+        let session = MockCommandSession::new().with_filesystem(
+            MockFileSystemProvider::new()
+                .with_current_dir(workdir.clone())
+                .with_configured_project(workdir.clone())
+                .with_file(
+                    workdir.join("empack.lock"),
+                    "version = 1\n\n[dependencies.sodium]\nplatform = \"modrinth\"\nproject_id = \"AANobbMI\"\nversion_id = \"sod-1\"\ntype = \"mod\"\nfilename = \"sodium.jar\"\n".to_string(),
+                ),
+        );
+
+        let err = handle_sync(&session, true)
+            .await
+            .expect_err("stale lock should fail");
+
+        assert_eq!(
+            crate::application::exit::classify_error(&err),
+            crate::application::exit::EmpackExitCode::Usage
+        );
+        assert!(err.to_string().contains("fabric_api is not in empack.lock"));
+        assert!(session.process_provider.get_calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn locked_sync_installs_locked_versions() {
+        let workdir = mock_root().join("configured-project");
+        let session = MockCommandSession::new()
+            .with_filesystem(
+                MockFileSystemProvider::new()
+                    .with_current_dir(workdir.clone())
+                    .with_configured_project(workdir.clone())
+                    .with_file(workdir.join("empack.lock"), LOCKED_EMPACK_LOCK.to_string()),
+            )
+            .with_network(MockNetworkProvider::new())
+            .with_process(
+                MockProcessProvider::new()
+                    .with_packwiz_add_slug("P7dR8mSH".to_string(), "fabric_api".to_string())
+                    .with_packwiz_add_slug("AANobbMI".to_string(), "sodium".to_string()),
+            );
+
+        handle_sync(&session, true)
+            .await
+            .expect("locked sync succeeds");
+
+        assert!(session.process_provider.verify_call(
+            crate::empack::packwiz::PACKWIZ_BIN,
+            &[
+                "--no-refresh",
+                "modrinth",
+                "add",
+                "--project-id",
+                "AANobbMI",
+                "--version-id",
+                "sod-1",
+                "-y"
+            ],
+            &workdir.join("pack")
+        ));
+        assert!(session.process_provider.verify_call(
+            crate::empack::packwiz::PACKWIZ_BIN,
+            &[
+                "--no-refresh",
+                "modrinth",
+                "add",
+                "--project-id",
+                "P7dR8mSH",
+                "--version-id",
+                "fapi-1",
+                "-y"
+            ],
             &workdir.join("pack")
         ));
+        let lock = session
+            .filesystem()
+            .read_to_string(&workdir.join("empack.lock"))
+            .expect("lock");
+        assert_eq!(lock, LOCKED_EMPACK_LOCK, "locked sync never rewrites the lock");
     }
 
     #[tokio::test]
-    async fn it_succeeds_normally_when_no_planning_resolutions_fail() {
-        let installed_mods = HashSet::new();
+    async fn locked_sync_reinstalls_drifted_files() {
+        let installed_mods: HashSet<String> = ["fabric_api", "sodium"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
         let workdir = mock_root().join("configured-project");
         let session = MockCommandSession::new()
             .with_filesystem(
                 MockFileSystemProvider::new()
                     .with_current_dir(workdir.clone())
                     .with_configured_project(workdir.clone())
-                    .with_installed_mods(installed_mods),
+                    .with_installed_mods(installed_mods)
+                    .with_file(workdir.join("empack.lock"), LOCKED_EMPACK_LOCK.to_string())
+                    .with_file(
+                        workdir.join("pack/mods/fabric_api.pw.toml"),
+                        modrinth_pw_toml("fabric_api", "P7dR8mSH", "fapi-1"),
+                    )
+                    .with_file(
+                        workdir.join("pack/mods/sodium.pw.toml"),
+                        modrinth_pw_toml("sodium", "AANobbMI", "sod-2"),
+                    ),
             )
             .with_network(MockNetworkProvider::new())
             .with_process(
                 MockProcessProvider::new()
-                    .with_packwiz_result(
-                        vec![
-                            "--no-refresh".to_string(),
-                            "modrinth".to_string(),
-                            "add".to_string(),
-                            "--project-id".to_string(),
-                            "P7dR8mSH".to_string(),
-                            "-y".to_string(),
-                        ],
-                        Ok(ProcessOutput {
-                            stdout: String::new(),
-                            stderr: String::new(),
-                            success: true,
-                        }),
-                    )
-                    .with_packwiz_result(
-                        vec![
-                            "--no-refresh".to_string(),
-                            "modrinth".to_string(),
-                            "add".to_string(),
-                            "--project-id".to_string(),
-                            "AANobbMI".to_string(),
-                            "-y".to_string(),
-                        ],
-                        Ok(ProcessOutput {
-                            stdout: String::new(),
-                            stderr: String::new(),
-                            success: true,
-                        }),
-                    )
-                    .with_packwiz_result(
-                        vec!["refresh".to_string()],
-                        Ok(ProcessOutput {
-                            stdout: String::new(),
-                            stderr: String::new(),
-                            success: true,
-                        }),
-                    ),
+                    .with_packwiz_add_slug("AANobbMI".to_string(), "sodium".to_string()),
             );
 
-        let result = handle_sync(&session).await;
+        handle_sync(&session, true)
+            .await
+            .expect("locked sync succeeds");
 
-        assert!(result.is_ok(), "handle_sync should succeed when all resolutions pass: {result:?}");
         let calls = session.process_provider.get_calls();
-        assert_eq!(calls.len(), 3, "Both resolved actions + final refresh should execute");
+        assert_eq!(calls.len(), 1);
+        assert!(session.process_provider.verify_call(
+            crate::empack::packwiz::PACKWIZ_BIN,
+            &[
+                "modrinth",
+                "add",
+                "--project-id",
+                "AANobbMI",
+                "--version-id",
+                "sod-1",
+                "-y"
+            ],
+            &workdir.join("pack")
+        ));
     }
 }
 
@@ -4632,7 +6009,15 @@ mod handle_build_continue_tests {
         .expect("pending build exists");
         assert_eq!(
             pending.targets,
-            vec!["mrpack", "client", "server", "client-full", "server-full"]
+            vec![
+                "mrpack",
+                "client",
+                "server",
+                "client-full",
+                "server-full",
+                "curseforge",
+                "prism"
+            ]
         );
         assert_eq!(pending.entries.len(), 1);
         assert_eq!(
@@ -6541,6 +7926,8 @@ async fn test_parse_build_targets_all_keyword() {
             BuildTarget::Server,
             BuildTarget::ClientFull,
             BuildTarget::ServerFull,
+            BuildTarget::Curseforge,
+            BuildTarget::Prism,
         ]
     );
 }
@@ -6811,6 +8198,8 @@ async fn test_all_valid_build_targets_individually() {
         ("server", BuildTarget::Server),
         ("client-full", BuildTarget::ClientFull),
         ("server-full", BuildTarget::ServerFull),
+        ("curseforge", BuildTarget::Curseforge),
+        ("prism", BuildTarget::Prism),
     ];
 
     for (input, expected) in cases {
@@ -7619,9 +9008,13 @@ mod init_interactive_tests {
                 MockFileSystemProvider::new()
                     .with_current_dir(workdir.clone())
                     .with_configured_project(workdir.clone())
+                    .with_file(
+                        mods_dir.join("sodium.pw.toml"),
+                        "name = \"Sodium\"\nfilename = \"sodium.jar\"\n\n[update.modrinth]\nmod-id = \"AANobbMI\"\n\n[deps]\nEr0ErS9e = \"*\"\n".to_string(),
+                    )
                     .with_file(
                         mods_dir.join("fabric-language-kotlin.pw.toml"),
-                        "name = \"Fabric Language Kotlin\"\nfilename = \"fabric-language-kotlin-1.12.0.jar\"\n".to_string(),
+                        "name = \"Fabric Language Kotlin\"\nfilename = \"fabric-language-kotlin-1.12.0.jar\"\n\n[update.modrinth]\nmod-id = \"Er0ErS9e\"\n".to_string(),
                     ),
             )
             .with_interactive(
@@ -7934,6 +9327,7 @@ mod tracked_local_dependency_tests {
                     path: relative_path.to_string(),
                     source_url: Some("https://example.com/example-pack.zip".to_string()),
                     sha256,
+                    side: None,
                 }),
             )
             .expect("add local dependency");
@@ -7985,6 +9379,7 @@ mod tracked_local_dependency_tests {
                     path: "pack/resourcepacks/example-pack.zip".to_string(),
                     source_url: Some("https://example.com/example-pack.zip".to_string()),
                     sha256: "deadbeef".to_string(),
+                    side: None,
                 }),
             )
             .expect("add local dependency");
@@ -8034,6 +9429,7 @@ mod tracked_local_dependency_tests {
                     path: outside_path.to_string_lossy().to_string(),
                     source_url: Some("https://example.com/example-pack.zip".to_string()),
                     sha256: "deadbeef".to_string(),
+                    side: None,
                 }),
             )
             .expect("add local dependency");
@@ -8091,6 +9487,7 @@ mod tracked_local_dependency_tests {
                     path: "../outside-parent-dir-tracked-local.zip".to_string(),
                     source_url: Some("https://example.com/example-pack.zip".to_string()),
                     sha256: "deadbeef".to_string(),
+                    side: None,
                 }),
             )
             .expect("add local dependency");
@@ -8151,6 +9548,7 @@ mod tracked_local_dependency_tests {
                     path: relative_path.to_string(),
                     source_url: Some("https://example.com/example-pack.zip".to_string()),
                     sha256,
+                    side: None,
                 }),
             )
             .expect("add local dependency");
@@ -8216,11 +9614,12 @@ mod tracked_local_dependency_tests {
                     path: "pack/resourcepacks/example-pack.zip".to_string(),
                     source_url: Some("https://example.com/example-pack.zip".to_string()),
                     sha256: "deadbeef".to_string(),
+                    side: None,
                 }),
             )
             .expect("add local dependency");
 
-        let error = handle_sync(&session)
+        let error = handle_sync(&session, false)
             .await
             .expect_err("missing local dependency should fail sync");
 
@@ -8254,11 +9653,12 @@ mod tracked_local_dependency_tests {
                     path: "pack/resourcepacks/example-pack.zip".to_string(),
                     source_url: Some("https://example.com/example-pack.zip".to_string()),
                     sha256: "deadbeef".to_string(),
+                    side: None,
                 }),
             )
             .expect("add local dependency");
 
-        handle_sync(&session)
+        handle_sync(&session, false)
             .await
             .expect("dry-run sync should report local dependency drift without failing");
 
@@ -8296,6 +9696,7 @@ mod tracked_local_dependency_tests {
                     path: outside_path.to_string_lossy().to_string(),
                     source_url: Some("https://example.com/example-pack.zip".to_string()),
                     sha256: outside_sha256,
+                    side: None,
                 }),
             )
             .expect("add local dependency");
@@ -8338,6 +9739,7 @@ mod tracked_local_dependency_tests {
                     path: "../outside-parent-dir-validate-local-dependency.zip".to_string(),
                     source_url: Some("https://example.com/example-pack.zip".to_string()),
                     sha256: outside_sha256,
+                    side: None,
                 }),
             )
             .expect("add local dependency");
@@ -8381,6 +9783,7 @@ mod tracked_local_dependency_tests {
                     path: relative_path.to_string(),
                     source_url: Some("https://example.com/example-pack.zip".to_string()),
                     sha256,
+                    side: None,
                 }),
             )
             .expect("add local dependency");
@@ -8437,6 +9840,7 @@ mod tracked_local_dependency_tests {
                     path: relative_path.to_string(),
                     source_url: Some("https://example.com/example-pack.zip".to_string()),
                     sha256,
+                    side: None,
                 }),
             )
             .expect("add local dependency");
@@ -8494,6 +9898,7 @@ mod tracked_local_dependency_tests {
                     path: relative_path.to_string(),
                     source_url: Some("https://example.com/example-pack.zip".to_string()),
                     sha256: compute_sha256_hex_for_bytes(b"different-bytes"),
+                    side: None,
                 }),
             )
             .expect("add local dependency");
@@ -9054,57 +10459,427 @@ mod handle_clean_dry_run_tests {
         );
     }
 
-    #[tokio::test]
-    async fn dry_run_cache_target() {
-        let workdir = mock_root().join("configured-project");
-        let mut session = configured_session(&workdir);
-        session.config_provider.app_config.dry_run = true;
+    #[tokio::test]
+    async fn dry_run_cache_target() {
+        let workdir = mock_root().join("configured-project");
+        let mut session = configured_session(&workdir);
+        session.config_provider.app_config.dry_run = true;
+
+        let result = handle_clean(&session, vec!["cache".to_string()]).await;
+        assert!(result.is_ok());
+    }
+}
+
+// ===== EXECUTE_COMMAND_WITH_SESSION DISPATCH TESTS =====
+
+mod execute_command_dispatch_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn dispatches_version_command() {
+        let session = MockCommandSession::new();
+        let result = execute_command_with_session(Commands::Version, &session).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn dispatches_requirements_command() {
+        let session = MockCommandSession::new()
+            .with_process(MockProcessProvider::new().with_packwiz_version("1.2.3".to_string()));
+        let result = execute_command_with_session(Commands::Requirements, &session).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn dispatches_clean_command() {
+        let workdir = mock_root().join("configured-project");
+        let session = configured_session(&workdir);
+        let result = execute_command_with_session(
+            Commands::Clean { targets: vec!["cache".to_string()] },
+            &session,
+        )
+        .await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn dispatches_sync_uninitialized_returns_error() {
+        let session = MockCommandSession::new().with_filesystem(
+            MockFileSystemProvider::new()
+                .with_current_dir(mock_root().join("uninit-dispatch")),
+        );
+        let result = execute_command_with_session(Commands::Sync { locked: false }, &session).await;
+        assert!(result.is_err());
+    }
+}
+
+// ===== HANDLE_SEARCH =====
+
+mod handle_search_tests {
+    use super::*;
+
+    fn search_args(query: &str, platform: SearchPlatform, add: bool) -> SearchArgs {
+        SearchArgs {
+            query: vec![query.to_string()],
+            platform,
+            project_type: CliProjectType::Mod,
+            category: None,
+            limit: 10,
+            add,
+        }
+    }
+
+    fn sodium_candidates() -> Vec<ProjectInfo> {
+        vec![
+            ProjectInfo {
+                confidence: 100,
+                ..modrinth_project("AANobbMI", "Sodium")
+            },
+            ProjectInfo {
+                platform: ProjectPlatform::CurseForge,
+                project_id: "394468".to_string(),
+                title: "Sodium Reforged".to_string(),
+                downloads: 50_000,
+                confidence: 72,
+                project_type: "mod".to_string(),
+            },
+        ]
+    }
+
+    #[tokio::test]
+    async fn it_filters_by_the_pack_and_marks_installed_projects() {
+        let workdir = mock_root().join("configured-project");
+        let session = configured_session(&workdir).with_network(
+            MockNetworkProvider::new()
+                .with_search_candidates("sodium".to_string(), sodium_candidates()),
+        );
+        let mut args = search_args("sodium", SearchPlatform::Both, false);
+        args.category = Some("optimization".to_string());
+
+        let result = handle_search(&session, &args).await;
+
+        assert!(result.is_ok(), "search failed: {result:?}");
+        let calls = session
+            .network_provider
+            .mock_resolver
+            .candidate_calls
+            .lock()
+            .unwrap()
+            .clone();
+        assert_eq!(calls.len(), 1);
+        let (query, project_type, filters) = &calls[0];
+        assert_eq!(query, "sodium");
+        assert_eq!(project_type, "mod");
+        assert_eq!(filters.minecraft_version.as_deref(), Some("1.21.1"));
+        assert_eq!(filters.mod_loader.as_deref(), Some("fabric"));
+        assert_eq!(filters.category.as_deref(), Some("optimization"));
+        assert_eq!(
+            filters.platforms,
+            vec![ProjectPlatform::Modrinth, ProjectPlatform::CurseForge]
+        );
+
+        let records = session.display().take_records();
+        let results = records["results"].as_array().expect("results array");
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["project_id"], "AANobbMI");
+        assert_eq!(results[0]["installed"], true);
+        assert_eq!(results[1]["project_id"], "394468");
+        assert_eq!(results[1]["installed"], false);
+        assert!(session.process_provider.get_calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_searches_outside_a_pack_without_version_filters() {
+        let session = MockCommandSession::new()
+            .with_filesystem(
+                MockFileSystemProvider::new()
+                    .with_current_dir(mock_root().join("no-pack-search")),
+            )
+            .with_network(
+                MockNetworkProvider::new()
+                    .with_search_candidates("sodium".to_string(), sodium_candidates()),
+            );
+        let mut args = search_args("sodium", SearchPlatform::Curseforge, false);
+        args.limit = 1;
+
+        let result = handle_search(&session, &args).await;
+
+        assert!(result.is_ok(), "search failed: {result:?}");
+        let calls = session
+            .network_provider
+            .mock_resolver
+            .candidate_calls
+            .lock()
+            .unwrap()
+            .clone();
+        let filters = &calls[0].2;
+        assert_eq!(filters.minecraft_version, None);
+        assert_eq!(filters.mod_loader, None);
+        assert_eq!(filters.platforms, vec![ProjectPlatform::CurseForge]);
+
+        let records = session.display().take_records();
+        let results = records["results"].as_array().expect("results array");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["platform"], "curseforge");
+    }
+
+    #[tokio::test]
+    async fn it_reports_no_results_as_not_found() {
+        let workdir = mock_root().join("configured-project");
+        let session = configured_session(&workdir);
+
+        let error = handle_search(&session, &search_args("nothing", SearchPlatform::Both, false))
+            .await
+            .expect_err("no candidates should fail");
+
+        assert_eq!(classify_error(&error), EmpackExitCode::NotFound);
+    }
+
+    #[tokio::test]
+    async fn it_adds_the_selected_result() {
+        let workdir = mock_root().join("configured-project");
+        let session = configured_session(&workdir)
+            .with_network(
+                MockNetworkProvider::new()
+                    .with_search_candidates("sodium".to_string(), sodium_candidates()),
+            )
+            .with_interactive(MockInteractiveProvider::new().with_fuzzy_select(1))
+            .with_process(MockProcessProvider::new().with_packwiz_result(
+                vec![
+                    "curseforge".to_string(),
+                    "add".to_string(),
+                    "--addon-id".to_string(),
+                    "394468".to_string(),
+                    "-y".to_string(),
+                ],
+                Ok(ProcessOutput {
+                    stdout: String::new(),
+                    stderr: String::new(),
+                    success: true,
+                }),
+            ));
+
+        let result =
+            handle_search(&session, &search_args("sodium", SearchPlatform::Both, true)).await;
+
+        assert!(result.is_ok(), "search --add failed: {result:?}");
+        assert!(session.process_provider.verify_call(
+            crate::empack::packwiz::PACKWIZ_BIN,
+            &["curseforge", "add", "--addon-id", "394468", "-y"],
+            &workdir.join("pack")
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_requires_a_pack_before_adding() {
+        let session = MockCommandSession::new()
+            .with_filesystem(
+                MockFileSystemProvider::new()
+                    .with_current_dir(mock_root().join("no-pack-search-add")),
+            )
+            .with_network(
+                MockNetworkProvider::new()
+                    .with_search_candidates("sodium".to_string(), sodium_candidates()),
+            );
+
+        let result =
+            handle_search(&session, &search_args("sodium", SearchPlatform::Both, true)).await;
+
+        assert!(result.is_err());
+        assert!(
+            session
+                .network_provider
+                .mock_resolver
+                .candidate_calls
+                .lock()
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
+
+// ===== JSON OUTPUT DOCUMENT =====
+
+mod json_result_document_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn list_records_dependency_rows() {
+        let workdir = mock_root().join("configured-project");
+        let session = configured_session(&workdir);
+        let command = Commands::List {
+            project_type: None,
+            platform: None,
+            sort: ListSort::Slug,
+        };
+        let name = command.name();
+
+        let result = execute_command_with_session(command, &session).await;
+        let document = json_result_document(name, &result, session.display());
+
+        assert_eq!(document["command"], "list");
+        assert_eq!(document["success"], true);
+        assert_eq!(document["exit_code"], 0);
+        assert!(document["data"]["dependencies"].is_array());
+        assert!(document.get("error").is_none());
+    }
+
+    #[tokio::test]
+    async fn failure_carries_exit_code_and_message() {
+        let session = MockCommandSession::new().with_filesystem(
+            MockFileSystemProvider::new().with_current_dir(mock_root().join("uninit-json")),
+        );
+        let command = Commands::Add {
+            mods: vec![],
+            force: false,
+            platform: None,
+            project_type: None,
+            version_id: None,
+            file_id: None,
+        };
+        let name = command.name();
+
+        let result = execute_command_with_session(command, &session).await;
+        let document = json_result_document(name, &result, session.display());
 
-        let result = handle_clean(&session, vec!["cache".to_string()]).await;
-        assert!(result.is_ok());
+        assert_eq!(document["command"], "add");
+        assert_eq!(document["success"], false);
+        assert_eq!(document["exit_code"], EmpackExitCode::Usage.as_i32());
+        assert_eq!(document["error"]["kind"], "usage");
+        assert_eq!(document["error"]["message"], "No mods specified to add");
     }
-}
 
-// ===== EXECUTE_COMMAND_WITH_SESSION DISPATCH TESTS =====
+    #[test]
+    fn take_records_drains_recorded_results() {
+        let session = MockCommandSession::new();
+        record_json(&session, "added", &["sodium"]);
 
-mod execute_command_dispatch_tests {
-    use super::*;
+        let first = json_result_document("add", &Ok(()), session.display());
+        let second = json_result_document("add", &Ok(()), session.display());
+
+        assert_eq!(first["data"]["added"], serde_json::json!(["sodium"]));
+        assert_eq!(second["data"], serde_json::json!({}));
+    }
 
     #[tokio::test]
-    async fn dispatches_version_command() {
+    async fn version_records_build_information() {
         let session = MockCommandSession::new();
+
         let result = execute_command_with_session(Commands::Version, &session).await;
-        assert!(result.is_ok());
+        let document = json_result_document("version", &result, session.display());
+
+        let version = &document["data"]["version"];
+        assert_eq!(version["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(version["target"], std::env::consts::ARCH);
+        assert!(version["commit"].is_string());
+        assert!(version["build_date"].is_string());
     }
 
     #[tokio::test]
-    async fn dispatches_requirements_command() {
-        let session = MockCommandSession::new()
-            .with_process(MockProcessProvider::new().with_packwiz_version("1.2.3".to_string()));
+    async fn requirements_records_tool_availability() {
+        let session = MockCommandSession::new().with_process(
+            MockProcessProvider::new().with_packwiz_result(
+                vec!["--help".to_string()],
+                Ok(ProcessOutput {
+                    stdout: String::new(),
+                    stderr: "packwiz not found".to_string(),
+                    success: false,
+                }),
+            ),
+        );
+
         let result = execute_command_with_session(Commands::Requirements, &session).await;
-        assert!(result.is_ok());
+        let document = json_result_document("requirements", &result, session.display());
+
+        let requirements = &document["data"]["requirements"];
+        assert_eq!(requirements["packwiz"]["available"], false);
+        assert_eq!(requirements["packwiz"]["path"], session.packwiz_bin());
+        assert!(requirements["java"]["available"].is_boolean());
+        assert_eq!(
+            requirements["archive"],
+            serde_json::json!(["zip", "tar.gz", "7z"])
+        );
     }
 
     #[tokio::test]
-    async fn dispatches_clean_command() {
-        let workdir = mock_root().join("configured-project");
-        let session = configured_session(&workdir);
+    async fn init_records_created_project() {
+        let workdir = mock_root().join("json-init");
+        let session = MockCommandSession::new()
+            .with_filesystem(MockFileSystemProvider::new().with_current_dir(workdir.clone()))
+            .with_interactive(MockInteractiveProvider::new().with_yes_mode(true));
+
         let result = execute_command_with_session(
-            Commands::Clean { targets: vec!["cache".to_string()] },
+            Commands::Init(InitArgs {
+                dir: Some("vanilla-pack".to_string()),
+                modloader: Some("none".to_string()),
+                mc_version: Some("1.21.1".to_string()),
+                author: Some("Test Author".to_string()),
+                ..Default::default()
+            }),
             &session,
         )
         .await;
-        assert!(result.is_ok());
+        let document = json_result_document("init", &result, session.display());
+
+        assert_eq!(document["success"], true, "{document}");
+        let init = &document["data"]["init"];
+        assert_eq!(
+            init["project_dir"],
+            workdir.join("vanilla-pack").display().to_string()
+        );
+        assert_eq!(init["author"], "Test Author");
+        assert_eq!(init["minecraft_version"], "1.21.1");
+        assert_eq!(init["loader"], "none");
+        assert!(init["loader_version"].is_null());
     }
 
     #[tokio::test]
-    async fn dispatches_sync_uninitialized_returns_error() {
+    async fn clean_records_cleaned_targets() {
+        let workdir = mock_root().join("built-project");
         let session = MockCommandSession::new().with_filesystem(
             MockFileSystemProvider::new()
-                .with_current_dir(mock_root().join("uninit-dispatch")),
+                .with_current_dir(workdir.clone())
+                .with_built_project(workdir.clone()),
+        );
+
+        let result = execute_command_with_session(
+            Commands::Clean {
+                targets: vec!["builds".to_string()],
+            },
+            &session,
+        )
+        .await;
+        let document = json_result_document("clean", &result, session.display());
+
+        assert_eq!(
+            document["data"]["cleaned"],
+            serde_json::json!({ "builds": true })
+        );
+    }
+
+    #[tokio::test]
+    async fn clean_dry_run_records_planned_targets() {
+        let workdir = mock_root().join("built-project");
+        let mut session = MockCommandSession::new().with_filesystem(
+            MockFileSystemProvider::new()
+                .with_current_dir(workdir.clone())
+                .with_built_project(workdir.clone()),
+        );
+        session.config_provider.app_config.dry_run = true;
+
+        let result = execute_command_with_session(
+            Commands::Clean {
+                targets: vec!["all".to_string()],
+            },
+            &session,
+        )
+        .await;
+        let document = json_result_document("clean", &result, session.display());
+
+        assert_eq!(
+            document["data"]["plan"],
+            serde_json::json!(["builds", "cache"])
         );
-        let result = execute_command_with_session(Commands::Sync {}, &session).await;
-        assert!(result.is_err());
     }
 }
 
@@ -9246,3 +11021,348 @@ mod plan_helpers_tests {
         assert!(plan_loader(Some(&plan)).is_none());
     }
 }
+
+mod handle_migrate_tests {
+    use super::*;
+    use crate::empack::updates::UpdateApiBases;
+
+    const MIGRATE_EMPACK_YML: &str = r#"empack:
+  dependencies:
+    sodium:
+      status: resolved
+      title: Sodium
+      platform: modrinth
+      project_id: AANobbMI
+      type: mod
+      version: sodium-old
+    lithium:
+      status: resolved
+      title: Lithium
+      platform: modrinth
+      project_id: gvQqBUqZ
+      type: mod
+  minecraft_version: "1.21.1"
+  loader: fabric
+  loader_version: "0.15.0"
+  acceptable_game_versions:
+    - "1.21"
+  name: "Test Pack"
+  author: "Test Author"
+  version: "1.0.0"
+"#;
+
+    fn migrate_session(workdir: &Path) -> MockCommandSession {
+        MockCommandSession::new()
+            .with_filesystem(
+                MockFileSystemProvider::new()
+                    .with_current_dir(workdir.to_path_buf())
+                    .with_configured_project(workdir.to_path_buf())
+                    .with_file(workdir.join("empack.yml"), MIGRATE_EMPACK_YML.to_string())
+                    .with_file(
+                        workdir.join("pack").join("mods").join("sodium.pw.toml"),
+                        "name = \"Sodium\"\nfilename = \"sodium-old.jar\"\n\n[update.modrinth]\nmod-id = \"AANobbMI\"\nversion = \"sodium-old\"\n".to_string(),
+                    )
+                    .with_file(
+                        workdir.join("pack").join("mods").join("lithium.pw.toml"),
+                        "name = \"Lithium\"\nfilename = \"lithium.jar\"\n\n[update.modrinth]\nmod-id = \"gvQqBUqZ\"\nversion = \"lith-old\"\n".to_string(),
+                    ),
+            )
+            .with_network(MockNetworkProvider::new().with_search_candidates(
+                "Lithium".to_string(),
+                vec![ProjectInfo {
+                    platform: ProjectPlatform::CurseForge,
+                    project_id: "360438".to_string(),
+                    title: "Lithium".to_string(),
+                    downloads: 10_000,
+                    confidence: 100,
+                    project_type: "mod".to_string(),
+                }],
+            ))
+    }
+
+    fn migrate_args(apply: bool) -> MigrateArgs {
+        MigrateArgs {
+            mc_version: "1.21.4".to_string(),
+            loader: None,
+            loader_version: Some("0.16.9".to_string()),
+            apply,
+        }
+    }
+
+    fn api_bases(server: &mockito::ServerGuard) -> UpdateApiBases {
+        UpdateApiBases {
+            modrinth: vec![server.url()],
+            curseforge: vec![server.url()],
+        }
+    }
+
+    async fn mock_versions(
+        server: &mut mockito::ServerGuard,
+        project_id: &str,
+        status: usize,
+        body: &str,
+    ) -> mockito::Mock {
+        server
+            .mock("GET", format!("/v2/project/{project_id}/version").as_str())
+            .match_query(mockito::Matcher::Any)
+            .with_status(status)
+            .with_body(body)
+            .create_async()
+            .await
+    }
+
+    const SODIUM_NEW: &str = r#"[{"id":"sodium-new","version_number":"0.6.5","date_published":"2024-12-01T00:00:00Z","files":[]}]"#;
+
+    #[tokio::test]
+    async fn migrate_reports_without_changing_the_project() {
+        let workdir = mock_root().join("configured-project");
+        let mut server = mockito::Server::new_async().await;
+        let sodium = mock_versions(&mut server, "AANobbMI", 200, SODIUM_NEW).await;
+        let lithium = mock_versions(&mut server, "gvQqBUqZ", 200, "[]").await;
+        let session = migrate_session(&workdir);
+
+        handle_migrate_with_api_bases(&session, &migrate_args(false), &api_bases(&server))
+            .await
+            .expect("migration report succeeds");
+
+        sodium.assert_async().await;
+        lithium.assert_async().await;
+        assert!(session.process_provider.get_calls().is_empty());
+        let config = session
+            .filesystem()
+            .read_to_string(&workdir.join("empack.yml"))
+            .expect("empack.yml");
+        assert_eq!(config, MIGRATE_EMPACK_YML);
+    }
+
+    #[tokio::test]
+    async fn migrate_apply_retargets_and_leaves_blocked_mods_for_sync() {
+        let workdir = mock_root().join("configured-project");
+        let mut server = mockito::Server::new_async().await;
+        let _sodium = mock_versions(&mut server, "AANobbMI", 200, SODIUM_NEW).await;
+        let _lithium = mock_versions(&mut server, "gvQqBUqZ", 200, "[]").await;
+        let session = migrate_session(&workdir);
+
+        handle_migrate_with_api_bases(&session, &migrate_args(true), &api_bases(&server))
+            .await
+            .expect("migration succeeds");
+
+        let pack_dir = workdir.join("pack");
+        assert!(session.process_provider.verify_call(
+            crate::empack::packwiz::PACKWIZ_BIN,
+            &[
+                "--no-refresh",
+                "modrinth",
+                "add",
+                "--project-id",
+                "AANobbMI",
+                "--version-id",
+                "sodium-new",
+                "-y"
+            ],
+            &pack_dir
+        ));
+        assert!(session.process_provider.verify_call(
+            crate::empack::packwiz::PACKWIZ_BIN,
+            &["--no-refresh", "remove", "-y", "lithium"],
+            &pack_dir
+        ));
+        assert!(session.process_provider.verify_call(
+            crate::empack::packwiz::PACKWIZ_BIN,
+            &["refresh"],
+            &pack_dir
+        ));
+
+        let config = session
+            .filesystem()
+            .config_manager(workdir.clone())
+            .load_empack_config()
+            .expect("empack.yml");
+        assert_eq!(config.empack.minecraft_version.as_deref(), Some("1.21.4"));
+        assert_eq!(config.empack.loader_version.as_deref(), Some("0.16.9"));
+        assert_eq!(config.empack.acceptable_game_versions, None);
+        match config.empack.dependencies.get("sodium") {
+            Some(DependencyEntry::Resolved(record)) => {
+                assert_eq!(record.version.as_deref(), Some("sodium-new"));
+            }
+            other => panic!("unexpected sodium entry: {other:?}"),
+        }
+        match config.empack.dependencies.get("lithium") {
+            Some(DependencyEntry::Search(search)) => {
+                assert_eq!(search.title, "Lithium");
+                assert_eq!(search.platform, Some(ProjectPlatform::CurseForge));
+            }
+            other => panic!("unexpected lithium entry: {other:?}"),
+        }
+
+        let pack_toml = session
+            .filesystem()
+            .read_to_string(&pack_dir.join("pack.toml"))
+            .expect("pack.toml");
+        assert!(pack_toml.contains("minecraft = \"1.21.4\""));
+        assert!(pack_toml.contains("fabric = \"0.16.9\""));
+    }
+
+    #[tokio::test]
+    async fn migrate_apply_refuses_when_a_check_fails() {
+        let workdir = mock_root().join("configured-project");
+        let mut server = mockito::Server::new_async().await;
+        let _sodium = mock_versions(&mut server, "AANobbMI", 200, SODIUM_NEW).await;
+        let _lithium = mock_versions(&mut server, "gvQqBUqZ", 500, "").await;
+        let session = migrate_session(&workdir);
+
+        let err =
+            handle_migrate_with_api_bases(&session, &migrate_args(true), &api_bases(&server))
+                .await
+                .expect_err("failed check should stop the migration");
+
+        assert!(err.to_string().contains("could not be checked for migration"));
+        assert!(session.process_provider.get_calls().is_empty());
+        let config = session
+            .filesystem()
+            .read_to_string(&workdir.join("empack.yml"))
+            .expect("empack.yml");
+        assert_eq!(config, MIGRATE_EMPACK_YML);
+    }
+
+    #[tokio::test]
+    async fn migrate_rejects_unknown_loader_as_usage_error() {
+        let workdir = mock_root().join("configured-project");
+        let session = migrate_session(&workdir);
+        let mut args = migrate_args(false);
+        args.loader = Some("rift".to_string());
+
+        let err = handle_migrate(&session, &args)
+            .await
+            .expect_err("unknown loader should fail");
+
+        assert_eq!(classify_error(&err), EmpackExitCode::Usage);
+    }
+}
+
+// ===== HANDLE_VERIFY =====
+
+mod handle_verify_tests {
+    use super::*;
+    use crate::empack::signing::SigningKey;
+
+    const ARTIFACT: &str = "Test Pack-v1.0.0-server.zip";
+
+    /// A session with a signed artifact, its SHA256SUMS, and the public key.
+    fn signed_session(dist: &Path, data: &[u8]) -> (MockCommandSession, String) {
+        use sha2::Digest;
+
+        let keypair = minisign::KeyPair::generate_unencrypted_keypair().unwrap();
+        let secret = keypair.sk.to_box(None).unwrap().into_string();
+        let signature = SigningKey::parse(&secret, None)
+            .unwrap()
+            .sign(data, ARTIFACT, 0)
+            .unwrap();
+        let digest = crate::empack::content::hex::encode(sha2::Sha256::digest(data));
+        let filesystem = MockFileSystemProvider::new()
+            .with_file(dist.join(format!("{ARTIFACT}.minisig")), signature)
+            .with_file(dist.join("SHA256SUMS"), format!("{digest}  {ARTIFACT}\n"))
+            .with_file(dist.join("empack.pub"), keypair.pk.to_box().unwrap().into_string());
+        filesystem
+            .write_bytes(&dist.join(ARTIFACT), data)
+            .unwrap();
+        (MockCommandSession::new().with_filesystem(filesystem), keypair.pk.to_base64())
+    }
+
+    fn verify_command(dist: &Path, pubkey: &str) -> Commands {
+        Commands::Verify {
+            artifact: dist.join(ARTIFACT).display().to_string(),
+            pubkey: pubkey.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn verifies_signature_and_checksum_with_key_file_or_base64() {
+        let dist = mock_root().join("downloads");
+        let (session, base64_key) = signed_session(&dist, b"server pack");
+
+        let key_file = dist.join("empack.pub").display().to_string();
+        execute_command_with_session(verify_command(&dist, &key_file), &session)
+            .await
+            .expect("key file verifies");
+        execute_command_with_session(verify_command(&dist, &base64_key), &session)
+            .await
+            .expect("base64 key verifies");
+    }
+
+    #[tokio::test]
+    async fn fails_when_the_artifact_was_tampered_with() {
+        let dist = mock_root().join("downloads");
+        let (session, base64_key) = signed_session(&dist, b"server pack");
+        session
+            .filesystem()
+            .write_bytes(&dist.join(ARTIFACT), b"server pack with a backdoor")
+            .unwrap();
+
+        let err = execute_command_with_session(verify_command(&dist, &base64_key), &session)
+            .await
+            .expect_err("tampered artifact fails");
+
+        assert!(err.to_string().contains("Signature does not match"));
+        assert_eq!(classify_error(&err), EmpackExitCode::General);
+    }
+
+    #[tokio::test]
+    async fn fails_when_the_checksum_entry_disagrees() {
+        let dist = mock_root().join("downloads");
+        let (session, base64_key) = signed_session(&dist, b"server pack");
+        session
+            .filesystem()
+            .write_file(&dist.join("SHA256SUMS"), &format!("{}  {ARTIFACT}\n", "0".repeat(64)))
+            .unwrap();
+
+        let err = execute_command_with_session(verify_command(&dist, &base64_key), &session)
+            .await
+            .expect_err("checksum mismatch fails");
+
+        assert!(err.to_string().contains("SHA256SUMS entry does not match"));
+        assert_eq!(classify_error(&err), EmpackExitCode::General);
+    }
+}
+
+mod handle_cache_gc_tests {
+    use super::*;
+    use crate::application::cli::CacheCommand;
+    use crate::empack::store::ContentStore;
+
+    fn gc_command() -> Commands {
+        Commands::Cache {
+            action: CacheCommand::Gc,
+        }
+    }
+
+    #[tokio::test]
+    async fn evicts_objects_only_deleted_projects_used() {
+        let kept = mock_root().join("kept-pack");
+        let gone = mock_root().join("deleted-pack");
+        let mut session = MockCommandSession::new().with_filesystem(
+            MockFileSystemProvider::new().with_file(kept.join("empack.yml"), "empack: {}\n".to_string()),
+        );
+        let (used, orphaned) = {
+            let store = ContentStore::open(session.filesystem()).unwrap();
+            let object = |name: String| store.root().join("objects").join(name);
+            (
+                object(store.admit_for_project(&kept, b"server jar", None, "srv.jar").unwrap()),
+                object(store.admit_for_project(&gone, b"old installer", None, "a.jar").unwrap()),
+            )
+        };
+
+        session.config_provider.app_config.dry_run = true;
+        execute_command_with_session(gc_command(), &session)
+            .await
+            .expect("dry run succeeds");
+        assert!(session.filesystem().exists(&orphaned));
+
+        session.config_provider.app_config.dry_run = false;
+        execute_command_with_session(gc_command(), &session)
+            .await
+            .expect("gc succeeds");
+        assert!(session.filesystem().exists(&used));
+        assert!(!session.filesystem().exists(&orphaned));
+    }
+}
@@ -1,5 +1,6 @@
 //! Configuration precedence: defaults, .env, env vars, CLI args.
 
+use crate::networking::endpoints::{Endpoints, Service};
 use crate::primitives::*;
 use clap::Parser;
 use serde::Deserialize;
@@ -11,6 +12,7 @@ pub mod defaults {
     pub const NET_TIMEOUT: &str = "30";
     pub const CPU_PARALLELS: &str = "2";
     pub const LOG_OUTPUT: &str = "stderr";
+    pub const OUTPUT_FORMAT: &str = "text";
     pub const TTY_CAPS_DETECT_INTENT: &str = "auto";
     pub const CURSEFORGE_API_CLIENT_KEY: &str =
         "$2a$10$78GooA4YTCKFQI9vgZ1oEeVM.jNyeNKSIFUhFkwiA0L/Uwv19BFAq";
@@ -18,7 +20,7 @@ pub mod defaults {
 
 mod default_fns {
     use super::*;
-    use crate::primitives::{LogFormat, LogOutput, TerminalCapsDetectIntent};
+    use crate::primitives::{LogFormat, LogOutput, OutputFormat, TerminalCapsDetectIntent};
 
     pub fn log_level() -> u8 {
         defaults::LOG_LEVEL.parse().unwrap()
@@ -40,6 +42,10 @@ mod default_fns {
         defaults::LOG_OUTPUT.parse().unwrap()
     }
 
+    pub fn output_format() -> OutputFormat {
+        defaults::OUTPUT_FORMAT.parse().unwrap()
+    }
+
     pub fn tty_caps_detect_intent() -> TerminalCapsDetectIntent {
         defaults::TTY_CAPS_DETECT_INTENT.parse().unwrap()
     }
@@ -81,6 +87,101 @@ pub struct AppConfig {
     #[serde(default = "default_fns::curseforge_api_client_key")]
     pub curseforge_api_client_key: Option<String>,
 
+    /// Minisign secret key used to sign build artifacts
+    #[arg(long, env = "EMPACK_SIGNING_KEY", value_name = "PATH")]
+    #[serde(default)]
+    pub signing_key: Option<PathBuf>,
+
+    /// Password for an encrypted signing key
+    #[arg(long, env = "EMPACK_SIGNING_KEY_PASSWORD", hide_env_values = true)]
+    #[serde(default)]
+    pub signing_key_password: Option<String>,
+
+    /// Modrinth API base URLs, comma-separated in fallback order
+    #[arg(
+        long,
+        env = "EMPACK_MODRINTH_API_URL",
+        value_name = "URL[,URL...]",
+        help_heading = "Endpoints"
+    )]
+    #[serde(default)]
+    pub modrinth_api_url: Option<String>,
+
+    /// CurseForge API base URLs, comma-separated in fallback order
+    #[arg(
+        long,
+        env = "EMPACK_CURSEFORGE_API_URL",
+        value_name = "URL[,URL...]",
+        help_heading = "Endpoints"
+    )]
+    #[serde(default)]
+    pub curseforge_api_url: Option<String>,
+
+    /// Modrinth CDN base URLs, comma-separated in fallback order
+    #[arg(
+        long,
+        env = "EMPACK_MODRINTH_CDN_URL",
+        value_name = "URL[,URL...]",
+        help_heading = "Endpoints"
+    )]
+    #[serde(default)]
+    pub modrinth_cdn_url: Option<String>,
+
+    /// Forge Maven base URLs, comma-separated in fallback order
+    #[arg(
+        long,
+        env = "EMPACK_FORGE_MAVEN_URL",
+        value_name = "URL[,URL...]",
+        help_heading = "Endpoints"
+    )]
+    #[serde(default)]
+    pub forge_maven_url: Option<String>,
+
+    /// NeoForge Maven base URLs, comma-separated in fallback order
+    #[arg(
+        long,
+        env = "EMPACK_NEOFORGE_MAVEN_URL",
+        value_name = "URL[,URL...]",
+        help_heading = "Endpoints"
+    )]
+    #[serde(default)]
+    pub neoforge_maven_url: Option<String>,
+
+    /// Fabric Maven base URLs, comma-separated in fallback order
+    #[arg(
+        long,
+        env = "EMPACK_FABRIC_MAVEN_URL",
+        value_name = "URL[,URL...]",
+        help_heading = "Endpoints"
+    )]
+    #[serde(default)]
+    pub fabric_maven_url: Option<String>,
+
+    /// Quilt Maven base URLs, comma-separated in fallback order
+    #[arg(
+        long,
+        env = "EMPACK_QUILT_MAVEN_URL",
+        value_name = "URL[,URL...]",
+        help_heading = "Endpoints"
+    )]
+    #[serde(default)]
+    pub quilt_maven_url: Option<String>,
+
+    /// Mojang piston-meta base URLs, comma-separated in fallback order
+    #[arg(
+        long,
+        env = "EMPACK_MOJANG_META_URL",
+        value_name = "URL[,URL...]",
+        help_heading = "Endpoints"
+    )]
+    #[serde(default)]
+    pub mojang_meta_url: Option<String>,
+
+    /// Endpoints parsed from the URL settings by `validate`
+    #[arg(skip)]
+    #[serde(skip)]
+    pub endpoints: Endpoints,
+
     /// Verbosity level (0=error, 1=warn, 2=info, 3=debug, 4=trace)
     #[arg(long, env = "EMPACK_LOG_LEVEL", default_value = defaults::LOG_LEVEL)]
     #[serde(default = "default_fns::log_level")]
@@ -101,6 +202,17 @@ pub struct AppConfig {
     #[serde(default = "default_fns::tty_caps_detect_intent")]
     pub color: TerminalCapsDetectIntent,
 
+    /// Command output mode (text, json)
+    #[arg(
+        long,
+        global = true,
+        env = "EMPACK_OUTPUT",
+        default_value = defaults::OUTPUT_FORMAT,
+        help = "Output mode: text, or one JSON result document on stdout"
+    )]
+    #[serde(default = "default_fns::output_format")]
+    pub output: OutputFormat,
+
     /// Skip prompts and use defaults (global non-interactive mode)
     #[arg(
         short = 'y',
@@ -121,6 +233,36 @@ pub struct AppConfig {
     )]
     #[serde(default)]
     pub dry_run: bool,
+
+    /// Keep partial changes when a project-changing command fails (for debugging)
+    #[arg(
+        long,
+        global = true,
+        env = "EMPACK_NO_ROLLBACK",
+        help = "Keep partial project changes when a command fails"
+    )]
+    #[serde(default)]
+    pub no_rollback: bool,
+
+    /// Wait for another empack process to release the project lock
+    #[arg(
+        long,
+        global = true,
+        env = "EMPACK_WAIT_LOCK",
+        help = "Wait for the project lock instead of failing when another empack process holds it"
+    )]
+    #[serde(default)]
+    pub wait_lock: bool,
+
+    /// Serve network requests only from local caches
+    #[arg(
+        long,
+        global = true,
+        env = "EMPACK_OFFLINE",
+        help = "Use only cached API responses, version lists, and jars; fail on anything uncached"
+    )]
+    #[serde(default)]
+    pub offline: bool,
 }
 
 impl Default for AppConfig {
@@ -132,12 +274,27 @@ impl Default for AppConfig {
             modrinth_api_client_id: None,
             modrinth_api_client_key: None,
             curseforge_api_client_key: default_fns::curseforge_api_client_key(),
+            signing_key: None,
+            signing_key_password: None,
+            modrinth_api_url: None,
+            curseforge_api_url: None,
+            modrinth_cdn_url: None,
+            forge_maven_url: None,
+            neoforge_maven_url: None,
+            fabric_maven_url: None,
+            quilt_maven_url: None,
+            mojang_meta_url: None,
+            endpoints: Endpoints::default(),
             log_level: default_fns::log_level(),
             log_format: default_fns::log_format(),
             log_output: default_fns::log_output(),
             color: default_fns::tty_caps_detect_intent(),
+            output: default_fns::output_format(),
             yes: false,
             dry_run: false,
+            no_rollback: false,
+            wait_lock: false,
+            offline: false,
         }
     }
 }
@@ -170,6 +327,36 @@ impl AppConfig {
         if other.curseforge_api_client_key.is_some() {
             self.curseforge_api_client_key = other.curseforge_api_client_key;
         }
+        if other.signing_key.is_some() {
+            self.signing_key = other.signing_key;
+        }
+        if other.signing_key_password.is_some() {
+            self.signing_key_password = other.signing_key_password;
+        }
+        if other.modrinth_api_url.is_some() {
+            self.modrinth_api_url = other.modrinth_api_url;
+        }
+        if other.curseforge_api_url.is_some() {
+            self.curseforge_api_url = other.curseforge_api_url;
+        }
+        if other.modrinth_cdn_url.is_some() {
+            self.modrinth_cdn_url = other.modrinth_cdn_url;
+        }
+        if other.forge_maven_url.is_some() {
+            self.forge_maven_url = other.forge_maven_url;
+        }
+        if other.neoforge_maven_url.is_some() {
+            self.neoforge_maven_url = other.neoforge_maven_url;
+        }
+        if other.fabric_maven_url.is_some() {
+            self.fabric_maven_url = other.fabric_maven_url;
+        }
+        if other.quilt_maven_url.is_some() {
+            self.quilt_maven_url = other.quilt_maven_url;
+        }
+        if other.mojang_meta_url.is_some() {
+            self.mojang_meta_url = other.mojang_meta_url;
+        }
 
         if other.log_level != default_fns::log_level() {
             self.log_level = other.log_level;
@@ -187,6 +374,15 @@ impl AppConfig {
         if other.dry_run {
             self.dry_run = other.dry_run;
         }
+        if other.no_rollback {
+            self.no_rollback = other.no_rollback;
+        }
+        if other.wait_lock {
+            self.wait_lock = other.wait_lock;
+        }
+        if other.offline {
+            self.offline = other.offline;
+        }
 
         if !matches!(other.log_format, LogFormat::Text) {
             self.log_format = other.log_format;
@@ -197,6 +393,9 @@ impl AppConfig {
         if !matches!(other.color, TerminalCapsDetectIntent::Auto) {
             self.color = other.color;
         }
+        if !matches!(other.output, OutputFormat::Text) {
+            self.output = other.output;
+        }
 
         self
     }
@@ -212,6 +411,83 @@ impl AppConfig {
             );
         }
 
+        self.endpoints = self.parse_endpoints()?;
+
         Ok(())
     }
+
+    /// Endpoints from the URL settings; unset services stay upstream.
+    fn parse_endpoints(&self) -> Result<Endpoints, ConfigError> {
+        let settings = [
+            (
+                Service::ModrinthApi,
+                "--modrinth-api-url",
+                &self.modrinth_api_url,
+            ),
+            (
+                Service::CurseForgeApi,
+                "--curseforge-api-url",
+                &self.curseforge_api_url,
+            ),
+            (
+                Service::ModrinthCdn,
+                "--modrinth-cdn-url",
+                &self.modrinth_cdn_url,
+            ),
+            (
+                Service::ForgeMaven,
+                "--forge-maven-url",
+                &self.forge_maven_url,
+            ),
+            (
+                Service::NeoForgeMaven,
+                "--neoforge-maven-url",
+                &self.neoforge_maven_url,
+            ),
+            (
+                Service::FabricMaven,
+                "--fabric-maven-url",
+                &self.fabric_maven_url,
+            ),
+            (
+                Service::QuiltMaven,
+                "--quilt-maven-url",
+                &self.quilt_maven_url,
+            ),
+            (
+                Service::MojangMeta,
+                "--mojang-meta-url",
+                &self.mojang_meta_url,
+            ),
+        ];
+
+        let mut endpoints = Endpoints::default();
+        for (service, flag, value) in settings {
+            let Some(value) = value else {
+                continue;
+            };
+            let mut bases = Vec::new();
+            for base in value
+                .split(',')
+                .map(str::trim)
+                .filter(|base| !base.is_empty())
+            {
+                match reqwest::Url::parse(base) {
+                    Ok(url) if matches!(url.scheme(), "http" | "https") => bases.push(base),
+                    Ok(_) => {
+                        return Err(ConfigError::ValidationFailed {
+                            reason: format!("{flag} needs http or https URLs, got '{base}'"),
+                        });
+                    }
+                    Err(e) => {
+                        return Err(ConfigError::ValidationFailed {
+                            reason: format!("{flag} has an invalid URL '{base}': {e}"),
+                        });
+                    }
+                }
+            }
+            endpoints = endpoints.with_bases(service, bases);
+        }
+        Ok(endpoints)
+    }
 }
@@ -1,11 +1,14 @@
 use crate::empack::builds::BuildError;
 use crate::empack::config::ConfigError as ProjectConfigError;
 use crate::empack::import::ImportError;
+use crate::empack::lockfile::LockfileError;
 use crate::empack::packwiz::PackwizError;
 use crate::empack::parsing::ParseError as DomainParseError;
 use crate::empack::search::SearchError;
+use crate::empack::signing::SigningError;
 use crate::empack::state::StateError;
 use crate::networking::NetworkingError;
+use crate::networking::download::DownloadError;
 use crate::primitives::ConfigError;
 use anyhow::Error;
 use std::process::ExitCode as ProcessExitCode;
@@ -29,6 +32,18 @@ impl EmpackExitCode {
     pub fn as_process_exit_code(self) -> ProcessExitCode {
         ProcessExitCode::from(self as u8)
     }
+
+    /// Stable lowercase name used in JSON output
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::General => "general",
+            Self::Usage => "usage",
+            Self::Network => "network",
+            Self::NotFound => "not-found",
+            Self::Interrupted => "interrupted",
+        }
+    }
 }
 
 pub fn classify_error(error: &Error) -> EmpackExitCode {
@@ -53,12 +68,18 @@ pub fn classify_error(error: &Error) -> EmpackExitCode {
         return EmpackExitCode::Network;
     }
 
+    if let Some(download_error) = find_chain_error::<DownloadError>(error) {
+        return classify_download_error(download_error);
+    }
+
     if let Some(import_error) = find_chain_error::<ImportError>(error) {
         return match import_error {
             ImportError::DownloadFailed(_) => EmpackExitCode::Network,
             ImportError::ArchiveRead(_)
             | ImportError::CurseForgeManifestMissing
             | ImportError::ModrinthManifestMissing
+            | ImportError::PackwizFileMissing(_)
+            | ImportError::PrismFileMissing(_)
             | ImportError::ParseFailed(_)
             | ImportError::MissingField { .. }
             | ImportError::UnknownLoader(_)
@@ -71,10 +92,32 @@ pub fn classify_error(error: &Error) -> EmpackExitCode {
         return classify_build_error(build_error);
     }
 
+    if let Some(lockfile_error) = find_chain_error::<LockfileError>(error) {
+        return match lockfile_error {
+            LockfileError::Missing
+            | LockfileError::UnsupportedVersion { .. }
+            | LockfileError::OutOfDate { .. } => EmpackExitCode::Usage,
+            LockfileError::Drift { .. } => EmpackExitCode::General,
+        };
+    }
+
     if let Some(state_error) = find_chain_error::<StateError>(error) {
         return classify_state_error(state_error);
     }
 
+    if let Some(signing_error) = find_chain_error::<SigningError>(error) {
+        return match signing_error {
+            SigningError::SecretKey { .. }
+            | SigningError::EncryptedKey
+            | SigningError::PublicKey { .. } => EmpackExitCode::Usage,
+            SigningError::Sign { .. }
+            | SigningError::Signature { .. }
+            | SigningError::SignatureMismatch { .. }
+            | SigningError::ChecksumMissing { .. }
+            | SigningError::ChecksumMismatch { .. } => EmpackExitCode::General,
+        };
+    }
+
     if find_chain_error::<PackwizError>(error).is_some() {
         return EmpackExitCode::General;
     }
@@ -104,6 +147,16 @@ fn classify_build_error(error: &BuildError) -> EmpackExitCode {
         BuildError::IoError { .. }
         | BuildError::CommandFailed { .. }
         | BuildError::MissingTool { .. } => EmpackExitCode::General,
+        BuildError::Network { .. } => EmpackExitCode::Network,
+        BuildError::Store { .. } => EmpackExitCode::General,
+        BuildError::Download { source } => classify_download_error(source),
+    }
+}
+
+fn classify_download_error(error: &DownloadError) -> EmpackExitCode {
+    match error {
+        DownloadError::Status { .. } | DownloadError::Exhausted { .. } => EmpackExitCode::Network,
+        DownloadError::Io { .. } => EmpackExitCode::General,
     }
 }
 
@@ -115,7 +168,9 @@ fn classify_state_error(error: &StateError) -> EmpackExitCode {
         | StateError::ConfigError { .. }
         | StateError::ConfigManagementError { .. } => EmpackExitCode::Usage,
         StateError::BuildError { source } => classify_build_error(source),
-        StateError::IoError { .. } | StateError::CommandFailed { .. } => EmpackExitCode::General,
+        StateError::IoError { .. }
+        | StateError::CommandFailed { .. }
+        | StateError::Locked { .. } => EmpackExitCode::General,
     }
 }
 
@@ -161,6 +216,7 @@ fn classify_message(message: &str) -> EmpackExitCode {
         || normalized.contains("tracked local dependency failed validation")
         || normalized.contains("tracked local dependencies failed validation")
         || normalized.contains("no pending restricted build to continue")
+        || normalized.contains("no dependency named")
     {
         return EmpackExitCode::Usage;
     }
@@ -197,6 +253,14 @@ mod tests {
         assert_eq!(classify_error(&error), EmpackExitCode::Usage);
     }
 
+    #[test]
+    fn classify_error_maps_stale_lockfile_to_usage() {
+        let error = anyhow::Error::new(LockfileError::OutOfDate {
+            details: "sodium is not in empack.lock".to_string(),
+        });
+        assert_eq!(classify_error(&error), EmpackExitCode::Usage);
+    }
+
     #[test]
     fn classify_error_maps_packwiz_failure_to_general() {
         let error = anyhow::Error::new(PackwizError::CommandFailed {
@@ -206,6 +270,39 @@ mod tests {
         assert_eq!(classify_error(&error), EmpackExitCode::General);
     }
 
+    #[test]
+    fn classify_error_maps_signature_mismatch_to_general() {
+        let error = anyhow::Error::new(SigningError::ChecksumMismatch {
+            file: "Pack-v1.0.0-server.zip".to_string(),
+            expected: "aa".to_string(),
+            actual: "bb".to_string(),
+        });
+        assert_eq!(classify_error(&error), EmpackExitCode::General);
+
+        let error = anyhow::Error::new(SigningError::PublicKey {
+            reason: "base64 conversion failed".to_string(),
+        });
+        assert_eq!(classify_error(&error), EmpackExitCode::Usage);
+    }
+
+    #[test]
+    fn classify_error_maps_offline_cache_miss_to_network() {
+        let error = anyhow::Error::new(BuildError::from(NetworkingError::Offline {
+            resource: "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json".to_string(),
+        }));
+        assert_eq!(classify_error(&error), EmpackExitCode::Network);
+    }
+
+    #[test]
+    fn classify_error_maps_exhausted_download_to_network() {
+        let error = anyhow::Error::new(BuildError::from(DownloadError::Exhausted {
+            url: "https://maven.neoforged.net/installer.jar".to_string(),
+            attempts: 3,
+            reason: "connection reset".to_string(),
+        }));
+        assert_eq!(classify_error(&error), EmpackExitCode::Network);
+    }
+
     #[test]
     fn classify_error_maps_config_parse_to_usage() {
         let error = anyhow::Error::new(ConfigError::ParseError {
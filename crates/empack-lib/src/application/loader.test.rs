@@ -1,5 +1,6 @@
 use super::*;
 use crate::application::config::defaults;
+use crate::networking::endpoints::{Endpoints, Service};
 use crate::primitives::TerminalCapsDetectIntent;
 use std::path::{Path, PathBuf};
 
@@ -48,12 +49,27 @@ fn test_config_merging() {
         modrinth_api_client_id: Some("modrinth-id".to_string()),
         modrinth_api_client_key: Some("modrinth-key".to_string()),
         curseforge_api_client_key: Some("curseforge-key".to_string()),
+        signing_key: Some(PathBuf::from("/tmp/empack.key")),
+        signing_key_password: Some("hunter2".to_string()),
         log_level: 4,
         net_timeout: 45,
         color: TerminalCapsDetectIntent::Always,
+        output: crate::primitives::OutputFormat::Json,
         cpu_jobs: 16,
         yes: true,
         dry_run: true,
+        no_rollback: true,
+        wait_lock: true,
+        offline: true,
+        modrinth_api_url: Some("https://mirror.corp/modrinth".to_string()),
+        curseforge_api_url: None,
+        modrinth_cdn_url: None,
+        forge_maven_url: None,
+        neoforge_maven_url: None,
+        fabric_maven_url: None,
+        quilt_maven_url: None,
+        mojang_meta_url: None,
+        endpoints: Endpoints::default(),
         log_format: crate::primitives::LogFormat::Yaml,
         log_output: crate::primitives::LogOutput::Stdout,
     };
@@ -75,12 +91,22 @@ fn test_config_merging() {
         merged.curseforge_api_client_key,
         Some("curseforge-key".to_string())
     );
+    assert_eq!(merged.signing_key, Some(PathBuf::from("/tmp/empack.key")));
+    assert_eq!(merged.signing_key_password, Some("hunter2".to_string()));
     assert_eq!(merged.log_level, 4);
     assert_eq!(merged.net_timeout, 45);
     assert_eq!(merged.color, TerminalCapsDetectIntent::Always);
+    assert_eq!(merged.output, crate::primitives::OutputFormat::Json);
     assert_eq!(merged.cpu_jobs, 16);
     assert!(merged.yes);
     assert!(merged.dry_run);
+    assert!(merged.no_rollback);
+    assert!(merged.wait_lock);
+    assert!(merged.offline);
+    assert_eq!(
+        merged.modrinth_api_url,
+        Some("https://mirror.corp/modrinth".to_string())
+    );
     assert_eq!(merged.log_format, crate::primitives::LogFormat::Yaml);
     assert_eq!(merged.log_output, crate::primitives::LogOutput::Stdout);
 }
@@ -194,6 +220,45 @@ fn test_load_from_reads_env_local_and_applies_cli_overrides() {
     assert!(config.dry_run);
 }
 
+#[test]
+fn test_load_from_parses_endpoint_urls() {
+    let _guard = crate::test_support::env_lock().lock().unwrap();
+    crate::display::test_utils::clean_test_env();
+    let _cli_env = crate::test_support::isolate_cli_env();
+
+    let temp_dir = tempfile::TempDir::new().expect("temp dir");
+    let _cwd = CurrentDirGuard::set(temp_dir.path());
+
+    let config = AppConfig::load_from([
+        "empack",
+        "--modrinth-api-url",
+        "https://mirror.corp/modrinth/, https://api.modrinth.com",
+        "--forge-maven-url",
+        "https://maven.corp/forge",
+    ])
+    .expect("load config with endpoint URLs");
+
+    assert_eq!(
+        config.endpoints.bases(Service::ModrinthApi),
+        ["https://mirror.corp/modrinth", "https://api.modrinth.com"]
+    );
+    assert_eq!(
+        config.endpoints.bases(Service::ForgeMaven),
+        ["https://maven.corp/forge"]
+    );
+    assert_eq!(
+        config.endpoints.base(Service::CurseForgeApi),
+        Service::CurseForgeApi.upstream()
+    );
+
+    let result = AppConfig::load_from(["empack", "--mojang-meta-url", "ftp://mirror.corp"]);
+    assert!(matches!(
+        result,
+        Err(crate::primitives::ConfigError::ValidationFailed { reason })
+            if reason.contains("--mojang-meta-url")
+    ));
+}
+
 #[test]
 fn test_load_from_reports_invalid_env_file() {
     let _guard = crate::test_support::env_lock().lock().unwrap();
@@ -11,6 +11,7 @@ use crate::empack::packwiz::{LivePackwizOps, PackwizOps};
 use crate::empack::search::{ProjectResolver, ProjectResolverTrait};
 use crate::empack::state::PackStateManager;
 use crate::networking::cache::HttpCache;
+use crate::networking::endpoints::Endpoints;
 use crate::networking::rate_budget::HostBudgetRegistry;
 use crate::networking::rate_limit::RateLimiterManager;
 use crate::terminal::TerminalCapabilities;
@@ -31,6 +32,29 @@ pub struct FileMetadata {
     pub created_unix_ms: Option<u64>,
 }
 
+/// Exclusive advisory file lock, released when dropped
+pub struct FileLock {
+    _handle: Box<dyn Send>,
+}
+
+impl FileLock {
+    /// Wrap whatever keeps the lock alive; dropping it releases the lock.
+    pub fn new(handle: impl Send + 'static) -> Self {
+        Self {
+            _handle: Box::new(handle),
+        }
+    }
+}
+
+/// Outcome of a non-blocking lock attempt
+pub enum FileLockAttempt {
+    Acquired(FileLock),
+    /// Someone else holds the lock; `owner` is what the holder recorded
+    Held {
+        owner: String,
+    },
+}
+
 pub trait FileSystemProvider {
     fn current_dir(&self) -> Result<PathBuf>;
 
@@ -61,6 +85,52 @@ pub trait FileSystemProvider {
     fn remove_file(&self, path: &Path) -> Result<()>;
 
     fn remove_dir_all(&self, path: &Path) -> Result<()>;
+
+    /// Append `content` to `path`, creating the file if needed.
+    ///
+    /// Downloads call this once per received chunk, so it must not rewrite
+    /// what the file already holds.
+    fn append_bytes(&self, path: &Path, content: &[u8]) -> Result<()>;
+
+    /// Move `from` onto `to`, replacing any file already there.
+    fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        let bytes = self.read_bytes(from)?;
+        self.write_bytes(to, &bytes)?;
+        self.remove_file(from)
+    }
+
+    /// Try to take an exclusive advisory lock on `path` without blocking,
+    /// creating the file if needed and recording `owner` in it once acquired.
+    fn try_lock_file(&self, path: &Path, owner: &str) -> Result<FileLockAttempt>;
+}
+
+/// Paths of every file under `dir`, relative to `root` with `/` separators,
+/// sorted. Unreadable directories are skipped.
+pub fn list_relative_files(
+    filesystem: &dyn FileSystemProvider,
+    root: &Path,
+    dir: &Path,
+) -> Vec<String> {
+    let mut found = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = filesystem.get_file_list(&dir) else {
+            continue;
+        };
+        for entry in entries {
+            if filesystem.is_directory(&entry) {
+                pending.push(entry);
+            } else if let Ok(relative) = entry.strip_prefix(root) {
+                let parts: Vec<_> = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                    .collect();
+                found.push(parts.join("/"));
+            }
+        }
+    }
+    found.sort();
+    found
 }
 
 /// Provider trait for network operations
@@ -75,6 +145,21 @@ pub trait NetworkProvider {
 
     /// Per-host adaptive rate budget registry.
     fn rate_budgets(&self) -> &HostBudgetRegistry;
+
+    /// Whether `--offline` limits this provider to local caches.
+    fn is_offline(&self) -> bool {
+        false
+    }
+
+    /// Base URLs of the platform APIs, CDNs, and Maven repositories.
+    fn endpoints(&self) -> &Endpoints {
+        Endpoints::upstream()
+    }
+
+    /// How long a download waits before its first retry.
+    fn retry_base_delay(&self) -> std::time::Duration {
+        crate::networking::download::RETRY_BASE_DELAY
+    }
 }
 
 /// Process execution output
@@ -218,12 +303,17 @@ pub trait ConfigProvider {
 pub trait ArchiveProvider {
     fn extract_zip(&self, archive_path: &Path, dest_dir: &Path) -> Result<()>;
 
+    /// Create a reproducible archive whose entries carry `source_date_epoch`
     fn create_archive(
         &self,
         source_dir: &Path,
         dest_path: &Path,
         format: crate::empack::archive::ArchiveFormat,
+        source_date_epoch: u64,
     ) -> Result<()>;
+
+    /// Rewrite an existing zip with sorted entries and fixed metadata
+    fn normalize_zip(&self, archive_path: &Path, source_date_epoch: u64) -> Result<()>;
 }
 
 pub struct LiveArchiveProvider;
@@ -239,10 +329,16 @@ impl ArchiveProvider for LiveArchiveProvider {
         source_dir: &Path,
         dest_path: &Path,
         format: crate::empack::archive::ArchiveFormat,
+        source_date_epoch: u64,
     ) -> Result<()> {
-        crate::empack::archive::create_archive(source_dir, dest_path, format)
+        crate::empack::archive::create_archive(source_dir, dest_path, format, source_date_epoch)
             .with_context(|| format!("Failed to create archive: {}", dest_path.display()))
     }
+
+    fn normalize_zip(&self, archive_path: &Path, source_date_epoch: u64) -> Result<()> {
+        crate::empack::archive::normalize_zip(archive_path, source_date_epoch)
+            .with_context(|| format!("Failed to normalize archive: {}", archive_path.display()))
+    }
 }
 
 pub trait InteractiveProvider {
@@ -256,7 +352,7 @@ pub trait InteractiveProvider {
     fn fuzzy_select(&self, prompt: &str, options: &[String]) -> Result<Option<usize>>;
 }
 
-pub trait Session {
+pub trait Session: Sync {
     fn display(&self) -> &dyn DisplayProvider;
 
     fn filesystem(&self) -> &dyn FileSystemProvider;
@@ -417,6 +513,55 @@ impl FileSystemProvider for LiveFileSystemProvider {
         std::fs::remove_dir_all(path)
             .with_context(|| format!("Failed to remove directory: {}", path.display()))
     }
+
+    fn append_bytes(&self, path: &Path, content: &[u8]) -> Result<()> {
+        use std::io::Write;
+
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(content))
+            .with_context(|| format!("Failed to append to file: {}", path.display()))
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        std::fs::rename(from, to)
+            .with_context(|| format!("Failed to rename {} to {}", from.display(), to.display()))
+    }
+
+    fn try_lock_file(&self, path: &Path, owner: &str) -> Result<FileLockAttempt> {
+        use std::io::{Read, Write};
+
+        if let Some(parent) = path.parent() {
+            self.create_dir_all(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .with_context(|| format!("Failed to open lock file: {}", path.display()))?;
+
+        match file.try_lock() {
+            Ok(()) => {
+                file.set_len(0)
+                    .and_then(|()| file.write_all(owner.as_bytes()))
+                    .with_context(|| format!("Failed to write lock file: {}", path.display()))?;
+                Ok(FileLockAttempt::Acquired(FileLock::new(file)))
+            }
+            Err(std::fs::TryLockError::WouldBlock) => {
+                // Windows locks are mandatory, so the holder's record may be unreadable
+                let mut recorded = String::new();
+                let _ = file.read_to_string(&mut recorded);
+                Ok(FileLockAttempt::Held { owner: recorded })
+            }
+            Err(std::fs::TryLockError::Error(e)) => {
+                Err(e).with_context(|| format!("Failed to lock file: {}", path.display()))
+            }
+        }
+    }
 }
 
 fn system_time_to_unix_ms(
@@ -428,15 +573,26 @@ fn system_time_to_unix_ms(
         .map(|duration| duration.as_millis() as u64)
 }
 
+/// DNS resolver that refuses every lookup, so an offline client fails any
+/// request that slipped past the caches instead of reaching the network.
+struct OfflineResolver;
+
+impl reqwest::dns::Resolve for OfflineResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            Err(crate::networking::NetworkingError::Offline { resource: host }.into())
+        })
+    }
+}
+
 pub struct LiveNetworkProvider {
     client: Client,
     cache: Arc<HttpCache>,
+    offline: bool,
     rate_limiter: Arc<RateLimiterManager>,
     rate_budgets: Arc<HostBudgetRegistry>,
-    #[cfg(feature = "test-utils")]
-    modrinth_base_url: Option<String>,
-    #[cfg(feature = "test-utils")]
-    curseforge_base_url: Option<String>,
+    endpoints: Endpoints,
 }
 
 impl LiveNetworkProvider {
@@ -445,27 +601,41 @@ impl LiveNetworkProvider {
     }
 
     pub fn with_timeout(timeout_secs: u64) -> Self {
+        Self::with_options(timeout_secs, false)
+    }
+
+    /// Create a provider; `offline` serves requests only from the caches.
+    pub fn with_options(timeout_secs: u64, offline: bool) -> Self {
         let cache_dir = crate::platform::cache::http_cache_dir()
             .unwrap_or_else(|_| std::env::temp_dir().join("empack").join("http_cache"));
-        let client = Client::builder()
-            .timeout(std::time::Duration::from_secs(timeout_secs))
-            .build()
-            .expect("Failed to build HTTP client");
+        let mut builder = Client::builder().timeout(std::time::Duration::from_secs(timeout_secs));
+        if offline {
+            builder = builder.no_proxy().dns_resolver(Arc::new(OfflineResolver));
+        }
+        let client = builder.build().expect("Failed to build HTTP client");
         let rate_budgets = Arc::new(HostBudgetRegistry::new());
+        let mut cache = HttpCache::new(cache_dir);
+        if offline {
+            cache = cache.keeping_stale_entries();
+        }
         Self {
             client: client.clone(),
-            cache: Arc::new(HttpCache::new(cache_dir)),
+            cache: Arc::new(cache),
+            offline,
             rate_limiter: Arc::new(RateLimiterManager::new_with_budgets(client, &rate_budgets)),
             rate_budgets,
-            #[cfg(feature = "test-utils")]
-            modrinth_base_url: None,
-            #[cfg(feature = "test-utils")]
-            curseforge_base_url: None,
+            endpoints: Endpoints::default(),
         }
     }
 
-    pub async fn new_async(timeout_secs: u64) -> Self {
-        let provider = Self::with_timeout(timeout_secs);
+    /// Send requests to `endpoints` instead of the upstream services.
+    pub fn with_endpoints(mut self, endpoints: Endpoints) -> Self {
+        self.endpoints = endpoints;
+        self
+    }
+
+    pub async fn new_async(timeout_secs: u64, offline: bool) -> Self {
+        let provider = Self::with_options(timeout_secs, offline);
         if let Err(error) = provider.cache.load_from_disk().await {
             tracing::warn!(
                 error = %error,
@@ -490,10 +660,18 @@ impl LiveNetworkProvider {
         Self {
             client: client.clone(),
             cache: Arc::new(HttpCache::new(cache_dir)),
+            offline: false,
             rate_limiter: Arc::new(RateLimiterManager::new_with_budgets(client, &rate_budgets)),
             rate_budgets,
-            modrinth_base_url,
-            curseforge_base_url,
+            endpoints: Endpoints::default()
+                .with_bases(
+                    crate::networking::endpoints::Service::ModrinthApi,
+                    modrinth_base_url,
+                )
+                .with_bases(
+                    crate::networking::endpoints::Service::CurseForgeApi,
+                    curseforge_base_url,
+                ),
         }
     }
 }
@@ -513,32 +691,29 @@ impl NetworkProvider for LiveNetworkProvider {
         &self.rate_budgets
     }
 
+    fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    fn endpoints(&self) -> &Endpoints {
+        &self.endpoints
+    }
+
     fn project_resolver(
         &self,
         client: Client,
         curseforge_api_key: Option<String>,
     ) -> Box<dyn ProjectResolverTrait + Send + Sync> {
-        #[cfg(feature = "test-utils")]
-        {
-            Box::new(ProjectResolver::new_with_base_urls_and_networking(
+        Box::new(
+            ProjectResolver::with_networking(
                 client,
                 curseforge_api_key,
-                self.modrinth_base_url.clone(),
-                self.curseforge_base_url.clone(),
                 self.cache.clone(),
                 self.rate_limiter.clone(),
-            ))
-        }
-
-        #[cfg(not(feature = "test-utils"))]
-        {
-            Box::new(ProjectResolver::with_networking(
-                client,
-                curseforge_api_key,
-                self.cache.clone(),
-                self.rate_limiter.clone(),
-            ))
-        }
+            )
+            .with_endpoints(self.endpoints.clone())
+            .with_offline(self.offline),
+        )
     }
 }
 
@@ -577,6 +752,24 @@ fn cleanup_process_interrupt_marker(working_dir: &Path) {
     }
 }
 
+/// Restore the rollback snapshot of the project enclosing `working_dir`.
+pub(crate) fn restore_process_interrupt_snapshot(working_dir: &Path) {
+    let mut current = Some(working_dir);
+
+    while let Some(dir) = current {
+        if dir.join(crate::empack::transaction::ROLLBACK_FILE).exists() {
+            let _ = crate::empack::transaction::restore_pending(&LiveFileSystemProvider, dir);
+            return;
+        }
+
+        if is_empack_project_boundary(dir) {
+            return;
+        }
+
+        current = dir.parent();
+    }
+}
+
 pub struct LiveProcessProvider {
     custom_path: Option<String>,
 }
@@ -872,6 +1065,7 @@ impl ProcessProvider for LiveProcessProvider {
             crate::terminal::cursor::force_show_cursor();
             crate::logger::global_shutdown();
             cleanup_process_interrupt_marker(working_dir);
+            restore_process_interrupt_snapshot(working_dir);
 
             std::process::exit(130)
         }
@@ -1215,7 +1409,8 @@ impl
         };
 
         let multi_progress = Arc::new(MultiProgress::new());
-        let display_provider = LiveDisplayProvider::new_with_arc(multi_progress.clone());
+        let display_provider = LiveDisplayProvider::new_with_arc(multi_progress.clone())
+            .with_output(app_config.output);
 
         Self {
             multi_progress,
@@ -1236,7 +1431,9 @@ impl
 
     pub fn new(app_config: AppConfig) -> Self {
         let packwiz_bin_path = Self::resolve_packwiz_bin_path();
-        let network_provider = LiveNetworkProvider::with_timeout(app_config.net_timeout);
+        let network_provider =
+            LiveNetworkProvider::with_options(app_config.net_timeout, app_config.offline)
+                .with_endpoints(app_config.endpoints.clone());
         Self::build_live_session(app_config, packwiz_bin_path, network_provider)
     }
 
@@ -1247,7 +1444,10 @@ impl
                 tracing::warn!(error = %e, "packwiz-tx binary resolution task failed; falling back to PATH lookup");
                 crate::empack::packwiz::PACKWIZ_BIN.to_string()
             });
-        let network_provider = LiveNetworkProvider::new_async(app_config.net_timeout).await;
+        let network_provider =
+            LiveNetworkProvider::new_async(app_config.net_timeout, app_config.offline)
+                .await
+                .with_endpoints(app_config.endpoints.clone());
         Self::build_live_session(app_config, packwiz_bin_path, network_provider)
     }
 }
@@ -1345,12 +1545,12 @@ where
 
 impl<F, N, P, C, I, A> Session for CommandSession<F, N, P, C, I, A>
 where
-    F: FileSystemProvider,
-    N: NetworkProvider,
-    P: ProcessProvider,
-    C: ConfigProvider,
-    I: InteractiveProvider,
-    A: ArchiveProvider,
+    F: FileSystemProvider + Sync,
+    N: NetworkProvider + Sync,
+    P: ProcessProvider + Sync,
+    C: ConfigProvider + Sync,
+    I: InteractiveProvider + Sync,
+    A: ArchiveProvider + Sync,
 {
     fn display(&self) -> &dyn DisplayProvider {
         &self.display_provider
@@ -1898,6 +2098,7 @@ mod tests {
                 &source_dir,
                 &archive_path,
                 crate::empack::archive::ArchiveFormat::Zip,
+                crate::empack::archive::MIN_SOURCE_DATE_EPOCH,
             )
             .expect("create archive");
         provider
@@ -1929,11 +2130,41 @@ mod tests {
                 &temp.path().join("missing-source"),
                 &temp.path().join("output.zip"),
                 crate::empack::archive::ArchiveFormat::Zip,
+                crate::empack::archive::MIN_SOURCE_DATE_EPOCH,
             )
             .expect_err("missing source should fail");
         assert!(create_err.to_string().contains("Failed to create archive:"));
     }
 
+    #[test]
+    fn live_filesystem_provider_locks_exclude_other_holders() {
+        let provider = LiveFileSystemProvider;
+        let temp = TempDir::new().expect("temp dir");
+        let lock_path = temp.path().join(".empack").join("lock");
+
+        let FileLockAttempt::Acquired(lock) = provider
+            .try_lock_file(&lock_path, "4242")
+            .expect("first lock")
+        else {
+            panic!("first attempt should acquire the lock");
+        };
+        match provider
+            .try_lock_file(&lock_path, "9999")
+            .expect("second lock")
+        {
+            FileLockAttempt::Held { owner } => assert_eq!(owner, "4242"),
+            FileLockAttempt::Acquired(_) => panic!("second attempt should see the holder"),
+        }
+
+        drop(lock);
+        assert!(matches!(
+            provider
+                .try_lock_file(&lock_path, "9999")
+                .expect("third lock"),
+            FileLockAttempt::Acquired(_)
+        ));
+    }
+
     #[test]
     fn live_filesystem_provider_round_trips_files_and_directory_ops() {
         let provider = LiveFileSystemProvider;
@@ -2038,6 +2269,34 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn offline_live_network_provider_refuses_uncached_requests() {
+        let provider = LiveNetworkProvider::with_options(1, true);
+        assert!(provider.is_offline());
+        assert!(!LiveNetworkProvider::with_timeout(1).is_offline());
+
+        let error = provider
+            .http_client()
+            .expect("offline client")
+            .get("https://api.modrinth.com/v2/project/sodium")
+            .send()
+            .await
+            .expect_err("offline client should not reach the network");
+        let chain = anyhow::Error::new(error)
+            .chain()
+            .map(|cause| cause.to_string())
+            .collect::<Vec<_>>()
+            .join(" | ");
+        assert!(
+            chain.contains("api.modrinth.com/v2/project/sodium"),
+            "{chain}"
+        );
+        assert!(
+            chain.contains("Offline mode: api.modrinth.com is not cached"),
+            "{chain}"
+        );
+    }
+
     #[cfg(feature = "test-utils")]
     #[test]
     fn live_network_provider_exposes_client_budget_and_resolver() {
@@ -2428,6 +2687,7 @@ mod tests {
                 &source_dir,
                 &archive_path,
                 crate::empack::archive::ArchiveFormat::Zip,
+                crate::empack::archive::MIN_SOURCE_DATE_EPOCH,
             )
             .expect("create archive");
         assert!(archive_path.exists());
@@ -2442,7 +2702,7 @@ mod tests {
         let temp = TempDir::new().expect("temp dir");
         let _cache_dir = unsafe { EnvVarGuard::set("EMPACK_CACHE_DIR", temp.path().as_os_str()) };
 
-        let provider = LiveNetworkProvider::new_async(1).await;
+        let provider = LiveNetworkProvider::new_async(1, false).await;
         assert_eq!(
             provider.cache.cache_dir(),
             &crate::platform::cache::http_cache_dir().expect("http cache dir")
@@ -2461,7 +2721,7 @@ mod tests {
             )
             .await;
 
-        let reloaded = LiveNetworkProvider::new_async(1).await;
+        let reloaded = LiveNetworkProvider::new_async(1, false).await;
         let cached = reloaded
             .cache
             .get("https://example.invalid/cache")
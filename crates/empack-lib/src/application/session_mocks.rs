@@ -8,8 +8,9 @@ use crate::application::config::AppConfig;
 use crate::application::session::{InteractiveProvider, ProcessOutput, Session, *};
 use crate::display::{DisplayProvider, LiveDisplayProvider};
 use crate::empack::config::ConfigManager;
+use crate::empack::content::{SideEnv, SideRequirement};
 use crate::empack::packwiz::{MockPackwizOps, PackwizOps};
-use crate::empack::search::{ProjectInfo, ProjectResolverTrait, SearchError};
+use crate::empack::search::{CandidateFilters, ProjectInfo, ProjectResolverTrait, SearchError};
 use indicatif::MultiProgress;
 use reqwest::Client;
 use std::collections::HashMap;
@@ -64,6 +65,8 @@ pub struct MockFileSystemProvider {
     pub deferred_files: DeferredFileMap,
     /// Path-specific write failures injected by tests.
     pub write_failures: Arc<Mutex<HashMap<PathBuf, String>>>,
+    /// Held advisory locks: path -> recorded owner
+    pub file_locks: Arc<Mutex<HashMap<PathBuf, String>>>,
 }
 
 impl MockFileSystemProvider {
@@ -79,6 +82,7 @@ impl MockFileSystemProvider {
             metadata: Arc::new(Mutex::new(HashMap::new())),
             deferred_files: Arc::new(Mutex::new(HashMap::new())),
             write_failures: Arc::new(Mutex::new(HashMap::new())),
+            file_locks: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -211,6 +215,12 @@ impl MockFileSystemProvider {
             .push((filename, content));
     }
 
+    /// Simulate a lock held by another process; it is never released.
+    pub fn with_held_lock(self, path: PathBuf, owner: impl Into<String>) -> Self {
+        self.file_locks.lock().unwrap().insert(path, owner.into());
+        self
+    }
+
     pub fn with_write_failure(self, path: PathBuf, message: impl Into<String>) -> Self {
         self.add_write_failure(path, message);
         self
@@ -464,6 +474,20 @@ impl FileSystemProvider for MockFileSystemProvider {
         Ok(())
     }
 
+    fn append_bytes(&self, path: &std::path::Path, content: &[u8]) -> Result<()> {
+        self.track_parent_directories(path);
+        let text = self.files.lock().unwrap().remove(path);
+        let mut binary_files = self.binary_files.lock().unwrap();
+        let bytes = binary_files
+            .entry(path.to_path_buf())
+            .or_insert_with(|| text.map(String::into_bytes).unwrap_or_default());
+        bytes.extend_from_slice(content);
+        let len = bytes.len() as u64;
+        drop(binary_files);
+        self.set_default_file_metadata(path, false, len);
+        Ok(())
+    }
+
     fn exists(&self, path: &std::path::Path) -> bool {
         // Check both files and directories
         self.files.lock().unwrap().contains_key(path)
@@ -654,6 +678,32 @@ impl FileSystemProvider for MockFileSystemProvider {
 
         Ok(())
     }
+
+    fn try_lock_file(&self, path: &std::path::Path, owner: &str) -> Result<FileLockAttempt> {
+        let mut locks = self.file_locks.lock().unwrap();
+        if let Some(holder) = locks.get(path) {
+            return Ok(FileLockAttempt::Held {
+                owner: holder.clone(),
+            });
+        }
+        locks.insert(path.to_path_buf(), owner.to_string());
+        Ok(FileLockAttempt::Acquired(FileLock::new(MockLockRelease {
+            locks: self.file_locks.clone(),
+            path: path.to_path_buf(),
+        })))
+    }
+}
+
+/// Releases a mock advisory lock when dropped
+struct MockLockRelease {
+    locks: Arc<Mutex<HashMap<PathBuf, String>>>,
+    path: PathBuf,
+}
+
+impl Drop for MockLockRelease {
+    fn drop(&mut self) {
+        self.locks.lock().unwrap().remove(&self.path);
+    }
 }
 
 type ResolverCall = (Client, Option<String>);
@@ -666,6 +716,8 @@ pub struct MockNetworkProvider {
     fail_http_client: bool,
     http_timeout: std::time::Duration,
     rate_budgets: crate::networking::rate_budget::HostBudgetRegistry,
+    offline: bool,
+    endpoints: crate::networking::endpoints::Endpoints,
 }
 
 impl MockNetworkProvider {
@@ -677,9 +729,21 @@ impl MockNetworkProvider {
             fail_http_client: false,
             http_timeout: std::time::Duration::from_secs(5),
             rate_budgets: crate::networking::rate_budget::HostBudgetRegistry::empty(),
+            offline: false,
+            endpoints: crate::networking::endpoints::Endpoints::default(),
         }
     }
 
+    pub fn with_offline(mut self) -> Self {
+        self.offline = true;
+        self
+    }
+
+    pub fn with_endpoints(mut self, endpoints: crate::networking::endpoints::Endpoints) -> Self {
+        self.endpoints = endpoints;
+        self
+    }
+
     pub fn with_failing_http_client(mut self) -> Self {
         self.fail_http_client = true;
         self
@@ -712,6 +776,24 @@ impl MockNetworkProvider {
             .insert(query, Err(error_message));
         self
     }
+
+    pub fn with_search_candidates(self, query: String, candidates: Vec<ProjectInfo>) -> Self {
+        self.mock_resolver
+            .candidates
+            .lock()
+            .unwrap()
+            .insert(query, candidates);
+        self
+    }
+
+    pub fn with_side_env(self, project_id: &str, env: SideEnv) -> Self {
+        self.mock_resolver
+            .side_envs
+            .lock()
+            .unwrap()
+            .insert(project_id.to_string(), env);
+        self
+    }
 }
 
 impl Default for MockNetworkProvider {
@@ -736,6 +818,18 @@ impl NetworkProvider for MockNetworkProvider {
         &self.rate_budgets
     }
 
+    fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    fn endpoints(&self) -> &crate::networking::endpoints::Endpoints {
+        &self.endpoints
+    }
+
+    fn retry_base_delay(&self) -> std::time::Duration {
+        std::time::Duration::ZERO
+    }
+
     fn project_resolver(
         &self,
         client: Client,
@@ -753,12 +847,20 @@ impl NetworkProvider for MockNetworkProvider {
 #[derive(Clone)]
 pub struct MockProjectResolver {
     pub responses: Arc<Mutex<HashMap<String, std::result::Result<ProjectInfo, String>>>>,
+    pub candidates: Arc<Mutex<HashMap<String, Vec<ProjectInfo>>>>,
+    /// Every `search_candidates` call as (query, project type, filters)
+    pub candidate_calls: Arc<Mutex<Vec<(String, String, CandidateFilters)>>>,
+    /// Side requirements returned by `fetch_side_env`, keyed by project ID
+    pub side_envs: Arc<Mutex<HashMap<String, SideEnv>>>,
 }
 
 impl MockProjectResolver {
     pub fn new() -> Self {
         Self {
             responses: Arc::new(Mutex::new(HashMap::new())),
+            candidates: Arc::new(Mutex::new(HashMap::new())),
+            candidate_calls: Arc::new(Mutex::new(Vec::new())),
+            side_envs: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -818,6 +920,63 @@ impl ProjectResolverTrait for MockProjectResolver {
             }
         })
     }
+
+    fn search_candidates(
+        &self,
+        title: &str,
+        project_type: &str,
+        filters: &CandidateFilters,
+        min_confidence: u8,
+    ) -> Pin<Box<dyn Future<Output = std::result::Result<Vec<ProjectInfo>, SearchError>> + Send + '_>>
+    {
+        self.candidate_calls.lock().unwrap().push((
+            title.to_string(),
+            project_type.to_string(),
+            filters.clone(),
+        ));
+        let candidates = self
+            .candidates
+            .lock()
+            .unwrap()
+            .get(title)
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|candidate| candidate.confidence >= min_confidence)
+            .filter(|candidate| {
+                filters.platforms.is_empty() || filters.platforms.contains(&candidate.platform)
+            })
+            .collect::<Vec<_>>();
+        let query = title.to_string();
+
+        Box::pin(async move {
+            if candidates.is_empty() {
+                Err(SearchError::NoResults { query })
+            } else {
+                Ok(candidates)
+            }
+        })
+    }
+
+    fn fetch_side_env(
+        &self,
+        _platform: crate::primitives::ProjectPlatform,
+        project_id: &str,
+        _file_id: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = std::result::Result<SideEnv, SearchError>> + Send + '_>> {
+        let env = self
+            .side_envs
+            .lock()
+            .unwrap()
+            .get(project_id)
+            .cloned()
+            .unwrap_or(SideEnv {
+                client: SideRequirement::Unknown,
+                server: SideRequirement::Unknown,
+            });
+
+        Box::pin(async move { Ok(env) })
+    }
 }
 
 /// Mock archive provider for testing with spy pattern.
@@ -827,6 +986,7 @@ impl ProjectResolverTrait for MockProjectResolver {
 pub struct MockArchiveProvider {
     pub extract_calls: Arc<Mutex<Vec<(PathBuf, PathBuf)>>>,
     pub create_calls: Arc<Mutex<Vec<(PathBuf, PathBuf)>>>,
+    pub normalize_calls: Arc<Mutex<Vec<(PathBuf, u64)>>>,
     files: Option<Arc<Mutex<HashMap<PathBuf, String>>>>,
     directories: Option<Arc<Mutex<HashSet<PathBuf>>>>,
 }
@@ -836,6 +996,7 @@ impl MockArchiveProvider {
         Self {
             extract_calls: Arc::new(Mutex::new(Vec::new())),
             create_calls: Arc::new(Mutex::new(Vec::new())),
+            normalize_calls: Arc::new(Mutex::new(Vec::new())),
             files: None,
             directories: None,
         }
@@ -876,6 +1037,7 @@ impl crate::application::session::ArchiveProvider for MockArchiveProvider {
         source_dir: &std::path::Path,
         dest_path: &std::path::Path,
         _format: crate::empack::archive::ArchiveFormat,
+        _source_date_epoch: u64,
     ) -> Result<()> {
         self.create_calls
             .lock()
@@ -894,6 +1056,14 @@ impl crate::application::session::ArchiveProvider for MockArchiveProvider {
 
         Ok(())
     }
+
+    fn normalize_zip(&self, archive_path: &std::path::Path, source_date_epoch: u64) -> Result<()> {
+        self.normalize_calls
+            .lock()
+            .unwrap()
+            .push((archive_path.to_path_buf(), source_date_epoch));
+        Ok(())
+    }
 }
 
 /// Process call record for spy pattern
@@ -1166,6 +1336,25 @@ impl MockProcessProvider {
         let mods_dir = working_dir.join("mods");
         directories.lock().unwrap().insert(mods_dir.clone());
         let pw_toml_path = mods_dir.join(format!("{}.pw.toml", slug));
+
+        // Pinned adds rewrite the metafile with update metadata, like packwiz does
+        let pinned_version = args
+            .iter()
+            .position(|arg| *arg == "--version-id" || *arg == "--file-id")
+            .and_then(|i| args.get(i + 1));
+        if let Some(version) = pinned_version {
+            let update = if args.contains(&"curseforge") {
+                format!("[update.curseforge]\nproject-id = {project_id}\nfile-id = {version}\n")
+            } else {
+                format!("[update.modrinth]\nmod-id = \"{project_id}\"\nversion = \"{version}\"\n")
+            };
+            files.lock().unwrap().insert(
+                pw_toml_path,
+                format!("name = \"{slug}\"\nfilename = \"{slug}.jar\"\n\n{update}"),
+            );
+            return;
+        }
+
         files
             .lock()
             .unwrap()
@@ -2166,6 +2355,7 @@ mod tests {
                 &mock_root().join("source"),
                 &mock_root().join("dist").join("pack.mrpack"),
                 ArchiveFormat::Zip,
+                0,
             )
             .expect("create_archive without connected filesystem should still succeed");
 
@@ -2328,6 +2518,7 @@ mod tests {
                 &mock_root().join("source"),
                 &mock_root().join("dest").join("pack.zip"),
                 crate::empack::archive::ArchiveFormat::Zip,
+                0,
             )
             .unwrap();
     }
@@ -2,6 +2,7 @@ use crate::empack::config::{DependencySource, ProjectPlan, ProjectSpec};
 use crate::empack::parsing::ModLoader;
 use crate::empack::search::{ProjectResolverTrait, SearchError};
 use crate::primitives::{ProjectPlatform, ProjectType};
+use serde::Serialize;
 use std::collections::HashSet;
 use thiserror::Error;
 
@@ -27,7 +28,8 @@ pub struct SyncDependencyPlan {
     pub source: DependencySource,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
 pub enum SyncExecutionAction {
     Add {
         key: String,
@@ -42,7 +44,7 @@ pub enum SyncExecutionAction {
     },
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct AddResolution {
     pub title: String,
     pub commands: Vec<Vec<String>>,
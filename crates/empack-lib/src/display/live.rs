@@ -5,23 +5,40 @@
 
 use super::Display;
 use super::providers::*;
-use indicatif::{MultiProgress, ProgressBar};
-use std::sync::Arc;
+use crate::primitives::OutputFormat;
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget};
+use std::sync::{Arc, Mutex};
 
 /// Live implementation of DisplayProvider that owns display state for command lifecycle
 pub struct LiveDisplayProvider {
     multi_progress: Arc<MultiProgress>,
+    records: Mutex<serde_json::Map<String, serde_json::Value>>,
 }
 
 impl LiveDisplayProvider {
     pub fn new() -> Self {
+        Self::new_with_arc(Arc::new(MultiProgress::new()))
+    }
+
+    pub fn new_with_arc(multi_progress: Arc<MultiProgress>) -> Self {
         Self {
-            multi_progress: Arc::new(MultiProgress::new()),
+            multi_progress,
+            records: Mutex::new(serde_json::Map::new()),
         }
     }
 
-    pub fn new_with_arc(multi_progress: Arc<MultiProgress>) -> Self {
-        Self { multi_progress }
+    /// Switch human-readable output for the given output mode
+    ///
+    /// In JSON mode progress bars are hidden and status text goes to stderr,
+    /// leaving stdout for the final result document.
+    pub fn with_output(self, output: OutputFormat) -> Self {
+        let structured = output == OutputFormat::Json;
+        if structured {
+            self.multi_progress
+                .set_draw_target(ProgressDrawTarget::hidden());
+        }
+        super::set_status_to_stderr(structured);
+        self
     }
 }
 
@@ -45,6 +62,19 @@ impl DisplayProvider for LiveDisplayProvider {
     fn table(&self) -> Box<dyn StructuredProvider> {
         Box::new(LiveStructuredProvider)
     }
+
+    fn record(&self, key: &str, value: serde_json::Value) {
+        if let Ok(mut records) = self.records.lock() {
+            records.insert(key.to_string(), value);
+        }
+    }
+
+    fn take_records(&self) -> serde_json::Map<String, serde_json::Value> {
+        self.records
+            .lock()
+            .map(|mut records| std::mem::take(&mut *records))
+            .unwrap_or_default()
+    }
 }
 
 /// Live implementation of StatusProvider
@@ -116,6 +146,14 @@ impl ProgressProvider for LiveProgressProvider {
         Box::new(SimpleProgressTracker::new(bar))
     }
 
+    fn bytes(&self, total: u64, message: &str) -> Box<dyn ProgressTracker> {
+        let progress_bar = indicatif::ProgressBar::new(total)
+            .with_style(super::progress::bytes_style())
+            .with_message(message.to_string());
+        let bar = self.parent.add(progress_bar);
+        Box::new(SimpleProgressTracker::new(bar))
+    }
+
     fn spinner(&self, message: &str) -> Box<dyn ProgressTracker> {
         let progress_bar = indicatif::ProgressBar::new_spinner();
         progress_bar.set_message(message.to_string());
@@ -142,6 +180,14 @@ impl MultiProgressProvider for LiveMultiProgressProvider {
         Box::new(SimpleProgressTracker::new(bar))
     }
 
+    fn add_bytes_bar(&self, total: u64, message: &str) -> Box<dyn ProgressTracker> {
+        let progress_bar = indicatif::ProgressBar::new(total)
+            .with_style(super::progress::bytes_style())
+            .with_message(message.to_string());
+        let bar = self.parent.add(progress_bar);
+        Box::new(SimpleProgressTracker::new(bar))
+    }
+
     fn add_spinner(&self, message: &str) -> Box<dyn ProgressTracker> {
         let progress_bar = indicatif::ProgressBar::new_spinner();
         progress_bar.set_message(message.to_string());
@@ -200,6 +246,10 @@ impl ProgressTracker for SimpleProgressTracker {
         self.bar.inc(n);
     }
 
+    fn set_length(&self, len: u64) {
+        self.bar.set_length(len);
+    }
+
     fn set_message(&self, message: &str) {
         self.bar.set_message(message.to_string());
     }
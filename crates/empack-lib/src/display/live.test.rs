@@ -69,3 +69,16 @@ fn test_live_display_provider_new_with_arc() {
     bar.finish("shared done");
     multi.clear();
 }
+
+#[test]
+fn test_live_display_provider_records_results() {
+    let provider = LiveDisplayProvider::new();
+
+    provider.record("builds", serde_json::json!([{"target": "mrpack"}]));
+    provider.record("builds", serde_json::json!([]));
+
+    let records = provider.take_records();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records["builds"], serde_json::json!([]));
+    assert!(provider.take_records().is_empty());
+}
@@ -26,6 +26,7 @@ pub use providers::{
 
 static GLOBAL_DISPLAY: OnceLock<Display> = OnceLock::new();
 static ERROR_RENDERED: AtomicBool = AtomicBool::new(false);
+static STATUS_TO_STDERR: AtomicBool = AtomicBool::new(false);
 
 pub(crate) fn mark_error_rendered() {
     ERROR_RENDERED.store(true, Ordering::SeqCst);
@@ -39,6 +40,19 @@ pub(crate) fn take_error_rendered() -> bool {
     ERROR_RENDERED.swap(false, Ordering::SeqCst)
 }
 
+/// Send status and table text to stderr, keeping stdout for machine output
+pub(crate) fn set_status_to_stderr(enabled: bool) {
+    STATUS_TO_STDERR.store(enabled, Ordering::SeqCst);
+}
+
+pub(crate) fn emit_line(line: &str) {
+    if STATUS_TO_STDERR.load(Ordering::SeqCst) {
+        eprintln!("{line}");
+    } else {
+        println!("{line}");
+    }
+}
+
 /// Main display manager that coordinates all user-facing communication
 pub struct Display {
     capabilities: TerminalCapabilities,
@@ -13,6 +13,25 @@ fn has_unicode() -> bool {
     Display::capabilities().unicode != TerminalUnicodeCaps::Ascii
 }
 
+/// Bar style that counts bytes and shows the transfer rate
+pub(crate) fn bytes_style() -> ProgressStyle {
+    if has_unicode() {
+        ProgressStyle::with_template(
+            "{spinner:.green} {msg} [{wide_bar:.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec})",
+        )
+        .unwrap()
+        .tick_strings(&["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"])
+        .progress_chars("█▉▊▋▌▍▎▏  ")
+    } else {
+        ProgressStyle::with_template(
+            "{spinner} {msg} [{wide_bar}] {bytes}/{total_bytes} ({bytes_per_sec})",
+        )
+        .unwrap()
+        .tick_strings(&["-", "\\", "|", "/"])
+        .progress_chars("##-")
+    }
+}
+
 /// Progress display manager for long-running operations
 pub struct ProgressDisplay<'a> {
     styling: &'a StyleManager,
@@ -64,6 +83,17 @@ impl<'a> ProgressDisplay<'a> {
         ProgressTracker::new(pb, self.styling)
     }
 
+    /// Create a progress bar for a transfer of `total` bytes
+    ///
+    /// The total can grow later with `set_length` once it is known.
+    pub fn bytes(&self, total: u64) -> ProgressTracker<'_> {
+        let pb = ProgressBar::new(total);
+        pb.set_style(bytes_style());
+        pb.enable_steady_tick(Duration::from_millis(100));
+
+        ProgressTracker::new(pb, self.styling)
+    }
+
     /// Create a spinner for operations with unknown duration
     ///
     /// Example:
@@ -129,6 +159,11 @@ impl<'a> ProgressTracker<'a> {
         self.bar.inc(n);
     }
 
+    /// Set the total
+    pub fn set_length(&self, len: u64) {
+        self.bar.set_length(len);
+    }
+
     /// Update the message
     pub fn set_message(&self, message: &str) {
         self.bar.set_message(message.to_string());
@@ -208,6 +243,20 @@ impl<'a> MultiProgressTracker<'a> {
         ProgressTracker::new(pb, self.styling)
     }
 
+    /// Add a byte-counting progress bar to the multi-progress
+    pub fn add_bytes_bar(&self, total: u64, message: &str) -> ProgressTracker<'_> {
+        if self.multi.is_hidden() {
+            self.multi.set_draw_target(ProgressDrawTarget::stderr());
+        }
+
+        let pb = self.multi.add(ProgressBar::new(total));
+        pb.set_style(bytes_style());
+        pb.set_message(message.to_string());
+        pb.enable_steady_tick(Duration::from_millis(100));
+
+        ProgressTracker::new(pb, self.styling)
+    }
+
     /// Add a spinner to the multi-progress
     pub fn add_spinner(&self, message: &str) -> ProgressTracker<'_> {
         if self.multi.is_hidden() {
@@ -13,6 +13,17 @@ pub trait DisplayProvider {
 
     /// Display structured output (tables, lists)
     fn table(&self) -> Box<dyn StructuredProvider>;
+
+    /// Record a machine-readable command result under `key`
+    ///
+    /// Results are collected for `--output json`; providers that do not
+    /// support structured output discard them.
+    fn record(&self, _key: &str, _value: serde_json::Value) {}
+
+    /// Drain every result recorded so far
+    fn take_records(&self) -> serde_json::Map<String, serde_json::Value> {
+        serde_json::Map::new()
+    }
 }
 
 /// Provider trait for status updates and user feedback
@@ -62,6 +73,13 @@ pub trait ProgressProvider {
     /// Create a progress bar for operations with known total
     fn bar(&self, total: u64) -> Box<dyn ProgressTracker>;
 
+    /// Create a progress bar for a transfer of `total` bytes
+    fn bytes(&self, total: u64, message: &str) -> Box<dyn ProgressTracker> {
+        let tracker = self.bar(total);
+        tracker.set_message(message);
+        tracker
+    }
+
     /// Create a spinner for operations with unknown duration
     fn spinner(&self, message: &str) -> Box<dyn ProgressTracker>;
 
@@ -80,6 +98,9 @@ pub trait ProgressTracker {
     /// Increment position by n
     fn inc_by(&self, n: u64);
 
+    /// Set the total
+    fn set_length(&self, _len: u64) {}
+
     /// Update the message
     fn set_message(&self, message: &str);
 
@@ -101,6 +122,11 @@ pub trait MultiProgressProvider {
     /// Add a progress bar to the multi-progress
     fn add_bar(&self, total: u64, message: &str) -> Box<dyn ProgressTracker>;
 
+    /// Add a byte-counting progress bar to the multi-progress
+    fn add_bytes_bar(&self, total: u64, message: &str) -> Box<dyn ProgressTracker> {
+        self.add_bar(total, message)
+    }
+
     /// Add a spinner to the multi-progress
     fn add_spinner(&self, message: &str) -> Box<dyn ProgressTracker>;
 
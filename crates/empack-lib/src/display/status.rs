@@ -22,7 +22,7 @@ impl<'a> StatusDisplay<'a> {
     /// Output: `⠋ Checking tool dependencies...`
     pub fn checking(&self, task: &str) {
         let message = format!("Checking {}...", task);
-        println!("{}", self.styling.format_working(&message));
+        super::emit_line(&self.styling.format_working(&message));
         io::stdout().flush().unwrap_or(());
     }
 
@@ -36,7 +36,7 @@ impl<'a> StatusDisplay<'a> {
         } else {
             format!("{}: {}", item, details)
         };
-        println!("{}", self.styling.format_success(&message));
+        super::emit_line(&self.styling.format_success(&message));
     }
 
     /// Display an error status with details
@@ -50,7 +50,7 @@ impl<'a> StatusDisplay<'a> {
         } else {
             format!("{}: {}", item, details)
         };
-        println!("{}", self.styling.format_error(&message));
+        super::emit_line(&self.styling.format_error(&message));
     }
 
     /// Display a warning status with details
@@ -58,7 +58,7 @@ impl<'a> StatusDisplay<'a> {
     /// Example: `Display::status().warning("experimental feature enabled")`
     /// Output: `! experimental feature enabled`
     pub fn warning(&self, message: &str) {
-        println!("{}", self.styling.format_warning(message));
+        super::emit_line(&self.styling.format_warning(message));
     }
 
     /// Display an info status
@@ -66,7 +66,7 @@ impl<'a> StatusDisplay<'a> {
     /// Example: `Display::status().info("using default configuration")`
     /// Output: `· using default configuration`
     pub fn info(&self, message: &str) {
-        println!("{}", self.styling.format_info(message));
+        super::emit_line(&self.styling.format_info(message));
     }
 
     /// Display a simple message without status symbols
@@ -74,7 +74,7 @@ impl<'a> StatusDisplay<'a> {
     /// Example: `Display::status().message("Empack modpack manager")`
     /// Output: `Empack modpack manager`
     pub fn message(&self, text: &str) {
-        println!("{}", text);
+        super::emit_line(text);
     }
 
     /// Display an emphasized message
@@ -82,7 +82,7 @@ impl<'a> StatusDisplay<'a> {
     /// Example: `Display::status().emphasis("Configuration complete")`
     /// Output: `**Configuration complete**` (styled)
     pub fn emphasis(&self, text: &str) {
-        println!("{}", self.styling.style_emphasis(text));
+        super::emit_line(&self.styling.style_emphasis(text));
     }
 
     /// Display a subtle/secondary message
@@ -90,7 +90,7 @@ impl<'a> StatusDisplay<'a> {
     /// Example: `Display::status().subtle("Run 'empack --help' for usage")`
     /// Output: subtle gray styled text
     pub fn subtle(&self, text: &str) {
-        println!("{}", self.styling.style_subtle(text));
+        super::emit_line(&self.styling.style_subtle(text));
     }
 
     /// Display a list of items with bullets
@@ -105,7 +105,7 @@ impl<'a> StatusDisplay<'a> {
     /// ```
     pub fn list(&self, items: &[&str]) {
         for item in items {
-            println!("  {} {}", self.styling.bullet(), item);
+            super::emit_line(&format!("  {} {}", self.styling.bullet(), item));
         }
     }
 
@@ -114,7 +114,7 @@ impl<'a> StatusDisplay<'a> {
     /// Example: `Display::status().complete("Dependencies checked")`
     /// Output: `✓ Dependencies checked`
     pub fn complete(&self, task: &str) {
-        println!("{}", self.styling.format_success(task));
+        super::emit_line(&self.styling.format_success(task));
     }
 }
 
@@ -135,8 +135,8 @@ impl<'a> StatusDisplay<'a> {
     ///
     /// Example: `Display::status().section("Checking Dependencies")`
     pub fn section(&self, title: &str) {
-        println!();
-        println!("{}", self.styling.style_emphasis(title));
+        super::emit_line("");
+        super::emit_line(&self.styling.style_emphasis(title));
     }
 
     /// Display a step in a multi-step process
@@ -145,6 +145,10 @@ impl<'a> StatusDisplay<'a> {
     /// Output: `[1/3] Loading configuration`
     pub fn step(&self, current: usize, total: usize, description: &str) {
         let prefix = format!("[{}/{}]", current, total);
-        println!("{} {}", self.styling.style_subtle(&prefix), description);
+        super::emit_line(&format!(
+            "{} {}",
+            self.styling.style_subtle(&prefix),
+            description
+        ));
     }
 }
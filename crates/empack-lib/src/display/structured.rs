@@ -49,30 +49,30 @@ impl<'a> StructuredDisplay<'a> {
         let max_key_len = pairs.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
 
         for (key, value) in pairs {
-            println!(
+            super::emit_line(&format!(
                 "{:width$} | {}",
                 self.styling.style_subtle(key),
                 value,
                 width = max_key_len
-            );
+            ));
         }
     }
 
     /// Create a bulleted list
     pub fn list(&self, items: &[&str]) {
         for item in items {
-            println!("{} {}", self.styling.bullet(), item);
+            super::emit_line(&format!("{} {}", self.styling.bullet(), item));
         }
     }
 
     /// Create a numbered list
     pub fn numbered_list(&self, items: &[&str]) {
         for (i, item) in items.iter().enumerate() {
-            println!(
+            super::emit_line(&format!(
                 "{}. {}",
                 self.styling.style_subtle(&(i + 1).to_string()),
                 item
-            );
+            ));
         }
     }
 }
@@ -197,7 +197,7 @@ impl<'a> TableDisplay<'a> {
             }
         }
 
-        println!("{}", output);
+        super::emit_line(&output);
     }
 
     fn render_separator(&self, widths: &[usize]) {
@@ -210,6 +210,6 @@ impl<'a> TableDisplay<'a> {
             }
         }
 
-        println!("{}", self.styling.style_subtle(&output));
+        super::emit_line(&self.styling.style_subtle(&output));
     }
 }
@@ -53,6 +53,9 @@ pub enum ArchiveError {
 
     #[error("source directory does not exist: {0}")]
     SourceNotFound(PathBuf),
+
+    #[error("timestamp {0} cannot be stored in a zip archive")]
+    Timestamp(u64),
 }
 
 /// Extract a zip archive to a directory.
@@ -74,152 +77,316 @@ pub fn extract_zip(archive_path: &Path, output_dir: &Path) -> Result<(), Archive
     Ok(())
 }
 
+/// Earliest timestamp a zip entry can carry (1980-01-01 00:00:00 UTC).
+///
+/// Archive timestamps are clamped to this value so every format stores the
+/// same date.
+pub const MIN_SOURCE_DATE_EPOCH: u64 = 315_532_800;
+
 /// Create an archive from a directory in the specified format.
 ///
 /// The archive contains the contents of `source_dir` with relative paths
 /// rooted at `source_dir` itself (i.e., `source_dir/foo.txt` becomes
 /// `foo.txt` in the archive).
 ///
+/// Output is reproducible: entries are sorted by path, every entry carries
+/// `source_date_epoch` as its modification time, permissions are normalized
+/// to `0o755` for directories and executables and `0o644` otherwise, and
+/// compression settings are fixed.
+///
 /// Returns `ArchiveError::SourceNotFound` if the directory does not exist,
 /// or `ArchiveError::EmptySource` if it contains no files.
 pub fn create_archive(
     source_dir: &Path,
     output_path: &Path,
     format: ArchiveFormat,
+    source_date_epoch: u64,
 ) -> Result<(), ArchiveError> {
     if !source_dir.exists() {
         return Err(ArchiveError::SourceNotFound(source_dir.to_path_buf()));
     }
 
-    if is_dir_empty(source_dir)? {
+    let entries = collect_entries(source_dir)?;
+    if entries.iter().all(|entry| entry.is_dir) {
         return Err(ArchiveError::EmptySource(source_dir.to_path_buf()));
     }
 
+    let epoch = source_date_epoch.max(MIN_SOURCE_DATE_EPOCH);
     match format {
-        ArchiveFormat::Zip => create_zip_archive(source_dir, output_path),
-        ArchiveFormat::TarGz => create_tar_gz_archive(source_dir, output_path),
-        ArchiveFormat::SevenZ => create_7z_archive(source_dir, output_path),
+        ArchiveFormat::Zip => create_zip_archive(&entries, output_path, epoch),
+        ArchiveFormat::TarGz => create_tar_gz_archive(&entries, output_path, epoch),
+        ArchiveFormat::SevenZ => create_7z_archive(&entries, output_path, epoch),
     }
 }
 
-fn is_dir_empty(dir: &Path) -> Result<bool, ArchiveError> {
-    for entry in std::fs::read_dir(dir).map_err(|e| ArchiveError::Io {
-        path: dir.to_path_buf(),
+/// Rewrite a zip archive in place with the same normalization as
+/// [`create_archive`].
+///
+/// Used on the `.mrpack` that `packwiz mr export` writes, whose entry order
+/// and timestamps vary between runs.
+pub fn normalize_zip(archive_path: &Path, source_date_epoch: u64) -> Result<(), ArchiveError> {
+    use std::io::{Read, Write};
+
+    let io_error = |e| ArchiveError::Io {
+        path: archive_path.to_path_buf(),
         source: e,
-    })? {
-        let entry = entry.map_err(|e| ArchiveError::Io {
-            path: dir.to_path_buf(),
-            source: e,
-        })?;
-        if entry.path().is_file() {
-            return Ok(false);
-        }
-        if entry.path().is_dir() && !is_dir_empty(&entry.path())? {
-            return Ok(false);
+    };
+
+    let file = File::open(archive_path).map_err(io_error)?;
+    let mut archive = zip::ZipArchive::new(file)?;
+    let mut entries = Vec::with_capacity(archive.len());
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        let mode = if entry.is_dir() {
+            0o755
+        } else {
+            normalized_mode(entry.unix_mode().unwrap_or(0o644))
+        };
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data).map_err(io_error)?;
+        entries.push((entry.name().to_string(), entry.is_dir(), mode, data));
+    }
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let epoch = source_date_epoch.max(MIN_SOURCE_DATE_EPOCH);
+    let base = zip_options(epoch)?
+        .compression_method(zip::CompressionMethod::Deflated)
+        .compression_level(Some(6));
+
+    let mut buffer = std::io::Cursor::new(Vec::new());
+    let mut writer = zip::ZipWriter::new(&mut buffer);
+    for (name, is_dir, mode, data) in entries {
+        let options = base.unix_permissions(mode);
+        if is_dir {
+            writer.add_directory(name, options)?;
+        } else {
+            writer.start_file(name, options)?;
+            writer.write_all(&data).map_err(io_error)?;
         }
     }
-    Ok(true)
+    writer.finish()?;
+
+    std::fs::write(archive_path, buffer.into_inner()).map_err(io_error)
 }
 
-fn create_zip_archive(source_dir: &Path, output_path: &Path) -> Result<(), ArchiveError> {
-    use std::io::{Read, Write};
-    use zip::CompressionMethod;
-    use zip::write::SimpleFileOptions;
+/// One file or directory to archive, in archive order.
+struct SourceEntry {
+    path: PathBuf,
+    /// Path inside the archive, `/`-separated, without a trailing slash
+    name: String,
+    is_dir: bool,
+    mode: u32,
+}
 
-    let file = File::create(output_path).map_err(|e| ArchiveError::Io {
-        path: output_path.to_path_buf(),
-        source: e,
-    })?;
-    let mut zip_writer = zip::ZipWriter::new(file);
-    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
-
-    fn walk_dir(
-        base: &Path,
-        current: &Path,
-        writer: &mut zip::ZipWriter<File>,
-        options: SimpleFileOptions,
-    ) -> Result<(), ArchiveError> {
-        let entries = std::fs::read_dir(current).map_err(|e| ArchiveError::Io {
-            path: current.to_path_buf(),
+/// Walk `source_dir` and return every entry sorted by archive path.
+fn collect_entries(source_dir: &Path) -> Result<Vec<SourceEntry>, ArchiveError> {
+    let mut entries = Vec::new();
+    let mut pending = vec![source_dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let read_dir = std::fs::read_dir(&current).map_err(|e| ArchiveError::Io {
+            path: current.clone(),
             source: e,
         })?;
-
-        for entry in entries {
+        for entry in read_dir {
             let entry = entry.map_err(|e| ArchiveError::Io {
-                path: current.to_path_buf(),
+                path: current.clone(),
                 source: e,
             })?;
             let path = entry.path();
-            let relative = path.strip_prefix(base).expect("path must be under base");
-            let name = relative.to_string_lossy().replace('\\', "/");
-
             let metadata = path.symlink_metadata().map_err(|e| ArchiveError::Io {
                 path: path.clone(),
                 source: e,
             })?;
-            if metadata.file_type().is_dir() {
-                writer
-                    .add_directory(format!("{}/", name), options)
-                    .map_err(ArchiveError::Zip)?;
-                walk_dir(base, &path, writer, options)?;
-            } else {
-                writer
-                    .start_file(name.to_string(), options)
-                    .map_err(ArchiveError::Zip)?;
-                let mut f = File::open(&path).map_err(|e| ArchiveError::Io {
-                    path: path.clone(),
-                    source: e,
-                })?;
-                let mut buffer = Vec::new();
-                f.read_to_end(&mut buffer).map_err(|e| ArchiveError::Io {
-                    path: path.clone(),
-                    source: e,
-                })?;
-                writer.write_all(&buffer).map_err(|e| ArchiveError::Io {
-                    path: path.clone(),
-                    source: e,
-                })?;
+            let relative = path
+                .strip_prefix(source_dir)
+                .expect("path must be under base");
+            let name = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            let is_dir = metadata.file_type().is_dir();
+            if is_dir {
+                pending.push(path.clone());
             }
+            entries.push(SourceEntry {
+                mode: if is_dir { 0o755 } else { file_mode(&metadata) },
+                path,
+                name,
+                is_dir,
+            });
         }
-        Ok(())
     }
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(entries)
+}
 
-    walk_dir(source_dir, source_dir, &mut zip_writer, options)?;
+#[cfg(unix)]
+fn file_mode(metadata: &std::fs::Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    normalized_mode(metadata.permissions().mode())
+}
 
-    zip_writer.finish().map_err(ArchiveError::Zip)?;
-    Ok(())
+#[cfg(not(unix))]
+fn file_mode(_metadata: &std::fs::Metadata) -> u32 {
+    0o644
 }
 
-fn create_tar_gz_archive(source_dir: &Path, output_path: &Path) -> Result<(), ArchiveError> {
-    use flate2::Compression;
-    use flate2::write::GzEncoder;
+/// Collapse a file mode to `0o755` when any execute bit is set, else `0o644`.
+fn normalized_mode(mode: u32) -> u32 {
+    if mode & 0o111 != 0 { 0o755 } else { 0o644 }
+}
 
-    let file = File::create(output_path).map_err(|e| ArchiveError::Io {
-        path: output_path.to_path_buf(),
+fn read_source(entry: &SourceEntry) -> Result<Vec<u8>, ArchiveError> {
+    std::fs::read(&entry.path).map_err(|e| ArchiveError::Io {
+        path: entry.path.clone(),
         source: e,
-    })?;
-    let enc = GzEncoder::new(file, Compression::default());
-    let mut tar_builder = tar::Builder::new(enc);
-    tar_builder
-        .append_dir_all(".", source_dir)
-        .map_err(|e| ArchiveError::Io {
-            path: source_dir.to_path_buf(),
-            source: e,
-        })?;
-    let enc = tar_builder.into_inner().map_err(|e| ArchiveError::Io {
+    })
+}
+
+/// Zip options shared by every entry: fixed timestamp and Unix host system.
+fn zip_options(epoch: u64) -> Result<zip::write::SimpleFileOptions, ArchiveError> {
+    let (year, month, day) = crate::empack::time::civil_date((epoch / 86_400) as i64);
+    let seconds = epoch % 86_400;
+    let year = u16::try_from(year).map_err(|_| ArchiveError::Timestamp(epoch))?;
+    let time = zip::DateTime::from_date_and_time(
+        year,
+        month as u8,
+        day as u8,
+        (seconds / 3_600) as u8,
+        (seconds % 3_600 / 60) as u8,
+        (seconds % 60) as u8,
+    )
+    .map_err(|_| ArchiveError::Timestamp(epoch))?;
+
+    Ok(zip::write::SimpleFileOptions::default()
+        .last_modified_time(time)
+        .system(zip::System::Unix))
+}
+
+fn create_zip_archive(
+    entries: &[SourceEntry],
+    output_path: &Path,
+    epoch: u64,
+) -> Result<(), ArchiveError> {
+    use std::io::Write;
+
+    let file = File::create(output_path).map_err(|e| ArchiveError::Io {
         path: output_path.to_path_buf(),
         source: e,
     })?;
-    enc.finish().map_err(|e| ArchiveError::Io {
+    let mut zip_writer = zip::ZipWriter::new(file);
+    let base = zip_options(epoch)?.compression_method(zip::CompressionMethod::Stored);
+
+    for entry in entries {
+        let options = base.unix_permissions(entry.mode);
+        if entry.is_dir {
+            zip_writer.add_directory(format!("{}/", entry.name), options)?;
+        } else {
+            zip_writer.start_file(entry.name.as_str(), options)?;
+            zip_writer
+                .write_all(&read_source(entry)?)
+                .map_err(|e| ArchiveError::Io {
+                    path: entry.path.clone(),
+                    source: e,
+                })?;
+        }
+    }
+
+    zip_writer.finish()?;
+    Ok(())
+}
+
+fn create_tar_gz_archive(
+    entries: &[SourceEntry],
+    output_path: &Path,
+    epoch: u64,
+) -> Result<(), ArchiveError> {
+    use flate2::{Compression, GzBuilder};
+
+    let io_error = |e| ArchiveError::Io {
         path: output_path.to_path_buf(),
         source: e,
-    })?;
+    };
+
+    let file = File::create(output_path).map_err(io_error)?;
+    let enc = GzBuilder::new()
+        .mtime(u32::try_from(epoch).unwrap_or(u32::MAX))
+        .write(file, Compression::default());
+    let mut tar_builder = tar::Builder::new(enc);
+
+    for entry in entries {
+        let mut header = tar::Header::new_gnu();
+        header.set_mode(entry.mode);
+        header.set_mtime(epoch);
+        header.set_uid(0);
+        header.set_gid(0);
+        if entry.is_dir {
+            header.set_entry_type(tar::EntryType::Directory);
+            header.set_size(0);
+            tar_builder
+                .append_data(&mut header, format!("{}/", entry.name), std::io::empty())
+                .map_err(io_error)?;
+        } else {
+            let data = read_source(entry)?;
+            header.set_entry_type(tar::EntryType::Regular);
+            header.set_size(data.len() as u64);
+            tar_builder
+                .append_data(&mut header, &entry.name, data.as_slice())
+                .map_err(io_error)?;
+        }
+    }
+
+    let enc = tar_builder.into_inner().map_err(io_error)?;
+    enc.finish().map_err(io_error)?;
     Ok(())
 }
 
-fn create_7z_archive(source_dir: &Path, output_path: &Path) -> Result<(), ArchiveError> {
-    sevenz_rust2::compress_to_path(source_dir, output_path)
-        .map_err(|e| ArchiveError::SevenZ(e.to_string()))?;
+fn create_7z_archive(
+    entries: &[SourceEntry],
+    output_path: &Path,
+    epoch: u64,
+) -> Result<(), ArchiveError> {
+    use sevenz_rust2::{ArchiveEntry, ArchiveWriter, NtTime};
+
+    /// Seconds between 1601-01-01 (the NT epoch) and 1970-01-01
+    const NT_UNIX_OFFSET: u64 = 11_644_473_600;
+    /// `FILE_ATTRIBUTE_DIRECTORY`
+    const DIRECTORY_ATTRIBUTE: u32 = 0x10;
+    /// 7-Zip's marker for Unix mode bits stored in the high 16 bits
+    const UNIX_EXTENSION: u32 = 0x8000;
+
+    let sevenz_error = |e: sevenz_rust2::Error| ArchiveError::SevenZ(e.to_string());
+    let mut writer = ArchiveWriter::create(output_path).map_err(sevenz_error)?;
+    let modified = NtTime::new((epoch + NT_UNIX_OFFSET) * 10_000_000);
+
+    for entry in entries {
+        let mut archive_entry = if entry.is_dir {
+            ArchiveEntry::new_directory(&entry.name)
+        } else {
+            ArchiveEntry::new_file(&entry.name)
+        };
+        archive_entry.has_last_modified_date = true;
+        archive_entry.last_modified_date = modified;
+        archive_entry.has_windows_attributes = true;
+        archive_entry.windows_attributes = UNIX_EXTENSION | (entry.mode << 16);
+        if entry.is_dir {
+            archive_entry.windows_attributes |= DIRECTORY_ATTRIBUTE;
+            writer
+                .push_archive_entry::<&[u8]>(archive_entry, None)
+                .map_err(sevenz_error)?;
+        } else {
+            let data = read_source(entry)?;
+            writer
+                .push_archive_entry(archive_entry, Some(data.as_slice()))
+                .map_err(sevenz_error)?;
+        }
+    }
+
+    writer.finish().map_err(|e| ArchiveError::Io {
+        path: output_path.to_path_buf(),
+        source: e,
+    })?;
     Ok(())
 }
 
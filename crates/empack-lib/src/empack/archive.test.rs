@@ -10,7 +10,7 @@ fn test_create_and_extract_zip_round_trip() {
     std::fs::write(src.join("sub/b.txt"), "beta").unwrap();
 
     let zip_path = tmp.path().join("out.zip");
-    create_archive(&src, &zip_path, ArchiveFormat::Zip).unwrap();
+    create_archive(&src, &zip_path, ArchiveFormat::Zip, MIN_SOURCE_DATE_EPOCH).unwrap();
 
     let extract_dir = tmp.path().join("verify");
     extract_zip(&zip_path, &extract_dir).unwrap();
@@ -33,7 +33,7 @@ fn test_create_tar_gz() {
     std::fs::write(src.join("file.txt"), "content").unwrap();
 
     let tar_path = tmp.path().join("out.tar.gz");
-    create_archive(&src, &tar_path, ArchiveFormat::TarGz).unwrap();
+    create_archive(&src, &tar_path, ArchiveFormat::TarGz, MIN_SOURCE_DATE_EPOCH).unwrap();
 
     let file = std::fs::File::open(&tar_path).unwrap();
     let dec = flate2::read::GzDecoder::new(file);
@@ -50,7 +50,7 @@ fn test_create_7z() {
     std::fs::write(src.join("data.bin"), vec![0u8; 1024]).unwrap();
 
     let sz_path = tmp.path().join("out.7z");
-    create_archive(&src, &sz_path, ArchiveFormat::SevenZ).unwrap();
+    create_archive(&src, &sz_path, ArchiveFormat::SevenZ, MIN_SOURCE_DATE_EPOCH).unwrap();
 
     assert!(sz_path.exists());
     assert!(std::fs::metadata(&sz_path).unwrap().len() > 0);
@@ -94,7 +94,7 @@ fn test_create_archive_empty_source_returns_error() {
     std::fs::create_dir_all(&src).unwrap();
 
     let zip_path = tmp.path().join("empty.zip");
-    let result = create_archive(&src, &zip_path, ArchiveFormat::Zip);
+    let result = create_archive(&src, &zip_path, ArchiveFormat::Zip, MIN_SOURCE_DATE_EPOCH);
     assert!(matches!(result, Err(ArchiveError::EmptySource(_))));
 }
 
@@ -105,6 +105,7 @@ fn test_create_archive_nonexistent_source_returns_error() {
         &tmp.path().join("nonexistent"),
         &tmp.path().join("out.zip"),
         ArchiveFormat::Zip,
+        MIN_SOURCE_DATE_EPOCH,
     );
     assert!(matches!(result, Err(ArchiveError::SourceNotFound(_))));
 }
@@ -116,6 +117,7 @@ fn test_create_archive_nonexistent_source_tar_gz_returns_error() {
         &tmp.path().join("nonexistent"),
         &tmp.path().join("out.tar.gz"),
         ArchiveFormat::TarGz,
+        MIN_SOURCE_DATE_EPOCH,
     );
     assert!(matches!(result, Err(ArchiveError::SourceNotFound(_))));
 }
@@ -127,6 +129,7 @@ fn test_create_archive_nonexistent_source_7z_returns_error() {
         &tmp.path().join("nonexistent"),
         &tmp.path().join("out.7z"),
         ArchiveFormat::SevenZ,
+        MIN_SOURCE_DATE_EPOCH,
     );
     assert!(matches!(result, Err(ArchiveError::SourceNotFound(_))));
 }
@@ -145,7 +148,7 @@ fn test_create_archive_empty_source_tar_gz_returns_error() {
     std::fs::create_dir_all(&src).unwrap();
 
     let tar_path = tmp.path().join("empty.tar.gz");
-    let result = create_archive(&src, &tar_path, ArchiveFormat::TarGz);
+    let result = create_archive(&src, &tar_path, ArchiveFormat::TarGz, MIN_SOURCE_DATE_EPOCH);
     assert!(matches!(result, Err(ArchiveError::EmptySource(_))));
 }
 
@@ -156,7 +159,7 @@ fn test_create_archive_empty_source_7z_returns_error() {
     std::fs::create_dir_all(&src).unwrap();
 
     let sz_path = tmp.path().join("empty.7z");
-    let result = create_archive(&src, &sz_path, ArchiveFormat::SevenZ);
+    let result = create_archive(&src, &sz_path, ArchiveFormat::SevenZ, MIN_SOURCE_DATE_EPOCH);
     assert!(matches!(result, Err(ArchiveError::EmptySource(_))));
 }
 
@@ -167,7 +170,7 @@ fn test_create_archive_empty_nested_dirs_returns_error() {
     std::fs::create_dir_all(src.join("a/b/c")).unwrap();
 
     let zip_path = tmp.path().join("nested-empty.zip");
-    let result = create_archive(&src, &zip_path, ArchiveFormat::Zip);
+    let result = create_archive(&src, &zip_path, ArchiveFormat::Zip, MIN_SOURCE_DATE_EPOCH);
     assert!(matches!(result, Err(ArchiveError::EmptySource(_))));
 }
 
@@ -184,3 +187,89 @@ fn test_archive_format_display() {
     assert_eq!(format!("{}", ArchiveFormat::TarGz), "tar.gz");
     assert_eq!(format!("{}", ArchiveFormat::SevenZ), "7z");
 }
+
+/// Write the same tree in a different order and with different mtimes.
+fn write_source_tree(dir: &Path, reverse: bool, mtime_secs: u64) {
+    let mut files = vec![("a.txt", "alpha"), ("sub/b.txt", "beta"), ("sub/deeper/c.txt", "gamma")];
+    if reverse {
+        files.reverse();
+    }
+    for (name, content) in files {
+        let path = dir.join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, content).unwrap();
+        let mtime = std::time::UNIX_EPOCH + std::time::Duration::from_secs(mtime_secs);
+        File::options().write(true).open(&path).unwrap().set_modified(mtime).unwrap();
+    }
+}
+
+#[test]
+fn test_create_archive_is_byte_identical_across_runs() {
+    let tmp = tempdir().unwrap();
+    let first = tmp.path().join("first");
+    let second = tmp.path().join("second");
+    write_source_tree(&first, false, 1_000_000_000);
+    write_source_tree(&second, true, 1_700_000_000);
+
+    for format in [ArchiveFormat::Zip, ArchiveFormat::TarGz, ArchiveFormat::SevenZ] {
+        let a = tmp.path().join(format!("a.{}", format.extension()));
+        let b = tmp.path().join(format!("b.{}", format.extension()));
+        create_archive(&first, &a, format, 1_750_000_000).unwrap();
+        create_archive(&second, &b, format, 1_750_000_000).unwrap();
+        assert_eq!(std::fs::read(&a).unwrap(), std::fs::read(&b).unwrap(), "{format}");
+    }
+}
+
+#[test]
+fn test_create_zip_sorts_entries_and_stamps_source_date_epoch() {
+    let tmp = tempdir().unwrap();
+    let src = tmp.path().join("src");
+    write_source_tree(&src, true, 1_000_000_000);
+
+    let zip_path = tmp.path().join("out.zip");
+    // 2026-10-17 12:04:05 UTC; zip stores seconds at 2s resolution
+    create_archive(&src, &zip_path, ArchiveFormat::Zip, 1_792_238_645).unwrap();
+
+    let mut archive = zip::ZipArchive::new(File::open(&zip_path).unwrap()).unwrap();
+    let names: Vec<_> = archive.file_names().map(str::to_string).collect();
+    assert_eq!(names, ["a.txt", "sub/", "sub/b.txt", "sub/deeper/", "sub/deeper/c.txt"]);
+    let entry = archive.by_name("sub/b.txt").unwrap();
+    let modified = entry.last_modified().unwrap();
+    assert_eq!(
+        (modified.year(), modified.month(), modified.day(), modified.hour(), modified.minute()),
+        (2026, 10, 17, 12, 4)
+    );
+    assert_eq!(entry.unix_mode().map(|mode| mode & 0o777), Some(0o644));
+}
+
+#[test]
+fn test_normalize_zip_rewrites_export_deterministically() {
+    use std::io::Write;
+
+    let tmp = tempdir().unwrap();
+    let write_export = |path: &Path, names: [&str; 2], year: u16| {
+        let mut writer = zip::ZipWriter::new(File::create(path).unwrap());
+        let time = zip::DateTime::from_date_and_time(year, 1, 2, 3, 4, 6).unwrap();
+        let options = zip::write::SimpleFileOptions::default().last_modified_time(time);
+        for name in names {
+            writer.start_file(name, options).unwrap();
+            writer.write_all(name.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+    };
+    let first = tmp.path().join("first.mrpack");
+    let second = tmp.path().join("second.mrpack");
+    write_export(&first, ["modrinth.index.json", "overrides/config.toml"], 2001);
+    write_export(&second, ["overrides/config.toml", "modrinth.index.json"], 2024);
+
+    normalize_zip(&first, MIN_SOURCE_DATE_EPOCH).unwrap();
+    normalize_zip(&second, MIN_SOURCE_DATE_EPOCH).unwrap();
+
+    assert_eq!(std::fs::read(&first).unwrap(), std::fs::read(&second).unwrap());
+    let extract_dir = tmp.path().join("verify");
+    extract_zip(&first, &extract_dir).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(extract_dir.join("overrides/config.toml")).unwrap(),
+        "overrides/config.toml"
+    );
+}
@@ -0,0 +1,166 @@
+//! Input fingerprints for incremental builds
+//!
+//! Each successful build target records a fingerprint of everything that
+//! feeds it: `empack.yml`, the contents of `pack/` and `templates/`, the
+//! archive format, the archive timestamp, and the empack and packwiz
+//! versions, along with the SHA-256 of each artifact it wrote. A later build
+//! skips the target only when the fingerprint still matches and every
+//! artifact is still there with the same hash.
+
+use crate::application::session::{FileSystemProvider, list_relative_files};
+use crate::empack::archive::ArchiveFormat;
+use crate::primitives::BuildTarget;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Fingerprint directory, relative to the artifact root.
+pub(crate) const FINGERPRINT_DIR: &str = ".fingerprints";
+
+/// Project directories whose contents feed every build target.
+const INPUT_DIRS: [&str; 2] = ["pack", "templates"];
+
+#[derive(Debug, Error)]
+pub enum BuildCacheError {
+    #[error("Failed to read build input {path}: {reason}")]
+    Input { path: PathBuf, reason: String },
+
+    #[error("Failed to write fingerprint {path}: {reason}")]
+    Persist { path: PathBuf, reason: String },
+}
+
+/// Tool versions that change build output without touching project files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolVersions {
+    pub empack: String,
+    pub packwiz: String,
+}
+
+/// What a target was last built from and what it produced.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TargetRecord {
+    pub fingerprint: String,
+    pub artifacts: Vec<RecordedArtifact>,
+    #[serde(default)]
+    pub excluded: Vec<String>,
+}
+
+/// An artifact a target wrote.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedArtifact {
+    /// Relative to the artifact root where possible
+    pub path: PathBuf,
+    /// Hex SHA-256 of the artifact's bytes
+    pub sha256: String,
+}
+
+/// Hash every input of `target` into a hex SHA-256 digest.
+pub fn compute_fingerprint(
+    filesystem: &dyn FileSystemProvider,
+    workdir: &Path,
+    target: BuildTarget,
+    archive_format: ArchiveFormat,
+    source_date_epoch: u64,
+    tools: &ToolVersions,
+) -> Result<String, BuildCacheError> {
+    let mut hasher = Sha256::new();
+    let mut field = |label: &str, bytes: &[u8]| {
+        hasher.update(label.as_bytes());
+        hasher.update((bytes.len() as u64).to_le_bytes());
+        hasher.update(bytes);
+    };
+
+    field("target", target.to_string().as_bytes());
+    field("archive-format", archive_format.extension().as_bytes());
+    field("source-date-epoch", &source_date_epoch.to_le_bytes());
+    field("empack", tools.empack.as_bytes());
+    field("packwiz", tools.packwiz.as_bytes());
+
+    let mut inputs = vec!["empack.yml".to_string()];
+    for dir in INPUT_DIRS {
+        inputs.extend(list_relative_files(filesystem, workdir, &workdir.join(dir)));
+    }
+    for relative in inputs {
+        let path = workdir.join(&relative);
+        if !filesystem.exists(&path) {
+            continue;
+        }
+        let bytes = filesystem
+            .read_bytes(&path)
+            .map_err(|e| BuildCacheError::Input {
+                path: path.clone(),
+                reason: e.to_string(),
+            })?;
+        field(&relative, &bytes);
+    }
+
+    Ok(crate::empack::content::hex::encode(hasher.finalize()))
+}
+
+/// Hex SHA-256 of the file at `path`, or `None` when it cannot be read.
+pub fn hash_artifact(filesystem: &dyn FileSystemProvider, path: &Path) -> Option<String> {
+    let bytes = filesystem.read_bytes(path).ok()?;
+    Some(crate::empack::content::hex::encode(Sha256::digest(&bytes)))
+}
+
+/// Load the record for `target`, if one was stored and still parses.
+pub fn load_record(
+    filesystem: &dyn FileSystemProvider,
+    dist_dir: &Path,
+    target: BuildTarget,
+) -> Option<TargetRecord> {
+    let content = filesystem
+        .read_to_string(&record_path(dist_dir, target))
+        .ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Persist the record for `target`.
+pub fn store_record(
+    filesystem: &dyn FileSystemProvider,
+    dist_dir: &Path,
+    target: BuildTarget,
+    record: &TargetRecord,
+) -> Result<(), BuildCacheError> {
+    let path = record_path(dist_dir, target);
+    let json = serde_json::to_string_pretty(record).map_err(|e| BuildCacheError::Persist {
+        path: path.clone(),
+        reason: e.to_string(),
+    })?;
+    filesystem
+        .write_file(&path, &json)
+        .map_err(|e| BuildCacheError::Persist {
+            path,
+            reason: e.to_string(),
+        })
+}
+
+/// Forget the record for `target` so the next build runs in full.
+pub fn remove_record(
+    filesystem: &dyn FileSystemProvider,
+    dist_dir: &Path,
+    target: BuildTarget,
+) -> Result<(), BuildCacheError> {
+    let path = record_path(dist_dir, target);
+    if !filesystem.exists(&path) {
+        return Ok(());
+    }
+    filesystem
+        .remove_file(&path)
+        .map_err(|e| BuildCacheError::Persist {
+            path,
+            reason: e.to_string(),
+        })
+}
+
+fn record_path(dist_dir: &Path, target: BuildTarget) -> PathBuf {
+    dist_dir
+        .join(FINGERPRINT_DIR)
+        .join(format!("{target}.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    include!("build_cache.test.rs");
+}
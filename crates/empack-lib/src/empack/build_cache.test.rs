@@ -0,0 +1,103 @@
+use super::*;
+use crate::application::session_mocks::{MockFileSystemProvider, mock_root};
+
+fn tools() -> ToolVersions {
+    ToolVersions {
+        empack: "0.1.0".to_string(),
+        packwiz: "v0.2.0".to_string(),
+    }
+}
+
+fn project_filesystem(workdir: &Path) -> MockFileSystemProvider {
+    MockFileSystemProvider::new()
+        .with_file(workdir.join("empack.yml"), "empack:\n  dependencies: {}\n".to_string())
+        .with_file(workdir.join("pack/pack.toml"), "name = \"Pack\"\n".to_string())
+        .with_file(
+            workdir.join("pack/mods/sodium.pw.toml"),
+            "name = \"Sodium\"\n".to_string(),
+        )
+        .with_file(workdir.join("templates/client/README.md"), "hello\n".to_string())
+}
+
+fn fingerprint(fs: &MockFileSystemProvider, workdir: &Path, target: BuildTarget) -> String {
+    compute_fingerprint(fs, workdir, target, ArchiveFormat::Zip, 0, &tools()).unwrap()
+}
+
+#[test]
+fn fingerprint_tracks_project_inputs() {
+    let workdir = mock_root().join("pack-project");
+    let fs = project_filesystem(&workdir);
+    let original = fingerprint(&fs, &workdir, BuildTarget::Client);
+
+    assert_eq!(fingerprint(&fs, &workdir, BuildTarget::Client), original);
+    assert_ne!(fingerprint(&fs, &workdir, BuildTarget::Server), original);
+
+    fs.write_file(&workdir.join("templates/client/README.md"), "changed\n")
+        .unwrap();
+    let edited_template = fingerprint(&fs, &workdir, BuildTarget::Client);
+    assert_ne!(edited_template, original);
+
+    fs.write_file(&workdir.join("pack/mods/jei.pw.toml"), "name = \"JEI\"\n")
+        .unwrap();
+    assert_ne!(fingerprint(&fs, &workdir, BuildTarget::Client), edited_template);
+}
+
+#[test]
+fn fingerprint_tracks_archive_settings_and_tools() {
+    let workdir = mock_root().join("pack-project");
+    let fs = project_filesystem(&workdir);
+    let original = fingerprint(&fs, &workdir, BuildTarget::Client);
+
+    let tar = compute_fingerprint(&fs, &workdir, BuildTarget::Client, ArchiveFormat::TarGz, 0, &tools())
+        .unwrap();
+    assert_ne!(tar, original);
+
+    let restamped =
+        compute_fingerprint(&fs, &workdir, BuildTarget::Client, ArchiveFormat::Zip, 1_700_000_000, &tools())
+            .unwrap();
+    assert_ne!(restamped, original);
+
+    let newer = ToolVersions {
+        packwiz: "v0.3.0".to_string(),
+        ..tools()
+    };
+    let upgraded =
+        compute_fingerprint(&fs, &workdir, BuildTarget::Client, ArchiveFormat::Zip, 0, &newer).unwrap();
+    assert_ne!(upgraded, original);
+}
+
+#[test]
+fn records_round_trip_and_remove() {
+    let dist_dir = mock_root().join("pack-project/dist");
+    let fs = MockFileSystemProvider::new();
+    let record = TargetRecord {
+        fingerprint: "abc".to_string(),
+        artifacts: vec![RecordedArtifact {
+            path: PathBuf::from("Pack-v1.0.0-client.zip"),
+            sha256: "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855".to_string(),
+        }],
+        excluded: vec!["Sodium".to_string()],
+    };
+
+    assert_eq!(load_record(&fs, &dist_dir, BuildTarget::Client), None);
+    store_record(&fs, &dist_dir, BuildTarget::Client, &record).unwrap();
+    assert_eq!(load_record(&fs, &dist_dir, BuildTarget::Client), Some(record));
+    assert_eq!(load_record(&fs, &dist_dir, BuildTarget::Server), None);
+
+    remove_record(&fs, &dist_dir, BuildTarget::Client).unwrap();
+    assert_eq!(load_record(&fs, &dist_dir, BuildTarget::Client), None);
+    remove_record(&fs, &dist_dir, BuildTarget::Client).unwrap();
+}
+
+#[test]
+fn hash_artifact_reads_the_file() {
+    let path = mock_root().join("pack-project/dist/empty.zip");
+    let fs = MockFileSystemProvider::new();
+
+    assert_eq!(hash_artifact(&fs, &path), None);
+    fs.write_bytes(&path, b"").unwrap();
+    assert_eq!(
+        hash_artifact(&fs, &path).as_deref(),
+        Some("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+    );
+}
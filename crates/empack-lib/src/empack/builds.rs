@@ -1,20 +1,25 @@
 //! Build system for empack targets
-//! Five-target system: mrpack, client, server, client-full, server-full
+//! Seven-target system: mrpack, client, server, client-full, server-full, curseforge, prism
 
 use crate::application::session::execute_process_with_live_issues;
 use crate::empack::PackwizInstaller;
+use crate::empack::build_cache::{self, RecordedArtifact, TargetRecord, ToolVersions};
+use crate::empack::config::DependencyEntry;
+use crate::empack::content::DependencySide;
+use crate::empack::signing::{self, SigningKey};
+use crate::empack::store::{ContentHash, ContentStore, StoreError};
 use crate::empack::templates::TemplateEngine;
 use crate::empack::versions::{
     canonicalize_forge_loader_version, parse_version, uses_forge_style_neoforge_coordinate,
     uses_legacy_forge_coordinate,
 };
+use crate::networking::download::{Download, DownloadEngine, DownloadError};
 use crate::primitives::*;
-#[cfg(test)]
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use sha1::{Digest, Sha1};
 
 #[derive(Deserialize)]
@@ -70,6 +75,184 @@ struct QuiltMavenVersioning {
     release: String,
 }
 
+/// CurseForge modpack `manifest.json`, the shape `import.rs` parses back.
+#[derive(Debug, Serialize)]
+struct CurseForgeManifest {
+    minecraft: CurseForgeMinecraft,
+    #[serde(rename = "manifestType")]
+    manifest_type: &'static str,
+    #[serde(rename = "manifestVersion")]
+    manifest_version: u32,
+    name: String,
+    version: String,
+    author: String,
+    files: Vec<CurseForgeFile>,
+    overrides: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct CurseForgeMinecraft {
+    version: String,
+    #[serde(rename = "modLoaders")]
+    mod_loaders: Vec<CurseForgeModLoader>,
+}
+
+#[derive(Debug, Serialize)]
+struct CurseForgeModLoader {
+    id: String,
+    primary: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct CurseForgeFile {
+    #[serde(rename = "projectID")]
+    project_id: u64,
+    #[serde(rename = "fileID")]
+    file_id: u64,
+    required: bool,
+}
+
+/// Prism Launcher / MultiMC `mmc-pack.json` component list.
+#[derive(Debug, Serialize)]
+struct PrismPack {
+    components: Vec<PrismComponent>,
+    #[serde(rename = "formatVersion")]
+    format_version: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct PrismComponent {
+    uid: &'static str,
+    version: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    important: bool,
+}
+
+/// Build the `mmc-pack.json` components for a pack's Minecraft and loader versions.
+fn prism_components(pack_info: &PackInfo) -> Vec<PrismComponent> {
+    let mut components = vec![PrismComponent {
+        uid: "net.minecraft",
+        version: pack_info.mc_version.clone(),
+        important: true,
+    }];
+
+    let loader_uid = match pack_info.loader_type.as_str() {
+        "fabric" => Some("net.fabricmc.fabric-loader"),
+        "quilt" => Some("org.quiltmc.quilt-loader"),
+        "forge" => Some("net.minecraftforge"),
+        "neoforge" => Some("net.neoforged"),
+        _ => None,
+    };
+    let Some(loader_uid) = loader_uid else {
+        return components;
+    };
+
+    if matches!(pack_info.loader_type.as_str(), "fabric" | "quilt") {
+        components.push(PrismComponent {
+            uid: "net.fabricmc.intermediary",
+            version: pack_info.mc_version.clone(),
+            important: false,
+        });
+    }
+
+    let loader_version = if pack_info.loader_type == "forge" {
+        canonicalize_forge_loader_version(&pack_info.mc_version, &pack_info.loader_version)
+    } else {
+        pack_info.loader_version.clone()
+    };
+    components.push(PrismComponent {
+        uid: loader_uid,
+        version: loader_version,
+        important: false,
+    });
+
+    components
+}
+
+/// Pre-launch command that syncs the instance through packwiz-installer.
+const PRISM_PRELAUNCH_COMMAND: &str =
+    "PreLaunchCommand=\"$INST_JAVA\" -jar packwiz-installer-bootstrap.jar pack/pack.toml";
+
+/// Make sure a rendered `instance.cfg` runs packwiz-installer before launch.
+fn ensure_prism_prelaunch(instance_cfg: &str) -> String {
+    if instance_cfg
+        .lines()
+        .any(|line| line.trim_start().starts_with("PreLaunchCommand="))
+    {
+        return instance_cfg.to_string();
+    }
+
+    let mut content = instance_cfg.trim_end().to_string();
+    if !content
+        .lines()
+        .any(|line| line.trim() == "OverrideCommands=true")
+    {
+        content.push_str("\nOverrideCommands=true");
+    }
+    content.push('\n');
+    content.push_str(PRISM_PRELAUNCH_COMMAND);
+    content.push('\n');
+    content
+}
+
+/// The license part of a Modrinth project, from `GET /v2/project/{id}` or
+/// `GET /v2/projects`.
+#[derive(Deserialize)]
+pub(crate) struct ModrinthProjectLicense {
+    #[serde(default)]
+    pub(crate) id: String,
+    pub(crate) license: ModrinthLicense,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ModrinthLicense {
+    pub(crate) id: String,
+}
+
+/// SPDX licenses that allow a Modrinth-only file to be redistributed inside
+/// CurseForge overrides. `-only` / `-or-later` suffixes are stripped first.
+const REDISTRIBUTABLE_LICENSES: &[&str] = &[
+    "MIT",
+    "Apache-2.0",
+    "BSD-2-Clause",
+    "BSD-3-Clause",
+    "ISC",
+    "Zlib",
+    "Unlicense",
+    "CC0-1.0",
+    "CC-BY-4.0",
+    "CC-BY-SA-4.0",
+    "MPL-2.0",
+    "LGPL-2.1",
+    "LGPL-3.0",
+    "GPL-2.0",
+    "GPL-3.0",
+    "AGPL-3.0",
+];
+
+/// Check downloaded bytes against a packwiz `hash-format` / `hash` pair.
+///
+/// Formats empack cannot compute (such as `murmur2`) are accepted as-is.
+fn download_hash_matches(bytes: &[u8], hash_format: &str, expected: &str) -> bool {
+    let actual = match hash_format {
+        "sha1" => crate::empack::content::hex::encode(Sha1::digest(bytes)),
+        "sha256" => crate::empack::content::hex::encode(sha2::Sha256::digest(bytes)),
+        "sha512" => crate::empack::content::hex::encode(sha2::Sha512::digest(bytes)),
+        _ => return true,
+    };
+    actual.eq_ignore_ascii_case(expected)
+}
+
+fn license_allows_redistribution(spdx_id: &str) -> bool {
+    let base = spdx_id
+        .strip_suffix("-only")
+        .or_else(|| spdx_id.strip_suffix("-or-later"))
+        .unwrap_or(spdx_id);
+    REDISTRIBUTABLE_LICENSES
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(base))
+}
+
 /// Build system errors
 #[derive(Debug, Error)]
 pub enum BuildError {
@@ -96,6 +279,57 @@ pub enum BuildError {
 
     #[error("Pack info extraction failed: {reason}")]
     PackInfoError { reason: String },
+
+    #[error("Download unavailable: {source}")]
+    Network {
+        #[from]
+        source: crate::networking::NetworkingError,
+    },
+
+    #[error("Content store error: {source}")]
+    Store {
+        #[from]
+        source: StoreError,
+    },
+
+    #[error("Download failed: {source}")]
+    Download {
+        #[from]
+        source: DownloadError,
+    },
+}
+
+/// Checksum file written to the artifact root after every build.
+pub const CHECKSUMS_FILE: &str = "SHA256SUMS";
+
+/// Where the build keeps its copy of `url`: server jars, loader installers,
+/// and the metadata that locates them, under `<jar cache>/downloads`.
+///
+/// The name is a hash of the URL, which keeps distinct downloads apart,
+/// followed by the URL's last path segment for readability.
+pub fn download_cache_path(url: &str) -> Result<PathBuf, BuildError> {
+    use sha2::Sha256;
+
+    let jar_cache =
+        crate::platform::cache::jar_cache_dir().map_err(|e| BuildError::ConfigError {
+            reason: format!("jar cache directory unavailable: {e}"),
+        })?;
+    let digest = crate::empack::content::hex::encode(Sha256::digest(url.as_bytes()));
+    let segment = url
+        .split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit('/').next())
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            segment.replace(
+                |c: char| !c.is_ascii_alphanumeric() && !"._-".contains(c),
+                "_",
+            )
+        })
+        .unwrap_or_else(|| "index".to_string());
+    Ok(jar_cache
+        .join("downloads")
+        .join(format!("{}-{}", &digest[..16], segment)))
 }
 
 /// Build orchestrator with state tracking and template processing
@@ -110,6 +344,9 @@ pub struct BuildOrchestrator<'a> {
 
     archive_format: crate::empack::archive::ArchiveFormat,
     continue_full_builds: bool,
+    force_rebuild: bool,
+    /// Archive entry timestamp; resolved from the last commit when unset
+    source_date_epoch: Option<u64>,
 
     template_engine: Option<TemplateEngine>,
 
@@ -126,6 +363,8 @@ impl<'a> std::fmt::Debug for BuildOrchestrator<'a> {
             .field("pack_info", &self.pack_info)
             .field("archive_format", &self.archive_format)
             .field("continue_full_builds", &self.continue_full_builds)
+            .field("force_rebuild", &self.force_rebuild)
+            .field("source_date_epoch", &self.source_date_epoch)
             .field(
                 "template_engine",
                 &self.template_engine.as_ref().map(|_| "<TemplateEngine>"),
@@ -135,6 +374,53 @@ impl<'a> std::fmt::Debug for BuildOrchestrator<'a> {
     }
 }
 
+/// Group `targets` into waves that can build concurrently.
+///
+/// Waves follow `BuildConfig::dependencies` between requested targets, and
+/// each target is placed as late as its dependents allow: a prerequisite
+/// such as `mrpack` finishes before anything else starts, so a blocked
+/// export stops the pipeline early. Dependencies that were not requested
+/// are satisfied by the shared prerequisites instead. Request order is kept
+/// within each wave.
+fn plan_build_waves(targets: &[BuildTarget]) -> Vec<Vec<BuildTarget>> {
+    let registry = BuildOrchestrator::create_build_registry();
+    let depends_on = |target: &BuildTarget, dependency: &BuildTarget| {
+        registry
+            .get(target)
+            .is_some_and(|config| config.dependencies.contains(dependency))
+    };
+
+    // Longest chain of requested dependents above each target
+    let mut height: HashMap<BuildTarget, usize> = HashMap::new();
+    let mut remaining: Vec<BuildTarget> = targets.to_vec();
+    while !remaining.is_empty() {
+        let before = remaining.len();
+        remaining.retain(|target| {
+            let mut level = 0;
+            for dependent in targets.iter().filter(|t| depends_on(t, target)) {
+                match height.get(dependent) {
+                    Some(h) => level = level.max(h + 1),
+                    None => return true,
+                }
+            }
+            height.insert(*target, level);
+            false
+        });
+        assert!(
+            remaining.len() < before,
+            "build target dependencies form a cycle"
+        );
+    }
+
+    let deepest = height.values().copied().max().unwrap_or(0);
+    let mut waves: Vec<Vec<BuildTarget>> = vec![Vec::new(); deepest + 1];
+    for target in targets {
+        waves[deepest - height[target]].push(*target);
+    }
+    waves.retain(|wave| !wave.is_empty());
+    waves
+}
+
 fn forge_installer_coordinate(mc_version: &str, loader_version: &str) -> String {
     let normalized_loader_version = canonicalize_forge_loader_version(mc_version, loader_version);
 
@@ -199,7 +485,7 @@ pub struct BuildConfig {
 }
 
 /// Build result for a specific target.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct BuildResult {
     /// The target that was built.
     pub target: BuildTarget,
@@ -213,10 +499,14 @@ pub struct BuildResult {
     pub warnings: Vec<String>,
     /// CurseForge mods that require manual download.
     pub restricted_mods: Vec<crate::empack::packwiz::RestrictedModInfo>,
+    /// Dependencies left out because they only install on the other side.
+    pub excluded: Vec<String>,
+    /// The target was skipped because its inputs and artifacts are unchanged.
+    pub up_to_date: bool,
 }
 
 /// Individual build artifact
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct BuildArtifact {
     pub name: String,
     pub path: PathBuf,
@@ -247,6 +537,8 @@ impl<'a> BuildOrchestrator<'a> {
             pack_info: None,
             archive_format,
             continue_full_builds: false,
+            force_rebuild: false,
+            source_date_epoch: None,
             template_engine: None,
             session,
         })
@@ -257,6 +549,256 @@ impl<'a> BuildOrchestrator<'a> {
         self
     }
 
+    /// Rebuild every target even when its recorded fingerprint still matches.
+    pub fn force_rebuild(mut self) -> Self {
+        self.force_rebuild = true;
+        self
+    }
+
+    /// Stamp archive entries with `epoch` instead of the last commit time.
+    pub fn source_date_epoch(mut self, epoch: u64) -> Self {
+        self.source_date_epoch = Some(epoch);
+        self
+    }
+
+    /// Timestamp for archive entries.
+    ///
+    /// Falls back to the last commit time of the project, then to the
+    /// earliest date a zip archive can store.
+    fn resolve_source_date_epoch(&self) -> u64 {
+        if let Some(epoch) = self.source_date_epoch {
+            return epoch;
+        }
+        self.session
+            .process()
+            .execute("git", &["log", "-1", "--format=%ct"], &self.workdir)
+            .ok()
+            .filter(|output| output.success)
+            .and_then(|output| output.stdout.trim().parse().ok())
+            .unwrap_or(crate::empack::archive::MIN_SOURCE_DATE_EPOCH)
+    }
+
+    fn archive_epoch(&self) -> u64 {
+        self.source_date_epoch
+            .unwrap_or(crate::empack::archive::MIN_SOURCE_DATE_EPOCH)
+    }
+
+    /// Write a CycloneDX SBOM next to the artifact of every successful
+    /// target and add it to the target's artifacts.
+    ///
+    /// Components come from `empack.lock`, or from the packwiz metafiles
+    /// when the project has no lock yet.
+    fn write_sboms(&mut self, results: &mut [BuildResult]) -> Result<(), BuildError> {
+        self.write_sboms_with_api_base(results, ProjectPlatform::Modrinth.api_base_url())
+    }
+
+    fn write_sboms_with_api_base(
+        &mut self,
+        results: &mut [BuildResult],
+        modrinth_api_base: &str,
+    ) -> Result<(), BuildError> {
+        let built: Vec<BuildTarget> = results
+            .iter()
+            .filter(|result| result.success)
+            .map(|result| result.target)
+            .collect();
+        if built.is_empty() {
+            return Ok(());
+        }
+
+        let filesystem = self.session.filesystem();
+        let dependencies = filesystem
+            .config_manager(self.workdir.clone())
+            .load_empack_config()
+            .map(|config| config.empack.dependencies)
+            .unwrap_or_default();
+        let lock = crate::empack::lockfile::load_lock(filesystem, &self.workdir)
+            .map_err(|e| BuildError::ConfigError {
+                reason: format!("Failed to load empack.lock for the SBOM: {e:#}"),
+            })?
+            .unwrap_or_else(|| {
+                crate::empack::lockfile::lock_from_project(filesystem, &self.workdir, &dependencies)
+            });
+        let licenses = self.fetch_modrinth_licenses(&lock, modrinth_api_base);
+
+        let pack_info = self.load_pack_info()?.clone();
+        let pack = crate::empack::sbom::PackMetadata {
+            name: &pack_info.name,
+            version: &pack_info.version,
+            timestamp: self.archive_epoch(),
+        };
+        for result in results.iter_mut().filter(|result| result.success) {
+            let components = crate::empack::sbom::components(
+                &dependencies,
+                &lock,
+                &licenses,
+                result.target,
+                &built,
+            );
+            let json =
+                crate::empack::sbom::render(pack, result.target, components).map_err(|e| {
+                    BuildError::ConfigError {
+                        reason: format!("Failed to render SBOM: {e}"),
+                    }
+                })?;
+            let path = self.dist_dir.join(crate::empack::sbom::file_name(
+                &pack_info.name,
+                &pack_info.version,
+                result.target,
+            ));
+            filesystem
+                .write_file(&path, &json)
+                .map_err(|e| BuildError::ConfigError {
+                    reason: format!("Failed to write {}: {e}", path.display()),
+                })?;
+            result.artifacts.retain(|artifact| artifact.path != path);
+            result.artifacts.push(self.create_artifact(&path)?);
+        }
+        Ok(())
+    }
+
+    /// Licenses of the Modrinth projects in `lock`, keyed by project ID.
+    ///
+    /// A failed lookup is reported and leaves the licenses out of the SBOM.
+    fn fetch_modrinth_licenses(
+        &self,
+        lock: &crate::empack::lockfile::EmpackLock,
+        modrinth_api_base: &str,
+    ) -> HashMap<String, String> {
+        use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
+
+        let ids = crate::empack::sbom::modrinth_project_ids(lock);
+        if ids.is_empty() {
+            return HashMap::new();
+        }
+        let ids = serde_json::to_string(&ids).unwrap_or_default();
+        let url = format!(
+            "{}/v2/projects?ids={}",
+            modrinth_api_base.trim_end_matches('/'),
+            utf8_percent_encode(&ids, NON_ALPHANUMERIC)
+        );
+        let licenses = self.fetch_url_text(&url).and_then(|body| {
+            crate::empack::sbom::parse_modrinth_licenses(&body).map_err(|e| {
+                BuildError::ConfigError {
+                    reason: format!("invalid Modrinth projects response: {e}"),
+                }
+            })
+        });
+        licenses.unwrap_or_else(|e| {
+            self.session.display().status().warning(&format!(
+                "Could not fetch Modrinth licenses for the SBOM: {e}"
+            ));
+            HashMap::new()
+        })
+    }
+
+    /// Write `<artifact>.minisig` next to every artifact of the successful
+    /// targets when a signing key is configured.
+    fn sign_artifacts(&self, results: &[BuildResult]) -> Result<(), BuildError> {
+        let app_config = self.session.config().app_config();
+        let Some(key_path) = &app_config.signing_key else {
+            return Ok(());
+        };
+
+        let filesystem = self.session.filesystem();
+        let key = filesystem
+            .read_to_string(key_path)
+            .map_err(|e| BuildError::ConfigError {
+                reason: format!("Failed to read signing key {}: {e}", key_path.display()),
+            })
+            .and_then(|text| {
+                SigningKey::parse(&text, app_config.signing_key_password.clone()).map_err(|e| {
+                    BuildError::ConfigError {
+                        reason: e.to_string(),
+                    }
+                })
+            })?;
+
+        let mut signed = 0;
+        for artifact in results
+            .iter()
+            .filter(|result| result.success)
+            .flat_map(|result| &result.artifacts)
+        {
+            let bytes =
+                filesystem
+                    .read_bytes(&artifact.path)
+                    .map_err(|e| BuildError::ConfigError {
+                        reason: format!(
+                            "Failed to read {} for signing: {e}",
+                            artifact.path.display()
+                        ),
+                    })?;
+            let signature = key
+                .sign(&bytes, &artifact.name, self.archive_epoch())
+                .map_err(|e| BuildError::ConfigError {
+                    reason: e.to_string(),
+                })?;
+            let path = signing::signature_path(&artifact.path);
+            filesystem
+                .write_file(&path, &signature)
+                .map_err(|e| BuildError::ConfigError {
+                    reason: format!("Failed to write {}: {e}", path.display()),
+                })?;
+            signed += 1;
+        }
+        if signed > 0 {
+            self.session.display().status().info(&format!(
+                "Signed {signed} artifact(s) with {}",
+                key_path.display()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Write `SHA256SUMS` listing the artifacts of `results`, named relative
+    /// to the artifact root and sorted. Signatures are left out so the file
+    /// is the same with and without a signing key; `empack verify` checks
+    /// each signature against the public key instead.
+    fn write_checksums(&self, results: &[BuildResult]) -> Result<(), BuildError> {
+        use sha2::Sha256;
+
+        let mut artifacts: Vec<(String, &Path)> = results
+            .iter()
+            .filter(|result| result.success)
+            .flat_map(|result| &result.artifacts)
+            .map(|artifact| {
+                let relative = artifact
+                    .path
+                    .strip_prefix(&self.dist_dir)
+                    .unwrap_or(&artifact.path);
+                let parts: Vec<_> = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                    .collect();
+                (parts.join("/"), artifact.path.as_path())
+            })
+            .collect();
+        if artifacts.is_empty() {
+            return Ok(());
+        }
+        artifacts.sort();
+        artifacts.dedup();
+
+        let filesystem = self.session.filesystem();
+        let mut checksums = String::new();
+        for (name, path) in artifacts {
+            let bytes = filesystem
+                .read_bytes(path)
+                .map_err(|e| BuildError::ConfigError {
+                    reason: format!("Failed to read {} for checksums: {e}", path.display()),
+                })?;
+            let digest = crate::empack::content::hex::encode(Sha256::digest(&bytes));
+            checksums.push_str(&format!("{digest}  {name}\n"));
+        }
+
+        filesystem
+            .write_file(&self.dist_dir.join(CHECKSUMS_FILE), &checksums)
+            .map_err(|e| BuildError::ConfigError {
+                reason: format!("Failed to write {CHECKSUMS_FILE}: {e}"),
+            })
+    }
+
     /// Load pack info from pack.toml
     fn load_pack_info(&mut self) -> Result<&PackInfo, BuildError> {
         if self.pack_info.is_none() {
@@ -410,7 +952,12 @@ impl<'a> BuildOrchestrator<'a> {
             })?;
 
         let jar_path = dist_dir.join("srv.jar");
-        self.download_file(&version_meta.downloads.server.url, &jar_path)?;
+        let expected = ContentHash::Sha1(version_meta.downloads.server.sha1.to_ascii_lowercase());
+        self.download_file(
+            &version_meta.downloads.server.url,
+            &jar_path,
+            Some(&expected),
+        )?;
 
         self.verify_server_jar_sha1(&jar_path, &version_meta.downloads.server.sha1)?;
 
@@ -481,7 +1028,7 @@ impl<'a> BuildOrchestrator<'a> {
             f = installer_filename
         );
         let installer_path = dist_dir.join(&installer_filename);
-        self.download_file(&installer_url, &installer_path)?;
+        self.download_maven_artifact(&installer_url, &installer_path)?;
 
         let installer_path_str = installer_path.to_string_lossy().to_string();
         let dist_dir_str = dist_dir.to_string_lossy().to_string();
@@ -565,7 +1112,7 @@ impl<'a> BuildOrchestrator<'a> {
             f = installer_filename
         );
         let installer_path = dist_dir.join(&installer_filename);
-        self.download_file(&installer_url, &installer_path)?;
+        self.download_maven_artifact(&installer_url, &installer_path)?;
 
         let install_dir_flag = format!("--install-dir={}", dist_dir.to_string_lossy());
         let installer_path_str = installer_path.to_string_lossy().to_string();
@@ -633,7 +1180,7 @@ impl<'a> BuildOrchestrator<'a> {
         let (url, installer_filename) = neoforge_installer_artifact(mc, version)?;
 
         let installer_path = dist_dir.join(&installer_filename);
-        self.download_file(&url, &installer_path)?;
+        self.download_maven_artifact(&url, &installer_path)?;
 
         let output = self
             .session
@@ -692,7 +1239,7 @@ impl<'a> BuildOrchestrator<'a> {
         let installer_filename = format!("forge-{}-installer.jar", composite);
         let installer_path = dist_dir.join(&installer_filename);
 
-        self.download_file(&url, &installer_path)?;
+        self.download_maven_artifact(&url, &installer_path)?;
 
         let output = self
             .session
@@ -754,6 +1301,7 @@ impl<'a> BuildOrchestrator<'a> {
         self.download_file(
             "https://github.com/neoforged/ServerStarterJar/releases/latest/download/server.jar",
             &srv_jar,
+            None,
         )?;
 
         if !self.session.filesystem().exists(&srv_jar) {
@@ -765,15 +1313,21 @@ impl<'a> BuildOrchestrator<'a> {
         Ok(())
     }
 
-    /// Download a file from `url` and write it to `dest` using reqwest.
+    /// Download a file from `url` through the content store and write it to
+    /// `dest`.
     ///
     /// Returns immediately when `dest` already exists, enabling callers to
     /// pre-populate the path (e.g. in tests) and skip the network round-trip.
-    fn download_file(&self, url: &str, dest: &Path) -> Result<(), BuildError> {
+    fn download_file(
+        &self,
+        url: &str,
+        dest: &Path,
+        expected: Option<&ContentHash>,
+    ) -> Result<(), BuildError> {
         if self.session.filesystem().exists(dest) {
             return Ok(());
         }
-        let bytes = self.fetch_url_bytes(url)?;
+        let bytes = self.fetch_stored(url, expected)?;
         self.session
             .filesystem()
             .write_bytes(dest, &bytes)
@@ -786,6 +1340,73 @@ impl<'a> BuildOrchestrator<'a> {
             })
     }
 
+    /// Download a Maven artifact, verified against the `.sha1` file the
+    /// repository publishes beside it.
+    ///
+    /// Without a readable `.sha1` the artifact is still downloaded, unverified.
+    fn download_maven_artifact(&self, url: &str, dest: &Path) -> Result<(), BuildError> {
+        if self.session.filesystem().exists(dest) {
+            return Ok(());
+        }
+        let expected = match self.fetch_url_text(&format!("{url}.sha1")) {
+            Ok(text) => text
+                .split_whitespace()
+                .next()
+                .filter(|sha1| sha1.len() == 40 && sha1.chars().all(|c| c.is_ascii_hexdigit()))
+                .and_then(|sha1| ContentHash::parse("sha1", sha1)),
+            Err(e) => {
+                tracing::debug!(url, error = %e, "no Maven checksum; downloading unverified");
+                None
+            }
+        };
+        self.download_file(url, dest, expected.as_ref())
+    }
+
+    /// Fetch `url` from the content store, downloading it only on a miss.
+    ///
+    /// With an expected hash, the store is checked first and a download is
+    /// admitted only if it matches. Without one, online fetches always
+    /// download and admit the bytes under their own hash and the URL, and
+    /// offline fetches serve the last download from that URL. Either way the
+    /// bytes are kept only in the store and the project records a reference
+    /// to the object.
+    fn fetch_stored(
+        &self,
+        url: &str,
+        expected: Option<&ContentHash>,
+    ) -> Result<Vec<u8>, BuildError> {
+        let store = ContentStore::open(self.session.filesystem())?;
+        let hit = match expected {
+            Some(hash) => store.resolve(hash).zip(store.get(hash)),
+            None if self.session.network().is_offline() => store.get_url(url),
+            None => None,
+        };
+        if let Some((object, bytes)) = hit {
+            if let Err(e) = store.record_reference(&self.workdir, &object) {
+                tracing::warn!(url, error = %e, "failed to record content store reference");
+            }
+            return Ok(bytes);
+        }
+        if self.session.network().is_offline() {
+            return Err(crate::networking::NetworkingError::Offline {
+                resource: url.to_string(),
+            }
+            .into());
+        }
+
+        let bytes = self.fetch_url_bytes_from_network(url)?;
+        match expected {
+            Some(hash) => store.admit_for_project(&self.workdir, &bytes, Some(hash), url)?,
+            None => store.admit_url_for_project(&self.workdir, url, &bytes)?,
+        };
+        // The store holds the bytes now; the cached copy is redundant.
+        let _ = self
+            .session
+            .filesystem()
+            .remove_file(&download_cache_path(url)?);
+        Ok(bytes)
+    }
+
     /// Fetch URL content as a String.
     fn fetch_url_text(&self, url: &str) -> Result<String, BuildError> {
         let bytes = self.fetch_url_bytes(url)?;
@@ -794,13 +1415,32 @@ impl<'a> BuildOrchestrator<'a> {
         })
     }
 
-    /// Fetch raw bytes from a URL using the session HTTP client.
+    /// Fetch raw bytes from a URL through the download cache.
     ///
-    /// Uses the shared `reqwest::Client` from the session and the existing
-    /// tokio multi-thread runtime via `block_in_place` + `Handle::current()`.
-    /// Retries up to 3 times on transient failures (connection errors, timeouts)
-    /// with exponential backoff (1s, 2s, 4s).
+    /// Online, every fetch goes to the network and refreshes the cached copy.
+    /// Offline, only the cached copy is served and a miss fails immediately.
     fn fetch_url_bytes(&self, url: &str) -> Result<Vec<u8>, BuildError> {
+        if self.session.network().is_offline() {
+            return self
+                .session
+                .filesystem()
+                .read_bytes(&download_cache_path(url)?)
+                .map_err(|_| {
+                    crate::networking::NetworkingError::Offline {
+                        resource: url.to_string(),
+                    }
+                    .into()
+                });
+        }
+        self.fetch_url_bytes_from_network(url)
+    }
+
+    /// Download `url` into the download cache and return its bytes.
+    ///
+    /// Runs the shared download engine on the existing tokio multi-thread
+    /// runtime via `block_in_place` + `Handle::current()`, so an interrupted
+    /// transfer resumes from its `.part` file instead of starting over.
+    fn fetch_url_bytes_from_network(&self, url: &str) -> Result<Vec<u8>, BuildError> {
         if let Ok(handle) = tokio::runtime::Handle::try_current()
             && handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::CurrentThread
         {
@@ -809,85 +1449,25 @@ impl<'a> BuildOrchestrator<'a> {
             });
         }
 
-        let client = self
-            .session
-            .network()
-            .http_client()
-            .map_err(|e| BuildError::ConfigError {
+        let engine =
+            DownloadEngine::for_session(self.session).map_err(|e| BuildError::ConfigError {
                 reason: format!("HTTP client unavailable: {}", e),
             })?;
-
-        let url_owned = url.to_string();
-        let timeout = self.session.config().app_config().net_timeout;
-        let max_attempts: u32 = 3;
-
+        let dest = download_cache_path(url)?;
+        let progress = self.session.display().progress();
         tokio::task::block_in_place(|| {
-            tokio::runtime::Handle::current().block_on(async {
-                let mut last_error = None;
-
-                for attempt in 0..max_attempts {
-                    if attempt > 0 {
-                        let backoff = std::time::Duration::from_secs(1 << (attempt - 1));
-                        tokio::time::sleep(backoff).await;
-                    }
-
-                    match client
-                        .get(&url_owned)
-                        .timeout(std::time::Duration::from_secs(timeout))
-                        .send()
-                        .await
-                    {
-                        Ok(resp) => {
-                            if resp.status().is_client_error() {
-                                return Err(BuildError::CommandFailed {
-                                    command: format!(
-                                        "HTTP GET {} returned status {}",
-                                        url_owned,
-                                        resp.status()
-                                    ),
-                                });
-                            }
-
-                            if !resp.status().is_success() {
-                                last_error = Some(format!(
-                                    "HTTP GET {} returned status {}",
-                                    url_owned,
-                                    resp.status()
-                                ));
-                                continue;
-                            }
-
-                            match resp.bytes().await {
-                                Ok(b) => return Ok(b.to_vec()),
-                                Err(e) => {
-                                    last_error = Some(format!(
-                                        "failed to read response body from {}: {}",
-                                        url_owned, e
-                                    ));
-                                    continue;
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            last_error = Some(format!("HTTP GET {} failed: {}", url_owned, e));
-                            continue;
-                        }
-                    }
-                }
+            tokio::runtime::Handle::current()
+                .block_on(engine.fetch(&Download::new(url, &dest), progress.as_ref()))
+        })?;
 
-                Err(BuildError::CommandFailed {
-                    command: last_error.unwrap_or_else(|| {
-                        format!(
-                            "HTTP GET {} failed after {} attempts",
-                            url_owned, max_attempts
-                        )
-                    }),
-                })
+        self.session
+            .filesystem()
+            .read_bytes(&dest)
+            .map_err(|e| BuildError::IoError {
+                source: std::io::Error::other(format!("{}: {}", dest.display(), e)),
             })
-        })
     }
 
-    #[cfg(test)]
     fn create_build_registry() -> HashMap<BuildTarget, BuildConfig> {
         let mut registry = HashMap::new();
 
@@ -941,6 +1521,26 @@ impl<'a> BuildOrchestrator<'a> {
             },
         );
 
+        registry.insert(
+            BuildTarget::Curseforge,
+            BuildConfig {
+                target: BuildTarget::Curseforge,
+                handler: "build_curseforge_impl".to_string(),
+                dependencies: vec![],
+                output_dir: PathBuf::new(),
+            },
+        );
+
+        registry.insert(
+            BuildTarget::Prism,
+            BuildConfig {
+                target: BuildTarget::Prism,
+                handler: "build_prism_impl".to_string(),
+                dependencies: vec![BuildTarget::Mrpack],
+                output_dir: PathBuf::new(),
+            },
+        );
+
         registry
     }
 
@@ -1056,7 +1656,7 @@ impl<'a> BuildOrchestrator<'a> {
 
         self.session
             .archive()
-            .create_archive(&dist_dir, &archive_path, format)
+            .create_archive(&dist_dir, &archive_path, format, self.archive_epoch())
             .map_err(|e| BuildError::CommandFailed {
                 command: format!("create distribution archive: {}", e),
             })?;
@@ -1120,6 +1720,8 @@ impl<'a> BuildOrchestrator<'a> {
                     artifacts: vec![],
                     warnings: vec![],
                     restricted_mods,
+                    excluded: vec![],
+                    up_to_date: false,
                 });
             }
             let warning = if combined_output.contains("manual download")
@@ -1140,9 +1742,19 @@ impl<'a> BuildOrchestrator<'a> {
                 artifacts: vec![],
                 warnings: vec![warning],
                 restricted_mods: vec![],
+                excluded: vec![],
+                up_to_date: false,
             });
         }
 
+        // packwiz writes entries in map order with the current time
+        self.session
+            .archive()
+            .normalize_zip(&output_file, self.archive_epoch())
+            .map_err(|e| BuildError::CommandFailed {
+                command: format!("normalize mrpack: {}", e),
+            })?;
+
         let artifact = self.create_artifact(&output_file)?;
 
         Ok(BuildResult {
@@ -1152,62 +1764,320 @@ impl<'a> BuildOrchestrator<'a> {
             artifacts: vec![artifact],
             warnings: vec![],
             restricted_mods: vec![],
+            excluded: vec![],
+            up_to_date: false,
         })
     }
 
-    /// Build client implementation
-    fn build_client_impl(&mut self, bootstrap_jar_path: &Path) -> Result<BuildResult, BuildError> {
-        self.clean_target(BuildTarget::Client)?;
+    /// Build CurseForge modpack zip implementation
+    fn build_curseforge_impl(&mut self) -> Result<BuildResult, BuildError> {
+        self.build_curseforge_with_api_base(ProjectPlatform::Modrinth.api_base_url())
+    }
 
+    /// Write `manifest.json` and `overrides/` from the refreshed packwiz index.
+    ///
+    /// CurseForge metafiles become `files` entries. Modrinth-only files are
+    /// copied into overrides when their license allows redistribution and are
+    /// otherwise reported as warnings.
+    fn build_curseforge_with_api_base(
+        &mut self,
+        modrinth_api_base: &str,
+    ) -> Result<BuildResult, BuildError> {
+        self.clean_target(BuildTarget::Curseforge)?;
         self.refresh_pack()?;
 
-        let dist_dir = self.dist_dir.join("client");
-        self.session
-            .filesystem()
-            .create_dir_all(&dist_dir)
-            .map_err(|e| BuildError::ConfigError {
-                reason: e.to_string(),
-            })?;
-
-        self.process_build_templates("templates/common", &dist_dir)?;
-        self.process_build_templates("templates/client", &dist_dir)?;
+        let pack_info = self.load_pack_info()?.clone();
+        let pack_dir = self.workdir.join("pack");
+        let dist_dir = self.dist_dir.join(BuildTarget::Curseforge.to_string());
+        let overrides_dir = dist_dir.join("overrides");
 
-        let minecraft_dir = dist_dir.join(".minecraft");
         self.session
             .filesystem()
-            .create_dir_all(&minecraft_dir)
+            .create_dir_all(&overrides_dir)
             .map_err(|e| BuildError::ConfigError {
                 reason: e.to_string(),
             })?;
 
-        // Copy packwiz installer
-        let bootstrap_content = self
+        let index_content = self
             .session
             .filesystem()
-            .read_bytes(bootstrap_jar_path)
+            .read_to_string(&pack_dir.join("index.toml"))
             .map_err(|e| BuildError::ConfigError {
-                reason: e.to_string(),
+                reason: format!("Failed to read index.toml: {}", e),
             })?;
-        self.session
-            .filesystem()
-            .write_bytes(
-                &minecraft_dir.join("packwiz-installer-bootstrap.jar"),
-                &bootstrap_content,
-            )
+        let index: toml::Value =
+            toml::from_str(&index_content).map_err(|e| BuildError::ConfigError {
+                reason: format!("Failed to parse index.toml: {}", e),
+            })?;
+        let entries = index
+            .get("files")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        let mut files = Vec::new();
+        let mut warnings = Vec::new();
+
+        for entry in &entries {
+            let Some(file) = entry.get("file").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let is_metafile = entry
+                .get("metafile")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+
+            if is_metafile {
+                self.add_curseforge_metafile(
+                    &pack_dir,
+                    file,
+                    &overrides_dir,
+                    modrinth_api_base,
+                    &mut files,
+                    &mut warnings,
+                )?;
+            } else {
+                self.copy_override_file(&pack_dir.join(file), &overrides_dir.join(file))?;
+            }
+        }
+
+        let mod_loaders = if pack_info.loader_type == "vanilla" {
+            vec![]
+        } else {
+            vec![CurseForgeModLoader {
+                id: format!("{}-{}", pack_info.loader_type, pack_info.loader_version),
+                primary: true,
+            }]
+        };
+
+        let manifest = CurseForgeManifest {
+            minecraft: CurseForgeMinecraft {
+                version: pack_info.mc_version.clone(),
+                mod_loaders,
+            },
+            manifest_type: "minecraftModpack",
+            manifest_version: 1,
+            name: pack_info.name.clone(),
+            version: pack_info.version.clone(),
+            author: pack_info.author.clone(),
+            files,
+            overrides: "overrides",
+        };
+        let manifest_json =
+            serde_json::to_string_pretty(&manifest).map_err(|e| BuildError::ConfigError {
+                reason: format!("Failed to serialize manifest.json: {}", e),
+            })?;
+        self.session
+            .filesystem()
+            .write_file(&dist_dir.join("manifest.json"), &manifest_json)
             .map_err(|e| BuildError::ConfigError {
                 reason: e.to_string(),
             })?;
 
-        let pack_dir = self.workdir.join("pack");
-        self.copy_dir_contents(&pack_dir, &minecraft_dir.join("pack"))?;
+        let zip_path = self.zip_distribution(BuildTarget::Curseforge)?;
+        let artifact = self.create_artifact(&zip_path)?;
 
-        self.extract_mrpack()?;
-        let temp_extract_dir = self.dist_dir.join("temp-mrpack-extract");
-        let overrides_dir = temp_extract_dir.join("overrides");
-        if self.session.filesystem().exists(&overrides_dir) {
-            self.copy_dir_contents(&overrides_dir, &minecraft_dir)?;
+        Ok(BuildResult {
+            target: BuildTarget::Curseforge,
+            success: true,
+            output_path: Some(zip_path),
+            artifacts: vec![artifact],
+            warnings,
+            restricted_mods: vec![],
+            excluded: vec![],
+            up_to_date: false,
+        })
+    }
+
+    /// Add one packwiz metafile to a CurseForge build.
+    fn add_curseforge_metafile(
+        &self,
+        pack_dir: &Path,
+        file: &str,
+        overrides_dir: &Path,
+        modrinth_api_base: &str,
+        files: &mut Vec<CurseForgeFile>,
+        warnings: &mut Vec<String>,
+    ) -> Result<(), BuildError> {
+        let content = self
+            .session
+            .filesystem()
+            .read_to_string(&pack_dir.join(file))
+            .map_err(|e| BuildError::ConfigError {
+                reason: format!("Failed to read {}: {}", file, e),
+            })?;
+        let meta: toml::Value = toml::from_str(&content).map_err(|e| BuildError::ConfigError {
+            reason: format!("Failed to parse {}: {}", file, e),
+        })?;
+
+        let name = meta
+            .get("name")
+            .and_then(|v| v.as_str())
+            .unwrap_or(file)
+            .to_string();
+        let update = meta.get("update");
+
+        let curseforge = update.and_then(|u| u.get("curseforge"));
+        let project_id = curseforge
+            .and_then(|c| c.get("project-id"))
+            .and_then(|v| v.as_integer());
+        let file_id = curseforge
+            .and_then(|c| c.get("file-id"))
+            .and_then(|v| v.as_integer());
+        if let (Some(project_id), Some(file_id)) = (project_id, file_id) {
+            files.push(CurseForgeFile {
+                project_id: project_id as u64,
+                file_id: file_id as u64,
+                required: true,
+            });
+            return Ok(());
         }
 
+        let Some(mod_id) = update
+            .and_then(|u| u.get("modrinth"))
+            .and_then(|m| m.get("mod-id"))
+            .and_then(|v| v.as_str())
+        else {
+            warnings.push(format!(
+                "'{}' has no CurseForge or Modrinth source and was left out of the CurseForge pack",
+                name
+            ));
+            return Ok(());
+        };
+
+        let license_url = format!(
+            "{}/v2/project/{}",
+            modrinth_api_base.trim_end_matches('/'),
+            mod_id
+        );
+        let license = match self.fetch_url_text(&license_url).and_then(|text| {
+            serde_json::from_str::<ModrinthProjectLicense>(&text).map_err(|e| {
+                BuildError::ConfigError {
+                    reason: format!("invalid Modrinth project response: {}", e),
+                }
+            })
+        }) {
+            Ok(project) => project.license.id,
+            Err(e) => {
+                warnings.push(format!(
+                    "could not check the license of Modrinth-only '{}' ({}); it was left out of the CurseForge pack",
+                    name, e
+                ));
+                return Ok(());
+            }
+        };
+
+        if !license_allows_redistribution(&license) {
+            warnings.push(format!(
+                "'{}' is only on Modrinth and its license ({}) does not allow redistribution; it was left out of the CurseForge pack",
+                name, license
+            ));
+            return Ok(());
+        }
+
+        let filename = meta.get("filename").and_then(|v| v.as_str());
+        let download = meta.get("download");
+        let url = download.and_then(|d| d.get("url")).and_then(|v| v.as_str());
+        let (Some(filename), Some(url)) = (filename, url) else {
+            warnings.push(format!(
+                "'{}' has no download URL and was left out of the CurseForge pack",
+                name
+            ));
+            return Ok(());
+        };
+
+        let hash_format = download
+            .and_then(|d| d.get("hash-format"))
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        let expected_hash = download
+            .and_then(|d| d.get("hash"))
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        let bytes = match self
+            .fetch_stored(url, ContentHash::parse(hash_format, expected_hash).as_ref())
+        {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warnings.push(format!(
+                    "failed to download Modrinth-only '{}': {}; it was left out of the CurseForge pack",
+                    name, e
+                ));
+                return Ok(());
+            }
+        };
+
+        if !download_hash_matches(&bytes, hash_format, expected_hash) {
+            warnings.push(format!(
+                "downloaded file for '{}' does not match its {} hash; it was left out of the CurseForge pack",
+                name, hash_format
+            ));
+            return Ok(());
+        }
+
+        let folder = Path::new(file).parent().unwrap_or(Path::new(""));
+        let dest = overrides_dir.join(folder).join(filename);
+        self.session
+            .filesystem()
+            .create_dir_all(&overrides_dir.join(folder))
+            .map_err(|e| BuildError::ConfigError {
+                reason: e.to_string(),
+            })?;
+        self.session
+            .filesystem()
+            .write_bytes(&dest, &bytes)
+            .map_err(|e| BuildError::ConfigError {
+                reason: e.to_string(),
+            })
+    }
+
+    /// Copy a non-metafile pack entry into the overrides tree.
+    fn copy_override_file(&self, src: &Path, dst: &Path) -> Result<(), BuildError> {
+        let bytes =
+            self.session
+                .filesystem()
+                .read_bytes(src)
+                .map_err(|e| BuildError::ConfigError {
+                    reason: format!("Failed to read {}: {}", src.display(), e),
+                })?;
+        if let Some(parent) = dst.parent() {
+            self.session
+                .filesystem()
+                .create_dir_all(parent)
+                .map_err(|e| BuildError::ConfigError {
+                    reason: e.to_string(),
+                })?;
+        }
+        self.session
+            .filesystem()
+            .write_bytes(dst, &bytes)
+            .map_err(|e| BuildError::ConfigError {
+                reason: e.to_string(),
+            })
+    }
+
+    /// Build client implementation
+    fn build_client_impl(&mut self, bootstrap_jar_path: &Path) -> Result<BuildResult, BuildError> {
+        self.clean_target(BuildTarget::Client)?;
+
+        self.refresh_pack()?;
+
+        let dist_dir = self.dist_dir.join("client");
+        self.session
+            .filesystem()
+            .create_dir_all(&dist_dir)
+            .map_err(|e| BuildError::ConfigError {
+                reason: e.to_string(),
+            })?;
+
+        self.process_build_templates("templates/common", &dist_dir)?;
+        self.process_build_templates("templates/client", &dist_dir)?;
+
+        let excluded = self.populate_bootstrapped_minecraft_dir(
+            &dist_dir.join(".minecraft"),
+            bootstrap_jar_path,
+        )?;
+
         let zip_path = self.zip_distribution(BuildTarget::Client)?;
         let artifact = self.create_artifact(&zip_path)?;
 
@@ -1218,9 +2088,240 @@ impl<'a> BuildOrchestrator<'a> {
             artifacts: vec![artifact],
             warnings: vec![],
             restricted_mods: vec![],
+            excluded,
+            up_to_date: false,
+        })
+    }
+
+    /// Build Prism Launcher instance implementation
+    fn build_prism_impl(&mut self, bootstrap_jar_path: &Path) -> Result<BuildResult, BuildError> {
+        self.clean_target(BuildTarget::Prism)?;
+
+        self.refresh_pack()?;
+
+        let pack_info = self.load_pack_info()?.clone();
+        let dist_dir = self.dist_dir.join(BuildTarget::Prism.to_string());
+        self.session
+            .filesystem()
+            .create_dir_all(&dist_dir)
+            .map_err(|e| BuildError::ConfigError {
+                reason: e.to_string(),
+            })?;
+
+        self.process_build_templates("templates/common", &dist_dir)?;
+        self.process_build_templates("templates/client", &dist_dir)?;
+
+        let instance_cfg_path = dist_dir.join("instance.cfg");
+        let instance_cfg = if self.session.filesystem().exists(&instance_cfg_path) {
+            self.session
+                .filesystem()
+                .read_to_string(&instance_cfg_path)
+                .map_err(|e| BuildError::ConfigError {
+                    reason: e.to_string(),
+                })?
+        } else {
+            self.get_or_init_template_engine()?
+                .render_template("instance.cfg")
+                .map_err(|e| BuildError::ConfigError {
+                    reason: format!("Template rendering failed for instance.cfg: {}", e),
+                })?
+        };
+        self.session
+            .filesystem()
+            .write_file(&instance_cfg_path, &ensure_prism_prelaunch(&instance_cfg))
+            .map_err(|e| BuildError::ConfigError {
+                reason: e.to_string(),
+            })?;
+
+        let mmc_pack = PrismPack {
+            components: prism_components(&pack_info),
+            format_version: 1,
+        };
+        let mmc_pack_json =
+            serde_json::to_string_pretty(&mmc_pack).map_err(|e| BuildError::ConfigError {
+                reason: format!("Failed to serialize mmc-pack.json: {}", e),
+            })?;
+        self.session
+            .filesystem()
+            .write_file(&dist_dir.join("mmc-pack.json"), &mmc_pack_json)
+            .map_err(|e| BuildError::ConfigError {
+                reason: e.to_string(),
+            })?;
+
+        let excluded = self.populate_bootstrapped_minecraft_dir(
+            &dist_dir.join(".minecraft"),
+            bootstrap_jar_path,
+        )?;
+
+        let zip_path = self.zip_distribution(BuildTarget::Prism)?;
+        let artifact = self.create_artifact(&zip_path)?;
+
+        Ok(BuildResult {
+            target: BuildTarget::Prism,
+            success: true,
+            output_path: Some(zip_path),
+            artifacts: vec![artifact],
+            warnings: vec![],
+            restricted_mods: vec![],
+            excluded,
+            up_to_date: false,
         })
     }
 
+    /// Fill a client game directory with the bootstrap jar, the packwiz pack,
+    /// and the mrpack overrides. Returns the server-only content left out.
+    fn populate_bootstrapped_minecraft_dir(
+        &mut self,
+        minecraft_dir: &Path,
+        bootstrap_jar_path: &Path,
+    ) -> Result<Vec<String>, BuildError> {
+        self.session
+            .filesystem()
+            .create_dir_all(minecraft_dir)
+            .map_err(|e| BuildError::ConfigError {
+                reason: e.to_string(),
+            })?;
+
+        // Copy packwiz installer
+        let bootstrap_content = self
+            .session
+            .filesystem()
+            .read_bytes(bootstrap_jar_path)
+            .map_err(|e| BuildError::ConfigError {
+                reason: e.to_string(),
+            })?;
+        self.session
+            .filesystem()
+            .write_bytes(
+                &minecraft_dir.join("packwiz-installer-bootstrap.jar"),
+                &bootstrap_content,
+            )
+            .map_err(|e| BuildError::ConfigError {
+                reason: e.to_string(),
+            })?;
+
+        let pack_dir = self.workdir.join("pack");
+        self.copy_dir_contents(&pack_dir, &minecraft_dir.join("pack"))?;
+        let excluded =
+            self.exclude_other_side_content(&minecraft_dir.join("pack"), DependencySide::Client)?;
+
+        self.extract_mrpack()?;
+        let temp_extract_dir = self.dist_dir.join("temp-mrpack-extract");
+        let overrides_dir = temp_extract_dir.join("overrides");
+        if self.session.filesystem().exists(&overrides_dir) {
+            self.copy_dir_contents(&overrides_dir, minecraft_dir)?;
+        }
+
+        Ok(excluded)
+    }
+
+    /// Remove content that only installs on the other side from a copied
+    /// pack directory, then refresh the copy so its index stays consistent.
+    ///
+    /// A dependency's side comes from its `empack.yml` entry, falling back to
+    /// the `side` key packwiz writes into `.pw.toml` files.
+    fn exclude_other_side_content(
+        &self,
+        pack_copy: &Path,
+        target: DependencySide,
+    ) -> Result<Vec<String>, BuildError> {
+        let filesystem = self.session.filesystem();
+        let index_path = pack_copy.join("index.toml");
+        // The copy is written as bytes, so read it back the same way
+        let read_text = |path: &Path| {
+            filesystem
+                .read_bytes(path)
+                .ok()
+                .and_then(|bytes| String::from_utf8(bytes).ok())
+        };
+        let Some(index_content) = read_text(&index_path) else {
+            return Ok(Vec::new());
+        };
+        let index: toml::Value =
+            toml::from_str(&index_content).map_err(|e| BuildError::PackInfoError {
+                reason: format!("index.toml parse error: {}", e),
+            })?;
+
+        let dependencies = filesystem
+            .config_manager(self.workdir.clone())
+            .load_empack_config()
+            .map(|config| config.empack.dependencies)
+            .unwrap_or_default();
+
+        let files = index
+            .get("files")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+        let mut excluded = Vec::new();
+        for entry in files {
+            let Some(file) = entry.get("file").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let is_metafile = entry
+                .get("metafile")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let file_path = pack_copy.join(file);
+
+            let (name, side) = if is_metafile {
+                let key = Path::new(file)
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or(file)
+                    .trim_end_matches(".pw.toml")
+                    .to_string();
+                let side = match dependencies.get(&key) {
+                    Some(DependencyEntry::Resolved(record)) => record.side,
+                    _ => None,
+                }
+                .or_else(|| {
+                    let metafile: toml::Value = toml::from_str(&read_text(&file_path)?).ok()?;
+                    DependencySide::parse(metafile.get("side")?.as_str()?)
+                });
+                (key, side)
+            } else {
+                let tracked_path = format!("pack/{file}");
+                let side = dependencies.values().find_map(|dep| match dep {
+                    DependencyEntry::Local(record) if record.path == tracked_path => record.side,
+                    _ => None,
+                });
+                (file.to_string(), side)
+            };
+
+            if let Some(side) = side
+                && !side.installs_on(target)
+            {
+                filesystem
+                    .remove_file(&file_path)
+                    .map_err(|e| BuildError::ConfigError {
+                        reason: e.to_string(),
+                    })?;
+                excluded.push(name);
+            }
+        }
+
+        if !excluded.is_empty() {
+            let pack_file = pack_copy.join("pack.toml");
+            let output = execute_process_with_live_issues(
+                self.session,
+                self.session.packwiz_bin(),
+                &["--pack-file", &pack_file.to_string_lossy(), "refresh"],
+                &self.workdir,
+            )
+            .map_err(|e| BuildError::CommandFailed {
+                command: format!("packwiz refresh: {}", e),
+            })?;
+            if !output.success {
+                return Err(BuildError::CommandFailed {
+                    command: format!("packwiz refresh: {}", output.error_output()),
+                });
+            }
+        }
+
+        Ok(excluded)
+    }
+
     /// Build server implementation
     fn build_server_impl(&mut self, bootstrap_jar_path: &Path) -> Result<BuildResult, BuildError> {
         self.clean_target(BuildTarget::Server)?;
@@ -1239,6 +2340,8 @@ impl<'a> BuildOrchestrator<'a> {
 
         let pack_dir = self.workdir.join("pack");
         self.copy_dir_contents(&pack_dir, &dist_dir.join("pack"))?;
+        let excluded =
+            self.exclude_other_side_content(&dist_dir.join("pack"), DependencySide::Server)?;
 
         let bootstrap_content = self
             .session
@@ -1266,6 +2369,8 @@ impl<'a> BuildOrchestrator<'a> {
                 artifacts: vec![],
                 warnings: vec![format!("failed to download server JAR: {}", e)],
                 restricted_mods: vec![],
+                excluded: vec![],
+                up_to_date: false,
             });
         }
 
@@ -1288,6 +2393,8 @@ impl<'a> BuildOrchestrator<'a> {
             artifacts: vec![artifact],
             warnings: vec![],
             restricted_mods: vec![],
+            excluded,
+            up_to_date: false,
         })
     }
 
@@ -1317,6 +2424,8 @@ impl<'a> BuildOrchestrator<'a> {
         // Copy pack files so the installer can resolve .toml mod entries
         let pack_dir = self.workdir.join("pack");
         self.copy_dir_contents(&pack_dir, &dist_dir.join("pack"))?;
+        let excluded =
+            self.exclude_other_side_content(&dist_dir.join("pack"), DependencySide::Client)?;
 
         let installer = PackwizInstaller::new(
             self.session,
@@ -1338,6 +2447,8 @@ impl<'a> BuildOrchestrator<'a> {
                     artifacts: vec![],
                     warnings: vec![],
                     restricted_mods: restricted,
+                    excluded: vec![],
+                    up_to_date: false,
                 });
             }
         }
@@ -1352,6 +2463,8 @@ impl<'a> BuildOrchestrator<'a> {
             artifacts: vec![artifact],
             warnings: vec![],
             restricted_mods: vec![],
+            excluded,
+            up_to_date: false,
         })
     }
 
@@ -1386,12 +2499,16 @@ impl<'a> BuildOrchestrator<'a> {
                 artifacts: vec![],
                 warnings: vec![format!("failed to download server JAR: {}", e)],
                 restricted_mods: vec![],
+                excluded: vec![],
+                up_to_date: false,
             });
         }
 
         // Copy pack files so the installer can resolve .toml mod entries
         let pack_dir = self.workdir.join("pack");
         self.copy_dir_contents(&pack_dir, &dist_dir.join("pack"))?;
+        let excluded =
+            self.exclude_other_side_content(&dist_dir.join("pack"), DependencySide::Server)?;
 
         let installer = PackwizInstaller::new(
             self.session,
@@ -1413,6 +2530,8 @@ impl<'a> BuildOrchestrator<'a> {
                     artifacts: vec![],
                     warnings: vec![],
                     restricted_mods: restricted,
+                    excluded: vec![],
+                    up_to_date: false,
                 });
             }
         }
@@ -1427,10 +2546,12 @@ impl<'a> BuildOrchestrator<'a> {
             artifacts: vec![artifact],
             warnings: vec![],
             restricted_mods: vec![],
+            excluded,
+            up_to_date: false,
         })
     }
 
-    /// Execute the 5-target build pipeline with state management.
+    /// Execute the build pipeline with state management.
     /// Uses an RAII guard so the state marker is removed on both success and
     /// failure (including panics) without manual cleanup.
     pub async fn execute_build_pipeline(
@@ -1495,20 +2616,42 @@ impl<'a> BuildOrchestrator<'a> {
                     reason: format!("Failed to get installer JAR path: {}", e),
                 })?;
 
-        let mut results = Vec::new();
+        // Archive timestamps are an input of every target's fingerprint
+        self.source_date_epoch = Some(self.resolve_source_date_epoch());
 
+        let mut finished: HashMap<BuildTarget, Result<BuildResult, BuildError>> = HashMap::new();
+        let mut pending = Vec::new();
         for target in targets {
-            let result = match target {
-                BuildTarget::Mrpack => self.build_mrpack_impl()?,
-                BuildTarget::Client => self.build_client_impl(&bootstrap_jar_path)?,
-                BuildTarget::Server => self.build_server_impl(&bootstrap_jar_path)?,
-                BuildTarget::ClientFull => {
-                    self.build_client_full_impl(&bootstrap_jar_path, &installer_jar_path)?
-                }
-                BuildTarget::ServerFull => {
-                    self.build_server_full_impl(&bootstrap_jar_path, &installer_jar_path)?
-                }
+            if !self.force_rebuild
+                && let Some(result) = self.up_to_date_result(*target)
+            {
+                finished.insert(*target, Ok(result));
+                continue;
+            }
+            pending.push(*target);
+        }
+
+        let jobs = self.session.config().app_config().cpu_jobs.max(1);
+        for wave in plan_build_waves(&pending) {
+            self.prepare_wave(&wave)?;
+            let failed = self.build_wave(
+                &wave,
+                jobs,
+                &bootstrap_jar_path,
+                &installer_jar_path,
+                &mut finished,
+            );
+            if failed {
+                break;
+            }
+        }
+
+        let mut results = Vec::new();
+        for target in targets {
+            let Some(result) = finished.remove(target) else {
+                continue;
             };
+            let result = result?;
 
             if !result.success && !result.restricted_mods.is_empty() {
                 results.push(result);
@@ -1530,9 +2673,168 @@ impl<'a> BuildOrchestrator<'a> {
             results.push(result);
         }
 
+        self.write_sboms(&mut results)?;
+        self.sign_artifacts(&results)?;
+        self.write_checksums(&results)?;
         Ok(results)
     }
 
+    /// Run the work every target in `wave` shares, once, before any of them
+    /// starts: the packwiz refresh, pack metadata, and the extracted mrpack
+    /// for targets that depend on it.
+    fn prepare_wave(&mut self, wave: &[BuildTarget]) -> Result<(), BuildError> {
+        self.refresh_pack()?;
+        self.load_pack_info()?;
+
+        let registry = Self::create_build_registry();
+        let needs_mrpack = wave.iter().any(|target| {
+            registry
+                .get(target)
+                .is_some_and(|config| config.dependencies.contains(&BuildTarget::Mrpack))
+        });
+        if needs_mrpack {
+            self.extract_mrpack()?;
+        }
+        Ok(())
+    }
+
+    /// Build every target in `wave` on up to `jobs` worker threads.
+    ///
+    /// Each target gets its own spinner. Once a target fails, workers stop
+    /// picking up new ones. Returns whether any target failed.
+    fn build_wave(
+        &self,
+        wave: &[BuildTarget],
+        jobs: usize,
+        bootstrap_jar_path: &Path,
+        installer_jar_path: &Path,
+        finished: &mut HashMap<BuildTarget, Result<BuildResult, BuildError>>,
+    ) -> bool {
+        enum Event {
+            Started(BuildTarget),
+            Finished(BuildTarget, Result<BuildResult, BuildError>),
+        }
+
+        let multi = self.session.display().progress().multi();
+        let spinners: HashMap<BuildTarget, _> = wave
+            .iter()
+            .map(|target| (*target, multi.add_spinner(&format!("{target}: queued"))))
+            .collect();
+
+        let queue = std::sync::Mutex::new(wave.iter().copied());
+        let failed = std::sync::atomic::AtomicBool::new(false);
+        let runtime = tokio::runtime::Handle::try_current().ok();
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        let run_workers = || {
+            std::thread::scope(|scope| {
+                for _ in 0..jobs.min(wave.len()) {
+                    let mut worker = self.worker();
+                    let sender = sender.clone();
+                    let (queue, failed, runtime) = (&queue, &failed, &runtime);
+                    scope.spawn(move || {
+                        // Server JAR downloads block on the caller's tokio runtime
+                        let _runtime = runtime.as_ref().map(|handle| handle.enter());
+                        loop {
+                            if failed.load(std::sync::atomic::Ordering::SeqCst) {
+                                break;
+                            }
+                            let Some(target) = queue.lock().expect("build queue poisoned").next()
+                            else {
+                                break;
+                            };
+                            let _ = sender.send(Event::Started(target));
+                            let result =
+                                worker.build_target(target, bootstrap_jar_path, installer_jar_path);
+                            if !matches!(&result, Ok(result) if result.success) {
+                                failed.store(true, std::sync::atomic::Ordering::SeqCst);
+                            }
+                            let _ = sender.send(Event::Finished(target, result));
+                        }
+                    });
+                }
+                drop(sender);
+
+                for event in receiver {
+                    match event {
+                        Event::Started(target) => {
+                            spinners[&target].set_message(&format!("{target}: building"));
+                        }
+                        Event::Finished(target, result) => {
+                            match &result {
+                                Ok(result) if result.success => {
+                                    self.record_fingerprint(result);
+                                    spinners[&target].finish(&format!("{target}: built"));
+                                }
+                                _ => spinners[&target].abandon(&format!("{target}: failed")),
+                            }
+                            finished.insert(target, result);
+                        }
+                    }
+                }
+            })
+        };
+        // The workers' downloads block on this runtime, so this worker must
+        // hand its other tasks, and the I/O driver, to another thread.
+        match &runtime {
+            Some(handle)
+                if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread =>
+            {
+                tokio::task::block_in_place(run_workers)
+            }
+            _ => run_workers(),
+        }
+
+        for (target, spinner) in &spinners {
+            if !finished.contains_key(target) {
+                spinner.abandon(&format!("{target}: skipped"));
+            }
+        }
+        multi.clear();
+
+        failed.into_inner()
+    }
+
+    /// A copy of this orchestrator for one build worker thread.
+    ///
+    /// Shared prerequisites are already done, so the copy never repeats them.
+    fn worker(&self) -> Self {
+        Self {
+            workdir: self.workdir.clone(),
+            dist_dir: self.dist_dir.clone(),
+            pack_refreshed: self.pack_refreshed,
+            mrpack_extracted: self.mrpack_extracted,
+            pack_info: self.pack_info.clone(),
+            archive_format: self.archive_format,
+            continue_full_builds: self.continue_full_builds,
+            force_rebuild: self.force_rebuild,
+            source_date_epoch: self.source_date_epoch,
+            template_engine: None,
+            session: self.session,
+        }
+    }
+
+    fn build_target(
+        &mut self,
+        target: BuildTarget,
+        bootstrap_jar_path: &Path,
+        installer_jar_path: &Path,
+    ) -> Result<BuildResult, BuildError> {
+        match target {
+            BuildTarget::Mrpack => self.build_mrpack_impl(),
+            BuildTarget::Client => self.build_client_impl(bootstrap_jar_path),
+            BuildTarget::Server => self.build_server_impl(bootstrap_jar_path),
+            BuildTarget::ClientFull => {
+                self.build_client_full_impl(bootstrap_jar_path, installer_jar_path)
+            }
+            BuildTarget::ServerFull => {
+                self.build_server_full_impl(bootstrap_jar_path, installer_jar_path)
+            }
+            BuildTarget::Curseforge => self.build_curseforge_impl(),
+            BuildTarget::Prism => self.build_prism_impl(bootstrap_jar_path),
+        }
+    }
+
     /// Execute clean pipeline with state management.
     /// Uses an RAII guard: on success `complete()` removes the marker; on failure
     /// or panic the marker persists so `discover_state()` reports `Interrupted`.
@@ -1568,6 +2870,97 @@ impl<'a> BuildOrchestrator<'a> {
         Ok(())
     }
 
+    fn tool_versions(&self) -> ToolVersions {
+        ToolVersions {
+            empack: env!("CARGO_PKG_VERSION").to_string(),
+            packwiz: format!(
+                "{} {}",
+                crate::platform::packwiz_bin::PACKWIZ_TX_VERSION,
+                self.session.packwiz_bin()
+            ),
+        }
+    }
+
+    fn fingerprint(&self, target: BuildTarget) -> Option<String> {
+        build_cache::compute_fingerprint(
+            self.session.filesystem(),
+            &self.workdir,
+            target,
+            self.archive_format,
+            self.archive_epoch(),
+            &self.tool_versions(),
+        )
+        .inspect_err(|e| tracing::debug!("Cannot fingerprint {}: {}", target, e))
+        .ok()
+    }
+
+    /// Result for a target whose recorded inputs and artifacts are unchanged.
+    fn up_to_date_result(&self, target: BuildTarget) -> Option<BuildResult> {
+        let filesystem = self.session.filesystem();
+        let record = build_cache::load_record(filesystem, &self.dist_dir, target)?;
+        if record.artifacts.is_empty() || self.fingerprint(target)? != record.fingerprint {
+            return None;
+        }
+
+        let artifacts = record
+            .artifacts
+            .iter()
+            .map(|recorded| {
+                let path = self.dist_dir.join(&recorded.path);
+                let unchanged = build_cache::hash_artifact(filesystem, &path)? == recorded.sha256;
+                unchanged
+                    .then(|| self.create_artifact(&path).ok())
+                    .flatten()
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(BuildResult {
+            target,
+            success: true,
+            output_path: artifacts.first().map(|artifact| artifact.path.clone()),
+            artifacts,
+            warnings: vec![],
+            restricted_mods: vec![],
+            excluded: record.excluded,
+            up_to_date: true,
+        })
+    }
+
+    /// Remember the inputs of a successful build; failures only cost a rebuild.
+    fn record_fingerprint(&self, result: &BuildResult) {
+        let Some(fingerprint) = self.fingerprint(result.target) else {
+            return;
+        };
+        let filesystem = self.session.filesystem();
+        let Some(artifacts) = result
+            .artifacts
+            .iter()
+            .map(|artifact| {
+                Some(RecordedArtifact {
+                    path: artifact
+                        .path
+                        .strip_prefix(&self.dist_dir)
+                        .unwrap_or(&artifact.path)
+                        .to_path_buf(),
+                    sha256: build_cache::hash_artifact(filesystem, &artifact.path)?,
+                })
+            })
+            .collect::<Option<Vec<_>>>()
+        else {
+            return;
+        };
+        let record = TargetRecord {
+            fingerprint,
+            artifacts,
+            excluded: result.excluded.clone(),
+        };
+        if let Err(e) =
+            build_cache::store_record(filesystem, &self.dist_dir, result.target, &record)
+        {
+            tracing::debug!("Cannot record fingerprint for {}: {}", result.target, e);
+        }
+    }
+
     /// Prepare build environment
     fn prepare_build_environment(&self) -> Result<(), BuildError> {
         let pack_dir = self.workdir.join("pack");
@@ -1593,6 +2986,23 @@ impl<'a> BuildOrchestrator<'a> {
         }
         let pack_info = self.pack_info.as_ref();
 
+        // Artifacts in the artifact root go with their signatures
+        let mut files: Vec<PathBuf> =
+            build_cache::load_record(self.session.filesystem(), &self.dist_dir, target)
+                .map(|record| {
+                    record
+                        .artifacts
+                        .into_iter()
+                        .map(|artifact| self.dist_dir.join(artifact.path))
+                        .collect()
+                })
+                .unwrap_or_default();
+        build_cache::remove_record(self.session.filesystem(), &self.dist_dir, target).map_err(
+            |e| BuildError::ConfigError {
+                reason: e.to_string(),
+            },
+        )?;
+
         let dist_dir = self.dist_dir.join(target.to_string());
 
         if self.session.filesystem().exists(&dist_dir) {
@@ -1627,18 +3037,33 @@ impl<'a> BuildOrchestrator<'a> {
         }
 
         if let Some(info) = pack_info {
-            for ext in ["zip", "tar.gz", "7z"] {
-                let archive_file = self.dist_dir.join(format!(
+            files.extend(["zip", "tar.gz", "7z"].iter().map(|ext| {
+                self.dist_dir.join(format!(
                     "{}-v{}-{}.{}",
                     info.name, info.version, target, ext
-                ));
-                if self.session.filesystem().exists(&archive_file) {
-                    self.session
-                        .filesystem()
-                        .remove_file(&archive_file)
-                        .map_err(|e| BuildError::ConfigError {
+                ))
+            }));
+            if target == BuildTarget::Mrpack {
+                files.push(
+                    self.dist_dir
+                        .join(format!("{}-v{}.mrpack", info.name, info.version)),
+                );
+            }
+            files.push(self.dist_dir.join(crate::empack::sbom::file_name(
+                &info.name,
+                &info.version,
+                target,
+            )));
+        }
+
+        for file in files {
+            for path in [signing::signature_path(&file), file] {
+                if self.session.filesystem().exists(&path) {
+                    self.session.filesystem().remove_file(&path).map_err(|e| {
+                        BuildError::ConfigError {
                             reason: e.to_string(),
-                        })?;
+                        }
+                    })?;
                 }
             }
         }
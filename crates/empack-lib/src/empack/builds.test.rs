@@ -180,12 +180,14 @@ fn restricted_mods_process_output(mod_name: &str, url: &str, dest_path: &str) ->
 #[test]
 fn test_build_registry() {
     let registry = BuildOrchestrator::create_build_registry();
-    assert_eq!(registry.len(), 5);
+    assert_eq!(registry.len(), 7);
     assert!(registry.contains_key(&BuildTarget::Mrpack));
     assert!(registry.contains_key(&BuildTarget::Client));
     assert!(registry.contains_key(&BuildTarget::Server));
     assert!(registry.contains_key(&BuildTarget::ClientFull));
     assert!(registry.contains_key(&BuildTarget::ServerFull));
+    assert!(registry.contains_key(&BuildTarget::Curseforge));
+    assert!(registry.contains_key(&BuildTarget::Prism));
 
     // Test dependencies
     let client_config = &registry[&BuildTarget::Client];
@@ -201,6 +203,26 @@ fn test_build_registry() {
     assert_eq!(mrpack_config.handler, "build_mrpack_impl");
 }
 
+#[test]
+fn test_plan_build_waves_runs_prerequisites_before_other_targets() {
+    use BuildTarget::*;
+
+    assert_eq!(
+        plan_build_waves(&[Mrpack, Client, Server, ClientFull, ServerFull, Curseforge, Prism]),
+        vec![
+            vec![Mrpack],
+            vec![Client, Server, ClientFull, ServerFull, Curseforge, Prism],
+        ]
+    );
+    assert_eq!(plan_build_waves(&[Server, Mrpack]), vec![vec![Mrpack], vec![Server]]);
+    assert_eq!(
+        plan_build_waves(&[Client, Server, ServerFull]),
+        vec![vec![Client, Server, ServerFull]]
+    );
+    assert_eq!(plan_build_waves(&[Mrpack, ClientFull]), vec![vec![Mrpack, ClientFull]]);
+    assert!(plan_build_waves(&[]).is_empty());
+}
+
 #[test]
 fn test_prepare_build_environment() {
     let (_temp_dir, session) = create_test_orchestrator();
@@ -639,6 +661,54 @@ fn test_clean_target_removes_all_archive_formats() {
     assert!(!filesystem.exists(&workdir.join("dist").join("TestPack-v1.0.0-client.7z")));
 }
 
+#[test]
+fn test_clean_target_removes_mrpack_and_signatures() {
+    let mock = MockBuildOrchestrator::new();
+    mock.setup_basic_pack_structure().unwrap();
+
+    let workdir = mock.workdir().to_path_buf();
+    let mut orchestrator = mock.orchestrator();
+    orchestrator.load_pack_info().unwrap();
+
+    let filesystem = mock.session.filesystem();
+    let dist_dir = workdir.join("dist");
+    let mut outputs = vec![
+        dist_dir.join("TestPack-v1.0.0.mrpack"),
+        dist_dir.join("TestPack-v1.0.0-mrpack.cdx.json"),
+        dist_dir.join("TestPack-v0.9.0.mrpack"),
+    ];
+    // An artifact the build cache recorded under an earlier name
+    build_cache::store_record(
+        filesystem,
+        &dist_dir,
+        BuildTarget::Mrpack,
+        &build_cache::TargetRecord {
+            fingerprint: "old".to_string(),
+            artifacts: vec![build_cache::RecordedArtifact {
+                path: PathBuf::from("TestPack-v0.9.0.mrpack"),
+                sha256: "00".to_string(),
+            }],
+            excluded: vec![],
+        },
+    )
+    .unwrap();
+    outputs.extend(outputs.clone().iter().map(|path| signing::signature_path(path)));
+    for path in &outputs {
+        filesystem.write_file(path, "output").unwrap();
+    }
+    let client_archive = dist_dir.join("TestPack-v1.0.0-client.zip");
+    filesystem
+        .write_file(&signing::signature_path(&client_archive), "signature")
+        .unwrap();
+
+    orchestrator.clean_target(BuildTarget::Mrpack).unwrap();
+
+    for path in &outputs {
+        assert!(!filesystem.exists(path), "{} was kept", path.display());
+    }
+    assert!(filesystem.exists(&signing::signature_path(&client_archive)));
+}
+
 #[test]
 fn test_download_server_jar_skips_when_srv_exists() {
     let mock = MockBuildOrchestrator::new();
@@ -1592,6 +1662,138 @@ async fn test_fetch_url_text_rejects_invalid_utf8() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_fetch_url_bytes_serves_offline_builds_from_the_download_cache() {
+    let mut server = mockito::Server::new_async().await;
+    let download = server
+        .mock("GET", "/installer.jar")
+        .with_body("installer bytes")
+        .expect(1)
+        .create_async()
+        .await;
+    let url = format!("{}/installer.jar", server.url());
+
+    let workdir = mock_root().join("offline-downloads");
+    let mut session = MockCommandSession::new().with_filesystem(
+        MockFileSystemProvider::new()
+            .with_current_dir(workdir.clone())
+            .with_file(workdir.join("pack").join("pack.toml"), "name = \"Pack\"\n".to_string()),
+    );
+
+    let orchestrator =
+        BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip).unwrap();
+    assert_eq!(orchestrator.fetch_url_bytes(&url).unwrap(), b"installer bytes");
+    let cache_path = download_cache_path(&url).unwrap();
+    assert!(cache_path.to_string_lossy().ends_with("-installer.jar"));
+    assert!(session.filesystem().exists(&cache_path));
+
+    session.network_provider = MockNetworkProvider::new()
+        .with_offline()
+        .with_failing_http_client();
+    let orchestrator =
+        BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip).unwrap();
+    assert_eq!(orchestrator.fetch_url_bytes(&url).unwrap(), b"installer bytes");
+
+    let missing = format!("{}/srv.jar", server.url());
+    match orchestrator.fetch_url_bytes(&missing) {
+        Err(BuildError::Network {
+            source: crate::networking::NetworkingError::Offline { resource },
+        }) => assert_eq!(resource, missing),
+        other => panic!("expected an offline cache miss, got {other:?}"),
+    }
+    download.assert_async().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_download_maven_artifact_is_verified_and_served_from_the_content_store() {
+    use sha1::Digest;
+
+    let mut server = mockito::Server::new_async().await;
+    let installer_sha1 = crate::empack::content::hex::encode(sha1::Sha1::digest(b"installer"));
+    let download = server
+        .mock("GET", "/installer.jar")
+        .with_body("installer")
+        .expect(1)
+        .create_async()
+        .await;
+    let _checksum = server
+        .mock("GET", "/installer.jar.sha1")
+        .with_body(format!("{installer_sha1}\n"))
+        .create_async()
+        .await;
+    let _tampered = server
+        .mock("GET", "/tampered.jar")
+        .with_body("tampered")
+        .create_async()
+        .await;
+    let _tampered_checksum = server
+        .mock("GET", "/tampered.jar.sha1")
+        .with_body(crate::empack::content::hex::encode(sha1::Sha1::digest(b"genuine")))
+        .create_async()
+        .await;
+
+    let workdir = mock_root().join("content-store");
+    let session = MockCommandSession::new().with_filesystem(
+        MockFileSystemProvider::new()
+            .with_current_dir(workdir.clone())
+            .with_file(workdir.join("pack").join("pack.toml"), "name = \"Pack\"\n".to_string()),
+    );
+    let orchestrator =
+        BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip).unwrap();
+
+    let first = workdir.join("dist").join("first-installer.jar");
+    let second = workdir.join("dist").join("second-installer.jar");
+    orchestrator
+        .download_maven_artifact(&format!("{}/installer.jar", server.url()), &first)
+        .unwrap();
+    orchestrator
+        .download_maven_artifact(&format!("{}/installer.jar", server.url()), &second)
+        .unwrap();
+    assert_eq!(session.filesystem().read_bytes(&second).unwrap(), b"installer");
+    download.assert_async().await;
+
+    let tampered = workdir.join("dist").join("tampered.jar");
+    match orchestrator.download_maven_artifact(&format!("{}/tampered.jar", server.url()), &tampered)
+    {
+        Err(BuildError::Store {
+            source: StoreError::HashMismatch { algorithm, .. },
+        }) => assert_eq!(algorithm, "sha1"),
+        other => panic!("expected a hash mismatch, got {other:?}"),
+    }
+    assert!(!session.filesystem().exists(&tampered));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_fetch_stored_keeps_unhashed_downloads_only_in_the_content_store() {
+    let mut server = mockito::Server::new_async().await;
+    let download = server
+        .mock("GET", "/unhashed-installer.jar")
+        .with_body("unhashed installer")
+        .expect(1)
+        .create_async()
+        .await;
+    let url = format!("{}/unhashed-installer.jar", server.url());
+
+    let workdir = mock_root().join("content-store-unhashed");
+    let mut session = MockCommandSession::new().with_filesystem(
+        MockFileSystemProvider::new()
+            .with_current_dir(workdir.clone())
+            .with_file(workdir.join("pack").join("pack.toml"), "name = \"Pack\"\n".to_string()),
+    );
+    let orchestrator =
+        BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip).unwrap();
+    assert_eq!(orchestrator.fetch_stored(&url, None).unwrap(), b"unhashed installer");
+    assert!(!session.filesystem().exists(&download_cache_path(&url).unwrap()));
+
+    session.network_provider = MockNetworkProvider::new()
+        .with_offline()
+        .with_failing_http_client();
+    let orchestrator =
+        BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip).unwrap();
+    assert_eq!(orchestrator.fetch_stored(&url, None).unwrap(), b"unhashed installer");
+    download.assert_async().await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_fetch_url_bytes_reports_http_client_unavailable() {
     let workdir = mock_root().join("http-client-unavailable");
@@ -1989,12 +2191,12 @@ async fn test_execute_build_pipeline_success_cleans_temp_extract_dir_and_build_m
 }
 
 #[tokio::test]
-async fn test_execute_build_pipeline_failure_still_cleans_temp_extract_dir() {
-    let workdir = mock_root().join("build-pipeline-failure-cleanup");
+async fn test_execute_build_pipeline_skips_up_to_date_targets_until_forced() {
+    let workdir = mock_root().join("build-pipeline-incremental");
     let pack_file = workdir.join("pack").join("pack.toml");
     let output_file = workdir.join("dist").join("Test Pack-v1.0.0.mrpack");
-    let temp_extract_dir = workdir.join("dist").join("temp-mrpack-extract");
     let process = MockProcessProvider::new()
+        .with_mrpack_export_side_effects()
         .with_packwiz_result(
             vec![
                 "--pack-file".to_string(),
@@ -2012,91 +2214,89 @@ async fn test_execute_build_pipeline_failure_still_cleans_temp_extract_dir() {
                 "-o".to_string(),
                 output_file.display().to_string(),
             ],
-            Ok(ProcessOutput {
-                stdout: String::new(),
-                stderr: "mr export failed".to_string(),
-                success: false,
-            }),
+            Ok(successful_process_output()),
         );
     let session = MockCommandSession::new()
         .with_filesystem(
             MockFileSystemProvider::new()
                 .with_current_dir(workdir.clone())
-                .with_configured_project(workdir.clone())
-                .with_file(temp_extract_dir.join("stale.txt"), "stale".to_string()),
+                .with_configured_project(workdir.clone()),
         )
         .with_process(process);
-    let mut orchestrator =
-        BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip).unwrap();
-
-    let error = orchestrator
-        .execute_build_pipeline(&[BuildTarget::Mrpack])
-        .await
-        .unwrap_err();
-
-    match error {
-        BuildError::CommandFailed { command } => {
-            assert!(command.contains("Build failed for target Mrpack"));
+    let exports = || {
+        session
+            .process_provider
+            .get_calls()
+            .iter()
+            .filter(|call| call.args.iter().any(|arg| arg == "export"))
+            .count()
+    };
+    let build = |force: bool| {
+        let mut orchestrator =
+            BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip).unwrap();
+        if force {
+            orchestrator = orchestrator.force_rebuild();
         }
-        other => panic!("expected CommandFailed, got {other:?}"),
-    }
-    assert!(!session.filesystem().exists(&temp_extract_dir));
+        async move { orchestrator.execute_build_pipeline(&[BuildTarget::Mrpack]).await }
+    };
 
-    let state = session.state().unwrap().discover_state().unwrap();
-    assert!(matches!(
-        state,
-        crate::primitives::PackState::Interrupted { .. }
-            | crate::primitives::PackState::Building
-    ));
-}
+    let first = build(false).await.unwrap();
+    assert!(!first[0].up_to_date);
+    assert_eq!(exports(), 1);
 
-#[tokio::test]
-async fn test_execute_clean_pipeline_removes_target_outputs_and_clean_marker() {
-    let workdir = mock_root().join("clean-pipeline-success");
-    let client_dir = workdir.join("dist").join("client");
-    let archive_path = workdir.join("dist").join("Test Pack-v1.0.0-client.zip");
-    let session = MockCommandSession::new().with_filesystem(
-        MockFileSystemProvider::new()
-            .with_current_dir(workdir.clone())
-            .with_configured_project(workdir.clone())
-            .with_file(client_dir.join("mod.jar"), "mod".to_string())
-            .with_file(client_dir.join(".gitkeep"), String::new())
-            .with_file(archive_path.clone(), "archive".to_string()),
-    );
-    let mut orchestrator =
-        BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip).unwrap();
+    let second = build(false).await.unwrap();
+    assert!(second[0].success);
+    assert!(second[0].up_to_date);
+    assert_eq!(second[0].output_path.as_ref(), Some(&output_file));
+    assert_eq!(exports(), 1);
 
-    orchestrator
-        .execute_clean_pipeline(&[BuildTarget::Client])
+    let forced = build(true).await.unwrap();
+    assert!(!forced[0].up_to_date);
+    assert_eq!(exports(), 2);
+
+    session
+        .filesystem()
+        .write_file(&workdir.join("pack/mods/jei.pw.toml"), "name = \"JEI\"\n")
+        .unwrap();
+    let edited = build(false).await.unwrap();
+    assert!(!edited[0].up_to_date);
+    assert_eq!(exports(), 3);
+
+    session.filesystem().remove_file(&output_file).unwrap();
+    let missing_artifact = build(false).await.unwrap();
+    assert!(!missing_artifact[0].up_to_date);
+    assert_eq!(exports(), 4);
+
+    session.filesystem().write_file(&output_file, "").unwrap();
+    let truncated_artifact = build(false).await.unwrap();
+    assert!(!truncated_artifact[0].up_to_date);
+    assert_eq!(exports(), 5);
+
+    let new_epoch = BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip)
+        .unwrap()
+        .source_date_epoch(1_700_000_000)
+        .execute_build_pipeline(&[BuildTarget::Mrpack])
         .await
         .unwrap();
-
-    assert!(!session.filesystem().exists(&client_dir.join("mod.jar")));
-    assert!(session.filesystem().exists(&client_dir.join(".gitkeep")));
-    assert!(!session.filesystem().exists(&archive_path));
-
-    let state = session.state().unwrap().discover_state().unwrap();
-    assert!(!matches!(
-        state,
-        crate::primitives::PackState::Interrupted { .. }
-            | crate::primitives::PackState::Cleaning
-    ));
+    assert!(!new_epoch[0].up_to_date);
+    assert_eq!(exports(), 6);
 }
 
-#[test]
-fn test_extract_mrpack_builds_missing_artifact_and_caches_repeated_calls() {
-    let workdir = mock_root().join("extract-mrpack-cached");
+#[tokio::test]
+async fn test_execute_build_pipeline_normalizes_mrpack_and_writes_checksums() {
+    let workdir = mock_root().join("build-pipeline-reproducible");
     let pack_file = workdir.join("pack").join("pack.toml");
     let output_file = workdir.join("dist").join("Test Pack-v1.0.0.mrpack");
     let process = MockProcessProvider::new()
         .with_mrpack_export_side_effects()
-        .with_packwiz_result(
-            vec![
-                "--pack-file".to_string(),
-                pack_file.display().to_string(),
-                "refresh".to_string(),
-            ],
-            Ok(successful_process_output()),
+        .with_result(
+            "git".to_string(),
+            vec!["log".to_string(), "-1".to_string(), "--format=%ct".to_string()],
+            Ok(ProcessOutput {
+                stdout: "1792238645\n".to_string(),
+                stderr: String::new(),
+                success: true,
+            }),
         )
         .with_packwiz_result(
             vec![
@@ -2113,76 +2313,420 @@ fn test_extract_mrpack_builds_missing_artifact_and_caches_repeated_calls() {
         .with_filesystem(
             MockFileSystemProvider::new()
                 .with_current_dir(workdir.clone())
-                .with_configured_project(workdir.clone()),
+                .with_configured_project(workdir.clone())
+                .with_file(
+                    workdir.join("dist").join("Test Pack-v0.9.0.mrpack"),
+                    "left over from an earlier version".to_string(),
+                ),
         )
         .with_process(process);
+
     let mut orchestrator =
         BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip).unwrap();
-
-    orchestrator.extract_mrpack().unwrap();
-    orchestrator.extract_mrpack().unwrap();
-
-    assert!(session
-        .filesystem()
-        .exists(&workdir.join("dist").join("temp-mrpack-extract")));
-    assert_eq!(session.archive_provider.extract_calls.lock().unwrap().len(), 1);
-    let export_calls = session
-        .process_provider
-        .get_calls_for_command(crate::empack::packwiz::PACKWIZ_BIN)
-        .into_iter()
-        .filter(|call| call.args.iter().any(|arg| arg == "export"))
-        .count();
-    assert_eq!(export_calls, 1);
-}
-
-#[test]
-fn test_zip_distribution_requires_loaded_pack_info() {
-    let mock = MockBuildOrchestrator::new();
-    mock.setup_basic_pack_structure().unwrap();
-    let orchestrator = mock.orchestrator();
-
-    let result = orchestrator.zip_distribution(BuildTarget::Client);
-
-    match result {
-        Err(BuildError::PackInfoError { reason }) => {
-            assert!(reason.contains("Pack info not loaded"));
-        }
-        other => panic!("expected PackInfoError, got {other:?}"),
-    }
-}
-
-#[test]
-fn test_zip_distribution_requires_target_content() {
-    let mock = MockBuildOrchestrator::new();
-    mock.setup_basic_pack_structure().unwrap();
-    let mut orchestrator = mock.orchestrator();
-    orchestrator.load_pack_info().unwrap();
-    mock.session
-        .filesystem()
-        .create_dir_all(&mock.workdir().join("dist").join("client"))
+    orchestrator
+        .execute_build_pipeline(&[BuildTarget::Mrpack])
+        .await
         .unwrap();
 
-    let result = orchestrator.zip_distribution(BuildTarget::Client);
+    assert_eq!(
+        *session.archive_provider.normalize_calls.lock().unwrap(),
+        vec![(output_file.clone(), 1_792_238_645)]
+    );
+    let digest = |path: &Path| {
+        let bytes = session.filesystem().read_bytes(path).unwrap();
+        crate::empack::content::hex::encode(<sha2::Sha256 as Digest>::digest(&bytes))
+    };
+    // Without empack.lock the SBOM comes from the packwiz metafiles
+    let sbom_file = workdir
+        .join("dist")
+        .join("Test Pack-v1.0.0-mrpack.cdx.json");
+    assert_eq!(
+        session
+            .filesystem()
+            .read_to_string(&workdir.join("dist").join(CHECKSUMS_FILE))
+            .unwrap(),
+        format!(
+            "{}  Test Pack-v1.0.0-mrpack.cdx.json\n{}  Test Pack-v1.0.0.mrpack\n",
+            digest(&sbom_file),
+            digest(&output_file)
+        )
+    );
 
-    match result {
-        Err(BuildError::ValidationError { reason }) => {
-            assert!(reason.contains("No files to archive"));
-        }
-        other => panic!("expected ValidationError, got {other:?}"),
-    }
+    // An explicit epoch wins over the commit time
+    let mut orchestrator =
+        BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip)
+            .unwrap()
+            .force_rebuild()
+            .source_date_epoch(0);
+    orchestrator
+        .execute_build_pipeline(&[BuildTarget::Mrpack])
+        .await
+        .unwrap();
+    assert_eq!(
+        session.archive_provider.normalize_calls.lock().unwrap().last(),
+        Some(&(output_file, 0))
+    );
 }
 
-#[test]
-fn test_build_mrpack_returns_restricted_mods_on_export_failure() {
-    let workdir = mock_root().join("mrpack-manual-download");
+#[tokio::test]
+async fn test_execute_build_pipeline_signs_artifacts_with_configured_key() {
+    let workdir = mock_root().join("build-pipeline-signed");
     let pack_file = workdir.join("pack").join("pack.toml");
     let output_file = workdir.join("dist").join("Test Pack-v1.0.0.mrpack");
+    let key_path = workdir.join("empack.key");
+    let keypair = minisign::KeyPair::generate_unencrypted_keypair().unwrap();
     let process = MockProcessProvider::new()
+        .with_mrpack_export_side_effects()
         .with_packwiz_result(
             vec![
                 "--pack-file".to_string(),
                 pack_file.display().to_string(),
-                "refresh".to_string(),
+                "mr".to_string(),
+                "export".to_string(),
+                "-o".to_string(),
+                output_file.display().to_string(),
+            ],
+            Ok(successful_process_output()),
+        );
+    let mut session = MockCommandSession::new()
+        .with_filesystem(
+            MockFileSystemProvider::new()
+                .with_current_dir(workdir.clone())
+                .with_configured_project(workdir.clone())
+                .with_file(key_path.clone(), keypair.sk.to_box(None).unwrap().into_string()),
+        )
+        .with_process(process);
+    session.config_provider.app_config.signing_key = Some(key_path);
+
+    let mut orchestrator =
+        BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip).unwrap();
+    orchestrator
+        .execute_build_pipeline(&[BuildTarget::Mrpack])
+        .await
+        .unwrap();
+
+    let bytes = session.filesystem().read_bytes(&output_file).unwrap();
+    let signature = session
+        .filesystem()
+        .read_to_string(&signing::signature_path(&output_file))
+        .unwrap();
+    let public_key = keypair.pk.to_box().unwrap().into_string();
+    signing::VerifyingKey::parse(&public_key)
+        .unwrap()
+        .verify(&bytes, &signature, "Test Pack-v1.0.0.mrpack")
+        .unwrap();
+    let checksums = session
+        .filesystem()
+        .read_to_string(&workdir.join("dist").join(CHECKSUMS_FILE))
+        .unwrap();
+    assert!(!checksums.contains(".minisig"));
+    signing::verify_checksum(&checksums, &bytes, "Test Pack-v1.0.0.mrpack").unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_write_sboms_lists_locked_dependencies_with_modrinth_licenses() {
+    let mut server = mockito::Server::new_async().await;
+    let _projects = server
+        .mock("GET", "/v2/projects")
+        .match_query(mockito::Matcher::Any)
+        .with_body(r#"[{"id":"AANobbMI","license":{"id":"LGPL-3.0-only"}}]"#)
+        .create_async()
+        .await;
+
+    let workdir = mock_root().join("build-sbom");
+    let lock = r#"version = 1
+
+[dependencies.sodium]
+platform = "modrinth"
+project_id = "AANobbMI"
+version_id = "ver1"
+type = "mod"
+filename = "sodium.jar"
+url = "https://cdn.modrinth.com/sodium.jar"
+sha1 = "aa"
+"#;
+    let session = MockCommandSession::new().with_filesystem(
+        MockFileSystemProvider::new()
+            .with_current_dir(workdir.clone())
+            .with_configured_project(workdir.clone())
+            .with_file(workdir.join("empack.lock"), lock.to_string()),
+    );
+    let mut orchestrator =
+        BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip)
+            .unwrap()
+            .source_date_epoch(0);
+    let mut results = vec![BuildResult {
+        target: BuildTarget::Mrpack,
+        success: true,
+        output_path: None,
+        artifacts: vec![],
+        warnings: vec![],
+        restricted_mods: vec![],
+        excluded: vec![],
+        up_to_date: false,
+    }];
+
+    orchestrator
+        .write_sboms_with_api_base(&mut results, &server.url())
+        .unwrap();
+
+    let sbom_path = workdir.join("dist").join("Test Pack-v1.0.0-mrpack.cdx.json");
+    assert_eq!(results[0].artifacts[0].path, sbom_path);
+    let sbom: serde_json::Value =
+        serde_json::from_str(&session.filesystem().read_to_string(&sbom_path).unwrap()).unwrap();
+    assert_eq!(sbom["metadata"]["timestamp"], "1970-01-01T00:00:00Z");
+    assert_eq!(sbom["components"][0]["name"], "Sodium");
+    assert_eq!(sbom["components"][0]["licenses"][0]["license"]["id"], "LGPL-3.0-only");
+}
+
+#[tokio::test]
+async fn test_write_sboms_without_lock_reads_packwiz_metafiles() {
+    let workdir = mock_root().join("build-sbom-unlocked");
+    let session = MockCommandSession::new()
+        .with_filesystem(
+            MockFileSystemProvider::new()
+                .with_current_dir(workdir.clone())
+                .with_configured_project(workdir.clone())
+                .with_file(
+                    workdir.join("pack/mods/sodium.pw.toml"),
+                    r#"name = "Sodium"
+filename = "sodium.jar"
+
+[download]
+url = "https://cdn.modrinth.com/sodium.jar"
+hash-format = "sha1"
+hash = "aa"
+
+[update.modrinth]
+mod-id = "AANobbMI"
+version = "ver1"
+"#
+                    .to_string(),
+                ),
+        )
+        .with_network(MockNetworkProvider::new().with_offline());
+    let mut orchestrator =
+        BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip)
+            .unwrap()
+            .source_date_epoch(0);
+    let mut results = vec![BuildResult {
+        target: BuildTarget::Mrpack,
+        success: true,
+        output_path: None,
+        artifacts: vec![],
+        warnings: vec![],
+        restricted_mods: vec![],
+        excluded: vec![],
+        up_to_date: false,
+    }];
+
+    orchestrator.write_sboms(&mut results).unwrap();
+
+    let sbom_path = workdir.join("dist").join("Test Pack-v1.0.0-mrpack.cdx.json");
+    let sbom: serde_json::Value =
+        serde_json::from_str(&session.filesystem().read_to_string(&sbom_path).unwrap()).unwrap();
+    let components = sbom["components"].as_array().unwrap();
+    assert_eq!(components.len(), 1);
+    assert_eq!(components[0]["name"], "Sodium");
+    assert_eq!(components[0]["version"], "ver1");
+    assert_eq!(components[0]["hashes"][0]["content"], "aa");
+}
+
+#[tokio::test]
+async fn test_execute_build_pipeline_failure_still_cleans_temp_extract_dir() {
+    let workdir = mock_root().join("build-pipeline-failure-cleanup");
+    let pack_file = workdir.join("pack").join("pack.toml");
+    let output_file = workdir.join("dist").join("Test Pack-v1.0.0.mrpack");
+    let temp_extract_dir = workdir.join("dist").join("temp-mrpack-extract");
+    let process = MockProcessProvider::new()
+        .with_packwiz_result(
+            vec![
+                "--pack-file".to_string(),
+                pack_file.display().to_string(),
+                "refresh".to_string(),
+            ],
+            Ok(successful_process_output()),
+        )
+        .with_packwiz_result(
+            vec![
+                "--pack-file".to_string(),
+                pack_file.display().to_string(),
+                "mr".to_string(),
+                "export".to_string(),
+                "-o".to_string(),
+                output_file.display().to_string(),
+            ],
+            Ok(ProcessOutput {
+                stdout: String::new(),
+                stderr: "mr export failed".to_string(),
+                success: false,
+            }),
+        );
+    let session = MockCommandSession::new()
+        .with_filesystem(
+            MockFileSystemProvider::new()
+                .with_current_dir(workdir.clone())
+                .with_configured_project(workdir.clone())
+                .with_file(temp_extract_dir.join("stale.txt"), "stale".to_string()),
+        )
+        .with_process(process);
+    let mut orchestrator =
+        BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip).unwrap();
+
+    let error = orchestrator
+        .execute_build_pipeline(&[BuildTarget::Mrpack])
+        .await
+        .unwrap_err();
+
+    match error {
+        BuildError::CommandFailed { command } => {
+            assert!(command.contains("Build failed for target Mrpack"));
+        }
+        other => panic!("expected CommandFailed, got {other:?}"),
+    }
+    assert!(!session.filesystem().exists(&temp_extract_dir));
+
+    let state = session.state().unwrap().discover_state().unwrap();
+    assert!(matches!(
+        state,
+        crate::primitives::PackState::Interrupted { .. }
+            | crate::primitives::PackState::Building
+    ));
+}
+
+#[tokio::test]
+async fn test_execute_clean_pipeline_removes_target_outputs_and_clean_marker() {
+    let workdir = mock_root().join("clean-pipeline-success");
+    let client_dir = workdir.join("dist").join("client");
+    let archive_path = workdir.join("dist").join("Test Pack-v1.0.0-client.zip");
+    let session = MockCommandSession::new().with_filesystem(
+        MockFileSystemProvider::new()
+            .with_current_dir(workdir.clone())
+            .with_configured_project(workdir.clone())
+            .with_file(client_dir.join("mod.jar"), "mod".to_string())
+            .with_file(client_dir.join(".gitkeep"), String::new())
+            .with_file(archive_path.clone(), "archive".to_string()),
+    );
+    let mut orchestrator =
+        BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip).unwrap();
+
+    orchestrator
+        .execute_clean_pipeline(&[BuildTarget::Client])
+        .await
+        .unwrap();
+
+    assert!(!session.filesystem().exists(&client_dir.join("mod.jar")));
+    assert!(session.filesystem().exists(&client_dir.join(".gitkeep")));
+    assert!(!session.filesystem().exists(&archive_path));
+
+    let state = session.state().unwrap().discover_state().unwrap();
+    assert!(!matches!(
+        state,
+        crate::primitives::PackState::Interrupted { .. }
+            | crate::primitives::PackState::Cleaning
+    ));
+}
+
+#[test]
+fn test_extract_mrpack_builds_missing_artifact_and_caches_repeated_calls() {
+    let workdir = mock_root().join("extract-mrpack-cached");
+    let pack_file = workdir.join("pack").join("pack.toml");
+    let output_file = workdir.join("dist").join("Test Pack-v1.0.0.mrpack");
+    let process = MockProcessProvider::new()
+        .with_mrpack_export_side_effects()
+        .with_packwiz_result(
+            vec![
+                "--pack-file".to_string(),
+                pack_file.display().to_string(),
+                "refresh".to_string(),
+            ],
+            Ok(successful_process_output()),
+        )
+        .with_packwiz_result(
+            vec![
+                "--pack-file".to_string(),
+                pack_file.display().to_string(),
+                "mr".to_string(),
+                "export".to_string(),
+                "-o".to_string(),
+                output_file.display().to_string(),
+            ],
+            Ok(successful_process_output()),
+        );
+    let session = MockCommandSession::new()
+        .with_filesystem(
+            MockFileSystemProvider::new()
+                .with_current_dir(workdir.clone())
+                .with_configured_project(workdir.clone()),
+        )
+        .with_process(process);
+    let mut orchestrator =
+        BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip).unwrap();
+
+    orchestrator.extract_mrpack().unwrap();
+    orchestrator.extract_mrpack().unwrap();
+
+    assert!(session
+        .filesystem()
+        .exists(&workdir.join("dist").join("temp-mrpack-extract")));
+    assert_eq!(session.archive_provider.extract_calls.lock().unwrap().len(), 1);
+    let export_calls = session
+        .process_provider
+        .get_calls_for_command(crate::empack::packwiz::PACKWIZ_BIN)
+        .into_iter()
+        .filter(|call| call.args.iter().any(|arg| arg == "export"))
+        .count();
+    assert_eq!(export_calls, 1);
+}
+
+#[test]
+fn test_zip_distribution_requires_loaded_pack_info() {
+    let mock = MockBuildOrchestrator::new();
+    mock.setup_basic_pack_structure().unwrap();
+    let orchestrator = mock.orchestrator();
+
+    let result = orchestrator.zip_distribution(BuildTarget::Client);
+
+    match result {
+        Err(BuildError::PackInfoError { reason }) => {
+            assert!(reason.contains("Pack info not loaded"));
+        }
+        other => panic!("expected PackInfoError, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_zip_distribution_requires_target_content() {
+    let mock = MockBuildOrchestrator::new();
+    mock.setup_basic_pack_structure().unwrap();
+    let mut orchestrator = mock.orchestrator();
+    orchestrator.load_pack_info().unwrap();
+    mock.session
+        .filesystem()
+        .create_dir_all(&mock.workdir().join("dist").join("client"))
+        .unwrap();
+
+    let result = orchestrator.zip_distribution(BuildTarget::Client);
+
+    match result {
+        Err(BuildError::ValidationError { reason }) => {
+            assert!(reason.contains("No files to archive"));
+        }
+        other => panic!("expected ValidationError, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_build_mrpack_returns_restricted_mods_on_export_failure() {
+    let workdir = mock_root().join("mrpack-manual-download");
+    let pack_file = workdir.join("pack").join("pack.toml");
+    let output_file = workdir.join("dist").join("Test Pack-v1.0.0.mrpack");
+    let process = MockProcessProvider::new()
+        .with_packwiz_result(
+            vec![
+                "--pack-file".to_string(),
+                pack_file.display().to_string(),
+                "refresh".to_string(),
             ],
             Ok(successful_process_output()),
         )
@@ -2350,22 +2894,223 @@ fn test_build_server_uses_existing_srv_jar_and_archives_with_overrides() {
     let dist_dir = workdir.join("dist").join("server");
     let overrides_dir = workdir.join("dist").join("temp-mrpack-extract").join("overrides");
 
-    let filesystem = MockFileSystemProvider::new()
-        .with_current_dir(workdir.clone())
-        .with_configured_project(workdir.clone())
+    let filesystem = MockFileSystemProvider::new()
+        .with_current_dir(workdir.clone())
+        .with_configured_project(workdir.clone())
+        .with_file(bootstrap_jar_path.clone(), "bootstrap".to_string())
+        .with_deferred_file(
+            dist_dir.clone(),
+            "srv.jar".to_string(),
+            "existing server jar".to_string(),
+        )
+        .with_file(
+            workdir.join("templates").join("server").join("run.sh.template"),
+            "#!/bin/sh\necho {{NAME}}\n".to_string(),
+        )
+        .with_file(
+            overrides_dir.join("server.properties"),
+            "motd=Empack Test\n".to_string(),
+        );
+    let session = MockCommandSession::new().with_filesystem(filesystem);
+    let mut orchestrator =
+        BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip).unwrap();
+    orchestrator.pack_refreshed = true;
+    orchestrator.mrpack_extracted = true;
+
+    let result = orchestrator.build_server_impl(&bootstrap_jar_path).unwrap();
+
+    assert!(result.success);
+    assert_eq!(result.target, BuildTarget::Server);
+    assert_eq!(
+        result.output_path.as_ref(),
+        Some(&workdir.join("dist").join("Test Pack-v1.0.0-server.zip"))
+    );
+    assert!(session.filesystem().exists(&dist_dir.join("srv.jar")));
+    assert!(session
+        .filesystem()
+        .exists(&dist_dir.join("pack").join("pack.toml")));
+    assert!(session
+        .filesystem()
+        .exists(&dist_dir.join("server.properties")));
+    let rendered = session
+        .filesystem()
+        .read_to_string(&dist_dir.join("run.sh"))
+        .unwrap();
+    assert!(rendered.contains("Test Pack"));
+    let zip_path = workdir.join("dist").join("Test Pack-v1.0.0-server.zip");
+    assert!(session.filesystem().exists(&zip_path));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_build_wave_downloads_server_jar_on_single_worker_runtime() {
+    let mut server = mockito::Server::new_async().await;
+    let jar_bytes = b"fake server jar content";
+    let jar_sha1 = crate::empack::content::hex::encode(Sha1::digest(jar_bytes));
+    let manifest = serde_json::json!({
+        "versions": [{
+            "id": "1.21.1",
+            "url": format!("{}/v1/packages/1.21.1.json", server.url())
+        }]
+    });
+    let version_meta = serde_json::json!({
+        "downloads": {
+            "server": {
+                "url": format!("{}/server.jar", server.url()),
+                "sha1": jar_sha1
+            }
+        }
+    });
+    let _m1 = server
+        .mock("GET", "/mc/game/version_manifest_v2.json")
+        .with_body(manifest.to_string())
+        .create_async()
+        .await;
+    let _m2 = server
+        .mock("GET", "/v1/packages/1.21.1.json")
+        .with_body(version_meta.to_string())
+        .create_async()
+        .await;
+    let _m3 = server
+        .mock("GET", "/server.jar")
+        .with_body(jar_bytes.as_slice())
+        .create_async()
+        .await;
+
+    let workdir = mock_root().join("server-build-single-worker");
+    let bootstrap_jar_path = workdir.join("cache").join("packwiz-installer-bootstrap.jar");
+    let installer_jar_path = workdir.join("cache").join("packwiz-installer.jar");
+    let dist_dir = workdir.join("dist").join("server");
+    let filesystem = MockFileSystemProvider::new()
+        .with_current_dir(workdir.clone())
+        .with_configured_project(workdir.clone())
+        .with_file(
+            workdir.join("pack").join("pack.toml"),
+            "name = \"Test Pack\"\nauthor = \"Test Author\"\nversion = \"1.0.0\"\n\n[versions]\nminecraft = \"1.21.1\"\n"
+                .to_string(),
+        )
+        .with_file(bootstrap_jar_path.clone(), "bootstrap".to_string())
+        .with_file(
+            workdir.join("templates").join("server").join("run.sh.template"),
+            "#!/bin/sh\necho {{NAME}}\n".to_string(),
+        );
+    let network = MockNetworkProvider::new().with_endpoints(
+        crate::networking::endpoints::Endpoints::default()
+            .with_bases(crate::networking::endpoints::Service::MojangMeta, [server.url()]),
+    );
+    let session: &'static MockCommandSession = Box::leak(Box::new(
+        MockCommandSession::new()
+            .with_filesystem(filesystem)
+            .with_network(network),
+    ));
+
+    // The wave must run on the runtime's only worker, as the build pipeline does
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let bootstrap = bootstrap_jar_path.clone();
+    tokio::spawn(async move {
+        let mut orchestrator =
+            BuildOrchestrator::new(session, crate::empack::archive::ArchiveFormat::Zip).unwrap();
+        orchestrator.pack_refreshed = true;
+        orchestrator.mrpack_extracted = true;
+        let mut finished = HashMap::new();
+        let failed = orchestrator.build_wave(
+            &[BuildTarget::Server],
+            1,
+            &bootstrap,
+            &installer_jar_path,
+            &mut finished,
+        );
+        let _ = sender.send((failed, finished));
+    });
+
+    let (failed, finished) = tokio::task::spawn_blocking(move || {
+        receiver.recv_timeout(std::time::Duration::from_secs(30))
+    })
+    .await
+    .unwrap()
+    .expect("server build stalled on a single-worker runtime");
+
+    assert!(!failed, "{finished:?}");
+    assert!(finished[&BuildTarget::Server].as_ref().unwrap().success);
+    let downloaded = session
+        .filesystem()
+        .read_bytes(&dist_dir.join("srv.jar"))
+        .unwrap();
+    assert_eq!(downloaded, jar_bytes);
+}
+
+#[test]
+fn test_build_server_excludes_client_only_dependencies() {
+    let workdir = mock_root().join("server-side-filter");
+    let bootstrap_jar_path = workdir.join("cache").join("packwiz-installer-bootstrap.jar");
+    let dist_dir = workdir.join("dist").join("server");
+    let pack_dir = workdir.join("pack");
+
+    let filesystem = MockFileSystemProvider::new()
+        .with_current_dir(workdir.clone())
+        .with_configured_project(workdir.clone())
+        .with_file(
+            workdir.join("empack.yml"),
+            r#"empack:
+  dependencies:
+    sodium:
+      status: resolved
+      title: Sodium
+      platform: modrinth
+      project_id: AANobbMI
+      type: mod
+      side: client
+    lithium:
+      status: resolved
+      title: Lithium
+      platform: modrinth
+      project_id: gvQqBUqZ
+      type: mod
+  minecraft_version: "1.21.1"
+  loader: fabric
+  name: "Test Pack"
+  author: "Test Author"
+  version: "1.0.0"
+"#
+            .to_string(),
+        )
+        .with_file(
+            pack_dir.join("index.toml"),
+            r#"hash-format = "sha256"
+
+[[files]]
+file = "mods/sodium.pw.toml"
+hash = ""
+metafile = true
+
+[[files]]
+file = "mods/lithium.pw.toml"
+hash = ""
+metafile = true
+
+[[files]]
+file = "mods/zoomify.pw.toml"
+hash = ""
+metafile = true
+"#
+            .to_string(),
+        )
+        .with_file(
+            pack_dir.join("mods").join("sodium.pw.toml"),
+            "name = \"Sodium\"\n".to_string(),
+        )
+        .with_file(
+            pack_dir.join("mods").join("lithium.pw.toml"),
+            "name = \"Lithium\"\nside = \"both\"\n".to_string(),
+        )
+        .with_file(
+            pack_dir.join("mods").join("zoomify.pw.toml"),
+            "name = \"Zoomify\"\nside = \"client\"\n".to_string(),
+        )
         .with_file(bootstrap_jar_path.clone(), "bootstrap".to_string())
         .with_deferred_file(
             dist_dir.clone(),
             "srv.jar".to_string(),
             "existing server jar".to_string(),
-        )
-        .with_file(
-            workdir.join("templates").join("server").join("run.sh.template"),
-            "#!/bin/sh\necho {{NAME}}\n".to_string(),
-        )
-        .with_file(
-            overrides_dir.join("server.properties"),
-            "motd=Empack Test\n".to_string(),
         );
     let session = MockCommandSession::new().with_filesystem(filesystem);
     let mut orchestrator =
@@ -2376,25 +3121,73 @@ fn test_build_server_uses_existing_srv_jar_and_archives_with_overrides() {
     let result = orchestrator.build_server_impl(&bootstrap_jar_path).unwrap();
 
     assert!(result.success);
-    assert_eq!(result.target, BuildTarget::Server);
-    assert_eq!(
-        result.output_path.as_ref(),
-        Some(&workdir.join("dist").join("Test Pack-v1.0.0-server.zip"))
-    );
-    assert!(session.filesystem().exists(&dist_dir.join("srv.jar")));
-    assert!(session
-        .filesystem()
-        .exists(&dist_dir.join("pack").join("pack.toml")));
-    assert!(session
-        .filesystem()
-        .exists(&dist_dir.join("server.properties")));
-    let rendered = session
+    assert_eq!(result.excluded, vec!["sodium", "zoomify"]);
+    let copied_mods = dist_dir.join("pack").join("mods");
+    assert!(!session.filesystem().exists(&copied_mods.join("sodium.pw.toml")));
+    assert!(!session.filesystem().exists(&copied_mods.join("zoomify.pw.toml")));
+    assert!(session.filesystem().exists(&copied_mods.join("lithium.pw.toml")));
+    assert!(session.filesystem().exists(&pack_dir.join("mods").join("sodium.pw.toml")));
+
+    let copied_pack = dist_dir.join("pack").join("pack.toml");
+    assert!(session.process_provider.get_calls().iter().any(|call| {
+        call.args == ["--pack-file", copied_pack.to_string_lossy().as_ref(), "refresh"]
+    }));
+}
+
+#[test]
+fn test_exclude_other_side_content_uses_local_dependency_side_for_clients() {
+    let workdir = mock_root().join("client-side-filter");
+    let pack_copy = workdir.join("dist").join("client").join(".minecraft").join("pack");
+
+    let filesystem = MockFileSystemProvider::new()
+        .with_current_dir(workdir.clone())
+        .with_configured_project(workdir.clone())
+        .with_file(
+            workdir.join("empack.yml"),
+            r#"empack:
+  dependencies:
+    spark-server:
+      status: local
+      title: Spark Server
+      type: mod
+      path: pack/mods/spark-server.jar
+      sha256: unused
+      side: server
+  minecraft_version: "1.21.1"
+  loader: fabric
+  name: "Test Pack"
+  author: "Test Author"
+  version: "1.0.0"
+"#
+            .to_string(),
+        )
+        .with_file(
+            pack_copy.join("index.toml"),
+            r#"hash-format = "sha256"
+
+[[files]]
+file = "mods/spark-server.jar"
+hash = ""
+"#
+            .to_string(),
+        )
+        .with_file(pack_copy.join("mods").join("spark-server.jar"), "jar".to_string());
+    let session = MockCommandSession::new().with_filesystem(filesystem);
+    let orchestrator =
+        BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip).unwrap();
+
+    let excluded = orchestrator
+        .exclude_other_side_content(&pack_copy, DependencySide::Client)
+        .unwrap();
+    assert_eq!(excluded, vec!["mods/spark-server.jar"]);
+    assert!(!session
         .filesystem()
-        .read_to_string(&dist_dir.join("run.sh"))
+        .exists(&pack_copy.join("mods").join("spark-server.jar")));
+
+    let kept = orchestrator
+        .exclude_other_side_content(&pack_copy, DependencySide::Server)
         .unwrap();
-    assert!(rendered.contains("Test Pack"));
-    let zip_path = workdir.join("dist").join("Test Pack-v1.0.0-server.zip");
-    assert!(session.filesystem().exists(&zip_path));
+    assert!(kept.is_empty());
 }
 
 #[test]
@@ -2615,7 +3408,11 @@ async fn test_download_server_jar_vanilla_fetches_mojang_manifest() {
 
     let jar_path = dist_dir.join("srv.jar");
     orchestrator
-        .download_file(&meta.downloads.server.url, &jar_path)
+        .download_file(
+            &meta.downloads.server.url,
+            &jar_path,
+            Some(&ContentHash::Sha1(jar_sha1.to_string())),
+        )
         .unwrap();
 
     let downloaded = mock.session.filesystem().read_bytes(&jar_path).unwrap();
@@ -2771,7 +3568,7 @@ async fn test_download_server_jar_quilt_parses_maven_and_invokes_java() {
     );
     let installer_path = dist_dir.join("quilt-installer-0.12.0.jar");
     orchestrator
-        .download_file(&installer_url, &installer_path)
+        .download_file(&installer_url, &installer_path, None)
         .unwrap();
     assert!(mock.session.filesystem().exists(&installer_path));
 }
@@ -2858,7 +3655,9 @@ async fn test_download_server_jar_neoforge_downloads_and_runs_installer() {
         "{}/releases/net/neoforged/neoforge/21.1.86/neoforge-21.1.86-installer.jar",
         server.url()
     );
-    orchestrator.download_file(&url, &installer_path).unwrap();
+    orchestrator
+        .download_file(&url, &installer_path, None)
+        .unwrap();
     assert!(mock.session.filesystem().exists(&installer_path));
 
     // Verify the URL construction logic for standard NeoForge
@@ -2968,7 +3767,9 @@ async fn test_download_server_jar_forge_downloads_and_runs_installer() {
         "{}/net/minecraftforge/forge/1.20.1-47.3.0/forge-1.20.1-47.3.0-installer.jar",
         server.url()
     );
-    orchestrator.download_file(&url, &installer_path).unwrap();
+    orchestrator
+        .download_file(&url, &installer_path, None)
+        .unwrap();
     assert!(mock.session.filesystem().exists(&installer_path));
 
     // Verify URL construction for Forge
@@ -3037,6 +3838,7 @@ async fn test_download_server_starter_jar_succeeds_when_run_scripts_exist() {
         .download_file(
             &format!("{}/releases/latest/download/server.jar", server.url()),
             &srv_jar,
+            None,
         )
         .unwrap();
 
@@ -3140,7 +3942,7 @@ async fn test_download_file_skips_http_when_dest_exists() {
 
     // URL is unreachable; download_file must return Ok because the cache hit
     // prevents the HTTP call entirely.
-    let result = orchestrator.download_file("http://unreachable.invalid/file.jar", &dest);
+    let result = orchestrator.download_file("http://unreachable.invalid/file.jar", &dest, None);
     assert!(
         result.is_ok(),
         "cache-first check should skip HTTP: {result:?}"
@@ -3240,6 +4042,382 @@ async fn test_fetch_url_bytes_exhausts_retries_on_persistent_server_error() {
     );
 }
 
+// ===== CURSEFORGE BUILD TESTS =====
+
+const CURSEFORGE_INDEX_TOML: &str = r#"hash-format = "sha256"
+
+[[files]]
+file = "mods/jei.pw.toml"
+hash = ""
+metafile = true
+
+[[files]]
+file = "mods/sodium.pw.toml"
+hash = ""
+metafile = true
+
+[[files]]
+file = "mods/direct.pw.toml"
+hash = ""
+metafile = true
+
+[[files]]
+file = "config/options.cfg"
+hash = ""
+"#;
+
+fn curseforge_session(workdir: &Path, sodium_url: &str, sodium_sha512: &str) -> MockCommandSession {
+    let filesystem = MockFileSystemProvider::new()
+        .with_current_dir(workdir.to_path_buf())
+        .with_configured_project(workdir.to_path_buf())
+        .with_file(
+            workdir.join("pack").join("index.toml"),
+            CURSEFORGE_INDEX_TOML.to_string(),
+        )
+        .with_file(
+            workdir.join("pack").join("mods").join("jei.pw.toml"),
+            r#"name = "Just Enough Items"
+filename = "jei.jar"
+
+[download]
+mode = "metadata:curseforge"
+hash-format = "sha1"
+hash = "abc"
+
+[update.curseforge]
+project-id = 238222
+file-id = 5101366
+"#
+            .to_string(),
+        )
+        .with_file(
+            workdir.join("pack").join("mods").join("sodium.pw.toml"),
+            format!(
+                r#"name = "Sodium"
+filename = "sodium.jar"
+
+[download]
+url = "{sodium_url}"
+hash-format = "sha512"
+hash = "{sodium_sha512}"
+
+[update.modrinth]
+mod-id = "AANobbMI"
+version = "abc123"
+"#
+            ),
+        )
+        .with_file(
+            workdir.join("pack").join("mods").join("direct.pw.toml"),
+            r#"name = "Direct Mod"
+filename = "direct.jar"
+
+[download]
+url = "https://example.com/direct.jar"
+hash-format = "sha1"
+hash = "abc"
+"#
+            .to_string(),
+        )
+        .with_file(
+            workdir.join("pack").join("config").join("options.cfg"),
+            "fov=90\n".to_string(),
+        );
+    MockCommandSession::new().with_filesystem(filesystem)
+}
+
+#[test]
+fn test_license_allows_redistribution() {
+    assert!(license_allows_redistribution("MIT"));
+    assert!(license_allows_redistribution("LGPL-3.0-only"));
+    assert!(license_allows_redistribution("GPL-3.0-or-later"));
+    assert!(!license_allows_redistribution("LicenseRef-All-Rights-Reserved"));
+    assert!(!license_allows_redistribution("LicenseRef-Custom"));
+}
+
+#[test]
+fn test_download_hash_matches_known_formats_and_skips_unknown() {
+    let sha1 = crate::empack::content::hex::encode(Sha1::digest(b"jar"));
+    assert!(download_hash_matches(b"jar", "sha1", &sha1));
+    assert!(download_hash_matches(b"jar", "sha1", &sha1.to_uppercase()));
+    assert!(!download_hash_matches(b"other", "sha1", &sha1));
+    assert!(download_hash_matches(b"jar", "murmur2", "12345"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_build_curseforge_writes_manifest_and_embeds_open_modrinth_files() {
+    let mut server = mockito::Server::new_async().await;
+    let jar_bytes = b"sodium jar bytes";
+    let jar_sha512 = {
+        use sha2::Digest;
+        crate::empack::content::hex::encode(sha2::Sha512::digest(jar_bytes))
+    };
+    let _project = server
+        .mock("GET", "/v2/project/AANobbMI")
+        .with_body(r#"{"license":{"id":"LGPL-3.0-only","name":"GNU LGPL v3"}}"#)
+        .create_async()
+        .await;
+    let _jar = server
+        .mock("GET", "/sodium.jar")
+        .with_body(jar_bytes.as_slice())
+        .create_async()
+        .await;
+
+    let workdir = mock_root().join("curseforge-build");
+    let session = curseforge_session(
+        &workdir,
+        &format!("{}/sodium.jar", server.url()),
+        &jar_sha512,
+    );
+    let mut orchestrator =
+        BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip).unwrap();
+    orchestrator.pack_refreshed = true;
+
+    let result = orchestrator
+        .build_curseforge_with_api_base(&server.url())
+        .unwrap();
+
+    assert!(result.success);
+    assert_eq!(result.target, BuildTarget::Curseforge);
+    let zip_path = workdir.join("dist").join("Test Pack-v1.0.0-curseforge.zip");
+    assert_eq!(result.output_path.as_ref(), Some(&zip_path));
+    assert!(session.filesystem().exists(&zip_path));
+    assert_eq!(result.warnings.len(), 1, "{:?}", result.warnings);
+    assert!(result.warnings[0].contains("Direct Mod"));
+
+    let dist_dir = workdir.join("dist").join("curseforge");
+    let embedded = session
+        .filesystem()
+        .read_bytes(&dist_dir.join("overrides").join("mods").join("sodium.jar"))
+        .unwrap();
+    assert_eq!(embedded, jar_bytes);
+    let options = session
+        .filesystem()
+        .read_bytes(&dist_dir.join("overrides").join("config").join("options.cfg"))
+        .unwrap();
+    assert_eq!(options, b"fov=90\n");
+
+    let manifest: serde_json::Value = serde_json::from_str(
+        &session
+            .filesystem()
+            .read_to_string(&dist_dir.join("manifest.json"))
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(manifest["manifestType"], "minecraftModpack");
+    assert_eq!(manifest["minecraft"]["version"], "1.21.1");
+    assert_eq!(manifest["minecraft"]["modLoaders"][0]["id"], "fabric-0.15.0");
+    assert_eq!(manifest["minecraft"]["modLoaders"][0]["primary"], true);
+    assert_eq!(manifest["overrides"], "overrides");
+    assert_eq!(
+        manifest["files"],
+        serde_json::json!([{ "projectID": 238222, "fileID": 5101366, "required": true }])
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_build_curseforge_warns_for_restrictively_licensed_modrinth_files() {
+    let mut server = mockito::Server::new_async().await;
+    let _project = server
+        .mock("GET", "/v2/project/AANobbMI")
+        .with_body(r#"{"license":{"id":"LicenseRef-All-Rights-Reserved"}}"#)
+        .create_async()
+        .await;
+    let jar = server
+        .mock("GET", "/sodium.jar")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let workdir = mock_root().join("curseforge-build-arr");
+    let session = curseforge_session(&workdir, &format!("{}/sodium.jar", server.url()), "abc");
+    let mut orchestrator =
+        BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip).unwrap();
+    orchestrator.pack_refreshed = true;
+
+    let result = orchestrator
+        .build_curseforge_with_api_base(&server.url())
+        .unwrap();
+
+    assert!(result.success);
+    assert!(
+        result.warnings.iter().any(|w| w.contains("Sodium")
+            && w.contains("LicenseRef-All-Rights-Reserved")),
+        "{:?}",
+        result.warnings
+    );
+    assert!(!session.filesystem().exists(
+        &workdir
+            .join("dist")
+            .join("curseforge")
+            .join("overrides")
+            .join("mods")
+            .join("sodium.jar")
+    ));
+    jar.assert_async().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_build_curseforge_manifest_round_trips_through_import() {
+    use std::io::Write;
+
+    let mut server = mockito::Server::new_async().await;
+    let _project = server
+        .mock("GET", "/v2/project/AANobbMI")
+        .with_body(r#"{"license":{"id":"LicenseRef-All-Rights-Reserved"}}"#)
+        .create_async()
+        .await;
+    let workdir = mock_root().join("curseforge-build-round-trip");
+    let session = curseforge_session(&workdir, &format!("{}/sodium.jar", server.url()), "abc");
+    let mut orchestrator =
+        BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip).unwrap();
+    orchestrator.pack_refreshed = true;
+    orchestrator
+        .build_curseforge_with_api_base(&server.url())
+        .unwrap();
+
+    let manifest = session
+        .filesystem()
+        .read_to_string(&workdir.join("dist").join("curseforge").join("manifest.json"))
+        .unwrap();
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    zip.start_file::<&str, ()>("manifest.json", zip::write::FileOptions::default())
+        .unwrap();
+    zip.write_all(manifest.as_bytes()).unwrap();
+    let bytes = zip.finish().unwrap().into_inner();
+    let archive_path = workdir.join("round-trip.zip");
+    session.filesystem().write_bytes(&archive_path, &bytes).unwrap();
+
+    let parsed = crate::empack::import::parse_curseforge_zip_with_filesystem(
+        session.filesystem(),
+        &archive_path,
+    )
+    .unwrap();
+
+    assert_eq!(parsed.identity.name, "Test Pack");
+    assert_eq!(parsed.target.minecraft_version, "1.21.1");
+    assert_eq!(parsed.target.loader_version, "0.15.0");
+    assert_eq!(parsed.content.len(), 1);
+}
+
+// ===== PRISM BUILD TESTS =====
+
+fn prism_pack_info(loader_type: &str, mc_version: &str, loader_version: &str) -> PackInfo {
+    PackInfo {
+        author: "A".to_string(),
+        name: "P".to_string(),
+        version: "1.0.0".to_string(),
+        mc_version: mc_version.to_string(),
+        loader_version: loader_version.to_string(),
+        loader_type: loader_type.to_string(),
+    }
+}
+
+#[test]
+fn test_prism_components_map_loaders_to_component_uids() {
+    let fabric = prism_components(&prism_pack_info("fabric", "1.21.1", "0.16.5"));
+    let uids: Vec<_> = fabric.iter().map(|c| (c.uid, c.version.as_str())).collect();
+    assert_eq!(
+        uids,
+        vec![
+            ("net.minecraft", "1.21.1"),
+            ("net.fabricmc.intermediary", "1.21.1"),
+            ("net.fabricmc.fabric-loader", "0.16.5"),
+        ]
+    );
+    assert!(fabric[0].important);
+
+    let neoforge = prism_components(&prism_pack_info("neoforge", "1.21.1", "21.1.77"));
+    assert_eq!(neoforge[1].uid, "net.neoforged");
+    assert_eq!(neoforge[1].version, "21.1.77");
+
+    let legacy_forge =
+        prism_components(&prism_pack_info("forge", "1.7.10", "10.13.4.1614-1.7.10"));
+    assert_eq!(legacy_forge[1].uid, "net.minecraftforge");
+    assert_eq!(legacy_forge[1].version, "10.13.4.1614");
+
+    let vanilla = prism_components(&prism_pack_info("vanilla", "1.21.1", ""));
+    assert_eq!(vanilla.len(), 1);
+}
+
+#[test]
+fn test_ensure_prism_prelaunch_appends_missing_command_only() {
+    let with_command = "[General]\nOverrideCommands=true\nPreLaunchCommand=custom\n";
+    assert_eq!(ensure_prism_prelaunch(with_command), with_command);
+
+    let without_command = ensure_prism_prelaunch("[General]\nname=Pack\n");
+    assert_eq!(
+        without_command,
+        format!("[General]\nname=Pack\nOverrideCommands=true\n{PRISM_PRELAUNCH_COMMAND}\n")
+    );
+}
+
+#[test]
+fn test_build_prism_writes_importable_instance() {
+    let workdir = mock_root().join("prism-build-success");
+    let bootstrap_jar_path = workdir.join("cache").join("packwiz-installer-bootstrap.jar");
+    let dist_dir = workdir.join("dist").join("prism");
+    let overrides_dir = workdir.join("dist").join("temp-mrpack-extract").join("overrides");
+
+    let filesystem = MockFileSystemProvider::new()
+        .with_current_dir(workdir.clone())
+        .with_configured_project(workdir.clone())
+        .with_file(bootstrap_jar_path.clone(), "bootstrap".to_string())
+        .with_file(
+            workdir.join("templates").join("client").join("notes.txt.template"),
+            "{{NAME}} {{VERSION}}".to_string(),
+        )
+        .with_file(overrides_dir.join("options.txt"), "fancy=true\n".to_string());
+    let session = MockCommandSession::new().with_filesystem(filesystem);
+    let mut orchestrator =
+        BuildOrchestrator::new(&session, crate::empack::archive::ArchiveFormat::Zip).unwrap();
+    orchestrator.pack_refreshed = true;
+    orchestrator.mrpack_extracted = true;
+
+    let result = orchestrator.build_prism_impl(&bootstrap_jar_path).unwrap();
+
+    assert!(result.success);
+    assert_eq!(result.target, BuildTarget::Prism);
+    let zip_path = workdir.join("dist").join("Test Pack-v1.0.0-prism.zip");
+    assert_eq!(result.output_path.as_ref(), Some(&zip_path));
+    assert!(session.filesystem().exists(&zip_path));
+
+    let minecraft_dir = dist_dir.join(".minecraft");
+    assert!(session
+        .filesystem()
+        .exists(&minecraft_dir.join("packwiz-installer-bootstrap.jar")));
+    assert!(session
+        .filesystem()
+        .exists(&minecraft_dir.join("pack").join("pack.toml")));
+    assert!(session.filesystem().exists(&minecraft_dir.join("options.txt")));
+    assert_eq!(
+        session
+            .filesystem()
+            .read_to_string(&dist_dir.join("notes.txt"))
+            .unwrap(),
+        "Test Pack 1.0.0"
+    );
+
+    let instance_cfg = session
+        .filesystem()
+        .read_to_string(&dist_dir.join("instance.cfg"))
+        .unwrap();
+    assert!(instance_cfg.contains("name=Test Pack"));
+    assert!(instance_cfg.contains("PreLaunchCommand="));
+    assert!(instance_cfg.contains("packwiz-installer-bootstrap.jar pack/pack.toml"));
+
+    let manifest =
+        crate::empack::import::parse_prism_instance_with_filesystem(session.filesystem(), &dist_dir)
+            .unwrap();
+    assert_eq!(manifest.identity.name, "Test Pack");
+    assert_eq!(manifest.target.minecraft_version, "1.21.1");
+    assert_eq!(
+        manifest.target.loader,
+        crate::empack::parsing::ModLoader::Fabric
+    );
+    assert_eq!(manifest.target.loader_version, "0.15.0");
+}
+
 // ===== W1-T4: NEOFORGE BUILD TESTS (B1) =====
 
 mod neoforge_build_tests {
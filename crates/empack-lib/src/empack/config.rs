@@ -1,6 +1,7 @@
 //! Configuration management for empack projects
 //! Unified empack.yml (user intent) and pack.toml (packwiz reality) handling
 
+use crate::empack::content::DependencySide;
 use crate::empack::parsing::ModLoader;
 use crate::primitives::{ProjectPlatform, ProjectType};
 use serde::{Deserialize, Serialize};
@@ -85,6 +86,10 @@ pub struct DependencyRecord {
     /// Optional pinned version ID
     #[serde(skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
+
+    /// Side restriction (client or server); absent means both
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub side: Option<DependencySide>,
 }
 
 /// A tracked local dependency entry in empack.yml
@@ -109,6 +114,10 @@ pub struct LocalDependencyRecord {
 
     /// SHA-256 of the tracked content
     pub sha256: String,
+
+    /// Side restriction (client or server); absent means both
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub side: Option<DependencySide>,
 }
 
 /// Hand-written search stub, resolved to DependencyRecord on sync
@@ -552,6 +561,7 @@ impl<'a> ConfigManager<'a> {
                     project_id: "AANobbMI".to_string(),
                     project_type: ProjectType::Mod,
                     version: None,
+                    side: None,
                 }),
             );
             deps.insert(
@@ -563,6 +573,7 @@ impl<'a> ConfigManager<'a> {
                     project_id: "gvQqBUqZ".to_string(),
                     project_type: ProjectType::Mod,
                     version: None,
+                    side: None,
                 }),
             );
             if loader == Some(ModLoader::Fabric) {
@@ -575,6 +586,7 @@ impl<'a> ConfigManager<'a> {
                         project_id: "P7dR8mSH".to_string(),
                         project_type: ProjectType::Mod,
                         version: None,
+                        side: None,
                     }),
                 );
             }
@@ -823,6 +835,37 @@ impl<'a> ConfigManager<'a> {
 
         Ok(())
     }
+
+    /// Move empack.yml to a new Minecraft version and loader.
+    ///
+    /// Clears `acceptable_game_versions`, which only made sense for the
+    /// previous Minecraft version.
+    pub fn set_platform_target(
+        &self,
+        minecraft_version: &str,
+        loader: Option<ModLoader>,
+        loader_version: Option<&str>,
+    ) -> Result<(), ConfigError> {
+        let empack_path = self.workdir.join("empack.yml");
+
+        let mut config = self.load_empack_config()?;
+
+        config.empack.minecraft_version = Some(minecraft_version.to_string());
+        config.empack.loader = loader;
+        config.empack.loader_version = loader_version.map(str::to_string);
+        config.empack.acceptable_game_versions = None;
+
+        let yaml_content = serde_saphyr::to_string(&config)
+            .map_err(|e| ConfigError::YamlSerError { source: e })?;
+
+        self.fs_provider
+            .write_file(&empack_path, &yaml_content)
+            .map_err(|e| ConfigError::IoError {
+                source: std::io::Error::other(e),
+            })?;
+
+        Ok(())
+    }
 }
 
 /// Serialize a fresh empack.yml string for a new project.
@@ -1112,6 +1112,7 @@ empack:
             project_id: "snDcZxV8".to_string(),
             project_type: ProjectType::Mod,
             version: None,
+            side: None,
         },
     );
 
@@ -1159,6 +1160,7 @@ empack:
         project_id: "P7dR8mSH".to_string(),
         project_type: ProjectType::Mod,
         version: None,
+        side: None,
     };
     let result1 = config_manager.add_dependency("fabric_api", record.clone());
     assert!(result1.is_ok());
@@ -1200,6 +1202,7 @@ empack:
             project_id: "P7dR8mSH".to_string(),
             project_type: ProjectType::Mod,
             version: None,
+            side: None,
         },
     );
 
@@ -1234,6 +1237,7 @@ fn test_add_dependency_no_existing_file() {
             project_id: "snDcZxV8".to_string(),
             project_type: ProjectType::Mod,
             version: None,
+            side: None,
         },
     );
 
@@ -1390,6 +1394,7 @@ fn test_serde_round_trip_resolved_modrinth_mod_no_version() {
         project_id: "AANobbMI".to_string(),
         project_type: ProjectType::Mod,
         version: None,
+        side: None,
     });
 
     let yaml = serde_saphyr::to_string(&entry).unwrap();
@@ -1404,6 +1409,7 @@ fn test_serde_round_trip_resolved_modrinth_mod_no_version() {
             project_id: "AANobbMI".to_string(),
             project_type: ProjectType::Mod,
             version: None,
+            side: None,
         })
     );
 }
@@ -1417,6 +1423,7 @@ fn test_serde_round_trip_resolved_curseforge_with_version() {
         project_id: "238222".to_string(),
         project_type: ProjectType::Mod,
         version: Some("4.7.0".to_string()),
+        side: None,
     });
 
     let yaml = serde_saphyr::to_string(&entry).unwrap();
@@ -1431,10 +1438,39 @@ fn test_serde_round_trip_resolved_curseforge_with_version() {
             project_id: "238222".to_string(),
             project_type: ProjectType::Mod,
             version: Some("4.7.0".to_string()),
+            side: None,
         })
     );
 }
 
+#[test]
+fn test_serde_round_trip_resolved_with_side() {
+    let entry = DependencyEntry::Resolved(DependencyRecord {
+        status: DependencyStatus::Resolved,
+        title: "Zoomify".to_string(),
+        platform: ProjectPlatform::Modrinth,
+        project_id: "w7ThoJFB".to_string(),
+        project_type: ProjectType::Mod,
+        version: None,
+        side: Some(DependencySide::Client),
+    });
+
+    let yaml = serde_saphyr::to_string(&entry).unwrap();
+    assert!(yaml.contains("side: client"));
+    let deserialized: DependencyEntry = serde_saphyr::from_str(&yaml).unwrap();
+    assert_eq!(deserialized, entry);
+
+    let unsided = serde_saphyr::to_string(&DependencyRecord {
+        side: None,
+        ..match entry {
+            DependencyEntry::Resolved(record) => record,
+            _ => unreachable!(),
+        }
+    })
+    .unwrap();
+    assert!(!unsided.contains("side"));
+}
+
 #[test]
 fn test_serde_round_trip_resolved_project_type_datapack() {
     let entry = DependencyEntry::Resolved(DependencyRecord {
@@ -1444,6 +1480,7 @@ fn test_serde_round_trip_resolved_project_type_datapack() {
         project_id: "test-dp".to_string(),
         project_type: ProjectType::Datapack,
         version: None,
+        side: None,
     });
 
     let yaml = serde_saphyr::to_string(&entry).unwrap();
@@ -1458,6 +1495,7 @@ fn test_serde_round_trip_resolved_project_type_datapack() {
             project_id: "test-dp".to_string(),
             project_type: ProjectType::Datapack,
             version: None,
+            side: None,
         })
     );
 }
@@ -1471,6 +1509,7 @@ fn test_serde_round_trip_resolved_project_type_resourcepack() {
         project_id: "test-rp".to_string(),
         project_type: ProjectType::ResourcePack,
         version: None,
+        side: None,
     });
 
     let yaml = serde_saphyr::to_string(&entry).unwrap();
@@ -1485,6 +1524,7 @@ fn test_serde_round_trip_resolved_project_type_resourcepack() {
             project_id: "test-rp".to_string(),
             project_type: ProjectType::ResourcePack,
             version: None,
+            side: None,
         })
     );
 }
@@ -1498,6 +1538,7 @@ fn test_serde_round_trip_resolved_project_type_shader() {
         project_id: "test-sh".to_string(),
         project_type: ProjectType::Shader,
         version: None,
+        side: None,
     });
 
     let yaml = serde_saphyr::to_string(&entry).unwrap();
@@ -1512,6 +1553,7 @@ fn test_serde_round_trip_resolved_project_type_shader() {
             project_id: "test-sh".to_string(),
             project_type: ProjectType::Shader,
             version: None,
+            side: None,
         })
     );
 }
@@ -1525,6 +1567,7 @@ fn test_serde_round_trip_resolved_unicode_title() {
         project_id: "1bokaNcj".to_string(),
         project_type: ProjectType::Mod,
         version: None,
+        side: None,
     });
 
     let yaml = serde_saphyr::to_string(&entry).unwrap();
@@ -1539,6 +1582,7 @@ fn test_serde_round_trip_resolved_unicode_title() {
             project_id: "1bokaNcj".to_string(),
             project_type: ProjectType::Mod,
             version: None,
+            side: None,
         })
     );
 }
@@ -1552,6 +1596,7 @@ fn test_serde_round_trip_resolved_special_chars_project_id() {
         project_id: "A-B_c.123".to_string(),
         project_type: ProjectType::Mod,
         version: None,
+        side: None,
     });
 
     let yaml = serde_saphyr::to_string(&entry).unwrap();
@@ -1566,6 +1611,7 @@ fn test_serde_round_trip_resolved_special_chars_project_id() {
             project_id: "A-B_c.123".to_string(),
             project_type: ProjectType::Mod,
             version: None,
+            side: None,
         })
     );
 }
@@ -1679,6 +1725,7 @@ type: mod
             project_id: "AANobbMI".to_string(),
             project_type: ProjectType::Mod,
             version: None,
+            side: None,
         })
     );
 }
@@ -1746,6 +1793,7 @@ fn test_local_dependency_entry_round_trips_with_exact_fields() {
         path: "pack/resourcepacks/example-pack.zip".to_string(),
         source_url: Some("https://example.com/example-pack.zip".to_string()),
         sha256: "deadbeefcafebabe".to_string(),
+        side: None,
     });
 
     let serialized = serde_saphyr::to_string(&entry).expect("serialize local dependency");
@@ -1774,6 +1822,7 @@ fn test_config_manager_find_and_remove_local_dependency_entries() {
         path: "pack/resourcepacks/example-pack.zip".to_string(),
         source_url: Some("https://example.com/example-pack.zip".to_string()),
         sha256: "deadbeefcafebabe".to_string(),
+        side: None,
     });
 
     config_manager
@@ -1854,6 +1903,7 @@ empack:
             project_id: "AANobbMI".to_string(),
             project_type: ProjectType::Mod,
             version: None,
+            side: None,
         })
     );
 
@@ -1896,6 +1946,7 @@ fn test_empack_config_round_trip_multiple_dependencies() {
                         project_id: "AANobbMI".to_string(),
                         project_type: ProjectType::Mod,
                         version: None,
+                        side: None,
                     }),
                 );
                 deps.insert(
@@ -1915,6 +1966,7 @@ fn test_empack_config_round_trip_multiple_dependencies() {
                         project_id: "431203".to_string(),
                         project_type: ProjectType::Shader,
                         version: Some("4.7.2".to_string()),
+                        side: None,
                     }),
                 );
                 deps
@@ -2120,6 +2172,7 @@ empack:
             project_id: "AANobbMI".to_string(),
             project_type: ProjectType::Mod,
             version: None,
+            side: None,
         })
     );
     assert_eq!(
@@ -2131,6 +2184,7 @@ empack:
             project_id: "P7dR8mSH".to_string(),
             project_type: ProjectType::Mod,
             version: None,
+            side: None,
         })
     );
 }
@@ -2175,6 +2229,7 @@ empack:
             project_id: "AANobbMI".to_string(),
             project_type: ProjectType::Mod,
             version: None,
+            side: None,
         },
     );
     assert!(result.is_ok());
@@ -2215,6 +2270,7 @@ empack:
             project_id: "AANobbMI".to_string(),
             project_type: ProjectType::Mod,
             version: None,
+            side: None,
         },
     );
     assert!(result.is_ok());
@@ -2280,6 +2336,7 @@ fn test_btreemap_dependencies_serialize_in_alphabetical_order() {
             project_id: "zebra-id".to_string(),
             project_type: ProjectType::Mod,
             version: None,
+            side: None,
         }),
     );
     deps.insert(
@@ -2291,6 +2348,7 @@ fn test_btreemap_dependencies_serialize_in_alphabetical_order() {
             project_id: "alpha-id".to_string(),
             project_type: ProjectType::Mod,
             version: None,
+            side: None,
         }),
     );
     deps.insert(
@@ -2302,6 +2360,7 @@ fn test_btreemap_dependencies_serialize_in_alphabetical_order() {
             project_id: "middle-id".to_string(),
             project_type: ProjectType::Mod,
             version: None,
+            side: None,
         }),
     );
 
@@ -2398,6 +2457,40 @@ empack:
     );
 }
 
+#[test]
+fn test_set_platform_target_rewrites_versions_and_keeps_dependencies() {
+    let workdir = mock_root().join("config");
+    let empack_content = r#"
+empack:
+  dependencies:
+    sodium:
+      status: resolved
+      title: Sodium
+      platform: modrinth
+      project_id: AANobbMI
+  minecraft_version: "1.20.1"
+  loader: forge
+  loader_version: "47.2.0"
+  acceptable_game_versions:
+    - "1.20"
+"#;
+
+    let provider = create_mock_config_provider(workdir.clone());
+    let provider = with_empack_yml(provider, &workdir, empack_content);
+    let config_manager = provider.config_manager(workdir.clone());
+
+    config_manager
+        .set_platform_target("1.21.1", Some(ModLoader::Fabric), Some("0.16.5"))
+        .unwrap();
+
+    let reloaded = config_manager.load_empack_config().unwrap();
+    assert_eq!(reloaded.empack.minecraft_version.as_deref(), Some("1.21.1"));
+    assert_eq!(reloaded.empack.loader, Some(ModLoader::Fabric));
+    assert_eq!(reloaded.empack.loader_version.as_deref(), Some("0.16.5"));
+    assert_eq!(reloaded.empack.acceptable_game_versions, None);
+    assert!(reloaded.empack.dependencies.contains_key("sodium"));
+}
+
 #[test]
 fn test_empack_yml_without_optional_fields() {
     let config = EmpackConfig {
@@ -2512,6 +2605,7 @@ empack:
                 project_id: "gvQqBUqZ".to_string(),
                 project_type: ProjectType::Mod,
                 version: None,
+                side: None,
             },
         )
         .unwrap();
@@ -5,6 +5,7 @@ use thiserror::Error;
 
 use crate::Result;
 use crate::application::session::NetworkProvider;
+use crate::networking::endpoints::{Service, send_with_fallback};
 
 // ---------------------------------------------------------------------------
 // SF-1: UrlKind classifier
@@ -199,9 +200,12 @@ impl ApiJarResolver<'_> {
     /// Attempt to identify a JAR via Modrinth's `/v2/version_file/{sha1}` endpoint.
     async fn query_modrinth(&self, sha1: &str) -> Result<Option<JarIdentity>> {
         let client = self.modrinth.http_client()?;
-        let url = format!("https://api.modrinth.com/v2/version_file/{sha1}?algorithm=sha1");
+        let urls = self.modrinth.endpoints().urls(
+            Service::ModrinthApi,
+            &format!("v2/version_file/{sha1}?algorithm=sha1"),
+        );
 
-        let response = client.get(&url).send().await?;
+        let response = send_with_fallback(urls, |url| client.get(url).send()).await?;
         if !response.status().is_success() {
             return Ok(None);
         }
@@ -223,7 +227,7 @@ impl ApiJarResolver<'_> {
     }
 
     /// Attempt to identify a JAR via CurseForge's fingerprint endpoint.
-    /// POST https://api.curseforge.com/v1/fingerprints with Murmur2 hash.
+    /// POST `{curseforge api}/v1/fingerprints` with Murmur2 hash.
     async fn query_curseforge(&self, murmur2_hash: u32) -> Result<Option<JarIdentity>> {
         let api_key = match self.curseforge_api_key {
             Some(k) => k,
@@ -232,12 +236,18 @@ impl ApiJarResolver<'_> {
 
         let client = self.curseforge.http_client()?;
 
-        let response = client
-            .post("https://api.curseforge.com/v1/fingerprints")
-            .header("x-api-key", api_key)
-            .json(&serde_json::json!({ "fingerprints": [murmur2_hash] }))
-            .send()
-            .await?;
+        let urls = self
+            .curseforge
+            .endpoints()
+            .urls(Service::CurseForgeApi, "v1/fingerprints");
+        let response = send_with_fallback(urls, |url| {
+            client
+                .post(url)
+                .header("x-api-key", api_key)
+                .json(&serde_json::json!({ "fingerprints": [murmur2_hash] }))
+                .send()
+        })
+        .await?;
 
         if !response.status().is_success() {
             return Ok(None);
@@ -357,6 +367,18 @@ pub enum SideRequirement {
     Unknown,
 }
 
+impl SideRequirement {
+    /// Parse a Modrinth `client_side`/`server_side` or mrpack `env` value.
+    pub fn from_modrinth(value: Option<&str>) -> Self {
+        match value {
+            Some("required") => SideRequirement::Required,
+            Some("optional") => SideRequirement::Optional,
+            Some("unsupported") => SideRequirement::Unsupported,
+            _ => SideRequirement::Unknown,
+        }
+    }
+}
+
 /// Client and server side requirements for a piece of content.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SideEnv {
@@ -364,6 +386,72 @@ pub struct SideEnv {
     pub server: SideRequirement,
 }
 
+impl SideEnv {
+    /// Collapse the requirements to the side the content is installed on.
+    ///
+    /// Anything that is not clearly one-sided is treated as `Both`.
+    pub fn side(&self) -> DependencySide {
+        use SideRequirement::*;
+        match (&self.client, &self.server) {
+            (Required, Unsupported) | (Required, Unknown) | (Optional, Unsupported) => {
+                DependencySide::Client
+            }
+            (Unsupported, Required) | (Unknown, Required) | (Unsupported, Optional) => {
+                DependencySide::Server
+            }
+            _ => DependencySide::Both,
+        }
+    }
+}
+
+/// Physical side a dependency is installed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencySide {
+    Client,
+    Server,
+    Both,
+}
+
+impl DependencySide {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DependencySide::Client => "client",
+            DependencySide::Server => "server",
+            DependencySide::Both => "both",
+        }
+    }
+
+    /// Parse a packwiz `.pw.toml` `side` value.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "client" => Some(DependencySide::Client),
+            "server" => Some(DependencySide::Server),
+            "both" => Some(DependencySide::Both),
+            _ => None,
+        }
+    }
+
+    /// Whether content on this side belongs in a distribution for `target`.
+    pub fn installs_on(&self, target: DependencySide) -> bool {
+        matches!(
+            (self, target),
+            (DependencySide::Both, _)
+                | (_, DependencySide::Both)
+                | (DependencySide::Client, DependencySide::Client)
+                | (DependencySide::Server, DependencySide::Server)
+        )
+    }
+
+    /// The side as stored in `empack.yml`, where `Both` is left implicit.
+    pub fn restriction(self) -> Option<Self> {
+        match self {
+            DependencySide::Both => None,
+            side => Some(side),
+        }
+    }
+}
+
 /// Which side an override directory targets.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum OverrideSide {
@@ -837,3 +837,39 @@ fn url_classify_error_debug() {
     let debug = format!("{:?}", err);
     assert!(debug.contains("Unrecognized"));
 }
+
+// ---------------------------------------------------------------------------
+// Side classification
+// ---------------------------------------------------------------------------
+
+#[test]
+fn side_env_collapses_to_dependency_side() {
+    let env = |client: Option<&str>, server: Option<&str>| SideEnv {
+        client: SideRequirement::from_modrinth(client),
+        server: SideRequirement::from_modrinth(server),
+    };
+
+    assert_eq!(
+        env(Some("required"), Some("unsupported")).side(),
+        DependencySide::Client
+    );
+    assert_eq!(
+        env(Some("unsupported"), Some("optional")).side(),
+        DependencySide::Server
+    );
+    assert_eq!(
+        env(Some("optional"), Some("optional")).side(),
+        DependencySide::Both
+    );
+    assert_eq!(env(None, None).side(), DependencySide::Both);
+}
+
+#[test]
+fn dependency_side_installs_on_matching_targets() {
+    assert!(DependencySide::Both.installs_on(DependencySide::Server));
+    assert!(DependencySide::Client.installs_on(DependencySide::Client));
+    assert!(!DependencySide::Client.installs_on(DependencySide::Server));
+    assert!(!DependencySide::Server.installs_on(DependencySide::Client));
+    assert_eq!(DependencySide::parse("server"), Some(DependencySide::Server));
+    assert_eq!(DependencySide::parse("sideways"), None);
+}
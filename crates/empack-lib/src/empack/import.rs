@@ -8,10 +8,17 @@ use serde::Deserialize;
 use thiserror::Error;
 
 use crate::Result;
-use crate::application::session::{Session, execute_process_with_live_issues};
-use crate::empack::config::{DependencyRecord, DependencyStatus};
-use crate::empack::content::{OverrideCategory, OverrideSide, SideEnv, SideRequirement};
+use crate::application::session::{FileSystemProvider, Session, execute_process_with_live_issues};
+use crate::empack::config::{
+    DependencyEntry, DependencyRecord, DependencyStatus, LocalDependencyRecord,
+};
+use crate::empack::content::{
+    DependencySide, JarIdentity, JarResolver, OverrideCategory, OverrideSide, SideEnv,
+    SideRequirement,
+};
 use crate::empack::parsing::ModLoader;
+use crate::empack::store::{ContentHash, ContentStore};
+use crate::networking::endpoints::{Endpoints, Service, send_with_fallback};
 use crate::networking::rate_budget::RateBudget;
 use crate::primitives::ProjectPlatform;
 use tracing::instrument;
@@ -22,15 +29,17 @@ use tracing::instrument;
 
 /// Parsed intermediate representation of a modpack manifest.
 ///
-/// Produced by platform-specific parsers (CurseForge zip, Modrinth mrpack).
-/// Consumed by the resolver and executor.
+/// Produced by platform-specific parsers (CurseForge zip, Modrinth mrpack,
+/// packwiz directory, Prism Launcher instance). Consumed by the resolver and executor.
 #[derive(Debug)]
 pub struct ModpackManifest {
     pub identity: PackIdentity,
     pub target: RuntimeTarget,
     pub content: Vec<ContentEntry>,
     pub overrides: Vec<OverrideEntry>,
+    pub options: PackOptions,
     pub source_platform: ProjectPlatform,
+    /// Archive or directory that override and embedded paths are relative to.
     pub archive_path: PathBuf,
 }
 
@@ -49,10 +58,18 @@ pub struct RuntimeTarget {
     pub loader_version: String,
 }
 
+/// Pack options declared by the source; CLI flags take precedence.
+#[derive(Debug, Default)]
+pub struct PackOptions {
+    pub datapack_folder: Option<String>,
+    pub acceptable_game_versions: Option<Vec<String>>,
+}
+
 #[derive(Debug, Clone)]
 pub enum ContentEntry {
     PlatformReferenced(PlatformRef),
     EmbeddedJar(EmbeddedJar),
+    DirectDownload(DirectDownloadRef),
 }
 
 #[derive(Debug, Clone)]
@@ -82,6 +99,19 @@ pub struct EmbeddedJar {
     pub env: SideEnv,
 }
 
+/// File known only by its download URL, such as a packwiz metafile without
+/// update metadata. Imported as a tracked local dependency.
+#[derive(Debug, Clone)]
+pub struct DirectDownloadRef {
+    pub destination_path: String,
+    pub url: Option<String>,
+    pub hashes: HashMap<String, String>,
+    pub name: String,
+    /// Dependency key for empack.yml (the packwiz metafile stem).
+    pub slug: String,
+    pub project_type: crate::primitives::ProjectType,
+}
+
 #[derive(Debug)]
 pub struct OverrideEntry {
     pub source_path: String,
@@ -95,6 +125,8 @@ pub struct OverrideEntry {
 pub struct ResolvedManifest {
     pub manifest: ModpackManifest,
     pub warnings: Vec<String>,
+    /// Embedded JARs identified by content hash and turned into platform references.
+    pub embedded_jars_identified: usize,
 }
 
 /// Configuration for the import executor.
@@ -124,6 +156,8 @@ pub struct ImportStats {
     pub platform_skipped: usize,
     pub embedded_jars_identified: usize,
     pub embedded_jars_unidentified: usize,
+    pub local_tracked: usize,
+    pub local_failed: usize,
     pub overrides_copied: usize,
     pub warnings: Vec<String>,
 }
@@ -143,6 +177,12 @@ pub enum ImportError {
     #[error("modrinth.index.json not found in mrpack archive")]
     ModrinthManifestMissing,
 
+    #[error("{0} not found in packwiz directory")]
+    PackwizFileMissing(String),
+
+    #[error("{0} not found in Prism Launcher instance")]
+    PrismFileMissing(String),
+
     #[error("failed to parse manifest: {0}")]
     ParseFailed(String),
 
@@ -424,6 +464,7 @@ fn parse_curseforge_zip_bytes(
         },
         content,
         overrides: override_entries,
+        options: PackOptions::default(),
         source_platform: ProjectPlatform::CurseForge,
         archive_path: archive_path.to_path_buf(),
     })
@@ -532,8 +573,8 @@ fn parse_modrinth_mrpack_bytes(
         .into_iter()
         .map(|f| {
             let env = SideEnv {
-                client: mr_side_requirement(f.env.client.as_deref()),
-                server: mr_side_requirement(f.env.server.as_deref()),
+                client: SideRequirement::from_modrinth(f.env.client.as_deref()),
+                server: SideRequirement::from_modrinth(f.env.server.as_deref()),
             };
             if !f.downloads.is_empty() {
                 let first_url = f.downloads.first().map(|s| s.as_str()).unwrap_or("");
@@ -596,11 +637,648 @@ fn parse_modrinth_mrpack_bytes(
         },
         content,
         overrides: override_entries,
+        options: PackOptions::default(),
         source_platform: ProjectPlatform::Modrinth,
         archive_path: file_path.to_path_buf(),
     })
 }
 
+// ---------------------------------------------------------------------------
+// packwiz directory TOML shapes
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+struct PwPack {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    author: Option<String>,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    index: PwPackIndex,
+    #[serde(default)]
+    versions: HashMap<String, String>,
+    #[serde(default)]
+    options: PwPackOptions,
+}
+
+#[derive(Deserialize)]
+struct PwPackIndex {
+    file: String,
+}
+
+#[derive(Deserialize, Default)]
+struct PwPackOptions {
+    #[serde(default, rename = "datapack-folder")]
+    datapack_folder: Option<String>,
+    #[serde(default, rename = "acceptable-game-versions")]
+    acceptable_game_versions: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct PwIndex {
+    #[serde(default)]
+    files: Vec<PwIndexFile>,
+}
+
+#[derive(Deserialize)]
+struct PwIndexFile {
+    file: String,
+    #[serde(default)]
+    metafile: bool,
+}
+
+#[derive(Deserialize)]
+struct PwMetafile {
+    name: String,
+    filename: String,
+    #[serde(default)]
+    side: Option<String>,
+    #[serde(default)]
+    download: PwDownload,
+    #[serde(default)]
+    update: Option<PwUpdate>,
+}
+
+#[derive(Deserialize, Default)]
+struct PwDownload {
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default, rename = "hash-format")]
+    hash_format: Option<String>,
+    #[serde(default)]
+    hash: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PwUpdate {
+    #[serde(default)]
+    modrinth: Option<PwModrinthUpdate>,
+    #[serde(default)]
+    curseforge: Option<PwCurseForgeUpdate>,
+}
+
+#[derive(Deserialize)]
+struct PwModrinthUpdate {
+    #[serde(rename = "mod-id")]
+    mod_id: String,
+    version: String,
+}
+
+#[derive(Deserialize)]
+struct PwCurseForgeUpdate {
+    #[serde(rename = "project-id")]
+    project_id: u64,
+    #[serde(rename = "file-id")]
+    file_id: u64,
+}
+
+/// Parse an existing packwiz directory (`pack.toml`, its index, and every metafile).
+pub fn parse_packwiz_directory(dir: &Path) -> Result<ModpackManifest> {
+    parse_packwiz_directory_with_filesystem(
+        &crate::application::session::LiveFileSystemProvider,
+        dir,
+    )
+}
+
+pub fn parse_packwiz_directory_with_filesystem(
+    fs: &dyn crate::application::session::FileSystemProvider,
+    dir: &Path,
+) -> Result<ModpackManifest> {
+    let pack: PwPack = read_packwiz_toml(fs, dir, "pack.toml")?;
+
+    let mc_version =
+        pack.versions
+            .get("minecraft")
+            .cloned()
+            .ok_or_else(|| ImportError::MissingField {
+                field: "versions.minecraft".to_string(),
+            })?;
+
+    let (loader, loader_version) = ["neoforge", "forge", "fabric", "quilt"]
+        .iter()
+        .find_map(|key| {
+            let version = pack.versions.get(*key)?;
+            let loader = ModLoader::parse(key).ok()?;
+            Some((loader, version.clone()))
+        })
+        .ok_or_else(|| ImportError::MissingField {
+            field: "versions.<loader>".to_string(),
+        })?;
+
+    // Index entries are relative to the index file, which is itself relative
+    // to pack.toml
+    let index_path = pack.index.file.replace('\\', "/");
+    let index_dir = index_path
+        .rsplit_once('/')
+        .map(|(parent, _)| format!("{parent}/"))
+        .unwrap_or_default();
+    let index: PwIndex = read_packwiz_toml(fs, dir, &index_path)?;
+
+    let datapack_folder = pack.options.datapack_folder.clone();
+    let mut content = Vec::new();
+    let mut override_entries = Vec::new();
+    for entry in index.files {
+        let relative = format!("{index_dir}{}", entry.file.replace('\\', "/"));
+        if entry.metafile {
+            let metafile: PwMetafile = read_packwiz_toml(fs, dir, &relative)?;
+            content.push(packwiz_content_entry(
+                &relative,
+                metafile,
+                datapack_folder.as_deref(),
+            ));
+        } else {
+            override_entries.push(OverrideEntry {
+                source_path: relative.clone(),
+                category: classify_override(&relative),
+                destination_path: relative,
+                side: OverrideSide::Both,
+            });
+        }
+    }
+
+    let source_platform = content
+        .iter()
+        .find_map(|entry| match entry {
+            ContentEntry::PlatformReferenced(pref) => Some(pref.platform),
+            _ => None,
+        })
+        .unwrap_or(ProjectPlatform::Modrinth);
+
+    let name = pack.name.unwrap_or_else(|| {
+        dir.file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or("Pack")
+            .to_string()
+    });
+
+    Ok(ModpackManifest {
+        identity: PackIdentity {
+            name,
+            version: pack.version.unwrap_or_else(|| "1.0.0".to_string()),
+            author: pack.author,
+            summary: pack.description,
+        },
+        target: RuntimeTarget {
+            minecraft_version: mc_version,
+            loader,
+            loader_version,
+        },
+        content,
+        overrides: override_entries,
+        options: PackOptions {
+            datapack_folder,
+            acceptable_game_versions: pack.options.acceptable_game_versions,
+        },
+        source_platform,
+        archive_path: dir.to_path_buf(),
+    })
+}
+
+fn read_packwiz_toml<T: serde::de::DeserializeOwned>(
+    fs: &dyn crate::application::session::FileSystemProvider,
+    dir: &Path,
+    relative: &str,
+) -> Result<T> {
+    let path = sanitize_archive_path(dir, relative)?;
+    if !fs.exists(&path) {
+        return Err(ImportError::PackwizFileMissing(relative.to_string()).into());
+    }
+    let content = fs.read_to_string(&path)?;
+    toml::from_str(&content)
+        .map_err(|e| ImportError::ParseFailed(format!("{relative}: {e}")).into())
+}
+
+/// Turn one packwiz metafile into a content entry.
+///
+/// `[update.modrinth]` and `[update.curseforge]` become platform references
+/// carrying the metafile's name, slug, URL, and hash so packwiz can re-add
+/// them offline. Anything else is a direct download.
+fn packwiz_content_entry(
+    metafile_path: &str,
+    metafile: PwMetafile,
+    datapack_folder: Option<&str>,
+) -> ContentEntry {
+    let (folder, file) = metafile_path
+        .rsplit_once('/')
+        .unwrap_or(("", metafile_path));
+    let slug = file.strip_suffix(".pw.toml").unwrap_or(file).to_string();
+    let destination_path = if folder.is_empty() {
+        metafile.filename.clone()
+    } else {
+        format!("{folder}/{}", metafile.filename)
+    };
+    let project_type = packwiz_project_type(folder, datapack_folder);
+
+    let mut hashes = HashMap::new();
+    if let (Some(format), Some(hash)) = (metafile.download.hash_format, metafile.download.hash) {
+        hashes.insert(format.to_lowercase(), hash.to_lowercase());
+    }
+    let env = match metafile.side.as_deref() {
+        Some("client") => SideEnv {
+            client: SideRequirement::Required,
+            server: SideRequirement::Unsupported,
+        },
+        Some("server") => SideEnv {
+            client: SideRequirement::Unsupported,
+            server: SideRequirement::Required,
+        },
+        _ => SideEnv {
+            client: SideRequirement::Required,
+            server: SideRequirement::Required,
+        },
+    };
+
+    let (platform, project_id, file_id) = match metafile.update {
+        Some(PwUpdate {
+            modrinth: Some(modrinth),
+            ..
+        }) => (ProjectPlatform::Modrinth, modrinth.mod_id, modrinth.version),
+        Some(PwUpdate {
+            curseforge: Some(curseforge),
+            ..
+        }) => (
+            ProjectPlatform::CurseForge,
+            curseforge.project_id.to_string(),
+            curseforge.file_id.to_string(),
+        ),
+        _ => {
+            return ContentEntry::DirectDownload(DirectDownloadRef {
+                destination_path,
+                url: metafile.download.url,
+                hashes,
+                name: metafile.name,
+                slug,
+                project_type,
+            });
+        }
+    };
+
+    let cf_class_id = (platform == ProjectPlatform::CurseForge
+        && project_type == crate::primitives::ProjectType::Datapack)
+        .then_some(6945);
+
+    ContentEntry::PlatformReferenced(PlatformRef {
+        destination_path,
+        platform,
+        project_id,
+        file_id: Some(file_id),
+        hashes,
+        download_urls: metafile.download.url.into_iter().collect(),
+        env,
+        required: true,
+        resolved_name: Some(metafile.name),
+        resolved_slug: Some(slug),
+        resolved_type: Some(project_type),
+        cf_class_id,
+    })
+}
+
+fn packwiz_project_type(
+    folder: &str,
+    datapack_folder: Option<&str>,
+) -> crate::primitives::ProjectType {
+    use crate::primitives::ProjectType;
+
+    if folder == "datapacks"
+        || datapack_folder.is_some_and(|datapacks| datapacks.trim_matches('/') == folder)
+    {
+        return ProjectType::Datapack;
+    }
+    match folder {
+        "resourcepacks" => ProjectType::ResourcePack,
+        "shaderpacks" => ProjectType::Shader,
+        _ => ProjectType::Mod,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Prism Launcher / MultiMC instance shapes
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+struct MmcPack {
+    #[serde(default)]
+    components: Vec<MmcComponent>,
+}
+
+#[derive(Deserialize)]
+struct MmcComponent {
+    uid: String,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default, rename = "cachedVersion")]
+    cached_version: Option<String>,
+}
+
+impl MmcComponent {
+    fn version(&self) -> Option<&str> {
+        self.version.as_deref().or(self.cached_version.as_deref())
+    }
+}
+
+/// Game directory names used by Prism Launcher (`.minecraft`) and MultiMC (`minecraft`).
+const PRISM_GAME_DIRS: &[&str] = &[".minecraft", "minecraft"];
+
+/// Launcher-generated entries under the game directory that are never pack content.
+const PRISM_RUNTIME_ENTRIES: &[&str] = &[
+    "logs",
+    "crash-reports",
+    "screenshots",
+    "saves",
+    "backups",
+    ".fabric",
+    ".mixin.out",
+    "usercache.json",
+    "usernamecache.json",
+    "command_history.txt",
+];
+
+/// A file inside an instance's game directory.
+struct PrismFile {
+    /// Path relative to the import source (instance directory or zip root).
+    source_path: String,
+    /// Path relative to the game directory.
+    relative: String,
+}
+
+/// Parse a Prism Launcher or MultiMC instance (directory or exported zip
+/// containing `instance.cfg` and `mmc-pack.json`).
+pub fn parse_prism_instance(path: &Path) -> Result<ModpackManifest> {
+    parse_prism_instance_with_filesystem(&crate::application::session::LiveFileSystemProvider, path)
+}
+
+pub fn parse_prism_instance_with_filesystem(
+    fs: &dyn crate::application::session::FileSystemProvider,
+    path: &Path,
+) -> Result<ModpackManifest> {
+    if fs.is_directory(path) {
+        return parse_prism_instance_directory(fs, path);
+    }
+
+    let bytes = fs
+        .read_bytes(path)
+        .with_context(|| format!("opening archive: {}", path.display()))?;
+    parse_prism_instance_zip_bytes(bytes, path)
+}
+
+fn parse_prism_instance_directory(
+    fs: &dyn crate::application::session::FileSystemProvider,
+    dir: &Path,
+) -> Result<ModpackManifest> {
+    let mmc_pack_path = dir.join("mmc-pack.json");
+    if !fs.exists(&mmc_pack_path) {
+        return Err(ImportError::PrismFileMissing("mmc-pack.json".to_string()).into());
+    }
+    let mmc_pack = fs.read_to_string(&mmc_pack_path)?;
+
+    let instance_cfg_path = dir.join("instance.cfg");
+    let instance_cfg = if fs.exists(&instance_cfg_path) {
+        Some(fs.read_to_string(&instance_cfg_path)?)
+    } else {
+        None
+    };
+
+    let mut files = Vec::new();
+    if let Some(game_dir) = PRISM_GAME_DIRS
+        .iter()
+        .find(|name| fs.is_directory(&dir.join(name)))
+    {
+        let mut relatives = Vec::new();
+        collect_directory_files(fs, &dir.join(game_dir), "", &mut relatives)?;
+        files = relatives
+            .into_iter()
+            .map(|relative| PrismFile {
+                source_path: format!("{game_dir}/{relative}"),
+                relative,
+            })
+            .collect();
+    }
+
+    build_prism_manifest(
+        &mmc_pack,
+        instance_cfg.as_deref(),
+        files,
+        |source_path| fs.read_bytes(&sanitize_archive_path(dir, source_path)?),
+        dir,
+    )
+}
+
+fn parse_prism_instance_zip_bytes(
+    archive_bytes: Vec<u8>,
+    archive_path: &Path,
+) -> Result<ModpackManifest> {
+    let mut archive = open_zip_archive(archive_bytes)?;
+    let names: Vec<String> = archive.file_names().map(str::to_string).collect();
+
+    let prefix = prism_instance_prefix(names.iter().map(String::as_str))
+        .ok_or_else(|| ImportError::PrismFileMissing("mmc-pack.json".to_string()))?;
+
+    let mmc_pack = read_zip_entry_to_string(
+        archive
+            .by_name(&format!("{prefix}mmc-pack.json"))
+            .map_err(|e| ImportError::ArchiveRead(e.to_string()))?,
+    )?;
+    let instance_cfg = match archive.by_name(&format!("{prefix}instance.cfg")) {
+        Ok(entry) => Some(read_zip_entry_to_string(entry)?),
+        Err(_) => None,
+    };
+
+    let game_prefix = PRISM_GAME_DIRS
+        .iter()
+        .map(|name| format!("{prefix}{name}/"))
+        .find(|game_prefix| names.iter().any(|n| n.starts_with(game_prefix.as_str())));
+
+    let mut files = Vec::new();
+    if let Some(game_prefix) = game_prefix {
+        for name in &names {
+            if let Some(relative) = name.strip_prefix(game_prefix.as_str())
+                && !relative.is_empty()
+                && !name.ends_with('/')
+            {
+                files.push(PrismFile {
+                    source_path: name.clone(),
+                    relative: relative.to_string(),
+                });
+            }
+        }
+    }
+
+    build_prism_manifest(
+        &mmc_pack,
+        instance_cfg.as_deref(),
+        files,
+        |source_path| {
+            let mut entry = archive
+                .by_name(source_path)
+                .map_err(|e| ImportError::ArchiveRead(e.to_string()))?;
+            let mut bytes = Vec::new();
+            entry.read_to_end(&mut bytes)?;
+            Ok(bytes)
+        },
+        archive_path,
+    )
+}
+
+/// Build a manifest from an instance's component list and game directory files.
+///
+/// Jars directly under `mods/` become embedded JARs for hash identification;
+/// everything else, apart from launcher runtime state and disabled mods, is
+/// carried over as an override.
+fn build_prism_manifest(
+    mmc_pack: &str,
+    instance_cfg: Option<&str>,
+    files: Vec<PrismFile>,
+    mut read_bytes: impl FnMut(&str) -> Result<Vec<u8>>,
+    source_path: &Path,
+) -> Result<ModpackManifest> {
+    let pack: MmcPack = serde_json::from_str(mmc_pack)
+        .map_err(|e| ImportError::ParseFailed(format!("mmc-pack.json: {e}")))?;
+
+    let mc_version = pack
+        .components
+        .iter()
+        .find(|c| c.uid == "net.minecraft")
+        .and_then(MmcComponent::version)
+        .ok_or_else(|| ImportError::MissingField {
+            field: "components.net.minecraft".to_string(),
+        })?
+        .to_string();
+
+    let (loader, loader_version) = pack
+        .components
+        .iter()
+        .find_map(|c| {
+            let loader = match c.uid.as_str() {
+                "net.neoforged" => ModLoader::NeoForge,
+                "net.minecraftforge" => ModLoader::Forge,
+                "net.fabricmc.fabric-loader" => ModLoader::Fabric,
+                "org.quiltmc.quilt-loader" => ModLoader::Quilt,
+                _ => return None,
+            };
+            Some((loader, c.version()?.to_string()))
+        })
+        .ok_or_else(|| ImportError::MissingField {
+            field: "components.<loader>".to_string(),
+        })?;
+
+    let mut content = Vec::new();
+    let mut override_entries = Vec::new();
+    for file in files {
+        let relative = file.relative.replace('\\', "/");
+        let top_level = relative.split('/').next().unwrap_or_default();
+        let lower = relative.to_lowercase();
+        if PRISM_RUNTIME_ENTRIES.contains(&top_level) || lower.ends_with(".disabled") {
+            continue;
+        }
+
+        let is_mod_jar = lower.starts_with("mods/")
+            && lower.ends_with(".jar")
+            && !relative["mods/".len()..].contains('/');
+        if is_mod_jar {
+            let bytes = read_bytes(&file.source_path)?;
+            let mut hashes = HashMap::new();
+            hashes.insert("sha1".to_string(), {
+                use sha1::Digest;
+                crate::empack::content::hex::encode(sha1::Sha1::digest(&bytes))
+            });
+            content.push(ContentEntry::EmbeddedJar(EmbeddedJar {
+                source_path: file.source_path,
+                destination_path: relative,
+                hashes,
+                file_size: bytes.len() as u64,
+                env: SideEnv {
+                    client: SideRequirement::Unknown,
+                    server: SideRequirement::Unknown,
+                },
+            }));
+        } else {
+            override_entries.push(OverrideEntry {
+                source_path: file.source_path,
+                category: classify_override(&relative),
+                destination_path: relative,
+                side: OverrideSide::Both,
+            });
+        }
+    }
+
+    let name = instance_cfg
+        .and_then(|cfg| {
+            cfg.lines()
+                .find_map(|line| line.trim().strip_prefix("name="))
+                .map(|name| name.trim().to_string())
+        })
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| {
+            source_path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("Pack")
+                .to_string()
+        });
+
+    Ok(ModpackManifest {
+        identity: PackIdentity {
+            name,
+            version: "1.0.0".to_string(),
+            author: None,
+            summary: None,
+        },
+        target: RuntimeTarget {
+            minecraft_version: mc_version,
+            loader,
+            loader_version,
+        },
+        content,
+        overrides: override_entries,
+        options: PackOptions::default(),
+        source_platform: ProjectPlatform::Modrinth,
+        archive_path: source_path.to_path_buf(),
+    })
+}
+
+/// Recursively list files under `dir` as `/`-separated paths relative to it.
+fn collect_directory_files(
+    fs: &dyn crate::application::session::FileSystemProvider,
+    dir: &Path,
+    prefix: &str,
+    out: &mut Vec<String>,
+) -> Result<()> {
+    let mut children: Vec<PathBuf> = fs.get_file_list(dir)?.into_iter().collect();
+    children.sort();
+    for child in children {
+        let Some(name) = child.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let relative = format!("{prefix}{name}");
+        if fs.is_directory(&child) {
+            collect_directory_files(fs, &child, &format!("{relative}/"), out)?;
+        } else {
+            out.push(relative);
+        }
+    }
+    Ok(())
+}
+
+/// Find the archive folder holding an instance's `mmc-pack.json` and
+/// `instance.cfg`.
+///
+/// Prism exports place them at the zip root; MultiMC nests them one folder deep.
+fn prism_instance_prefix<'a>(names: impl Iterator<Item = &'a str>) -> Option<String> {
+    let names: std::collections::HashSet<&str> = names.collect();
+    names
+        .iter()
+        .filter_map(|name| name.strip_suffix("mmc-pack.json"))
+        .filter(|prefix| {
+            prefix.is_empty() || (prefix.ends_with('/') && prefix.matches('/').count() == 1)
+        })
+        .filter(|prefix| names.contains(format!("{prefix}instance.cfg").as_str()))
+        .min_by_key(|prefix| prefix.len())
+        .map(|prefix| prefix.to_string())
+}
+
 // ---------------------------------------------------------------------------
 // Resolver
 // ---------------------------------------------------------------------------
@@ -609,26 +1287,41 @@ fn parse_modrinth_mrpack_bytes(
 /// pacing; the semaphore now only caps task and connection pressure.
 const RESOLVE_CONCURRENCY: usize = 10;
 
+/// Platform API bases, each in fallback order
 #[derive(Clone, Debug)]
 struct ResolveApiBases {
-    modrinth: String,
-    curseforge: String,
+    modrinth: Vec<String>,
+    curseforge: Vec<String>,
 }
 
 impl ResolveApiBases {
-    fn production() -> Self {
+    fn from_endpoints(modrinth: &Endpoints, curseforge: &Endpoints) -> Self {
+        let owned = |endpoints: &Endpoints, service| {
+            endpoints
+                .bases(service)
+                .into_iter()
+                .map(str::to_string)
+                .collect()
+        };
         Self {
-            modrinth: "https://api.modrinth.com".to_string(),
-            curseforge: "https://api.curseforge.com".to_string(),
+            modrinth: owned(modrinth, Service::ModrinthApi),
+            curseforge: owned(curseforge, Service::CurseForgeApi),
         }
     }
 
-    fn modrinth_url(&self, path: &str) -> String {
-        format!("{}/{}", self.modrinth.trim_end_matches('/'), path)
+    fn modrinth_urls(&self, path: &str) -> Vec<String> {
+        Self::join(&self.modrinth, path)
+    }
+
+    fn curseforge_urls(&self, path: &str) -> Vec<String> {
+        Self::join(&self.curseforge, path)
     }
 
-    fn curseforge_url(&self, path: &str) -> String {
-        format!("{}/{}", self.curseforge.trim_end_matches('/'), path)
+    fn join(bases: &[String], path: &str) -> Vec<String> {
+        bases
+            .iter()
+            .map(|base| format!("{}/{}", base.trim_end_matches('/'), path))
+            .collect()
     }
 }
 
@@ -642,38 +1335,53 @@ struct ResolveRateContext {
 /// Enrich a raw manifest via platform APIs to resolve names, types, and
 /// identify embedded JARs.
 ///
+/// Embedded JARs are identified through [`ApiJarResolver`](crate::empack::content::ApiJarResolver)
+/// before platform references are resolved, so matches are resolved like any
+/// other reference.
+///
 /// Platform references are resolved concurrently (bounded by
 /// [`RESOLVE_CONCURRENCY`]) to reduce wall-clock time on large modpacks.
 /// The output order matches the input order.
 #[instrument(skip_all, fields(content_count = manifest.content.len()))]
 pub async fn resolve_manifest(
     manifest: ModpackManifest,
+    filesystem: &dyn FileSystemProvider,
     modrinth_api: &dyn crate::application::session::NetworkProvider,
     curseforge_api: &dyn crate::application::session::NetworkProvider,
     curseforge_api_key: Option<&str>,
     display: &dyn crate::display::providers::DisplayProvider,
     rate_budgets: &crate::networking::rate_budget::HostBudgetRegistry,
 ) -> Result<ResolvedManifest> {
+    let jar_resolver = crate::empack::content::ApiJarResolver {
+        modrinth: modrinth_api,
+        curseforge: curseforge_api,
+        curseforge_api_key,
+    };
     resolve_manifest_with_api_bases(
         manifest,
+        filesystem,
         modrinth_api,
         curseforge_api,
         curseforge_api_key,
         display,
         rate_budgets,
-        ResolveApiBases::production(),
+        ResolveApiBases::from_endpoints(modrinth_api.endpoints(), curseforge_api.endpoints()),
+        &jar_resolver,
     )
     .await
 }
 
-async fn resolve_manifest_with_api_bases(
+#[allow(clippy::too_many_arguments)]
+async fn resolve_manifest_with_api_bases<R: JarResolver>(
     manifest: ModpackManifest,
+    filesystem: &dyn FileSystemProvider,
     modrinth_api: &dyn crate::application::session::NetworkProvider,
     curseforge_api: &dyn crate::application::session::NetworkProvider,
     curseforge_api_key: Option<&str>,
     display: &dyn crate::display::providers::DisplayProvider,
     rate_budgets: &crate::networking::rate_budget::HostBudgetRegistry,
     api_bases: ResolveApiBases,
+    jar_resolver: &R,
 ) -> Result<ResolvedManifest> {
     let resolve_start = std::time::Instant::now();
     let mut warnings = Vec::new();
@@ -685,8 +1393,15 @@ async fn resolve_manifest_with_api_bases(
     let cf_client = curseforge_api.http_client()?;
     let cf_key: Option<String> = curseforge_api_key.map(|k| k.to_string());
 
-    let cf_file_ids: Vec<u64> = manifest
-        .content
+    let (content, embedded_jars_identified) = identify_embedded_jars(
+        filesystem,
+        manifest.content,
+        &manifest.archive_path,
+        jar_resolver,
+    )
+    .await?;
+
+    let cf_file_ids: Vec<u64> = content
         .iter()
         .filter_map(|e| match e {
             ContentEntry::PlatformReferenced(p)
@@ -727,7 +1442,7 @@ async fn resolve_manifest_with_api_bases(
     let mut handles: Vec<ResolveHandle> = Vec::new();
     let mut passthrough: Vec<(usize, ContentEntry, Vec<String>)> = Vec::new();
 
-    for (i, entry) in manifest.content.into_iter().enumerate() {
+    for (i, entry) in content.into_iter().enumerate() {
         match entry {
             ContentEntry::PlatformReferenced(mut pref) => {
                 if pref.platform == ProjectPlatform::CurseForge
@@ -787,12 +1502,15 @@ async fn resolve_manifest_with_api_bases(
             }
             ContentEntry::EmbeddedJar(embed) => {
                 let w = vec![format!(
-                    "embedded JAR '{}' cannot be identified while inside archive; \
-                     resolve after extraction",
+                    "embedded JAR '{}' was not identified on Modrinth or CurseForge; \
+                     extracting it as-is",
                     embed.source_path
                 )];
                 passthrough.push((i, ContentEntry::EmbeddedJar(embed), w));
             }
+            ContentEntry::DirectDownload(download) => {
+                passthrough.push((i, ContentEntry::DirectDownload(download), Vec::new()));
+            }
         }
     }
 
@@ -869,9 +1587,127 @@ async fn resolve_manifest_with_api_bases(
             ..manifest
         },
         warnings,
+        embedded_jars_identified,
     })
 }
 
+/// Identify embedded JARs by content hash through a [`JarResolver`].
+///
+/// Matches become platform references so packwiz re-adds them and empack.yml
+/// tracks them; unmatched entries and lookup failures stay embedded. Archive
+/// entries are extracted to a scratch directory first because the CurseForge
+/// fingerprint needs the file on disk. An archive that cannot be read or
+/// opened fails the import.
+async fn identify_embedded_jars<R: JarResolver>(
+    filesystem: &dyn FileSystemProvider,
+    content: Vec<ContentEntry>,
+    archive_path: &Path,
+    jar_resolver: &R,
+) -> Result<(Vec<ContentEntry>, usize)> {
+    let has_jars = content.iter().any(|entry| {
+        matches!(entry, ContentEntry::EmbeddedJar(embed)
+            if embed.destination_path.to_lowercase().ends_with(".jar"))
+    });
+    if !has_jars {
+        return Ok((content, 0));
+    }
+
+    let mut archive = if filesystem.is_directory(archive_path) {
+        None
+    } else {
+        let bytes = filesystem
+            .read_bytes(archive_path)
+            .with_context(|| format!("reading {}", archive_path.display()))?;
+        Some(open_zip_archive(bytes)?)
+    };
+    let scratch = tempfile::tempdir().ok();
+
+    let mut identified = 0;
+    let mut out = Vec::with_capacity(content.len());
+    for entry in content {
+        let embed = match entry {
+            ContentEntry::EmbeddedJar(embed)
+                if embed.destination_path.to_lowercase().ends_with(".jar") =>
+            {
+                embed
+            }
+            other => {
+                out.push(other);
+                continue;
+            }
+        };
+
+        let path = match archive.as_mut() {
+            None => sanitize_archive_path(archive_path, &embed.source_path).ok(),
+            Some(archive) => scratch.as_ref().and_then(|scratch| {
+                let mut zip_entry = archive.by_name(&embed.source_path).ok()?;
+                let mut bytes = Vec::new();
+                zip_entry.read_to_end(&mut bytes).ok()?;
+                let dest = scratch.path().join(filename_from_path(&embed.source_path));
+                std::fs::write(&dest, bytes).ok()?;
+                Some(dest)
+            }),
+        };
+        let Some(path) = path else {
+            out.push(ContentEntry::EmbeddedJar(embed));
+            continue;
+        };
+
+        let request = crate::empack::content::JarIdentifyRequest {
+            path,
+            sha1: embed.hashes.get("sha1").cloned(),
+            sha512: embed.hashes.get("sha512").cloned(),
+        };
+        let (platform, project_id, file_id) = match jar_resolver.identify(request).await {
+            Ok(JarIdentity::Modrinth {
+                project_id,
+                version_id,
+                ..
+            }) => (ProjectPlatform::Modrinth, project_id, version_id),
+            Ok(JarIdentity::CurseForge {
+                project_id,
+                file_id,
+                ..
+            }) => (
+                ProjectPlatform::CurseForge,
+                project_id.to_string(),
+                file_id.to_string(),
+            ),
+            Ok(JarIdentity::Unidentified) => {
+                out.push(ContentEntry::EmbeddedJar(embed));
+                continue;
+            }
+            Err(e) => {
+                tracing::debug!(
+                    path = %embed.source_path,
+                    error = %e,
+                    "embedded JAR identification failed"
+                );
+                out.push(ContentEntry::EmbeddedJar(embed));
+                continue;
+            }
+        };
+
+        identified += 1;
+        out.push(ContentEntry::PlatformReferenced(PlatformRef {
+            destination_path: embed.destination_path,
+            platform,
+            project_id,
+            file_id: Some(file_id),
+            hashes: embed.hashes,
+            download_urls: Vec::new(),
+            env: embed.env,
+            required: true,
+            resolved_name: None,
+            resolved_slug: None,
+            resolved_type: None,
+            cf_class_id: None,
+        }));
+    }
+
+    Ok((out, identified))
+}
+
 /// Resolve a single platform reference using pre-extracted HTTP clients.
 ///
 /// This variant accepts `reqwest::Client` directly so it can be called from
@@ -972,9 +1808,9 @@ async fn resolve_modrinth_project_with_client(
     if pref.file_id.is_none()
         && let Some(sha1) = pref.hashes.get("sha1")
     {
-        let url = api_bases.modrinth_url(&format!("v2/version_file/{}?algorithm=sha1", sha1));
+        let urls = api_bases.modrinth_urls(&format!("v2/version_file/{}?algorithm=sha1", sha1));
         apply_rate_budget(budget).await;
-        if let Ok(resp) = client.get(&url).send().await {
+        if let Ok(resp) = send_with_fallback(urls, |url| client.get(url).send()).await {
             record_rate_budget(budget, &resp);
             if resp.status().is_success()
                 && let Ok(body) = resp.json::<MrVersionFileResponse>().await
@@ -984,10 +1820,10 @@ async fn resolve_modrinth_project_with_client(
         }
     }
 
-    let url = api_bases.modrinth_url(&format!("v2/project/{}", pref.project_id));
+    let urls = api_bases.modrinth_urls(&format!("v2/project/{}", pref.project_id));
 
     apply_rate_budget(budget).await;
-    let response = match client.get(&url).send().await {
+    let response = match send_with_fallback(urls, |url| client.get(url).send()).await {
         Ok(r) => r,
         Err(e) => {
             warnings.push(format!(
@@ -1061,10 +1897,14 @@ async fn resolve_curseforge_project_with_client(
         }
     };
 
-    let url = api_bases.curseforge_url(&format!("v1/mods/{}", pref.project_id));
+    let urls = api_bases.curseforge_urls(&format!("v1/mods/{}", pref.project_id));
 
     apply_rate_budget(budget).await;
-    let response = match client.get(&url).header("x-api-key", api_key).send().await {
+    let response = match send_with_fallback(urls, |url| {
+        client.get(url).header("x-api-key", api_key).send()
+    })
+    .await
+    {
         Ok(r) => r,
         Err(e) => {
             warnings.push(format!(
@@ -1151,12 +1991,14 @@ async fn resolve_curseforge_file_ids(
     for chunk in file_ids.chunks(50) {
         let body = serde_json::json!({ "fileIds": chunk });
         apply_rate_budget(budget).await;
-        let response = match client
-            .post(api_bases.curseforge_url("v1/mods/files"))
-            .header("x-api-key", api_key)
-            .json(&body)
-            .send()
-            .await
+        let response = match send_with_fallback(api_bases.curseforge_urls("v1/mods/files"), |url| {
+            client
+                .post(url)
+                .header("x-api-key", api_key)
+                .json(&body)
+                .send()
+        })
+        .await
         {
             Ok(r) => r,
             Err(e) => {
@@ -1199,8 +2041,10 @@ pub async fn execute_import(
         platform_referenced: 0,
         platform_failed: 0,
         platform_skipped: 0,
-        embedded_jars_identified: 0,
+        embedded_jars_identified: resolved.embedded_jars_identified,
         embedded_jars_unidentified: 0,
+        local_tracked: 0,
+        local_failed: 0,
         overrides_copied: 0,
         warnings: resolved.warnings.clone(),
     };
@@ -1219,7 +2063,12 @@ pub async fn execute_import(
     let datapack_folder = config
         .datapack_folder
         .clone()
+        .or_else(|| resolved.manifest.options.datapack_folder.clone())
         .or_else(|| detect_datapack_folder(&resolved.manifest));
+    let acceptable_game_versions = config
+        .acceptable_game_versions
+        .clone()
+        .or_else(|| resolved.manifest.options.acceptable_game_versions.clone());
 
     let empack_yml_content = format_empack_yml(
         &config.pack_name,
@@ -1229,7 +2078,7 @@ pub async fn execute_import(
         resolved.manifest.target.loader.as_str(),
         &resolved.manifest.target.loader_version,
         datapack_folder.as_deref(),
-        config.acceptable_game_versions.as_deref(),
+        acceptable_game_versions.as_deref(),
     );
 
     session
@@ -1254,12 +2103,12 @@ pub async fn execute_import(
         session.display().status().warning(w);
     }
 
-    if datapack_folder.is_some() || config.acceptable_game_versions.is_some() {
+    if datapack_folder.is_some() || acceptable_game_versions.is_some() {
         let pack_toml_path = config.target_dir.join("pack").join("pack.toml");
         crate::empack::packwiz::write_pack_toml_options(
             &pack_toml_path,
             datapack_folder.as_deref(),
-            config.acceptable_game_versions.as_deref(),
+            acceptable_game_versions.as_deref(),
             session.filesystem(),
         )
         .map_err(|e| anyhow::anyhow!("failed to write pack.toml options: {}", e))?;
@@ -1315,8 +2164,10 @@ pub async fn execute_import(
         project_id: String,
         project_type: crate::primitives::ProjectType,
         version: Option<String>,
+        side: Option<DependencySide>,
     }
     let mut pending_deps: Vec<PendingDep> = Vec::new();
+    let mut local_deps: Vec<(String, LocalDependencyRecord)> = Vec::new();
 
     for entry in &resolved.manifest.content {
         match entry {
@@ -1379,20 +2230,58 @@ pub async fn execute_import(
                                 .resolved_type
                                 .unwrap_or(crate::primitives::ProjectType::Mod),
                             version: pref.file_id.clone(),
+                            side: pref.env.side().restriction(),
                         });
                     }
                 }
             }
             ContentEntry::EmbeddedJar(embed) => {
                 let dest = sanitize_archive_path(&pack_dir, &embed.destination_path)?;
-                extract_embedded_from_archive(
+                let bytes = extract_embedded_from_archive(
                     &resolved.manifest.archive_path,
                     &embed.source_path,
                     &dest,
                     session.filesystem(),
                 )?;
+                if let Err(e) = ContentStore::open(session.filesystem()).and_then(|store| {
+                    store.admit_for_project(
+                        &config.target_dir,
+                        &bytes,
+                        ContentHash::best_of(&embed.hashes).as_ref(),
+                        &embed.source_path,
+                    )
+                }) {
+                    session.display().status().warning(&format!(
+                        "embedded '{}' was not added to the content store: {}",
+                        embed.destination_path, e
+                    ));
+                }
                 stats.embedded_jars_unidentified += 1;
             }
+            ContentEntry::DirectDownload(download) => {
+                content_progress.tick(&download.destination_path);
+
+                match import_direct_download(download, &pack_dir, session).await {
+                    Ok(Some(record)) => {
+                        stats.local_tracked += 1;
+                        local_deps.push((download.slug.clone(), record));
+                    }
+                    Ok(None) => {
+                        stats.platform_skipped += 1;
+                        session.display().status().warning(&format!(
+                            "no download URL for '{}'; skipping",
+                            download.destination_path
+                        ));
+                    }
+                    Err(e) => {
+                        stats.local_failed += 1;
+                        session.display().status().warning(&format!(
+                            "failed to import '{}' as a local dependency: {:#}",
+                            download.destination_path, e
+                        ));
+                    }
+                }
+            }
         }
         content_progress.inc();
     }
@@ -1432,6 +2321,7 @@ pub async fn execute_import(
             project_id: dep.project_id.clone(),
             project_type: dep.project_type,
             version: dep.version.clone(),
+            side: dep.side,
         };
         if let Err(e) = config_manager.add_dependency(&dep.derived_key, record) {
             session
@@ -1440,6 +2330,14 @@ pub async fn execute_import(
                 .warning(&format!("failed to update empack.yml: {}", e));
         }
     }
+    for (key, record) in local_deps {
+        if let Err(e) = config_manager.add_dependency_entry(&key, DependencyEntry::Local(record)) {
+            session
+                .display()
+                .status()
+                .warning(&format!("failed to update empack.yml: {}", e));
+        }
+    }
 
     if !add_durations.is_empty() {
         let total: std::time::Duration = add_durations.iter().sum();
@@ -1505,6 +2403,93 @@ pub async fn execute_import(
     })
 }
 
+/// Download a direct-download entry into the pack and describe it as a
+/// tracked local dependency.
+///
+/// Entries with a known hash are served from the content store when it has
+/// them, and admitted to it otherwise.
+///
+/// Returns `Ok(None)` when the entry has no URL to download from.
+async fn import_direct_download(
+    download: &DirectDownloadRef,
+    pack_dir: &Path,
+    session: &dyn Session,
+) -> Result<Option<LocalDependencyRecord>> {
+    let Some(url) = download.url.as_deref() else {
+        return Ok(None);
+    };
+
+    let store = ContentStore::open(session.filesystem())?;
+    let expected = ContentHash::best_of(&download.hashes);
+    let bytes = match expected.as_ref().and_then(|hash| store.get(hash)) {
+        Some(bytes) => bytes,
+        None => {
+            let client = session.network().http_client()?;
+            let response = session
+                .network()
+                .endpoints()
+                .send(url, |url| client.get(url).send())
+                .await
+                .with_context(|| format!("failed to download from {}", url))?;
+            if !response.status().is_success() {
+                anyhow::bail!("HTTP {} for {}", response.status(), url);
+            }
+            let bytes = response
+                .bytes()
+                .await
+                .with_context(|| format!("failed to read response body from {}", url))?;
+            verify_download_hashes(&bytes, &download.hashes)
+                .with_context(|| format!("download from {} failed verification", url))?;
+            bytes.to_vec()
+        }
+    };
+    store.admit_for_project(
+        pack_dir.parent().unwrap_or(pack_dir),
+        &bytes,
+        expected.as_ref(),
+        url,
+    )?;
+
+    let dest = sanitize_archive_path(pack_dir, &download.destination_path)?;
+    if let Some(parent) = dest.parent() {
+        session.filesystem().create_dir_all(parent)?;
+    }
+    session.filesystem().write_bytes(&dest, &bytes)?;
+
+    Ok(Some(LocalDependencyRecord {
+        status: DependencyStatus::Local,
+        title: download.name.clone(),
+        project_type: download.project_type,
+        path: format!("pack/{}", download.destination_path),
+        source_url: Some(url.to_string()),
+        sha256: {
+            use sha2::Digest;
+            crate::empack::content::hex::encode(sha2::Sha256::digest(&bytes))
+        },
+        side: None,
+    }))
+}
+
+/// Check downloaded bytes against every recorded hash empack can compute.
+///
+/// Formats other than sha1, sha256, and sha512 (md5, murmur2) are not checked.
+fn verify_download_hashes(bytes: &[u8], hashes: &HashMap<String, String>) -> Result<()> {
+    use sha2::Digest;
+
+    for (format, expected) in hashes {
+        let actual = match format.as_str() {
+            "sha1" => crate::empack::content::hex::encode(sha1::Sha1::digest(bytes)),
+            "sha256" => crate::empack::content::hex::encode(sha2::Sha256::digest(bytes)),
+            "sha512" => crate::empack::content::hex::encode(sha2::Sha512::digest(bytes)),
+            _ => continue,
+        };
+        if !actual.eq_ignore_ascii_case(expected) {
+            anyhow::bail!("{format} mismatch: expected {expected}, got {actual}");
+        }
+    }
+    Ok(())
+}
+
 /// Outcome of attempting to add a platform reference via packwiz.
 enum AddRefResult {
     /// packwiz add succeeded; the .pw.toml was created.
@@ -1560,15 +2545,6 @@ async fn add_platform_ref_with_retry(
     unreachable!()
 }
 
-fn side_from_env(env: &crate::empack::content::SideEnv) -> &'static str {
-    use crate::empack::content::SideRequirement::*;
-    match (&env.client, &env.server) {
-        (Required, Unsupported) | (Required, Unknown) | (Optional, Unsupported) => "client",
-        (Unsupported, Required) | (Unknown, Required) | (Unsupported, Optional) => "server",
-        _ => "both",
-    }
-}
-
 fn filename_from_path(dest: &str) -> String {
     std::path::Path::new(dest)
         .file_name()
@@ -1668,7 +2644,7 @@ async fn add_platform_ref(
                 }
 
                 args.push("--side".to_string());
-                args.push(side_from_env(&pref.env).to_string());
+                args.push(pref.env.side().as_str().to_string());
 
                 if let Some(slug) = &pref.resolved_slug {
                     args.push("--slug".to_string());
@@ -1823,16 +2799,29 @@ fn sanitize_archive_path(base: &Path, relative: &str) -> Result<PathBuf> {
     Ok(joined)
 }
 
+/// Copy one file out of the import source and return its bytes.
+///
+/// `archive_path` is a zip archive, or a directory for packwiz and Prism
+/// instance imports.
 fn extract_embedded_from_archive(
     archive_path: &Path,
     source_path: &str,
     dest_path: &Path,
     fs: &dyn crate::application::session::FileSystemProvider,
-) -> Result<()> {
+) -> Result<Vec<u8>> {
     if let Some(parent) = dest_path.parent() {
         fs.create_dir_all(parent)?;
     }
 
+    if fs.is_directory(archive_path) {
+        let source = sanitize_archive_path(archive_path, source_path)?;
+        let bytes = fs
+            .read_bytes(&source)
+            .with_context(|| format!("reading {}", source.display()))?;
+        fs.write_bytes(dest_path, &bytes)?;
+        return Ok(bytes);
+    }
+
     let archive_bytes = fs
         .read_bytes(archive_path)
         .with_context(|| format!("opening archive: {}", archive_path.display()))?;
@@ -1846,7 +2835,7 @@ fn extract_embedded_from_archive(
     std::io::Read::read_to_end(&mut entry, &mut bytes)?;
     fs.write_bytes(dest_path, &bytes)?;
 
-    Ok(())
+    Ok(bytes)
 }
 
 use crate::empack::config::format_empack_yml;
@@ -2013,15 +3002,6 @@ fn open_zip_archive(data: Vec<u8>) -> Result<zip::ZipArchive<Cursor<Vec<u8>>>> {
         .map_err(|e| ImportError::ArchiveRead(e.to_string()).into())
 }
 
-fn mr_side_requirement(value: Option<&str>) -> SideRequirement {
-    match value {
-        Some("required") => SideRequirement::Required,
-        Some("optional") => SideRequirement::Optional,
-        Some("unsupported") => SideRequirement::Unsupported,
-        _ => SideRequirement::Unknown,
-    }
-}
-
 /// Derive a dependency key from the platform slug, display name, or destination path.
 ///
 /// packwiz names `.pw.toml` files from the project slug (e.g., "jei" not
@@ -2152,6 +3132,7 @@ pub fn detect_local_source(path: &Path) -> Result<SourceKind> {
 
         return match ext.as_str() {
             "mrpack" => Ok(SourceKind::ModrinthMrpack),
+            "zip" if is_prism_instance_zip(path) => Ok(SourceKind::PrismInstance),
             "zip" => Ok(SourceKind::CurseForgeZip),
             _ => Err(ImportError::UnrecognizedSource(path.display().to_string()).into()),
         };
@@ -2163,6 +3144,10 @@ pub fn detect_local_source(path: &Path) -> Result<SourceKind> {
             return Err(ImportError::AlreadyEmpackProject.into());
         }
 
+        if path.join("instance.cfg").exists() && path.join("mmc-pack.json").exists() {
+            return Ok(SourceKind::PrismInstance);
+        }
+
         let pack_toml = path.join("pack.toml");
         if pack_toml.exists() {
             return Ok(SourceKind::PackwizDirectory);
@@ -2172,11 +3157,25 @@ pub fn detect_local_source(path: &Path) -> Result<SourceKind> {
     Err(ImportError::UnrecognizedSource(path.display().to_string()).into())
 }
 
+/// Whether a zip is a Prism Launcher or MultiMC instance export rather than a
+/// CurseForge modpack.
+fn is_prism_instance_zip(path: &Path) -> bool {
+    let Ok(file) = std::fs::File::open(path) else {
+        return false;
+    };
+    let Ok(archive) = zip::ZipArchive::new(file) else {
+        return false;
+    };
+    prism_instance_prefix(archive.file_names()).is_some()
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SourceKind {
     CurseForgeZip,
     ModrinthMrpack,
     PackwizDirectory,
+    /// Prism Launcher or MultiMC instance, as a directory or exported zip.
+    PrismInstance,
     ModrinthRemote {
         slug: String,
         version: Option<String>,
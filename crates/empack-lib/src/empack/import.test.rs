@@ -104,8 +104,8 @@ fn registry_with_budget(host: &str, budget: Arc<dyn RateBudget>) -> HostBudgetRe
 #[cfg(feature = "test-utils")]
 fn test_api_bases(modrinth: &str, curseforge: &str) -> ResolveApiBases {
     ResolveApiBases {
-        modrinth: modrinth.to_string(),
-        curseforge: curseforge.to_string(),
+        modrinth: vec![modrinth.to_string()],
+        curseforge: vec![curseforge.to_string()],
     }
 }
 
@@ -151,6 +151,19 @@ fn curseforge_pref(project_id: &str, file_id: Option<&str>) -> PlatformRef {
     }
 }
 
+#[cfg(feature = "test-utils")]
+struct StubJarResolver(JarIdentity);
+
+#[cfg(feature = "test-utils")]
+impl JarResolver for StubJarResolver {
+    async fn identify(
+        &self,
+        _request: crate::empack::content::JarIdentifyRequest,
+    ) -> Result<JarIdentity> {
+        Ok(self.0.clone())
+    }
+}
+
 #[cfg(feature = "test-utils")]
 fn manifest_with_content(content: Vec<ContentEntry>) -> ModpackManifest {
     ModpackManifest {
@@ -167,6 +180,7 @@ fn manifest_with_content(content: Vec<ContentEntry>) -> ModpackManifest {
         },
         content,
         overrides: Vec::new(),
+        options: PackOptions::default(),
         source_platform: ProjectPlatform::Modrinth,
         archive_path: std::path::PathBuf::from("/tmp/test.mrpack"),
     }
@@ -758,6 +772,7 @@ fn test_modpack_manifest_construction() {
             side: OverrideSide::Both,
             category: OverrideCategory::Config,
         }],
+        options: PackOptions::default(),
         source_platform: ProjectPlatform::Modrinth,
         archive_path: std::path::PathBuf::from("/tmp/test.mrpack"),
     };
@@ -772,12 +787,12 @@ fn test_modpack_manifest_construction() {
 // ---------------------------------------------------------------------------
 
 #[test]
-fn test_mr_side_requirement() {
-    assert_eq!(mr_side_requirement(Some("required")), SideRequirement::Required);
-    assert_eq!(mr_side_requirement(Some("optional")), SideRequirement::Optional);
-    assert_eq!(mr_side_requirement(Some("unsupported")), SideRequirement::Unsupported);
-    assert_eq!(mr_side_requirement(None), SideRequirement::Unknown);
-    assert_eq!(mr_side_requirement(Some("")), SideRequirement::Unknown);
+fn test_side_requirement_from_modrinth() {
+    assert_eq!(SideRequirement::from_modrinth(Some("required")), SideRequirement::Required);
+    assert_eq!(SideRequirement::from_modrinth(Some("optional")), SideRequirement::Optional);
+    assert_eq!(SideRequirement::from_modrinth(Some("unsupported")), SideRequirement::Unsupported);
+    assert_eq!(SideRequirement::from_modrinth(None), SideRequirement::Unknown);
+    assert_eq!(SideRequirement::from_modrinth(Some("")), SideRequirement::Unknown);
 }
 
 // ---------------------------------------------------------------------------
@@ -1086,6 +1101,7 @@ fn make_test_manifest(
         },
         content,
         overrides,
+        options: PackOptions::default(),
         source_platform: ProjectPlatform::Modrinth,
         archive_path: PathBuf::from("/test.mrpack"),
     }
@@ -1590,6 +1606,7 @@ async fn test_resolve_manifest_shares_curseforge_budget_across_file_lookup_and_p
         .create_async()
         .await;
 
+    let filesystem = crate::application::session_mocks::MockFileSystemProvider::new();
     let provider = TestNetworkProvider::new();
     let display = crate::display::LiveDisplayProvider::new();
     let budget: Arc<dyn RateBudget> = Arc::new(FixedWindowBudget::new(1, Duration::from_secs(2)));
@@ -1603,12 +1620,14 @@ async fn test_resolve_manifest_shares_curseforge_budget_across_file_lookup_and_p
     let start = Instant::now();
     let resolved = resolve_manifest_with_api_bases(
         manifest,
+        &filesystem,
         &provider,
         &provider,
         Some("test-api-key"),
         &display,
         &registry,
         api_bases,
+        &StubJarResolver(JarIdentity::Unidentified),
     )
     .await
     .unwrap();
@@ -1681,6 +1700,7 @@ async fn test_resolve_manifest_concurrent_modrinth_requests_share_budget_without
         content.push(ContentEntry::PlatformReferenced(pref));
     }
 
+    let filesystem = crate::application::session_mocks::MockFileSystemProvider::new();
     let provider = TestNetworkProvider::new();
     let display = crate::display::LiveDisplayProvider::new();
     let budget: Arc<dyn RateBudget> = Arc::new(HeaderDrivenBudget::new(300));
@@ -1691,12 +1711,14 @@ async fn test_resolve_manifest_concurrent_modrinth_requests_share_budget_without
     let start = Instant::now();
     let resolved = resolve_manifest_with_api_bases(
         manifest,
+        &filesystem,
         &provider,
         &provider,
         None,
         &display,
         &registry,
         api_bases,
+        &StubJarResolver(JarIdentity::Unidentified),
     )
     .await
     .unwrap();
@@ -1918,6 +1940,14 @@ async fn test_resolve_manifest_recovers_from_panics_and_passthrough_warnings() {
         ContentEntry::PlatformReferenced(panic_ref),
     ]);
 
+    let filesystem = crate::application::session_mocks::MockFileSystemProvider::new();
+    let archive = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()))
+        .finish()
+        .unwrap()
+        .into_inner();
+    filesystem
+        .write_bytes(&manifest.archive_path, &archive)
+        .unwrap();
     let provider = TestNetworkProvider::new();
     let display = crate::display::LiveDisplayProvider::new();
     let registry = HostBudgetRegistry::empty();
@@ -1925,12 +1955,14 @@ async fn test_resolve_manifest_recovers_from_panics_and_passthrough_warnings() {
 
     let resolved = resolve_manifest_with_api_bases(
         manifest,
+        &filesystem,
         &provider,
         &provider,
         None,
         &display,
         &registry,
         api_bases,
+        &StubJarResolver(JarIdentity::Unidentified),
     )
     .await
     .unwrap();
@@ -2057,10 +2089,12 @@ async fn test_execute_import_uses_url_derived_modrinth_version_id_for_packwiz_ad
                 cf_class_id: None,
             })],
             overrides: Vec::new(),
+            options: PackOptions::default(),
             source_platform: ProjectPlatform::Modrinth,
             archive_path: std::path::PathBuf::from("/tmp/test.mrpack"),
         },
         warnings: Vec::new(),
+        embedded_jars_identified: 0,
     };
 
     let result = execute_import(
@@ -2259,3 +2293,621 @@ async fn test_add_platform_ref_retry_exhaustion() {
     assert_eq!(calls.len(), 6);
     assert!(calls.iter().all(|call| call.args == expected_args));
 }
+
+// ---------------------------------------------------------------------------
+// packwiz directory import
+// ---------------------------------------------------------------------------
+
+const PW_PACK_TOML: &str = r#"name = "Old Pack"
+author = "Someone"
+version = "2.1.0"
+pack-format = "packwiz:1.1.0"
+
+[index]
+file = "index.toml"
+hash-format = "sha256"
+hash = "unused"
+
+[versions]
+minecraft = "1.21.1"
+fabric = "0.16.5"
+
+[options]
+datapack-folder = "config/paxi/datapacks"
+acceptable-game-versions = ["1.21"]
+"#;
+
+const PW_INDEX_TOML: &str = r#"hash-format = "sha256"
+
+[[files]]
+file = "mods/sodium.pw.toml"
+hash = "unused"
+metafile = true
+
+[[files]]
+file = "config/paxi/datapacks/terralith.pw.toml"
+hash = "unused"
+metafile = true
+
+[[files]]
+file = "mods/custom.pw.toml"
+hash = "unused"
+metafile = true
+
+[[files]]
+file = "config/custom.json"
+hash = "unused"
+"#;
+
+const PW_SODIUM: &str = r#"name = "Sodium"
+filename = "sodium-0.6.0.jar"
+side = "client"
+
+[download]
+url = "https://cdn.modrinth.com/data/AANobbMI/versions/v1/sodium-0.6.0.jar"
+hash-format = "sha512"
+hash = "ABCD"
+
+[update.modrinth]
+mod-id = "AANobbMI"
+version = "v1"
+"#;
+
+const PW_TERRALITH: &str = r#"name = "Terralith"
+filename = "terralith.zip"
+
+[download]
+hash-format = "sha1"
+hash = "1234"
+mode = "metadata:curseforge"
+
+[update.curseforge]
+file-id = 5101366
+project-id = 513688
+"#;
+
+fn pw_custom(url: &str, sha1: &str) -> String {
+    format!(
+        "name = \"Custom Tweaks\"\nfilename = \"custom.jar\"\n\n[download]\nurl = \"{url}\"\nhash-format = \"sha1\"\nhash = \"{sha1}\"\n"
+    )
+}
+
+fn write_packwiz_dir(dir: &std::path::Path, custom: &str) {
+    for (path, content) in [
+        ("pack.toml", PW_PACK_TOML),
+        ("index.toml", PW_INDEX_TOML),
+        ("mods/sodium.pw.toml", PW_SODIUM),
+        ("config/paxi/datapacks/terralith.pw.toml", PW_TERRALITH),
+        ("mods/custom.pw.toml", custom),
+        ("config/custom.json", "{}"),
+    ] {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+}
+
+#[test]
+fn test_parse_packwiz_directory_maps_metafiles_and_overrides() {
+    let dir = tempfile::tempdir().unwrap();
+    write_packwiz_dir(dir.path(), &pw_custom("https://example.com/custom.jar", "00"));
+
+    let manifest = parse_packwiz_directory(dir.path()).unwrap();
+    assert_eq!(manifest.identity.name, "Old Pack");
+    assert_eq!(manifest.identity.author.as_deref(), Some("Someone"));
+    assert_eq!(manifest.identity.version, "2.1.0");
+    assert_eq!(manifest.target.minecraft_version, "1.21.1");
+    assert_eq!(manifest.target.loader, ModLoader::Fabric);
+    assert_eq!(manifest.target.loader_version, "0.16.5");
+    assert_eq!(
+        manifest.options.datapack_folder.as_deref(),
+        Some("config/paxi/datapacks")
+    );
+    assert_eq!(
+        manifest.options.acceptable_game_versions,
+        Some(vec!["1.21".to_string()])
+    );
+    assert_eq!(manifest.archive_path, dir.path());
+    assert_eq!(manifest.content.len(), 3);
+
+    let ContentEntry::PlatformReferenced(sodium) = &manifest.content[0] else {
+        panic!("expected modrinth reference");
+    };
+    assert_eq!(sodium.platform, ProjectPlatform::Modrinth);
+    assert_eq!(sodium.project_id, "AANobbMI");
+    assert_eq!(sodium.file_id.as_deref(), Some("v1"));
+    assert_eq!(sodium.destination_path, "mods/sodium-0.6.0.jar");
+    assert_eq!(sodium.hashes.get("sha512").map(String::as_str), Some("abcd"));
+    assert_eq!(sodium.env.server, SideRequirement::Unsupported);
+    assert_eq!(sodium.resolved_name.as_deref(), Some("Sodium"));
+    assert_eq!(sodium.resolved_slug.as_deref(), Some("sodium"));
+
+    let ContentEntry::PlatformReferenced(terralith) = &manifest.content[1] else {
+        panic!("expected curseforge reference");
+    };
+    assert_eq!(terralith.platform, ProjectPlatform::CurseForge);
+    assert_eq!(terralith.project_id, "513688");
+    assert_eq!(terralith.file_id.as_deref(), Some("5101366"));
+    assert_eq!(
+        terralith.resolved_type,
+        Some(crate::primitives::ProjectType::Datapack)
+    );
+    assert_eq!(terralith.cf_class_id, Some(6945));
+
+    let ContentEntry::DirectDownload(custom) = &manifest.content[2] else {
+        panic!("expected direct download");
+    };
+    assert_eq!(custom.slug, "custom");
+    assert_eq!(custom.destination_path, "mods/custom.jar");
+    assert_eq!(custom.url.as_deref(), Some("https://example.com/custom.jar"));
+
+    assert_eq!(manifest.overrides.len(), 1);
+    assert_eq!(manifest.overrides[0].source_path, "config/custom.json");
+    assert_eq!(manifest.overrides[0].category, OverrideCategory::Config);
+}
+
+#[test]
+fn test_parse_packwiz_directory_requires_loader() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("pack.toml"),
+        "name = \"Vanilla\"\n[index]\nfile = \"index.toml\"\n[versions]\nminecraft = \"1.21.1\"\n",
+    )
+    .unwrap();
+
+    let err = parse_packwiz_directory(dir.path()).unwrap_err();
+    assert!(err.to_string().contains("versions.<loader>"));
+}
+
+#[test]
+fn test_parse_packwiz_directory_reports_missing_metafile() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("pack.toml"), PW_PACK_TOML).unwrap();
+    std::fs::write(dir.path().join("index.toml"), PW_INDEX_TOML).unwrap();
+
+    let err = parse_packwiz_directory(dir.path()).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<ImportError>(),
+        Some(ImportError::PackwizFileMissing(path)) if path == "mods/sodium.pw.toml"
+    ));
+}
+
+#[test]
+fn test_verify_download_hashes() {
+    let hashes = HashMap::from([
+        (
+            "sha1".to_string(),
+            "A9993E364706816ABA3E25717850C26C9CD0D89D".to_string(),
+        ),
+        ("murmur2".to_string(), "123".to_string()),
+    ]);
+    assert!(verify_download_hashes(b"abc", &hashes).is_ok());
+    assert!(verify_download_hashes(b"abd", &hashes).is_err());
+}
+
+#[cfg(feature = "test-utils")]
+#[tokio::test]
+async fn test_execute_import_tracks_direct_downloads_from_packwiz_directory() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/custom.jar")
+        .with_status(200)
+        .with_body("abc")
+        .create_async()
+        .await;
+
+    let source = tempfile::tempdir().unwrap();
+    write_packwiz_dir(
+        source.path(),
+        &pw_custom(
+            &format!("{}/custom.jar", server.url()),
+            "a9993e364706816aba3e25717850c26c9cd0d89d",
+        ),
+    );
+    let manifest = parse_packwiz_directory(source.path()).unwrap();
+
+    let target_dir = source.path().join("imported");
+    let process = crate::application::session_mocks::MockProcessProvider::new()
+        .with_packwiz_add_slug("AANobbMI".to_string(), "sodium".to_string());
+    let session = crate::application::session_mocks::MockCommandSession::new()
+        .with_filesystem(crate::application::session_mocks::MockFileSystemProvider::new())
+        .with_process(process);
+    // Overrides are copied through the session filesystem, which starts empty
+    session
+        .filesystem()
+        .write_file(&source.path().join("config/custom.json"), "{}")
+        .unwrap();
+
+    let result = execute_import(
+        ResolvedManifest {
+            manifest,
+            warnings: Vec::new(),
+            embedded_jars_identified: 0,
+        },
+        ImportConfig {
+            target_dir: target_dir.clone(),
+            pack_name: "Old Pack".to_string(),
+            author: "Someone".to_string(),
+            version: "2.1.0".to_string(),
+            datapack_folder: None,
+            acceptable_game_versions: None,
+        },
+        &session,
+    )
+    .await
+    .expect("execute import should succeed");
+
+    assert_eq!(result.stats.local_tracked, 1);
+    assert_eq!(result.stats.local_failed, 0);
+    assert_eq!(result.stats.overrides_copied, 1);
+    assert_eq!(
+        session
+            .filesystem()
+            .read_bytes(&target_dir.join("pack/mods/custom.jar"))
+            .unwrap(),
+        b"abc"
+    );
+    assert!(
+        session
+            .filesystem()
+            .exists(&target_dir.join("pack/config/custom.json"))
+    );
+
+    let config_manager = session.filesystem().config_manager(target_dir.clone());
+    match config_manager.find_dependency("custom").unwrap().unwrap().1 {
+        DependencyEntry::Local(record) => {
+            assert_eq!(record.path, "pack/mods/custom.jar");
+            assert_eq!(record.title, "Custom Tweaks");
+            assert_eq!(
+                record.sha256,
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+            );
+        }
+        other => panic!("expected local dependency, got {other:?}"),
+    }
+    let empack_yml = session
+        .filesystem()
+        .read_to_string(&target_dir.join("empack.yml"))
+        .unwrap();
+    assert!(empack_yml.contains("config/paxi/datapacks"));
+}
+
+#[cfg(feature = "test-utils")]
+#[tokio::test]
+async fn test_execute_import_counts_direct_download_hash_mismatch_as_failure() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/custom.jar")
+        .with_status(200)
+        .with_body("tampered")
+        .create_async()
+        .await;
+
+    let target_dir = mock_root().join("direct-download-mismatch");
+    let session = crate::application::session_mocks::MockCommandSession::new();
+    let manifest = manifest_with_content(vec![ContentEntry::DirectDownload(DirectDownloadRef {
+        destination_path: "mods/custom.jar".to_string(),
+        url: Some(format!("{}/custom.jar", server.url())),
+        hashes: HashMap::from([(
+            "sha1".to_string(),
+            "a9993e364706816aba3e25717850c26c9cd0d89d".to_string(),
+        )]),
+        name: "Custom Tweaks".to_string(),
+        slug: "custom".to_string(),
+        project_type: crate::primitives::ProjectType::Mod,
+    })]);
+
+    let result = execute_import(
+        ResolvedManifest {
+            manifest,
+            warnings: Vec::new(),
+            embedded_jars_identified: 0,
+        },
+        ImportConfig {
+            target_dir: target_dir.clone(),
+            pack_name: "Test Pack".to_string(),
+            author: "Test Author".to_string(),
+            version: "1.0.0".to_string(),
+            datapack_folder: None,
+            acceptable_game_versions: None,
+        },
+        &session,
+    )
+    .await
+    .expect("execute import should finish");
+
+    assert_eq!(result.stats.local_tracked, 0);
+    assert_eq!(result.stats.local_failed, 1);
+    assert!(
+        !session
+            .filesystem()
+            .exists(&target_dir.join("pack/mods/custom.jar"))
+    );
+}
+
+#[cfg(feature = "test-utils")]
+#[tokio::test]
+async fn test_execute_import_serves_direct_downloads_from_the_content_store() {
+    let target_dir = mock_root().join("direct-download-stored");
+    let session = crate::application::session_mocks::MockCommandSession::new();
+    let store = ContentStore::open(session.filesystem()).unwrap();
+    store.admit(b"abc", None, "custom.jar").unwrap();
+    let manifest = manifest_with_content(vec![ContentEntry::DirectDownload(DirectDownloadRef {
+        destination_path: "mods/custom.jar".to_string(),
+        url: Some("http://unreachable.invalid/custom.jar".to_string()),
+        hashes: HashMap::from([(
+            "sha1".to_string(),
+            "A9993E364706816ABA3E25717850C26C9CD0D89D".to_string(),
+        )]),
+        name: "Custom Tweaks".to_string(),
+        slug: "custom".to_string(),
+        project_type: crate::primitives::ProjectType::Mod,
+    })]);
+
+    let result = execute_import(
+        ResolvedManifest {
+            manifest,
+            warnings: Vec::new(),
+            embedded_jars_identified: 0,
+        },
+        ImportConfig {
+            target_dir: target_dir.clone(),
+            pack_name: "Test Pack".to_string(),
+            author: "Test Author".to_string(),
+            version: "1.0.0".to_string(),
+            datapack_folder: None,
+            acceptable_game_versions: None,
+        },
+        &session,
+    )
+    .await
+    .expect("execute import should finish");
+
+    assert_eq!(result.stats.local_tracked, 1);
+    assert_eq!(
+        session
+            .filesystem()
+            .read_bytes(&target_dir.join("pack/mods/custom.jar"))
+            .unwrap(),
+        b"abc"
+    );
+    let report = store.gc(true).unwrap();
+    assert_eq!((report.projects, report.removed_objects), (1, 0));
+}
+
+// ---------------------------------------------------------------------------
+// Prism Launcher / MultiMC instance import
+// ---------------------------------------------------------------------------
+
+const PRISM_INSTANCE_CFG: &str = "[General]\nConfigVersion=1.2\nname=Player Pack\niconKey=default\n";
+
+const PRISM_MMC_PACK: &str = r#"{
+    "components": [
+        { "uid": "net.minecraft", "version": "1.20.1", "important": true },
+        { "uid": "net.fabricmc.intermediary", "version": "1.20.1", "dependencyOnly": true },
+        { "uid": "net.fabricmc.fabric-loader", "version": "0.15.11" }
+    ],
+    "formatVersion": 1
+}"#;
+
+fn write_prism_instance(dir: &std::path::Path) {
+    for (path, content) in [
+        ("instance.cfg", PRISM_INSTANCE_CFG.as_bytes()),
+        ("mmc-pack.json", PRISM_MMC_PACK.as_bytes()),
+        (".minecraft/mods/sodium.jar", b"sodium jar".as_slice()),
+        (".minecraft/mods/old.jar.disabled", b"old".as_slice()),
+        (".minecraft/config/sodium-options.json", b"{}".as_slice()),
+        (".minecraft/resourcepacks/faithful.zip", b"zip".as_slice()),
+        (".minecraft/options.txt", b"fov:90".as_slice()),
+        (".minecraft/logs/latest.log", b"log".as_slice()),
+        (".minecraft/saves/World/level.dat", b"level".as_slice()),
+    ] {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+}
+
+fn create_prism_zip(prefix: &str, mmc_pack: &str, files: &[(&str, &[u8])]) -> NamedTempFile {
+    let tmp = NamedTempFile::with_suffix(".zip").unwrap();
+    let mut zip = zip::ZipWriter::new(tmp.reopen().unwrap());
+    let options = zip::write::FileOptions::<()>::default();
+    zip.start_file(format!("{prefix}instance.cfg"), options).unwrap();
+    zip.write_all(b"name=Zipped Pack\n").unwrap();
+    zip.start_file(format!("{prefix}mmc-pack.json"), options).unwrap();
+    zip.write_all(mmc_pack.as_bytes()).unwrap();
+    for (path, content) in files {
+        zip.start_file(format!("{prefix}{path}"), options).unwrap();
+        zip.write_all(content).unwrap();
+    }
+    zip.finish().unwrap();
+    tmp
+}
+
+#[test]
+fn test_detect_local_source_prism_instance_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    write_prism_instance(dir.path());
+    let kind = detect_local_source(dir.path()).unwrap();
+    assert_eq!(kind, SourceKind::PrismInstance);
+}
+
+#[test]
+fn test_detect_local_source_prism_instance_zip() {
+    let tmp = create_prism_zip("MyPack/", PRISM_MMC_PACK, &[]);
+    let kind = detect_local_source(tmp.path()).unwrap();
+    assert_eq!(kind, SourceKind::PrismInstance);
+}
+
+#[test]
+fn test_parse_prism_instance_directory_maps_jars_and_overrides() {
+    let dir = tempfile::tempdir().unwrap();
+    write_prism_instance(dir.path());
+
+    let manifest = parse_prism_instance(dir.path()).unwrap();
+    assert_eq!(manifest.identity.name, "Player Pack");
+    assert_eq!(manifest.target.minecraft_version, "1.20.1");
+    assert_eq!(manifest.target.loader, ModLoader::Fabric);
+    assert_eq!(manifest.target.loader_version, "0.15.11");
+    assert_eq!(manifest.archive_path, dir.path());
+
+    assert_eq!(manifest.content.len(), 1);
+    let ContentEntry::EmbeddedJar(sodium) = &manifest.content[0] else {
+        panic!("expected embedded jar");
+    };
+    assert_eq!(sodium.source_path, ".minecraft/mods/sodium.jar");
+    assert_eq!(sodium.destination_path, "mods/sodium.jar");
+    assert_eq!(sodium.file_size, 10);
+    let expected_sha1 = {
+        use sha1::Digest;
+        crate::empack::content::hex::encode(sha1::Sha1::digest(b"sodium jar"))
+    };
+    assert_eq!(sodium.hashes.get("sha1"), Some(&expected_sha1));
+
+    let mut overrides: Vec<_> = manifest
+        .overrides
+        .iter()
+        .map(|o| (o.destination_path.as_str(), o.category.clone()))
+        .collect();
+    overrides.sort_by(|a, b| a.0.cmp(b.0));
+    assert_eq!(
+        overrides,
+        vec![
+            ("config/sodium-options.json", OverrideCategory::Config),
+            ("options.txt", OverrideCategory::ClientConfig),
+            ("resourcepacks/faithful.zip", OverrideCategory::ResourcePack),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_prism_instance_zip_with_nested_folder() {
+    let mmc_pack = r#"{"components": [
+        { "uid": "net.minecraft", "version": "1.21.1" },
+        { "uid": "net.neoforged", "cachedVersion": "21.1.77" }
+    ]}"#;
+    let tmp = create_prism_zip(
+        "MyPack/",
+        mmc_pack,
+        &[
+            ("minecraft/mods/jei.jar", b"jei"),
+            ("minecraft/shaderpacks/complementary.zip", b"shader"),
+        ],
+    );
+
+    let manifest = parse_prism_instance(tmp.path()).unwrap();
+    assert_eq!(manifest.identity.name, "Zipped Pack");
+    assert_eq!(manifest.target.loader, ModLoader::NeoForge);
+    assert_eq!(manifest.target.loader_version, "21.1.77");
+
+    assert_eq!(manifest.content.len(), 1);
+    let ContentEntry::EmbeddedJar(jei) = &manifest.content[0] else {
+        panic!("expected embedded jar");
+    };
+    assert_eq!(jei.source_path, "MyPack/minecraft/mods/jei.jar");
+    assert_eq!(jei.destination_path, "mods/jei.jar");
+
+    assert_eq!(manifest.overrides.len(), 1);
+    assert_eq!(
+        manifest.overrides[0].source_path,
+        "MyPack/minecraft/shaderpacks/complementary.zip"
+    );
+    assert_eq!(manifest.overrides[0].category, OverrideCategory::ShaderPack);
+}
+
+#[test]
+fn test_parse_prism_instance_requires_loader() {
+    let vanilla = r#"{"components": [{ "uid": "net.minecraft", "version": "1.21.1" }]}"#;
+    let tmp = create_prism_zip("", vanilla, &[]);
+
+    let err = parse_prism_instance(tmp.path()).unwrap_err();
+    assert!(err.to_string().contains("components.<loader>"));
+}
+
+#[cfg(feature = "test-utils")]
+#[tokio::test]
+async fn test_resolve_manifest_reports_unreadable_archive() {
+    let manifest = manifest_with_content(vec![ContentEntry::EmbeddedJar(EmbeddedJar {
+        source_path: "mods/example.jar".to_string(),
+        destination_path: "mods/example.jar".to_string(),
+        hashes: HashMap::new(),
+        file_size: 6,
+        env: SideEnv {
+            client: SideRequirement::Unknown,
+            server: SideRequirement::Unknown,
+        },
+    })]);
+    let filesystem = crate::application::session_mocks::MockFileSystemProvider::new();
+    filesystem
+        .write_bytes(&manifest.archive_path, b"not a zip")
+        .unwrap();
+
+    let provider = TestNetworkProvider::new();
+    let display = crate::display::LiveDisplayProvider::new();
+    let registry = HostBudgetRegistry::empty();
+    let err = resolve_manifest_with_api_bases(
+        manifest,
+        &filesystem,
+        &provider,
+        &provider,
+        None,
+        &display,
+        &registry,
+        test_api_bases("http://example.com", "http://example.com"),
+        &StubJarResolver(JarIdentity::Unidentified),
+    )
+    .await
+    .unwrap_err();
+
+    assert!(matches!(
+        err.downcast_ref::<ImportError>(),
+        Some(ImportError::ArchiveRead(_))
+    ));
+}
+
+#[cfg(feature = "test-utils")]
+#[tokio::test]
+async fn test_resolve_manifest_identifies_prism_jars() {
+    let mut server = mockito::Server::new_async().await;
+    let project_mock = server
+        .mock("GET", "/v2/project/AANobbMI")
+        .with_status(200)
+        .with_body(r#"{"title":"Sodium","slug":"sodium","project_type":"mod"}"#)
+        .create_async()
+        .await;
+
+    let tmp = create_prism_zip("", PRISM_MMC_PACK, &[(".minecraft/mods/sodium.jar", b"sodium")]);
+    let manifest = parse_prism_instance(tmp.path()).unwrap();
+
+    let filesystem = crate::application::session::LiveFileSystemProvider;
+    let provider = TestNetworkProvider::new();
+    let display = crate::display::LiveDisplayProvider::new();
+    let registry = HostBudgetRegistry::empty();
+    let resolved = resolve_manifest_with_api_bases(
+        manifest,
+        &filesystem,
+        &provider,
+        &provider,
+        None,
+        &display,
+        &registry,
+        test_api_bases(&server.url(), &server.url()),
+        &StubJarResolver(JarIdentity::Modrinth {
+            project_id: "AANobbMI".to_string(),
+            version_id: "v1".to_string(),
+            title: "Sodium 0.5.8".to_string(),
+        }),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(resolved.embedded_jars_identified, 1);
+    let ContentEntry::PlatformReferenced(sodium) = &resolved.manifest.content[0] else {
+        panic!("expected identified jar to become a platform reference");
+    };
+    assert_eq!(sodium.project_id, "AANobbMI");
+    assert_eq!(sodium.file_id.as_deref(), Some("v1"));
+    assert_eq!(sodium.destination_path, "mods/sodium.jar");
+    assert_eq!(sodium.resolved_slug.as_deref(), Some("sodium"));
+    project_mock.assert_async().await;
+}
@@ -0,0 +1,241 @@
+//! Operation journal for project-mutating commands
+//!
+//! Each successful `add`, `remove`, `sync`, `update`, `migrate`, or `undo`
+//! that changes project files writes one entry to `.empack/journal/`. An
+//! entry stores the command line and the before/after contents of every
+//! changed file, which is enough to put the project back with `empack undo`.
+
+use crate::application::session::FileSystemProvider;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Journal directory, relative to the project root.
+pub(crate) const JOURNAL_DIR: &str = ".empack/journal";
+
+#[derive(Debug, Error)]
+pub enum JournalError {
+    #[error("Failed to access journal file {path}: {reason}")]
+    Io { path: PathBuf, reason: String },
+
+    #[error("Invalid journal entry {path}: {reason}")]
+    Parse { path: PathBuf, reason: String },
+
+    #[error("The journal is empty")]
+    Empty,
+
+    #[error("No journal entry {id}")]
+    NotFound { id: u32 },
+
+    #[error("Files changed since entry {id} was recorded: {}", .paths.join(", "))]
+    Diverged { id: u32, paths: Vec<String> },
+}
+
+/// Contents of one project file before and after a command.
+///
+/// `None` means the file did not exist.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileChange {
+    pub path: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// One recorded command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub id: u32,
+    pub command: String,
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub changes: Vec<FileChange>,
+}
+
+/// Compare two file captures and return the files whose contents differ.
+pub fn diff_files(
+    before: &BTreeMap<String, Option<String>>,
+    after: &BTreeMap<String, Option<String>>,
+) -> Vec<FileChange> {
+    let paths: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let old = before.get(path).cloned().flatten();
+            let new = after.get(path).cloned().flatten();
+            (old != new).then(|| FileChange {
+                path: path.clone(),
+                before: old,
+                after: new,
+            })
+        })
+        .collect()
+}
+
+/// Load every journal entry, oldest first.
+pub fn load_entries(
+    filesystem: &dyn FileSystemProvider,
+    workdir: &Path,
+) -> Result<Vec<JournalEntry>, JournalError> {
+    let dir = workdir.join(JOURNAL_DIR);
+    let Ok(files) = filesystem.get_file_list(&dir) else {
+        return Ok(Vec::new());
+    };
+
+    let mut entries = Vec::new();
+    for path in files {
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let content = filesystem
+            .read_to_string(&path)
+            .map_err(|e| JournalError::Io {
+                path: path.clone(),
+                reason: e.to_string(),
+            })?;
+        let entry: JournalEntry =
+            serde_json::from_str(&content).map_err(|e| JournalError::Parse {
+                path: path.clone(),
+                reason: e.to_string(),
+            })?;
+        entries.push(entry);
+    }
+    entries.sort_by_key(|entry| entry.id);
+    Ok(entries)
+}
+
+/// Append an entry for `changes`; nothing is written when there are none.
+pub fn record(
+    filesystem: &dyn FileSystemProvider,
+    workdir: &Path,
+    command: &str,
+    timestamp: u64,
+    changes: Vec<FileChange>,
+) -> Result<Option<JournalEntry>, JournalError> {
+    if changes.is_empty() {
+        return Ok(None);
+    }
+
+    let id = load_entries(filesystem, workdir)?
+        .last()
+        .map_or(1, |entry| entry.id + 1);
+    let entry = JournalEntry {
+        id,
+        command: command.to_string(),
+        timestamp,
+        changes,
+    };
+
+    let path = workdir.join(JOURNAL_DIR).join(format!("{id:04}.json"));
+    let json = serde_json::to_string_pretty(&entry).map_err(|e| JournalError::Parse {
+        path: path.clone(),
+        reason: e.to_string(),
+    })?;
+    filesystem
+        .write_file(&path, &json)
+        .map_err(|e| JournalError::Io {
+            path: path.clone(),
+            reason: e.to_string(),
+        })?;
+
+    Ok(Some(entry))
+}
+
+/// Restore the project to its state before entry `id` (default: the latest).
+///
+/// Every entry from `id` onward is reverted. The files those entries touched
+/// must still match what the latest of them recorded, unless `force` is set.
+/// Returns the entry that was undone and the files that were rewritten.
+pub fn undo(
+    filesystem: &dyn FileSystemProvider,
+    workdir: &Path,
+    id: Option<u32>,
+    force: bool,
+) -> Result<(u32, Vec<FileChange>), JournalError> {
+    let entries = load_entries(filesystem, workdir)?;
+    let id = match id {
+        Some(id) => id,
+        None => entries.last().ok_or(JournalError::Empty)?.id,
+    };
+    let start = entries
+        .iter()
+        .position(|entry| entry.id == id)
+        .ok_or(JournalError::NotFound { id })?;
+
+    // Oldest `before` and newest `after` per path across the reverted entries
+    let mut plan: BTreeMap<&str, (Option<&String>, Option<&String>)> = BTreeMap::new();
+    for entry in &entries[start..] {
+        for change in &entry.changes {
+            plan.entry(change.path.as_str())
+                .and_modify(|(_, after)| *after = change.after.as_ref())
+                .or_insert((change.before.as_ref(), change.after.as_ref()));
+        }
+    }
+
+    let mut diverged = Vec::new();
+    let mut restored = Vec::new();
+    for (relative, (before, expected)) in &plan {
+        let path = workdir.join(relative);
+        let current = if filesystem.exists(&path) {
+            Some(
+                filesystem
+                    .read_to_string(&path)
+                    .map_err(|e| JournalError::Io {
+                        path: path.clone(),
+                        reason: e.to_string(),
+                    })?,
+            )
+        } else {
+            None
+        };
+        if current.as_ref() != *expected {
+            diverged.push(relative.to_string());
+        }
+        if current.as_ref() != *before {
+            restored.push(FileChange {
+                path: relative.to_string(),
+                before: current,
+                after: before.cloned(),
+            });
+        }
+    }
+
+    if !diverged.is_empty() && !force {
+        return Err(JournalError::Diverged {
+            id,
+            paths: diverged,
+        });
+    }
+
+    for change in &restored {
+        let path = workdir.join(&change.path);
+        let result = match &change.after {
+            Some(content) => filesystem.write_file(&path, content),
+            None => filesystem.remove_file(&path),
+        };
+        result.map_err(|e| JournalError::Io {
+            path: path.clone(),
+            reason: e.to_string(),
+        })?;
+    }
+
+    Ok((id, restored))
+}
+
+/// Render a Unix timestamp as `YYYY-MM-DD HH:MM:SS UTC`.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let (year, month, day) = crate::empack::time::civil_date((timestamp / 86_400) as i64);
+    let seconds = timestamp % 86_400;
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC",
+        seconds / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    include!("journal.test.rs");
+}
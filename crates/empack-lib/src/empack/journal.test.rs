@@ -0,0 +1,120 @@
+use super::*;
+use crate::application::session_mocks::{MockFileSystemProvider, mock_root};
+
+fn change(path: &str, before: Option<&str>, after: Option<&str>) -> FileChange {
+    FileChange {
+        path: path.to_string(),
+        before: before.map(str::to_string),
+        after: after.map(str::to_string),
+    }
+}
+
+#[test]
+fn diff_files_reports_created_changed_and_removed_files() {
+    let before = BTreeMap::from([
+        ("empack.yml".to_string(), Some("a".to_string())),
+        ("empack.lock".to_string(), None),
+        ("pack/mods/jei.pw.toml".to_string(), Some("jei".to_string())),
+        ("pack/pack.toml".to_string(), Some("pack".to_string())),
+    ]);
+    let after = BTreeMap::from([
+        ("empack.yml".to_string(), Some("b".to_string())),
+        ("empack.lock".to_string(), Some("lock".to_string())),
+        ("pack/pack.toml".to_string(), Some("pack".to_string())),
+    ]);
+
+    assert_eq!(
+        diff_files(&before, &after),
+        vec![
+            change("empack.lock", None, Some("lock")),
+            change("empack.yml", Some("a"), Some("b")),
+            change("pack/mods/jei.pw.toml", Some("jei"), None),
+        ]
+    );
+}
+
+#[test]
+fn record_numbers_entries_and_skips_empty_changes() {
+    let workdir = mock_root().join("pack-project");
+    let fs = MockFileSystemProvider::new();
+
+    assert_eq!(record(&fs, &workdir, "empack sync", 1, Vec::new()).unwrap(), None);
+    record(&fs, &workdir, "empack add sodium", 10, vec![change("empack.yml", Some("a"), Some("b"))])
+        .unwrap();
+    record(&fs, &workdir, "empack add jei", 20, vec![change("empack.yml", Some("b"), Some("c"))])
+        .unwrap();
+
+    let entries = load_entries(&fs, &workdir).unwrap();
+    assert_eq!(
+        entries.iter().map(|e| (e.id, e.command.as_str())).collect::<Vec<_>>(),
+        vec![(1, "empack add sodium"), (2, "empack add jei")]
+    );
+    assert!(fs.exists(&workdir.join(JOURNAL_DIR).join("0002.json")));
+}
+
+#[test]
+fn undo_restores_state_before_the_selected_entry() {
+    let workdir = mock_root().join("pack-project");
+    let fs = MockFileSystemProvider::new()
+        .with_file(workdir.join("empack.yml"), "c".to_string())
+        .with_file(workdir.join("pack/mods/jei.pw.toml"), "jei".to_string());
+    record(&fs, &workdir, "empack add sodium", 10, vec![change("empack.yml", Some("a"), Some("b"))])
+        .unwrap();
+    record(
+        &fs,
+        &workdir,
+        "empack add jei",
+        20,
+        vec![
+            change("empack.yml", Some("b"), Some("c")),
+            change("pack/mods/jei.pw.toml", None, Some("jei")),
+        ],
+    )
+    .unwrap();
+
+    let (id, restored) = undo(&fs, &workdir, Some(1), false).unwrap();
+
+    assert_eq!(id, 1);
+    assert_eq!(restored.len(), 2);
+    assert_eq!(fs.read_to_string(&workdir.join("empack.yml")).unwrap(), "a");
+    assert!(!fs.exists(&workdir.join("pack/mods/jei.pw.toml")));
+}
+
+#[test]
+fn undo_refuses_diverged_files_without_force() {
+    let workdir = mock_root().join("pack-project");
+    let fs = MockFileSystemProvider::new()
+        .with_file(workdir.join("empack.yml"), "edited by hand".to_string());
+    record(&fs, &workdir, "empack add sodium", 10, vec![change("empack.yml", Some("a"), Some("b"))])
+        .unwrap();
+
+    let err = undo(&fs, &workdir, None, false).unwrap_err();
+    assert!(matches!(err, JournalError::Diverged { id: 1, ref paths } if paths == &["empack.yml"]));
+    assert_eq!(
+        fs.read_to_string(&workdir.join("empack.yml")).unwrap(),
+        "edited by hand"
+    );
+
+    undo(&fs, &workdir, None, true).unwrap();
+    assert_eq!(fs.read_to_string(&workdir.join("empack.yml")).unwrap(), "a");
+}
+
+#[test]
+fn undo_reports_missing_entries() {
+    let workdir = mock_root().join("pack-project");
+    let fs = MockFileSystemProvider::new();
+
+    assert!(matches!(undo(&fs, &workdir, None, false), Err(JournalError::Empty)));
+    record(&fs, &workdir, "empack sync", 10, vec![change("empack.lock", None, Some("x"))]).unwrap();
+    assert!(matches!(
+        undo(&fs, &workdir, Some(7), false),
+        Err(JournalError::NotFound { id: 7 })
+    ));
+}
+
+#[test]
+fn format_timestamp_renders_utc_dates() {
+    assert_eq!(format_timestamp(0), "1970-01-01 00:00:00 UTC");
+    assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00:00 UTC");
+    assert_eq!(format_timestamp(1_792_238_645), "2026-10-17 12:04:05 UTC");
+}
@@ -0,0 +1,356 @@
+//! empack.lock: exact files behind empack.yml dependencies
+//!
+//! `empack.yml` records intent (project IDs and optional pins). The lock
+//! records what that intent resolved to: the version or file ID, filename,
+//! download URL, and hashes of every resolved and local dependency, so
+//! `empack sync --locked` can reproduce the same files later.
+
+use crate::application::session::{FileSystemProvider, list_relative_files};
+use crate::empack::config::{DependencyEntry, DependencyRecord, LocalDependencyRecord};
+use crate::primitives::{ProjectPlatform, ProjectType};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+pub const LOCKFILE_NAME: &str = "empack.lock";
+pub const LOCKFILE_SCHEMA_VERSION: u32 = 1;
+
+const LOCKFILE_HEADER: &str = "# This file is maintained by empack. Do not edit it by hand.\n\n";
+
+/// Errors raised when empack.lock cannot back a locked sync
+#[derive(Debug, Error)]
+pub enum LockfileError {
+    #[error("empack.lock not found; run 'empack sync' to create it")]
+    Missing,
+
+    #[error("unsupported empack.lock schema version {version}")]
+    UnsupportedVersion { version: u32 },
+
+    #[error("empack.lock is out of date with empack.yml: {details}")]
+    OutOfDate { details: String },
+
+    #[error("installed files do not match empack.lock: {details}")]
+    Drift { details: String },
+}
+
+/// Lock document persisted as `empack.lock`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmpackLock {
+    pub version: u32,
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, LockedDependency>,
+}
+
+impl Default for EmpackLock {
+    fn default() -> Self {
+        Self {
+            version: LOCKFILE_SCHEMA_VERSION,
+            dependencies: BTreeMap::new(),
+        }
+    }
+}
+
+/// Exact file recorded for one dependency
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedDependency {
+    /// Project platform; absent for local dependencies
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platform: Option<ProjectPlatform>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+
+    /// Modrinth version ID or CurseForge file ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_id: Option<String>,
+
+    /// Project-relative path for local dependencies
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    #[serde(rename = "type")]
+    pub project_type: ProjectType,
+
+    pub filename: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha1: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha512: Option<String>,
+}
+
+impl LockedDependency {
+    /// Build a lock entry from a resolved record and its packwiz `.pw.toml`.
+    ///
+    /// Returns `None` when the metafile cannot be read or parsed.
+    pub fn from_metafile(
+        filesystem: &dyn FileSystemProvider,
+        record: &DependencyRecord,
+        metafile: &Path,
+    ) -> Option<Self> {
+        let content = filesystem.read_to_string(metafile).ok()?;
+        let toml: toml::Value = toml::from_str(&content).ok()?;
+
+        let filename = toml.get("filename")?.as_str()?.to_string();
+        let download = toml.get("download");
+        let url = download
+            .and_then(|d| d.get("url"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        let hash_format = download
+            .and_then(|d| d.get("hash-format"))
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        let hash = download
+            .and_then(|d| d.get("hash"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_ascii_lowercase());
+
+        let update = toml.get("update");
+        let version_id = match record.platform {
+            ProjectPlatform::Modrinth => update
+                .and_then(|u| u.get("modrinth"))
+                .and_then(|m| m.get("version"))
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            ProjectPlatform::CurseForge => update
+                .and_then(|u| u.get("curseforge"))
+                .and_then(|c| c.get("file-id"))
+                .and_then(|v| v.as_integer())
+                .map(|i| i.to_string()),
+        };
+
+        Some(Self {
+            platform: Some(record.platform),
+            project_id: Some(record.project_id.clone()),
+            version_id,
+            path: None,
+            project_type: record.project_type,
+            filename,
+            url,
+            sha1: hash.clone().filter(|_| hash_format == "sha1"),
+            sha512: hash.filter(|_| hash_format == "sha512"),
+        })
+    }
+
+    /// Build a lock entry by hashing a tracked local file.
+    pub fn from_local(
+        filesystem: &dyn FileSystemProvider,
+        workdir: &Path,
+        record: &LocalDependencyRecord,
+    ) -> Result<Self> {
+        let path = workdir.join(&record.path);
+        let bytes = filesystem
+            .read_bytes(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let filename = Path::new(&record.path)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| record.path.clone());
+
+        Ok(Self {
+            platform: None,
+            project_id: None,
+            version_id: None,
+            path: Some(record.path.clone()),
+            project_type: record.project_type,
+            filename,
+            url: record.source_url.clone(),
+            sha1: Some(sha1_hex(&bytes)),
+            sha512: Some(sha512_hex(&bytes)),
+        })
+    }
+
+    /// Describe how `actual` differs from this entry, if at all.
+    ///
+    /// Hashes are only compared when both sides record them.
+    pub fn file_drift(&self, actual: &LockedDependency) -> Option<String> {
+        if self.version_id != actual.version_id {
+            return Some(format!(
+                "version {} installed, {} locked",
+                actual.version_id.as_deref().unwrap_or("-"),
+                self.version_id.as_deref().unwrap_or("-")
+            ));
+        }
+        if self.filename != actual.filename {
+            return Some(format!(
+                "file {} installed, {} locked",
+                actual.filename, self.filename
+            ));
+        }
+        if hash_differs(&self.sha1, &actual.sha1) || hash_differs(&self.sha512, &actual.sha512) {
+            return Some(format!("hash of {} does not match", actual.filename));
+        }
+        None
+    }
+}
+
+pub fn lockfile_path(workdir: &Path) -> PathBuf {
+    workdir.join(LOCKFILE_NAME)
+}
+
+/// Load `empack.lock`, returning `None` when it does not exist.
+pub fn load_lock(
+    filesystem: &dyn FileSystemProvider,
+    workdir: &Path,
+) -> Result<Option<EmpackLock>> {
+    let path = lockfile_path(workdir);
+    if !filesystem.exists(&path) {
+        return Ok(None);
+    }
+
+    let contents = filesystem
+        .read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let lock: EmpackLock =
+        toml::from_str(&contents).with_context(|| format!("Failed to parse {}", path.display()))?;
+    if lock.version != LOCKFILE_SCHEMA_VERSION {
+        return Err(LockfileError::UnsupportedVersion {
+            version: lock.version,
+        }
+        .into());
+    }
+    Ok(Some(lock))
+}
+
+pub fn write_lock(
+    filesystem: &dyn FileSystemProvider,
+    workdir: &Path,
+    lock: &EmpackLock,
+) -> Result<()> {
+    let serialized = toml::to_string_pretty(lock).context("Failed to serialize empack.lock")?;
+    filesystem.write_file(
+        &lockfile_path(workdir),
+        &format!("{LOCKFILE_HEADER}{serialized}"),
+    )
+}
+
+/// Lock entries read straight from the project, for when `empack.lock` does
+/// not exist yet.
+///
+/// Resolved dependencies come from `<key>.pw.toml` anywhere under `pack/`
+/// and local ones are hashed. Dependencies without a readable file are left
+/// out.
+pub fn lock_from_project(
+    filesystem: &dyn FileSystemProvider,
+    workdir: &Path,
+    dependencies: &BTreeMap<String, DependencyEntry>,
+) -> EmpackLock {
+    let metafiles = list_relative_files(filesystem, workdir, &workdir.join("pack"));
+    let metafile = |key: &str| {
+        let name = format!("{key}.pw.toml");
+        metafiles
+            .iter()
+            .find(|path| path.rsplit('/').next() == Some(name.as_str()))
+            .map(|path| workdir.join(path))
+    };
+
+    let mut lock = EmpackLock::default();
+    for (key, entry) in dependencies {
+        let locked = match entry {
+            DependencyEntry::Resolved(record) => metafile(key)
+                .and_then(|path| LockedDependency::from_metafile(filesystem, record, &path)),
+            DependencyEntry::Local(record) => {
+                LockedDependency::from_local(filesystem, workdir, record).ok()
+            }
+            DependencyEntry::Search(_) => None,
+        };
+        if let Some(locked) = locked {
+            lock.dependencies.insert(key.clone(), locked);
+        }
+    }
+    lock
+}
+
+/// List every way `lock` fails to describe the dependencies in empack.yml.
+///
+/// Search entries always disagree: the lock can only reproduce dependencies
+/// that have already been resolved.
+pub fn check_agreement(
+    dependencies: &BTreeMap<String, DependencyEntry>,
+    lock: &EmpackLock,
+) -> Vec<String> {
+    let mut mismatches = Vec::new();
+
+    for (key, entry) in dependencies {
+        let locked = lock.dependencies.get(key);
+        match (entry, locked) {
+            (DependencyEntry::Search(_), _) => {
+                mismatches.push(format!("{key} is an unresolved search entry"));
+            }
+            (_, None) => mismatches.push(format!("{key} is not in empack.lock")),
+            (DependencyEntry::Resolved(record), Some(locked)) => {
+                if locked.platform != Some(record.platform)
+                    || locked.project_id.as_deref() != Some(record.project_id.as_str())
+                {
+                    mismatches.push(format!(
+                        "{key} is {} {} in empack.yml but locked to {}",
+                        record.platform,
+                        record.project_id,
+                        locked_source(locked)
+                    ));
+                } else if let Some(pin) = &record.version
+                    && locked.version_id.as_deref() != Some(pin.as_str())
+                {
+                    mismatches.push(format!(
+                        "{key} is pinned to {pin} but locked to {}",
+                        locked.version_id.as_deref().unwrap_or("-")
+                    ));
+                }
+            }
+            (DependencyEntry::Local(record), Some(locked)) => {
+                if locked.path.as_deref() != Some(record.path.as_str()) {
+                    mismatches.push(format!(
+                        "{key} is local file {} in empack.yml but locked to {}",
+                        record.path,
+                        locked_source(locked)
+                    ));
+                }
+            }
+        }
+    }
+
+    for key in lock.dependencies.keys() {
+        if !dependencies.contains_key(key) {
+            mismatches.push(format!("{key} is locked but not declared in empack.yml"));
+        }
+    }
+
+    mismatches
+}
+
+fn locked_source(locked: &LockedDependency) -> String {
+    match (&locked.platform, &locked.project_id, &locked.path) {
+        (Some(platform), Some(project_id), _) => format!("{platform} {project_id}"),
+        (_, _, Some(path)) => format!("local file {path}"),
+        _ => "an unknown source".to_string(),
+    }
+}
+
+fn hash_differs(locked: &Option<String>, installed: &Option<String>) -> bool {
+    match (locked, installed) {
+        (Some(locked), Some(installed)) => !locked.eq_ignore_ascii_case(installed),
+        _ => false,
+    }
+}
+
+fn sha1_hex(data: &[u8]) -> String {
+    use sha1::Digest;
+    crate::empack::content::hex::encode(sha1::Sha1::digest(data))
+}
+
+fn sha512_hex(data: &[u8]) -> String {
+    use sha2::Digest;
+    crate::empack::content::hex::encode(sha2::Sha512::digest(data))
+}
+
+#[cfg(test)]
+mod tests {
+    include!("lockfile.test.rs");
+}
@@ -0,0 +1,251 @@
+use super::*;
+use crate::application::session_mocks::{MockFileSystemProvider, mock_root};
+use crate::empack::config::{DependencySearch, DependencyStatus};
+
+fn sodium_record(version: Option<&str>) -> DependencyRecord {
+    DependencyRecord {
+        status: DependencyStatus::Resolved,
+        title: "Sodium".to_string(),
+        platform: ProjectPlatform::Modrinth,
+        project_id: "AANobbMI".to_string(),
+        project_type: ProjectType::Mod,
+        version: version.map(|v| v.to_string()),
+        side: None,
+    }
+}
+
+fn locked_sodium(version_id: &str) -> LockedDependency {
+    LockedDependency {
+        platform: Some(ProjectPlatform::Modrinth),
+        project_id: Some("AANobbMI".to_string()),
+        version_id: Some(version_id.to_string()),
+        path: None,
+        project_type: ProjectType::Mod,
+        filename: "sodium.jar".to_string(),
+        url: Some("https://cdn.modrinth.com/sodium.jar".to_string()),
+        sha1: None,
+        sha512: Some("abcd".to_string()),
+    }
+}
+
+#[test]
+fn from_metafile_reads_modrinth_download() {
+    let path = mock_root().join("pack/mods/sodium.pw.toml");
+    let fs = MockFileSystemProvider::new().with_file(
+        path.clone(),
+        r#"name = "Sodium"
+filename = "sodium.jar"
+
+[download]
+url = "https://cdn.modrinth.com/sodium.jar"
+hash-format = "sha512"
+hash = "ABCD"
+
+[update.modrinth]
+mod-id = "AANobbMI"
+version = "v1"
+"#
+        .to_string(),
+    );
+
+    let locked =
+        LockedDependency::from_metafile(&fs, &sodium_record(None), &path).expect("lock entry");
+    assert_eq!(locked, locked_sodium("v1"));
+}
+
+#[test]
+fn from_metafile_reads_curseforge_file_id_and_sha1() {
+    let path = mock_root().join("pack/mods/jei.pw.toml");
+    let fs = MockFileSystemProvider::new().with_file(
+        path.clone(),
+        r#"name = "JEI"
+filename = "jei.jar"
+
+[download]
+hash-format = "sha1"
+hash = "1234"
+mode = "metadata:curseforge"
+
+[update.curseforge]
+file-id = 5101366
+project-id = 238222
+"#
+        .to_string(),
+    );
+    let record = DependencyRecord {
+        platform: ProjectPlatform::CurseForge,
+        project_id: "238222".to_string(),
+        ..sodium_record(None)
+    };
+
+    let locked = LockedDependency::from_metafile(&fs, &record, &path).expect("lock entry");
+    assert_eq!(locked.version_id.as_deref(), Some("5101366"));
+    assert_eq!(locked.url, None);
+    assert_eq!(locked.sha1.as_deref(), Some("1234"));
+    assert_eq!(locked.sha512, None);
+}
+
+#[test]
+fn from_local_hashes_tracked_file() {
+    let workdir = mock_root().join("project");
+    let fs = MockFileSystemProvider::new()
+        .with_binary_file(workdir.join("pack/mods/custom.jar"), b"abc".to_vec());
+    let record = LocalDependencyRecord {
+        status: DependencyStatus::Local,
+        title: "Custom".to_string(),
+        project_type: ProjectType::Mod,
+        path: "pack/mods/custom.jar".to_string(),
+        source_url: None,
+        sha256: "unused".to_string(),
+        side: None,
+    };
+
+    let locked = LockedDependency::from_local(&fs, &workdir, &record).expect("lock entry");
+    assert_eq!(locked.filename, "custom.jar");
+    assert_eq!(locked.path.as_deref(), Some("pack/mods/custom.jar"));
+    assert_eq!(
+        locked.sha1.as_deref(),
+        Some("a9993e364706816aba3e25717850c26c9cd0d89d")
+    );
+    assert!(locked.sha512.as_deref().unwrap().starts_with("ddaf35a1"));
+}
+
+#[test]
+fn lock_round_trips_through_filesystem() {
+    let workdir = mock_root().join("project");
+    let fs = MockFileSystemProvider::new();
+    let mut lock = EmpackLock::default();
+    lock.dependencies
+        .insert("sodium".to_string(), locked_sodium("v1"));
+
+    write_lock(&fs, &workdir, &lock).expect("write lock");
+    let written = fs
+        .read_to_string(&lockfile_path(&workdir))
+        .expect("lock contents");
+    assert!(written.starts_with("# This file is maintained by empack"));
+
+    let loaded = load_lock(&fs, &workdir).expect("load").expect("lock exists");
+    assert_eq!(loaded, lock);
+}
+
+#[test]
+fn load_lock_returns_none_when_missing() {
+    let fs = MockFileSystemProvider::new();
+    assert!(load_lock(&fs, &mock_root()).expect("load").is_none());
+}
+
+#[test]
+fn lock_from_project_reads_metafiles_in_any_pack_folder() {
+    let workdir = mock_root().join("project");
+    let fs = MockFileSystemProvider::new().with_file(
+        workdir.join("pack/mods/client/sodium.pw.toml"),
+        r#"name = "Sodium"
+filename = "sodium.jar"
+
+[download]
+url = "https://cdn.modrinth.com/sodium.jar"
+hash-format = "sha512"
+hash = "ABCD"
+
+[update.modrinth]
+mod-id = "AANobbMI"
+version = "v1"
+"#
+        .to_string(),
+    );
+    let deps = BTreeMap::from([
+        (
+            "sodium".to_string(),
+            DependencyEntry::Resolved(sodium_record(None)),
+        ),
+        (
+            "lithium".to_string(),
+            DependencyEntry::Resolved(DependencyRecord {
+                title: "Lithium".to_string(),
+                project_id: "gvQqBUqZ".to_string(),
+                ..sodium_record(None)
+            }),
+        ),
+    ]);
+
+    let lock = lock_from_project(&fs, &workdir, &deps);
+
+    assert_eq!(
+        lock.dependencies,
+        BTreeMap::from([("sodium".to_string(), locked_sodium("v1"))])
+    );
+}
+
+#[test]
+fn check_agreement_accepts_matching_lock() {
+    let mut deps = BTreeMap::new();
+    deps.insert(
+        "sodium".to_string(),
+        DependencyEntry::Resolved(sodium_record(Some("v1"))),
+    );
+    let mut lock = EmpackLock::default();
+    lock.dependencies
+        .insert("sodium".to_string(), locked_sodium("v1"));
+
+    assert!(check_agreement(&deps, &lock).is_empty());
+}
+
+#[test]
+fn check_agreement_reports_each_disagreement() {
+    let mut deps = BTreeMap::new();
+    deps.insert(
+        "sodium".to_string(),
+        DependencyEntry::Resolved(sodium_record(Some("v2"))),
+    );
+    deps.insert(
+        "lithium".to_string(),
+        DependencyEntry::Resolved(DependencyRecord {
+            title: "Lithium".to_string(),
+            project_id: "gvQqBUqZ".to_string(),
+            ..sodium_record(None)
+        }),
+    );
+    deps.insert(
+        "appleskin".to_string(),
+        DependencyEntry::Search(DependencySearch {
+            title: "AppleSkin".to_string(),
+            platform: None,
+            project_type: None,
+        }),
+    );
+    let mut lock = EmpackLock::default();
+    lock.dependencies
+        .insert("sodium".to_string(), locked_sodium("v1"));
+    lock.dependencies
+        .insert("removed".to_string(), locked_sodium("v1"));
+
+    let mismatches = check_agreement(&deps, &lock);
+    assert_eq!(
+        mismatches,
+        vec![
+            "appleskin is an unresolved search entry".to_string(),
+            "lithium is not in empack.lock".to_string(),
+            "sodium is pinned to v2 but locked to v1".to_string(),
+            "removed is locked but not declared in empack.yml".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn file_drift_compares_version_and_hashes() {
+    let locked = locked_sodium("v1");
+    assert_eq!(locked.file_drift(&locked_sodium("v1")), None);
+    assert_eq!(
+        locked.file_drift(&locked_sodium("v2")).as_deref(),
+        Some("version v2 installed, v1 locked")
+    );
+
+    let tampered = LockedDependency {
+        sha512: Some("ffff".to_string()),
+        ..locked_sodium("v1")
+    };
+    assert_eq!(
+        locked.file_drift(&tampered).as_deref(),
+        Some("hash of sodium.jar does not match")
+    );
+}
@@ -0,0 +1,153 @@
+//! Minecraft version migration planning
+//!
+//! Checks each resolved dependency for a file compatible with a target
+//! Minecraft version and loader. When the dependency's own platform has
+//! nothing, the other platform is searched by title.
+
+use crate::application::sync::{loader_arg, project_type_arg};
+use crate::empack::config::DependencyRecord;
+use crate::empack::fuzzy;
+use crate::empack::parsing::ModLoader;
+use crate::empack::search::{CandidateFilters, ProjectResolverTrait, SearchError};
+use crate::empack::updates::{UpdateApiBases, UpdateFilter, fetch_latest_file};
+use crate::primitives::ProjectPlatform;
+use serde::Serialize;
+
+/// Minecraft version and loader a pack is being moved to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationTarget {
+    pub minecraft_version: String,
+    pub loader: Option<ModLoader>,
+}
+
+/// Outcome of checking one dependency against a migration target
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "state", rename_all = "kebab-case")]
+pub enum MigrationStatus {
+    /// The current platform has a compatible file
+    Ready {
+        version_id: String,
+        version_name: String,
+    },
+    /// Only the other platform has a compatible project
+    OtherPlatform {
+        platform: ProjectPlatform,
+        project_id: String,
+        title: String,
+    },
+    /// Neither platform has a compatible file
+    Missing,
+    /// The check could not be completed
+    Failed { error: String },
+}
+
+impl MigrationStatus {
+    /// Short state label used in tables
+    pub fn label(&self) -> &'static str {
+        match self {
+            MigrationStatus::Ready { .. } => "ready",
+            MigrationStatus::OtherPlatform { .. } => "other platform",
+            MigrationStatus::Missing => "missing",
+            MigrationStatus::Failed { .. } => "check failed",
+        }
+    }
+
+    /// True when the dependency cannot move with the pack as-is
+    pub fn is_blocked(&self) -> bool {
+        matches!(
+            self,
+            MigrationStatus::OtherPlatform { .. } | MigrationStatus::Missing
+        )
+    }
+}
+
+/// Check one resolved dependency against the migration target.
+///
+/// Version pins are ignored: a pin names a file for the current version.
+pub async fn check_dependency_migration(
+    client: &reqwest::Client,
+    api_bases: &UpdateApiBases,
+    curseforge_api_key: Option<&str>,
+    resolver: &dyn ProjectResolverTrait,
+    record: &DependencyRecord,
+    target: &MigrationTarget,
+) -> MigrationStatus {
+    let minecraft_versions = [target.minecraft_version.clone()];
+    let latest = fetch_latest_file(
+        client,
+        api_bases,
+        curseforge_api_key,
+        record.platform,
+        &record.project_id,
+        UpdateFilter {
+            minecraft_versions: &minecraft_versions,
+            loader: target.loader,
+            project_type: record.project_type,
+        },
+    )
+    .await;
+
+    match latest {
+        Ok(Some(file)) => {
+            return MigrationStatus::Ready {
+                version_id: file.version_id,
+                version_name: file.version_name,
+            };
+        }
+        Ok(None) => {}
+        Err(e) => {
+            return MigrationStatus::Failed {
+                error: e.to_string(),
+            };
+        }
+    }
+
+    let other = match record.platform {
+        ProjectPlatform::Modrinth => ProjectPlatform::CurseForge,
+        ProjectPlatform::CurseForge => ProjectPlatform::Modrinth,
+    };
+    let threshold = match other {
+        ProjectPlatform::Modrinth => fuzzy::MODRINTH_CONFIDENCE_THRESHOLD,
+        ProjectPlatform::CurseForge => fuzzy::CURSEFORGE_CONFIDENCE_THRESHOLD,
+    };
+    let filters = CandidateFilters {
+        minecraft_version: Some(target.minecraft_version.clone()),
+        mod_loader: target.loader.map(|loader| loader_arg(loader).to_string()),
+        category: None,
+        platforms: vec![other],
+    };
+
+    match resolver
+        .search_candidates(
+            &record.title,
+            project_type_arg(record.project_type),
+            &filters,
+            threshold,
+        )
+        .await
+    {
+        Ok(candidates) => match candidates.into_iter().next() {
+            Some(project) => MigrationStatus::OtherPlatform {
+                platform: project.platform,
+                project_id: project.project_id,
+                title: project.title,
+            },
+            None => MigrationStatus::Missing,
+        },
+        Err(
+            SearchError::NoResults { .. }
+            | SearchError::LowConfidence { .. }
+            | SearchError::ExtraWords { .. }
+            | SearchError::IncompatibleProject { .. }
+            | SearchError::MissingApiKey { .. },
+        ) => MigrationStatus::Missing,
+        Err(e) => MigrationStatus::Failed {
+            error: e.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("migrate.test.rs");
+}
@@ -0,0 +1,176 @@
+use super::*;
+use crate::application::session_mocks::MockProjectResolver;
+use crate::empack::config::DependencyStatus;
+use crate::empack::search::ProjectInfo;
+use crate::primitives::ProjectType;
+
+fn test_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(5))
+        .build()
+        .expect("http client")
+}
+
+fn test_api_bases(url: &str) -> UpdateApiBases {
+    UpdateApiBases {
+        modrinth: vec![url.to_string()],
+        curseforge: vec![url.to_string()],
+    }
+}
+
+fn sodium_record() -> DependencyRecord {
+    DependencyRecord {
+        status: DependencyStatus::Resolved,
+        title: "Sodium".to_string(),
+        platform: ProjectPlatform::Modrinth,
+        project_id: "AANobbMI".to_string(),
+        project_type: ProjectType::Mod,
+        version: Some("old-pin".to_string()),
+        side: None,
+    }
+}
+
+fn target() -> MigrationTarget {
+    MigrationTarget {
+        minecraft_version: "1.21.1".to_string(),
+        loader: Some(ModLoader::Fabric),
+    }
+}
+
+#[tokio::test]
+async fn check_dependency_migration_reports_ready_file_on_current_platform() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/v2/project/AANobbMI/version")
+        .match_query(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::UrlEncoded(
+                "game_versions".to_string(),
+                r#"["1.21.1"]"#.to_string(),
+            ),
+            mockito::Matcher::UrlEncoded("loaders".to_string(), r#"["fabric"]"#.to_string()),
+        ]))
+        .with_status(200)
+        .with_body(
+            r#"[{"id":"new-id","version_number":"0.6.0","date_published":"2024-08-01T00:00:00Z","files":[]}]"#,
+        )
+        .create_async()
+        .await;
+    let resolver = MockProjectResolver::new();
+
+    let status = check_dependency_migration(
+        &test_client(),
+        &test_api_bases(&server.url()),
+        None,
+        &resolver,
+        &sodium_record(),
+        &target(),
+    )
+    .await;
+
+    mock.assert_async().await;
+    assert_eq!(
+        status,
+        MigrationStatus::Ready {
+            version_id: "new-id".to_string(),
+            version_name: "0.6.0".to_string(),
+        }
+    );
+    assert!(resolver.candidate_calls.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn check_dependency_migration_searches_other_platform_when_missing() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/v2/project/AANobbMI/version")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+    let resolver = MockProjectResolver::new();
+    resolver.candidates.lock().unwrap().insert(
+        "Sodium".to_string(),
+        vec![ProjectInfo {
+            platform: ProjectPlatform::CurseForge,
+            project_id: "394468".to_string(),
+            title: "Sodium".to_string(),
+            downloads: 1000,
+            confidence: 100,
+            project_type: "mod".to_string(),
+        }],
+    );
+
+    let status = check_dependency_migration(
+        &test_client(),
+        &test_api_bases(&server.url()),
+        None,
+        &resolver,
+        &sodium_record(),
+        &target(),
+    )
+    .await;
+
+    assert_eq!(
+        status,
+        MigrationStatus::OtherPlatform {
+            platform: ProjectPlatform::CurseForge,
+            project_id: "394468".to_string(),
+            title: "Sodium".to_string(),
+        }
+    );
+    let calls = resolver.candidate_calls.lock().unwrap();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].2.platforms, vec![ProjectPlatform::CurseForge]);
+    assert_eq!(calls[0].2.minecraft_version.as_deref(), Some("1.21.1"));
+    assert_eq!(calls[0].2.mod_loader.as_deref(), Some("fabric"));
+}
+
+#[tokio::test]
+async fn check_dependency_migration_reports_missing_and_failed_lookups() {
+    let mut server = mockito::Server::new_async().await;
+    let _empty = server
+        .mock("GET", "/v2/project/AANobbMI/version")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+    let _broken = server
+        .mock("GET", "/v2/project/gvQqBUqZ/version")
+        .match_query(mockito::Matcher::Any)
+        .with_status(500)
+        .create_async()
+        .await;
+    let resolver = MockProjectResolver::new();
+
+    let missing = check_dependency_migration(
+        &test_client(),
+        &test_api_bases(&server.url()),
+        None,
+        &resolver,
+        &sodium_record(),
+        &target(),
+    )
+    .await;
+    assert_eq!(missing, MigrationStatus::Missing);
+    assert!(missing.is_blocked());
+
+    let lithium = DependencyRecord {
+        title: "Lithium".to_string(),
+        project_id: "gvQqBUqZ".to_string(),
+        ..sodium_record()
+    };
+    let failed = check_dependency_migration(
+        &test_client(),
+        &test_api_bases(&server.url()),
+        None,
+        &resolver,
+        &lithium,
+        &target(),
+    )
+    .await;
+    assert!(matches!(failed, MigrationStatus::Failed { .. }));
+    assert!(!failed.is_blocked());
+    assert_eq!(failed.label(), "check failed");
+}
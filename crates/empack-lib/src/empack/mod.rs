@@ -1,15 +1,26 @@
 pub mod archive;
+pub mod build_cache;
 pub mod builds;
 pub mod config;
 pub mod content;
 pub mod fuzzy;
 pub mod import;
+pub mod journal;
+pub mod lockfile;
+pub mod migrate;
 pub mod packwiz;
 pub mod parsing;
 pub mod restricted_build;
+pub mod sbom;
 pub mod search;
+pub mod sides;
+pub mod signing;
 pub mod state;
+pub mod store;
 pub mod templates;
+pub mod time;
+pub mod transaction;
+pub mod updates;
 pub mod versions;
 
 pub use archive::{ArchiveError, ArchiveFormat};
@@ -19,25 +30,28 @@ pub use config::{
     DependencyStatus, EmpackConfig, ProjectPlan, ProjectSpec,
 };
 pub use content::{
-    ApiJarResolver, JarIdentifyRequest, JarIdentity, JarResolver, OverrideCategory, OverrideSide,
-    SideEnv, SideRequirement, UrlClassifyError, UrlKind,
+    ApiJarResolver, DependencySide, JarIdentifyRequest, JarIdentity, JarResolver, OverrideCategory,
+    OverrideSide, SideEnv, SideRequirement, UrlClassifyError, UrlKind,
 };
 pub use import::{
     ContentEntry, EmbeddedJar, ImportConfig, ImportError, ImportResult, ImportStats,
     ModpackManifest, OverrideEntry, PackIdentity, PlatformRef, ResolvedManifest, RuntimeTarget,
     SourceKind, classify_override, detect_local_source, execute_import, parse_curseforge_zip,
-    parse_modrinth_mrpack, resolve_manifest,
+    parse_modrinth_mrpack, parse_prism_instance, resolve_manifest,
 };
+pub use lockfile::{EmpackLock, LockedDependency, LockfileError};
+pub use migrate::{MigrationStatus, MigrationTarget};
 #[cfg(feature = "test-utils")]
 pub use packwiz::MockPackwizOps;
 pub use packwiz::{
     InstallResult, PackwizError, PackwizInstaller, PackwizMetadata, PackwizOps, RestrictedModInfo,
-    write_pack_toml_options,
+    retarget_pack_toml, write_pack_toml_options,
 };
 pub use restricted_build::{
     PendingRestrictedBuild, PendingRestrictedBuildEntry, PendingRestrictedBuildFingerprint,
 };
 pub use state::{PackStateManager, StateTransitionResult};
+pub use updates::{InstalledFile, LatestFile, UpdateApiBases, UpdateCheckError, UpdateFilter};
 
 // Re-export primitives types for convenience
 pub use crate::primitives::{
@@ -554,6 +554,60 @@ pub fn write_pack_toml_options(
     Ok(())
 }
 
+/// Point an existing pack.toml at a new Minecraft version and loader.
+///
+/// Replaces `[versions]` minecraft and loader keys, dropping any other
+/// loader entry, and removes `acceptable-game-versions` from `[options]`
+/// since those versions were chosen for the old target.
+#[instrument(skip_all)]
+pub fn retarget_pack_toml(
+    pack_toml_path: &Path,
+    minecraft_version: &str,
+    loader: Option<(&str, &str)>,
+    fs: &dyn FileSystemProvider,
+) -> Result<(), PackwizError> {
+    let content = fs
+        .read_to_string(pack_toml_path)
+        .map_err(|e| PackwizError::ProcessFailed {
+            source: std::io::Error::other(e),
+        })?;
+
+    let mut table: toml::Table = toml::from_str(&content)
+        .map_err(|e| PackwizError::PackFormatError(format!("failed to parse pack.toml: {e}")))?;
+
+    let versions = table
+        .entry("versions")
+        .or_insert_with(|| toml::Value::Table(toml::map::Map::new()))
+        .as_table_mut()
+        .ok_or_else(|| PackwizError::PackFormatError("[versions] is not a table".into()))?;
+
+    for key in ["fabric", "forge", "quilt", "neoforge"] {
+        versions.remove(key);
+    }
+    versions.insert(
+        "minecraft".to_string(),
+        toml::Value::String(minecraft_version.to_string()),
+    );
+    if let Some((key, version)) = loader {
+        versions.insert(key.to_string(), toml::Value::String(version.to_string()));
+    }
+
+    if let Some(options) = table.get_mut("options").and_then(|v| v.as_table_mut()) {
+        options.remove("acceptable-game-versions");
+    }
+
+    let output = toml::to_string(&table).map_err(|e| {
+        PackwizError::PackFormatError(format!("failed to serialize pack.toml: {e}"))
+    })?;
+
+    fs.write_file(pack_toml_path, &output)
+        .map_err(|e| PackwizError::ProcessFailed {
+            source: std::io::Error::other(e),
+        })?;
+
+    Ok(())
+}
+
 fn write_pack_toml_versions(
     pack_toml_path: &Path,
     version_entries: &[(&str, &str)],
@@ -893,7 +947,7 @@ impl<'a> PackwizMetadata<'a> {
 }
 
 /// A CurseForge mod that packwiz-installer identified as restricted.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct RestrictedModInfo {
     /// Mod display name.
     pub name: String,
@@ -2020,3 +2020,59 @@ minecraft = "1.7.10"
         Some("10.13.4.1614")
     );
 }
+
+#[test]
+fn test_retarget_pack_toml_replaces_versions_and_drops_acceptable_versions() {
+    let workdir = mock_root().join("workdir");
+    let pack_toml_path = workdir.join("pack").join("pack.toml");
+
+    let existing = r#"name = "Migrating Pack"
+pack-format = "packwiz:1.1.0"
+
+[index]
+file = "index.toml"
+hash-format = "sha256"
+hash = ""
+
+[versions]
+minecraft = "1.20.1"
+forge = "47.2.0"
+
+[options]
+datapack-folder = "datapacks"
+acceptable-game-versions = ["1.20"]
+"#;
+
+    let fs = MockFileSystemProvider::new()
+        .with_current_dir(workdir.clone())
+        .with_file(pack_toml_path.clone(), existing.to_string());
+
+    retarget_pack_toml(&pack_toml_path, "1.21.1", Some(("fabric", "0.16.5")), &fs)
+        .expect("should retarget pack.toml");
+
+    let updated = fs.read_to_string(&pack_toml_path).unwrap();
+    let doc: toml::Table = toml::from_str(&updated).unwrap();
+    let versions = doc
+        .get("versions")
+        .and_then(|value| value.as_table())
+        .expect("[versions] should exist");
+    assert_eq!(
+        versions.get("minecraft").and_then(|value| value.as_str()),
+        Some("1.21.1")
+    );
+    assert_eq!(
+        versions.get("fabric").and_then(|value| value.as_str()),
+        Some("0.16.5")
+    );
+    assert!(versions.get("forge").is_none());
+
+    let options = doc
+        .get("options")
+        .and_then(|value| value.as_table())
+        .expect("[options] should exist");
+    assert_eq!(
+        options.get("datapack-folder").and_then(|value| value.as_str()),
+        Some("datapacks")
+    );
+    assert!(options.get("acceptable-game-versions").is_none());
+}
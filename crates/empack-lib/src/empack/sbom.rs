@@ -0,0 +1,335 @@
+//! CycloneDX software bill of materials for build artifacts
+//!
+//! Every successful target gets `<pack>-v<version>-<target>.cdx.json` next
+//! to its artifact. Components come from `empack.lock`, the resolved files
+//! behind `empack.yml`, and never from the archives themselves. Licenses are
+//! the SPDX IDs Modrinth reports; the CurseForge API does not report one, so
+//! CurseForge components carry an `empack:license` property saying so.
+
+use crate::empack::builds::ModrinthProjectLicense;
+use crate::empack::config::DependencyEntry;
+use crate::empack::content::DependencySide;
+use crate::empack::lockfile::{EmpackLock, LockedDependency};
+use crate::primitives::{BuildTarget, ProjectPlatform, ProjectType};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// CycloneDX specification version the documents conform to.
+pub const SPEC_VERSION: &str = "1.5";
+
+/// File name suffix of every SBOM.
+pub const SBOM_EXTENSION: &str = "cdx.json";
+
+/// `empack:license` value of CurseForge components, which have no license data.
+pub const CURSEFORGE_LICENSE_NOTE: &str = "unknown: CurseForge does not report licenses";
+
+/// The pack an SBOM describes.
+#[derive(Debug, Clone, Copy)]
+pub struct PackMetadata<'a> {
+    pub name: &'a str,
+    pub version: &'a str,
+    /// Seconds since the Unix epoch, normally the source date epoch
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Bom {
+    bom_format: &'static str,
+    spec_version: &'static str,
+    version: u32,
+    metadata: Metadata,
+    components: Vec<Component>,
+}
+
+#[derive(Debug, Serialize)]
+struct Metadata {
+    timestamp: String,
+    tools: Tools,
+    component: Component,
+}
+
+#[derive(Debug, Serialize)]
+struct Tools {
+    components: Vec<Component>,
+}
+
+/// One CycloneDX component.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Component {
+    #[serde(rename = "type")]
+    component_type: &'static str,
+    #[serde(rename = "bom-ref", skip_serializing_if = "Option::is_none")]
+    bom_ref: Option<String>,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    hashes: Vec<Hash>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    licenses: Vec<LicenseChoice>,
+    #[serde(rename = "externalReferences", skip_serializing_if = "Vec::is_empty")]
+    external_references: Vec<ExternalReference>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    properties: Vec<Property>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct Hash {
+    alg: &'static str,
+    content: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct LicenseChoice {
+    license: License,
+}
+
+/// SPDX IDs go in `id`; Modrinth's `LicenseRef-*` values are not SPDX IDs
+/// and go in `name`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct License {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct ExternalReference {
+    #[serde(rename = "type")]
+    reference_type: &'static str,
+    url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct Property {
+    name: &'static str,
+    value: String,
+}
+
+/// SBOM file name for `target`, placed in the artifact root.
+pub fn file_name(pack_name: &str, pack_version: &str, target: BuildTarget) -> String {
+    format!("{pack_name}-v{pack_version}-{target}.{SBOM_EXTENSION}")
+}
+
+/// Whether content restricted to `side` ships in `target`.
+///
+/// `mrpack` and `curseforge` keep everything and leave sides to the launcher.
+fn ships_in(side: Option<DependencySide>, target: BuildTarget) -> bool {
+    let target_side = match target {
+        BuildTarget::Client | BuildTarget::ClientFull | BuildTarget::Prism => {
+            DependencySide::Client
+        }
+        BuildTarget::Server | BuildTarget::ServerFull => DependencySide::Server,
+        BuildTarget::Mrpack | BuildTarget::Curseforge => DependencySide::Both,
+    };
+    side.is_none_or(|side| side.installs_on(target_side))
+}
+
+/// Components shipped in `target`, in lock order.
+///
+/// `built` lists every target of the current build; each component records
+/// the ones it appears in. `licenses` maps Modrinth project IDs to the
+/// license Modrinth reports.
+pub fn components(
+    dependencies: &BTreeMap<String, DependencyEntry>,
+    lock: &EmpackLock,
+    licenses: &HashMap<String, String>,
+    target: BuildTarget,
+    built: &[BuildTarget],
+) -> Vec<Component> {
+    lock.dependencies
+        .iter()
+        .filter_map(|(key, locked)| {
+            let (title, side) = match dependencies.get(key) {
+                Some(DependencyEntry::Resolved(record)) => (Some(&record.title), record.side),
+                Some(DependencyEntry::Local(record)) => (Some(&record.title), record.side),
+                _ => (None, None),
+            };
+            if !ships_in(side, target) {
+                return None;
+            }
+            let targets = built
+                .iter()
+                .filter(|built| ships_in(side, **built))
+                .copied()
+                .collect::<Vec<_>>();
+            let license = locked
+                .project_id
+                .as_ref()
+                .and_then(|project_id| licenses.get(project_id));
+            Some(component(
+                key,
+                title.unwrap_or(key),
+                locked,
+                license,
+                &targets,
+            ))
+        })
+        .collect()
+}
+
+fn component(
+    key: &str,
+    title: &str,
+    locked: &LockedDependency,
+    license: Option<&String>,
+    targets: &[BuildTarget],
+) -> Component {
+    let mut hashes = Vec::new();
+    if let Some(sha1) = &locked.sha1 {
+        hashes.push(Hash {
+            alg: "SHA-1",
+            content: sha1.clone(),
+        });
+    }
+    if let Some(sha512) = &locked.sha512 {
+        hashes.push(Hash {
+            alg: "SHA-512",
+            content: sha512.clone(),
+        });
+    }
+
+    let licenses = license
+        .map(|license| {
+            let license = if license.starts_with("LicenseRef-") {
+                License {
+                    id: None,
+                    name: Some(license.clone()),
+                }
+            } else {
+                License {
+                    id: Some(license.clone()),
+                    name: None,
+                }
+            };
+            vec![LicenseChoice { license }]
+        })
+        .unwrap_or_default();
+
+    let external_references = locked
+        .url
+        .iter()
+        .map(|url| ExternalReference {
+            reference_type: "distribution",
+            url: url.clone(),
+        })
+        .collect();
+
+    let mut properties = Vec::new();
+    let mut property = |name, value: Option<String>| {
+        if let Some(value) = value {
+            properties.push(Property { name, value });
+        }
+    };
+    property("empack:platform", locked.platform.map(|p| p.to_string()));
+    if locked.platform == Some(ProjectPlatform::CurseForge) {
+        property("empack:license", Some(CURSEFORGE_LICENSE_NOTE.to_string()));
+    }
+    property("empack:project-id", locked.project_id.clone());
+    property("empack:version-id", locked.version_id.clone());
+    property("empack:path", locked.path.clone());
+    property(
+        "empack:type",
+        Some(locked.project_type.modrinth_facet_name().to_string()),
+    );
+    property("empack:filename", Some(locked.filename.clone()));
+    for target in targets {
+        property("empack:target", Some(target.to_string()));
+    }
+
+    Component {
+        component_type: match locked.project_type {
+            ProjectType::Mod => "library",
+            _ => "file",
+        },
+        bom_ref: Some(key.to_string()),
+        name: title.to_string(),
+        version: locked.version_id.clone(),
+        hashes,
+        licenses,
+        external_references,
+        properties,
+    }
+}
+
+/// Render the SBOM for `target` as pretty-printed JSON.
+pub fn render(
+    pack: PackMetadata<'_>,
+    target: BuildTarget,
+    components: Vec<Component>,
+) -> Result<String, serde_json::Error> {
+    let (year, month, day) = crate::empack::time::civil_date((pack.timestamp / 86_400) as i64);
+    let seconds = pack.timestamp % 86_400;
+    let timestamp = format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        seconds / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60
+    );
+
+    let bom = Bom {
+        bom_format: "CycloneDX",
+        spec_version: SPEC_VERSION,
+        version: 1,
+        metadata: Metadata {
+            timestamp,
+            tools: Tools {
+                components: vec![Component {
+                    component_type: "application",
+                    bom_ref: None,
+                    name: "empack".to_string(),
+                    version: Some(env!("CARGO_PKG_VERSION").to_string()),
+                    hashes: Vec::new(),
+                    licenses: Vec::new(),
+                    external_references: Vec::new(),
+                    properties: Vec::new(),
+                }],
+            },
+            component: Component {
+                component_type: "application",
+                bom_ref: Some(format!("{}@{}", pack.name, pack.version)),
+                name: pack.name.to_string(),
+                version: Some(pack.version.to_string()),
+                hashes: Vec::new(),
+                licenses: Vec::new(),
+                external_references: Vec::new(),
+                properties: vec![Property {
+                    name: "empack:target",
+                    value: target.to_string(),
+                }],
+            },
+        },
+        components,
+    };
+
+    serde_json::to_string_pretty(&bom).map(|json| json + "\n")
+}
+
+/// Modrinth project IDs in `lock` whose licenses need looking up.
+pub fn modrinth_project_ids(lock: &EmpackLock) -> Vec<String> {
+    let mut ids: Vec<String> = lock
+        .dependencies
+        .values()
+        .filter(|locked| locked.platform == Some(ProjectPlatform::Modrinth))
+        .filter_map(|locked| locked.project_id.clone())
+        .collect();
+    ids.sort();
+    ids.dedup();
+    ids
+}
+
+/// Parse a Modrinth `GET /v2/projects` response into project ID to license.
+pub fn parse_modrinth_licenses(body: &str) -> Result<HashMap<String, String>, serde_json::Error> {
+    let projects: Vec<ModrinthProjectLicense> = serde_json::from_str(body)?;
+    Ok(projects
+        .into_iter()
+        .map(|project| (project.id, project.license.id))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    include!("sbom.test.rs");
+}
@@ -0,0 +1,167 @@
+use super::*;
+use crate::empack::config::{DependencyRecord, DependencyStatus, LocalDependencyRecord};
+use crate::primitives::ProjectPlatform;
+
+fn resolved(title: &str, project_id: &str, side: Option<DependencySide>) -> DependencyEntry {
+    DependencyEntry::Resolved(DependencyRecord {
+        status: DependencyStatus::Resolved,
+        title: title.to_string(),
+        platform: ProjectPlatform::Modrinth,
+        project_id: project_id.to_string(),
+        project_type: ProjectType::Mod,
+        version: None,
+        side,
+    })
+}
+
+fn locked(project_id: Option<&str>, filename: &str) -> LockedDependency {
+    LockedDependency {
+        platform: project_id.map(|_| ProjectPlatform::Modrinth),
+        project_id: project_id.map(str::to_string),
+        version_id: project_id.map(|_| "ver1".to_string()),
+        path: project_id.is_none().then(|| format!("pack/resourcepacks/{filename}")),
+        project_type: if project_id.is_some() {
+            ProjectType::Mod
+        } else {
+            ProjectType::ResourcePack
+        },
+        filename: filename.to_string(),
+        url: project_id.map(|id| format!("https://cdn.modrinth.com/{id}/{filename}")),
+        sha1: Some("aa".to_string()),
+        sha512: Some("bb".to_string()),
+    }
+}
+
+fn project() -> (BTreeMap<String, DependencyEntry>, EmpackLock) {
+    let dependencies = BTreeMap::from([
+        ("sodium".to_string(), resolved("Sodium", "AANobbMI", Some(DependencySide::Client))),
+        ("lithium".to_string(), resolved("Lithium", "gvQqBUqZ", None)),
+        (
+            "faithful".to_string(),
+            DependencyEntry::Local(LocalDependencyRecord {
+                status: DependencyStatus::Local,
+                title: "Faithful".to_string(),
+                project_type: ProjectType::ResourcePack,
+                path: "pack/resourcepacks/faithful.zip".to_string(),
+                source_url: None,
+                sha256: "cc".to_string(),
+                side: None,
+            }),
+        ),
+    ]);
+    let lock = EmpackLock {
+        dependencies: BTreeMap::from([
+            ("faithful".to_string(), locked(None, "faithful.zip")),
+            ("lithium".to_string(), locked(Some("gvQqBUqZ"), "lithium.jar")),
+            ("sodium".to_string(), locked(Some("AANobbMI"), "sodium.jar")),
+        ]),
+        ..EmpackLock::default()
+    };
+    (dependencies, lock)
+}
+
+fn targets_of(component: &Component) -> Vec<&str> {
+    component
+        .properties
+        .iter()
+        .filter(|p| p.name == "empack:target")
+        .map(|p| p.value.as_str())
+        .collect()
+}
+
+#[test]
+fn components_follow_dependency_sides() {
+    let (dependencies, lock) = project();
+    let built = [BuildTarget::Mrpack, BuildTarget::Client, BuildTarget::Server];
+
+    let server = components(&dependencies, &lock, &HashMap::new(), BuildTarget::Server, &built);
+    assert_eq!(
+        server.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(),
+        ["Faithful", "Lithium"]
+    );
+    assert_eq!(targets_of(&server[1]), ["mrpack", "client", "server"]);
+
+    let client = components(&dependencies, &lock, &HashMap::new(), BuildTarget::Client, &built);
+    let sodium = client.iter().find(|c| c.name == "Sodium").unwrap();
+    assert_eq!(targets_of(sodium), ["mrpack", "client"]);
+    assert_eq!(sodium.version.as_deref(), Some("ver1"));
+}
+
+#[test]
+fn render_writes_cyclonedx_with_licenses_hashes_and_urls() {
+    let (dependencies, lock) = project();
+    let licenses = HashMap::from([
+        ("AANobbMI".to_string(), "LGPL-3.0-only".to_string()),
+        ("gvQqBUqZ".to_string(), "LicenseRef-Custom".to_string()),
+    ]);
+    let components = components(
+        &dependencies,
+        &lock,
+        &licenses,
+        BuildTarget::Mrpack,
+        &[BuildTarget::Mrpack],
+    );
+    let pack = PackMetadata {
+        name: "Test Pack",
+        version: "1.0.0",
+        timestamp: 1_792_238_645,
+    };
+
+    let json: serde_json::Value =
+        serde_json::from_str(&render(pack, BuildTarget::Mrpack, components).unwrap()).unwrap();
+
+    assert_eq!(json["bomFormat"], "CycloneDX");
+    assert_eq!(json["specVersion"], SPEC_VERSION);
+    assert_eq!(json["metadata"]["timestamp"], "2026-10-17T12:04:05Z");
+    assert_eq!(json["metadata"]["component"]["name"], "Test Pack");
+    let components = json["components"].as_array().unwrap();
+    assert_eq!(components.len(), 3);
+    let sodium = &components[2];
+    assert_eq!(sodium["bom-ref"], "sodium");
+    assert_eq!(sodium["licenses"][0]["license"]["id"], "LGPL-3.0-only");
+    assert_eq!(sodium["hashes"][1]["alg"], "SHA-512");
+    assert_eq!(
+        sodium["externalReferences"][0]["url"],
+        "https://cdn.modrinth.com/AANobbMI/sodium.jar"
+    );
+    assert_eq!(components[1]["licenses"][0]["license"]["name"], "LicenseRef-Custom");
+    assert!(components[0].get("licenses").is_none());
+    assert_eq!(components[0]["type"], "file");
+}
+
+#[test]
+fn parse_modrinth_licenses_maps_project_ids() {
+    let body = r#"[{"id":"AANobbMI","slug":"sodium","license":{"id":"LGPL-3.0-only","name":""}}]"#;
+
+    let licenses = parse_modrinth_licenses(body).unwrap();
+
+    assert_eq!(licenses.get("AANobbMI").map(String::as_str), Some("LGPL-3.0-only"));
+    assert!(parse_modrinth_licenses("{}").is_err());
+}
+
+#[test]
+fn curseforge_components_note_the_missing_license() {
+    let (dependencies, mut lock) = project();
+    let sodium = lock.dependencies.get_mut("sodium").unwrap();
+    sodium.platform = Some(ProjectPlatform::CurseForge);
+    sodium.project_id = Some("394468".to_string());
+
+    let components = components(
+        &dependencies,
+        &lock,
+        &HashMap::new(),
+        BuildTarget::Mrpack,
+        &[BuildTarget::Mrpack],
+    );
+
+    let sodium = components.iter().find(|c| c.name == "Sodium").unwrap();
+    assert!(sodium.licenses.is_empty());
+    assert!(
+        sodium
+            .properties
+            .iter()
+            .any(|p| p.name == "empack:license" && p.value == CURSEFORGE_LICENSE_NOTE)
+    );
+    let lithium = components.iter().find(|c| c.name == "Lithium").unwrap();
+    assert!(!lithium.properties.iter().any(|p| p.name == "empack:license"));
+}
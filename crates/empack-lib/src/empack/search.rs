@@ -3,7 +3,11 @@
 //! Project matching with confidence scoring, platform
 //! priority, and fuzzy string matching.
 
+use crate::empack::content::SideEnv;
+use crate::empack::sides;
+use crate::empack::updates::UpdateApiBases;
 use crate::networking::cache::HttpCache;
+use crate::networking::endpoints::{Endpoints, Service};
 use crate::networking::rate_limit::RateLimiterManager;
 use crate::primitives::ProjectPlatform;
 use crate::primitives::empack::ProjectType;
@@ -79,6 +83,36 @@ pub trait ProjectResolverTrait: Send + Sync {
         mod_loader: Option<&str>,
         preferred_platform: Option<ProjectPlatform>,
     ) -> Pin<Box<dyn Future<Output = Result<ProjectInfo, SearchError>> + Send + '_>>;
+
+    /// Search the platforms in `filters` and return scored candidates, best first.
+    fn search_candidates(
+        &self,
+        title: &str,
+        project_type: &str,
+        filters: &CandidateFilters,
+        min_confidence: u8,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<ProjectInfo>, SearchError>> + Send + '_>>;
+
+    /// Look up the client/server requirements of a resolved project.
+    ///
+    /// `file_id` selects the CurseForge file whose side flags are read.
+    fn fetch_side_env(
+        &self,
+        platform: ProjectPlatform,
+        project_id: &str,
+        file_id: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<SideEnv, SearchError>> + Send + '_>>;
+}
+
+/// Filters applied when listing search candidates
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CandidateFilters {
+    pub minecraft_version: Option<String>,
+    pub mod_loader: Option<String>,
+    /// Modrinth category or CurseForge category slug
+    pub category: Option<String>,
+    /// Platforms to query, in order; empty means Modrinth then CurseForge
+    pub platforms: Vec<ProjectPlatform>,
 }
 
 /// Platform-specific project information
@@ -121,6 +155,13 @@ struct CurseForgeProject {
     name: String,
     #[serde(rename = "downloadCount")]
     download_count: u64,
+    #[serde(default)]
+    categories: Vec<CurseForgeCategory>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurseForgeCategory {
+    slug: String,
 }
 
 use crate::empack::fuzzy;
@@ -139,10 +180,10 @@ fn extract_loaders(categories: &[String]) -> Vec<String> {
 pub struct ProjectResolver {
     client: Client,
     curseforge_api_key: Option<String>,
-    modrinth_base_url: String,
-    curseforge_base_url: String,
+    endpoints: Endpoints,
     cache: Option<Arc<HttpCache>>,
     rate_limiter: Option<Arc<RateLimiterManager>>,
+    offline: bool,
 }
 
 impl ProjectResolver {
@@ -151,10 +192,10 @@ impl ProjectResolver {
         Self {
             client,
             curseforge_api_key,
-            modrinth_base_url: "https://api.modrinth.com".to_string(),
-            curseforge_base_url: "https://api.curseforge.com".to_string(),
+            endpoints: Endpoints::default(),
             cache: None,
             rate_limiter: None,
+            offline: false,
         }
     }
 
@@ -168,10 +209,10 @@ impl ProjectResolver {
         Self {
             client,
             curseforge_api_key,
-            modrinth_base_url: "https://api.modrinth.com".to_string(),
-            curseforge_base_url: "https://api.curseforge.com".to_string(),
+            endpoints: Endpoints::default(),
             cache: Some(cache),
             rate_limiter: Some(rate_limiter),
+            offline: false,
         }
     }
 
@@ -186,12 +227,12 @@ impl ProjectResolver {
         Self {
             client,
             curseforge_api_key,
-            modrinth_base_url: modrinth_base_url
-                .unwrap_or_else(|| "https://api.modrinth.com".to_string()),
-            curseforge_base_url: curseforge_base_url
-                .unwrap_or_else(|| "https://api.curseforge.com".to_string()),
+            endpoints: Endpoints::default()
+                .with_bases(Service::ModrinthApi, modrinth_base_url)
+                .with_bases(Service::CurseForgeApi, curseforge_base_url),
             cache: None,
             rate_limiter: None,
+            offline: false,
         }
     }
 
@@ -208,15 +249,27 @@ impl ProjectResolver {
         Self {
             client,
             curseforge_api_key,
-            modrinth_base_url: modrinth_base_url
-                .unwrap_or_else(|| "https://api.modrinth.com".to_string()),
-            curseforge_base_url: curseforge_base_url
-                .unwrap_or_else(|| "https://api.curseforge.com".to_string()),
+            endpoints: Endpoints::default()
+                .with_bases(Service::ModrinthApi, modrinth_base_url)
+                .with_bases(Service::CurseForgeApi, curseforge_base_url),
             cache: Some(cache),
             rate_limiter: Some(rate_limiter),
+            offline: false,
         }
     }
 
+    /// Send requests to `endpoints` instead of the upstream platform APIs
+    pub fn with_endpoints(mut self, endpoints: Endpoints) -> Self {
+        self.endpoints = endpoints;
+        self
+    }
+
+    /// Serve every request from the cache, ignoring expiry, and fail on a miss
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
     /// Resolve project with platform priority: Modrinth first, then CurseForge
     ///
     /// When `project_type` is `Some`, searches only that type.
@@ -371,13 +424,13 @@ impl ProjectResolver {
     ) -> Result<Option<ProjectInfo>, SearchError> {
         let (search_result, threshold, label) = match platform {
             ProjectPlatform::Modrinth => (
-                self.search_modrinth(title, project_type, minecraft_version, mod_loader)
+                self.search_modrinth(title, project_type, minecraft_version, mod_loader, None)
                     .await,
                 fuzzy::MODRINTH_CONFIDENCE_THRESHOLD,
                 "Modrinth",
             ),
             ProjectPlatform::CurseForge => (
-                self.search_curseforge(title, project_type, minecraft_version, mod_loader)
+                self.search_curseforge(title, project_type, minecraft_version, mod_loader, None)
                     .await,
                 fuzzy::CURSEFORGE_CONFIDENCE_THRESHOLD,
                 "CurseForge",
@@ -436,37 +489,51 @@ impl ProjectResolver {
         projects
     }
 
-    /// Search both platforms and return ranked candidates when confidence is ambiguous.
+    /// Search the requested platforms and return ranked candidates.
     ///
     /// Returns all results with confidence >= `min_confidence`, sorted by confidence
-    /// descending. Used by the UI layer to present a selection list when the top
-    /// result isn't a clear auto-select (confidence 70-89%).
+    /// descending. Used by `empack search` and by the UI layer to present a
+    /// selection list when the top result isn't a clear auto-select.
     pub async fn search_candidates(
         &self,
         title: &str,
         project_type: &str,
-        minecraft_version: Option<&str>,
-        mod_loader: Option<&str>,
+        filters: &CandidateFilters,
         min_confidence: u8,
-        preferred_platform: Option<ProjectPlatform>,
     ) -> Result<Vec<ProjectInfo>, SearchError> {
         let mut all_results = Vec::new();
+        let mut incompatible = None;
 
-        let platforms = if preferred_platform == Some(ProjectPlatform::CurseForge) {
-            vec![ProjectPlatform::CurseForge, ProjectPlatform::Modrinth]
-        } else {
+        let platforms = if filters.platforms.is_empty() {
             vec![ProjectPlatform::Modrinth, ProjectPlatform::CurseForge]
+        } else {
+            filters.platforms.clone()
         };
+        let minecraft_version = filters.minecraft_version.as_deref();
+        let mod_loader = filters.mod_loader.as_deref();
+        let category = filters.category.as_deref();
 
         for platform in platforms {
             let results = match platform {
                 ProjectPlatform::Modrinth => {
-                    self.search_modrinth(title, project_type, minecraft_version, mod_loader)
-                        .await
+                    self.search_modrinth(
+                        title,
+                        project_type,
+                        minecraft_version,
+                        mod_loader,
+                        category,
+                    )
+                    .await
                 }
                 ProjectPlatform::CurseForge => {
-                    self.search_curseforge(title, project_type, minecraft_version, mod_loader)
-                        .await
+                    self.search_curseforge(
+                        title,
+                        project_type,
+                        minecraft_version,
+                        mod_loader,
+                        category,
+                    )
+                    .await
                 }
             };
 
@@ -475,14 +542,20 @@ impl ProjectResolver {
                 Err(SearchError::NoResults { .. }) | Err(SearchError::MissingApiKey { .. }) => {
                     debug!("No results from {:?}, continuing", platform);
                 }
+                // Keep looking on the other platform; report the mismatch only
+                // if nothing compatible turns up anywhere.
+                Err(e @ SearchError::IncompatibleProject { .. }) => {
+                    debug!("Incompatible match on {:?}, continuing", platform);
+                    incompatible.get_or_insert(e);
+                }
                 Err(e) => return Err(e),
             }
         }
 
         if all_results.is_empty() {
-            return Err(SearchError::NoResults {
+            return Err(incompatible.unwrap_or_else(|| SearchError::NoResults {
                 query: title.to_string(),
-            });
+            }));
         }
 
         let scored = Self::score_results(title, all_results);
@@ -517,31 +590,38 @@ impl ProjectResolver {
         use crate::networking::cache::CachedResponse;
         use std::time::SystemTime;
 
-        // Check cache first
+        // Check cache first; offline, a stale entry beats no answer
         if let Some(cache) = &self.cache
             && let Some(cached) = cache.get(url).await
-            && !cached.is_expired()
+            && (self.offline || !cached.is_expired())
         {
             trace!("Cache hit for search URL: {}", url);
             return Ok((cached.status, cached.data));
         }
-
-        // Send through rate limiter or directly
-        let response = if let Some(rate_limiter) = &self.rate_limiter {
-            let rl_client = rate_limiter.client_for_platform(platform);
-            let mut req_builder = self.client.get(url);
-            for (key, value) in headers {
-                req_builder = req_builder.header(*key, *value);
+        if self.offline {
+            return Err(crate::networking::NetworkingError::Offline {
+                resource: url.to_string(),
             }
-            let request = req_builder.build()?;
-            rl_client.execute(request).await?
-        } else {
-            let mut req_builder = self.client.get(url);
-            for (key, value) in headers {
-                req_builder = req_builder.header(*key, *value);
-            }
-            req_builder.send().await?
-        };
+            .into());
+        }
+
+        // Send through rate limiter or directly, falling back across endpoints
+        let response = self
+            .endpoints
+            .send(url, |url| async move {
+                let mut req_builder = self.client.get(url);
+                for (key, value) in headers {
+                    req_builder = req_builder.header(*key, *value);
+                }
+                match &self.rate_limiter {
+                    Some(rate_limiter) => Ok(rate_limiter
+                        .client_for_platform(platform)
+                        .execute(req_builder.build()?)
+                        .await?),
+                    None => Ok::<_, SearchError>(req_builder.send().await?),
+                }
+            })
+            .await?;
 
         let status = response.status().as_u16();
         let etag = response
@@ -575,6 +655,7 @@ impl ProjectResolver {
         project_type: &str,
         minecraft_version: Option<&str>,
         mod_loader: Option<&str>,
+        category: Option<&str>,
     ) -> Result<Vec<ProjectInfo>, SearchError> {
         let normalized_type = self.normalize_project_type(project_type);
         let parsed_type = Self::parse_project_type(&normalized_type);
@@ -593,6 +674,10 @@ impl ProjectResolver {
             facets.push(format!("categories:{}", loader));
         }
 
+        if let Some(category) = category {
+            facets.push(format!("categories:{}", category));
+        }
+
         let facets_json = format!(
             "[{}]",
             facets
@@ -604,7 +689,7 @@ impl ProjectResolver {
 
         let url = format!(
             "{}/v2/search?query={}&facets={}",
-            self.modrinth_base_url,
+            self.endpoints.base(Service::ModrinthApi),
             utf8_percent_encode(title, NON_ALPHANUMERIC),
             utf8_percent_encode(&facets_json, NON_ALPHANUMERIC)
         );
@@ -678,6 +763,7 @@ impl ProjectResolver {
         project_type: &str,
         minecraft_version: Option<&str>,
         mod_loader: Option<&str>,
+        category: Option<&str>,
     ) -> Result<Vec<ProjectInfo>, SearchError> {
         let api_key =
             self.curseforge_api_key
@@ -719,7 +805,8 @@ impl ProjectResolver {
 
         let url = format!(
             "{}/v1/mods/search?{}",
-            self.curseforge_base_url, query_string
+            self.endpoints.base(Service::CurseForgeApi),
+            query_string
         );
 
         trace!("CurseForge search URL: {}", url);
@@ -740,7 +827,17 @@ impl ProjectResolver {
             });
         }
 
-        let search_response: CurseForgeSearchResponse = serde_json::from_slice(&body)?;
+        let mut search_response: CurseForgeSearchResponse = serde_json::from_slice(&body)?;
+
+        // CurseForge filters categories by numeric ID, so match slugs on the results
+        if let Some(category) = category {
+            search_response.data.retain(|project| {
+                project
+                    .categories
+                    .iter()
+                    .any(|c| c.slug.eq_ignore_ascii_case(category))
+            });
+        }
 
         if search_response.data.is_empty() {
             return Err(SearchError::NoResults {
@@ -772,7 +869,7 @@ impl ProjectResolver {
         let facets_json = format!("[[\"project_type:{}\"]]", project_type);
         let url = format!(
             "{}/v2/search?query={}&facets={}",
-            self.modrinth_base_url,
+            self.endpoints.base(Service::ModrinthApi),
             utf8_percent_encode(title, NON_ALPHANUMERIC),
             utf8_percent_encode(&facets_json, NON_ALPHANUMERIC)
         );
@@ -906,6 +1003,47 @@ impl ProjectResolverTrait for ProjectResolver {
             .await
         })
     }
+
+    fn search_candidates(
+        &self,
+        title: &str,
+        project_type: &str,
+        filters: &CandidateFilters,
+        min_confidence: u8,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<ProjectInfo>, SearchError>> + Send + '_>> {
+        let title = title.to_string();
+        let project_type = project_type.to_string();
+        let filters = filters.clone();
+
+        Box::pin(async move {
+            self.search_candidates(&title, &project_type, &filters, min_confidence)
+                .await
+        })
+    }
+
+    fn fetch_side_env(
+        &self,
+        platform: ProjectPlatform,
+        project_id: &str,
+        file_id: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<SideEnv, SearchError>> + Send + '_>> {
+        let project_id = project_id.to_string();
+        let file_id = file_id.map(|s| s.to_string());
+        let api_bases = UpdateApiBases::from_endpoints(&self.endpoints);
+
+        Box::pin(async move {
+            sides::fetch_side_env(
+                &self.client,
+                &api_bases,
+                self.curseforge_api_key.as_deref(),
+                platform,
+                &project_id,
+                file_id.as_deref(),
+            )
+            .await
+            .map_err(|e| SearchError::Other(e.into()))
+        })
+    }
 }
 
 #[cfg(test)]
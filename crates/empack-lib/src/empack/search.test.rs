@@ -775,6 +775,72 @@ async fn test_cache_hit_skips_network_call() {
     mr_mock.assert_async().await;
 }
 
+#[cfg(feature = "test-utils")]
+#[tokio::test]
+async fn test_offline_serves_expired_cache_and_fails_on_miss() {
+    use crate::networking::NetworkingError;
+    use crate::networking::cache::HttpCache;
+    use crate::networking::rate_limit::RateLimiterManager;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    // Zero TTL: every cached response is expired as soon as it is stored
+    let cache = Arc::new(HttpCache::with_ttl(temp_dir.path().to_path_buf(), Duration::ZERO));
+    let rate_limiter = Arc::new(RateLimiterManager::new(Client::new()));
+
+    let mut mr_server = mockito::Server::new_async().await;
+
+    let mr_mock = mr_server
+        .mock(
+            "GET",
+            mockito::Matcher::Regex(r"/v2/search\?.*".to_string()),
+        )
+        .with_status(200)
+        .with_body(modrinth_hit_json("OFFLINE1", "Sodium", 50_000))
+        .expect(1)
+        .create_async()
+        .await;
+
+    let resolver = |offline| {
+        ProjectResolver::new_with_base_urls_and_networking(
+            Client::new(),
+            None,
+            Some(mr_server.url()),
+            Some("http://unused-cf:1".to_string()),
+            cache.clone(),
+            rate_limiter.clone(),
+        )
+        .with_offline(offline)
+    };
+
+    resolver(false)
+        .resolve_project("Sodium", Some("mod"), None, None, None)
+        .await
+        .expect("online resolve should succeed");
+
+    let offline = resolver(true);
+    let result = offline
+        .resolve_project("Sodium", Some("mod"), None, None, None)
+        .await
+        .expect("offline resolve should use the expired cache entry");
+    assert_eq!(result.project_id, "OFFLINE1");
+
+    let error = offline
+        .resolve_project("Lithium", Some("mod"), None, None, None)
+        .await
+        .expect_err("uncached query should fail offline");
+    match error {
+        SearchError::NetworkError {
+            source: NetworkingError::Offline { resource },
+        } => assert!(resource.contains("query=Lithium"), "{resource}"),
+        other => panic!("expected an offline error, got {other:?}"),
+    }
+
+    mr_mock.assert_async().await;
+}
+
 #[cfg(feature = "test-utils")]
 #[tokio::test]
 async fn test_rate_limiter_retries_on_429() {
@@ -1010,7 +1076,7 @@ async fn test_search_candidates_returns_ranked_list() {
     );
 
     let candidates = resolver
-        .search_candidates("Sodium", "mod", None, None, 70, None)
+        .search_candidates("Sodium", "mod", &CandidateFilters::default(), 70)
         .await
         .expect("should return candidates");
 
@@ -1048,7 +1114,7 @@ async fn test_search_candidates_filters_below_min_confidence() {
     );
 
     let candidates = resolver
-        .search_candidates("Sodium", "mod", None, None, 80, None)
+        .search_candidates("Sodium", "mod", &CandidateFilters::default(), 80)
         .await
         .expect("should return filtered candidates");
 
@@ -1084,7 +1150,7 @@ async fn test_search_candidates_all_below_threshold_returns_error() {
     );
 
     let err = resolver
-        .search_candidates("Sodium", "mod", None, None, 70, None)
+        .search_candidates("Sodium", "mod", &CandidateFilters::default(), 70)
         .await
         .unwrap_err();
 
@@ -1124,8 +1190,13 @@ async fn test_search_candidates_respects_preferred_platform() {
 
     let candidates = resolver
         .search_candidates(
-            "Sodium", "mod", None, None, 70,
-            Some(ProjectPlatform::CurseForge),
+            "Sodium",
+            "mod",
+            &CandidateFilters {
+                platforms: vec![ProjectPlatform::CurseForge, ProjectPlatform::Modrinth],
+                ..CandidateFilters::default()
+            },
+            70,
         )
         .await
         .expect("should return candidates from both platforms");
@@ -1156,7 +1227,7 @@ async fn test_search_candidates_network_error_propagates() {
     );
 
     let err = resolver
-        .search_candidates("Sodium", "mod", None, None, 70, None)
+        .search_candidates("Sodium", "mod", &CandidateFilters::default(), 70)
         .await
         .unwrap_err();
 
@@ -1166,6 +1237,118 @@ async fn test_search_candidates_network_error_propagates() {
     );
 }
 
+#[cfg(feature = "test-utils")]
+#[tokio::test]
+async fn test_search_candidates_only_queries_requested_platforms() {
+    let mut mr_server = mockito::Server::new_async().await;
+    let mut cf_server = mockito::Server::new_async().await;
+
+    let modrinth = mr_server
+        .mock("GET", mockito::Matcher::Regex(r"/v2/search\?.*".to_string()))
+        .with_status(200)
+        .with_body(modrinth_hit_json("MR1", "Sodium", 80_000))
+        .expect(0)
+        .create_async()
+        .await;
+    cf_server
+        .mock("GET", mockito::Matcher::Regex(r"/v1/mods/search\?.*".to_string()))
+        .with_status(200)
+        .with_body(curseforge_hit_json(200, "Sodium", 60_000))
+        .create_async()
+        .await;
+
+    let resolver = ProjectResolver::new_with_base_urls(
+        Client::new(),
+        Some("test-api-key".to_string()),
+        Some(mr_server.url()),
+        Some(cf_server.url()),
+    );
+
+    let candidates = resolver
+        .search_candidates(
+            "Sodium",
+            "mod",
+            &CandidateFilters {
+                platforms: vec![ProjectPlatform::CurseForge],
+                ..CandidateFilters::default()
+            },
+            0,
+        )
+        .await
+        .expect("should return CurseForge candidates");
+
+    assert!(candidates
+        .iter()
+        .all(|c| c.platform == ProjectPlatform::CurseForge));
+    modrinth.assert_async().await;
+}
+
+#[cfg(feature = "test-utils")]
+#[tokio::test]
+async fn test_search_candidates_applies_category_filter() {
+    let mut mr_server = mockito::Server::new_async().await;
+    let mut cf_server = mockito::Server::new_async().await;
+
+    mr_server
+        .mock(
+            "GET",
+            mockito::Matcher::Regex(r"categories%3Aoptimization".to_string()),
+        )
+        .with_status(200)
+        .with_body(modrinth_hit_json("MR1", "Sodium", 80_000))
+        .create_async()
+        .await;
+    cf_server
+        .mock("GET", mockito::Matcher::Regex(r"/v1/mods/search\?.*".to_string()))
+        .with_status(200)
+        .with_body(
+            serde_json::json!({
+                "data": [
+                    {
+                        "id": 200,
+                        "name": "Sodium",
+                        "downloadCount": 60_000,
+                        "categories": [{ "slug": "performance" }]
+                    },
+                    {
+                        "id": 201,
+                        "name": "Sodium",
+                        "downloadCount": 50_000,
+                        "categories": [{ "slug": "optimization" }]
+                    }
+                ]
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let resolver = ProjectResolver::new_with_base_urls(
+        Client::new(),
+        Some("test-api-key".to_string()),
+        Some(mr_server.url()),
+        Some(cf_server.url()),
+    );
+
+    let candidates = resolver
+        .search_candidates(
+            "Sodium",
+            "mod",
+            &CandidateFilters {
+                category: Some("optimization".to_string()),
+                ..CandidateFilters::default()
+            },
+            0,
+        )
+        .await
+        .expect("should return filtered candidates");
+
+    let ids: Vec<&str> = candidates.iter().map(|c| c.project_id.as_str()).collect();
+    assert!(ids.contains(&"MR1"));
+    assert!(ids.contains(&"201"));
+    assert!(!ids.contains(&"200"));
+}
+
 // ===== PHASE 2: INCOMPATIBLE PROJECT DETECTION TESTS =====
 
 fn modrinth_hit_with_categories_json(
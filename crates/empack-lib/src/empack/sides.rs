@@ -0,0 +1,150 @@
+//! Client/server side discovery for resolved dependencies
+//!
+//! Modrinth reports `client_side`/`server_side` on the project. CurseForge
+//! tags individual files with "Client" and "Server" game versions.
+
+use crate::empack::content::{SideEnv, SideRequirement};
+use crate::empack::updates::{UpdateApiBases, UpdateCheckError};
+use crate::networking::endpoints::send_with_fallback;
+use crate::primitives::ProjectPlatform;
+use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
+use serde::Deserialize;
+use tracing::trace;
+
+/// Query the side requirements of a project.
+///
+/// CurseForge flags live on files, so `file_id` is required there; without
+/// it the requirements are reported as unknown.
+pub async fn fetch_side_env(
+    client: &reqwest::Client,
+    api_bases: &UpdateApiBases,
+    curseforge_api_key: Option<&str>,
+    platform: ProjectPlatform,
+    project_id: &str,
+    file_id: Option<&str>,
+) -> Result<SideEnv, UpdateCheckError> {
+    match platform {
+        ProjectPlatform::Modrinth => fetch_modrinth_side_env(client, api_bases, project_id).await,
+        ProjectPlatform::CurseForge => {
+            let api_key = curseforge_api_key.ok_or(UpdateCheckError::MissingApiKey {
+                platform: ProjectPlatform::CurseForge,
+            })?;
+            match file_id {
+                Some(file_id) => {
+                    fetch_curseforge_side_env(client, api_bases, api_key, project_id, file_id).await
+                }
+                None => Ok(unknown_env()),
+            }
+        }
+    }
+}
+
+fn unknown_env() -> SideEnv {
+    SideEnv {
+        client: SideRequirement::Unknown,
+        server: SideRequirement::Unknown,
+    }
+}
+
+#[derive(Deserialize)]
+struct ModrinthProjectSides {
+    client_side: Option<String>,
+    server_side: Option<String>,
+}
+
+async fn fetch_modrinth_side_env(
+    client: &reqwest::Client,
+    api_bases: &UpdateApiBases,
+    project_id: &str,
+) -> Result<SideEnv, UpdateCheckError> {
+    let urls = api_bases.modrinth_urls(&format!(
+        "v2/project/{}",
+        utf8_percent_encode(project_id, NON_ALPHANUMERIC)
+    ));
+    trace!("Modrinth project URL: {}", urls[0]);
+
+    let response = send_with_fallback(urls, |url| {
+        client.get(url).header("User-Agent", "empack/0.1.0").send()
+    })
+    .await?;
+    if !response.status().is_success() {
+        return Err(UpdateCheckError::UnexpectedStatus {
+            platform: ProjectPlatform::Modrinth,
+            project_id: project_id.to_string(),
+            status: response.status().as_u16(),
+        });
+    }
+
+    let project: ModrinthProjectSides = response.json().await?;
+    Ok(SideEnv {
+        client: SideRequirement::from_modrinth(project.client_side.as_deref()),
+        server: SideRequirement::from_modrinth(project.server_side.as_deref()),
+    })
+}
+
+#[derive(Deserialize)]
+struct CurseForgeFileResponse {
+    data: CurseForgeFileVersions,
+}
+
+#[derive(Deserialize)]
+struct CurseForgeFileVersions {
+    #[serde(rename = "gameVersions", default)]
+    game_versions: Vec<String>,
+}
+
+async fn fetch_curseforge_side_env(
+    client: &reqwest::Client,
+    api_bases: &UpdateApiBases,
+    api_key: &str,
+    project_id: &str,
+    file_id: &str,
+) -> Result<SideEnv, UpdateCheckError> {
+    let urls = api_bases.curseforge_urls(&format!(
+        "v1/mods/{}/files/{}",
+        utf8_percent_encode(project_id, NON_ALPHANUMERIC),
+        utf8_percent_encode(file_id, NON_ALPHANUMERIC)
+    ));
+    trace!("CurseForge file URL: {}", urls[0]);
+
+    let response = send_with_fallback(urls, |url| {
+        client
+            .get(url)
+            .header("x-api-key", api_key)
+            .header("User-Agent", "empack/0.1.0")
+            .send()
+    })
+    .await?;
+    if !response.status().is_success() {
+        return Err(UpdateCheckError::UnexpectedStatus {
+            platform: ProjectPlatform::CurseForge,
+            project_id: project_id.to_string(),
+            status: response.status().as_u16(),
+        });
+    }
+
+    let body: CurseForgeFileResponse = response.json().await?;
+    Ok(curseforge_side_env(&body.data.game_versions))
+}
+
+/// Derive side requirements from CurseForge's "Client"/"Server" file tags.
+///
+/// Files that carry neither tag predate the flags and are left unknown.
+fn curseforge_side_env(game_versions: &[String]) -> SideEnv {
+    let client = game_versions.iter().any(|v| v == "Client");
+    let server = game_versions.iter().any(|v| v == "Server");
+    let requirement = |flagged: bool| match (client || server, flagged) {
+        (false, _) => SideRequirement::Unknown,
+        (true, true) => SideRequirement::Required,
+        (true, false) => SideRequirement::Unsupported,
+    };
+    SideEnv {
+        client: requirement(client),
+        server: requirement(server),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("sides.test.rs");
+}
@@ -0,0 +1,104 @@
+use super::*;
+use crate::empack::content::DependencySide;
+
+fn test_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(5))
+        .build()
+        .expect("http client")
+}
+
+fn test_api_bases(url: &str) -> UpdateApiBases {
+    UpdateApiBases {
+        modrinth: vec![url.to_string()],
+        curseforge: vec![url.to_string()],
+    }
+}
+
+#[tokio::test]
+async fn fetch_side_env_reads_modrinth_project_sides() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/v2/project/AANobbMI")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id":"AANobbMI","client_side":"required","server_side":"unsupported"}"#)
+        .create_async()
+        .await;
+
+    let env = fetch_side_env(
+        &test_client(),
+        &test_api_bases(&server.url()),
+        None,
+        ProjectPlatform::Modrinth,
+        "AANobbMI",
+        None,
+    )
+    .await
+    .expect("side env");
+
+    mock.assert_async().await;
+    assert_eq!(env.side(), DependencySide::Client);
+}
+
+#[tokio::test]
+async fn fetch_side_env_reads_curseforge_file_flags() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/v1/mods/238222/files/5101366")
+        .match_header("x-api-key", "cf-key")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"data":{"id":5101366,"gameVersions":["1.21.1","NeoForge","Server"]}}"#)
+        .create_async()
+        .await;
+
+    let env = fetch_side_env(
+        &test_client(),
+        &test_api_bases(&server.url()),
+        Some("cf-key"),
+        ProjectPlatform::CurseForge,
+        "238222",
+        Some("5101366"),
+    )
+    .await
+    .expect("side env");
+
+    mock.assert_async().await;
+    assert_eq!(env.side(), DependencySide::Server);
+}
+
+#[tokio::test]
+async fn fetch_side_env_reports_status_errors() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", "/v2/project/missing")
+        .with_status(404)
+        .create_async()
+        .await;
+
+    let err = fetch_side_env(
+        &test_client(),
+        &test_api_bases(&server.url()),
+        None,
+        ProjectPlatform::Modrinth,
+        "missing",
+        None,
+    )
+    .await
+    .unwrap_err();
+
+    assert!(matches!(
+        err,
+        UpdateCheckError::UnexpectedStatus { status: 404, .. }
+    ));
+}
+
+#[test]
+fn curseforge_side_env_treats_untagged_files_as_both() {
+    let untagged = curseforge_side_env(&["1.20.1".to_string(), "Forge".to_string()]);
+    assert_eq!(untagged.side(), DependencySide::Both);
+
+    let tagged = curseforge_side_env(&["Client".to_string(), "Server".to_string()]);
+    assert_eq!(tagged.side(), DependencySide::Both);
+}
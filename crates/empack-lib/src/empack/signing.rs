@@ -0,0 +1,206 @@
+//! Detached minisign signatures for build artifacts
+//!
+//! With a signing key configured, every artifact gets `<artifact>.minisig`
+//! next to it. The signatures are ed25519 in minisign's prehashed format, so
+//! `minisign -Vm <artifact> -p <key>.pub` accepts them as well as
+//! `empack verify`. The trusted comment records the artifact's file name,
+//! which stops a signature from vouching for a renamed file.
+
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File name suffix of every signature.
+pub const SIGNATURE_EXTENSION: &str = "minisig";
+
+#[derive(Debug, Error)]
+pub enum SigningError {
+    #[error("Invalid signing key: {reason}")]
+    SecretKey { reason: String },
+
+    #[error("Signing key is encrypted; set EMPACK_SIGNING_KEY_PASSWORD")]
+    EncryptedKey,
+
+    #[error("Invalid public key: {reason}")]
+    PublicKey { reason: String },
+
+    #[error("Failed to sign {file}: {reason}")]
+    Sign { file: String, reason: String },
+
+    #[error("Invalid signature for {file}: {reason}")]
+    Signature { file: String, reason: String },
+
+    #[error("Signature does not match {file}: {reason}")]
+    SignatureMismatch { file: String, reason: String },
+
+    #[error("{file} is not listed in SHA256SUMS")]
+    ChecksumMissing { file: String },
+
+    #[error("SHA256SUMS entry does not match {file}: expected {expected}, got {actual}")]
+    ChecksumMismatch {
+        file: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+/// A decrypted secret key and the public key it belongs to.
+pub struct SigningKey {
+    secret: minisign::SecretKey,
+    public: minisign::PublicKey,
+}
+
+impl std::fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SigningKey")
+            .field("public", &self.public.to_base64())
+            .finish_non_exhaustive()
+    }
+}
+
+impl SigningKey {
+    /// Parse a minisign secret key file.
+    ///
+    /// Unencrypted keys need no password. Encrypted keys are decrypted with
+    /// `password`; without one they are rejected rather than prompted for.
+    pub fn parse(text: &str, password: Option<String>) -> Result<Self, SigningError> {
+        let secret_key_box = || minisign::SecretKeyBox::from_string(text);
+        let secret = match secret_key_box().and_then(|b| b.into_unencrypted_secret_key()) {
+            Ok(secret) => secret,
+            Err(unencrypted) => {
+                let Some(password) = password else {
+                    return Err(if unencrypted.to_string().contains("might be encrypted") {
+                        SigningError::EncryptedKey
+                    } else {
+                        SigningError::SecretKey {
+                            reason: unencrypted.to_string(),
+                        }
+                    });
+                };
+                secret_key_box()
+                    .and_then(|b| b.into_secret_key(Some(password)))
+                    .map_err(|e| SigningError::SecretKey {
+                        reason: e.to_string(),
+                    })?
+            }
+        };
+        let public =
+            minisign::PublicKey::from_secret_key(&secret).map_err(|e| SigningError::SecretKey {
+                reason: e.to_string(),
+            })?;
+        Ok(Self { secret, public })
+    }
+
+    /// Sign `data` as `file_name` and return the `.minisig` file content.
+    ///
+    /// `timestamp` goes into the trusted comment next to the file name.
+    pub fn sign(
+        &self,
+        data: &[u8],
+        file_name: &str,
+        timestamp: u64,
+    ) -> Result<String, SigningError> {
+        let trusted_comment = format!("timestamp:{timestamp}\tfile:{file_name}");
+        let untrusted_comment = format!("signature from empack for {file_name}");
+        minisign::sign(
+            Some(&self.public),
+            &self.secret,
+            Cursor::new(data),
+            Some(&trusted_comment),
+            Some(&untrusted_comment),
+        )
+        .map(|signature| signature.into_string())
+        .map_err(|e| SigningError::Sign {
+            file: file_name.to_string(),
+            reason: e.to_string(),
+        })
+    }
+}
+
+/// A public key that signatures are checked against.
+#[derive(Debug, Clone)]
+pub struct VerifyingKey(minisign::PublicKey);
+
+impl VerifyingKey {
+    /// Parse a minisign public key file, or the bare base64 key on its
+    /// second line.
+    pub fn parse(text: &str) -> Result<Self, SigningError> {
+        let text = text.trim();
+        let public = if text.lines().count() > 1 {
+            minisign::PublicKeyBox::from_string(text).and_then(|b| b.into_public_key())
+        } else {
+            minisign::PublicKey::from_base64(text)
+        };
+        public.map(Self).map_err(|e| SigningError::PublicKey {
+            reason: e.to_string(),
+        })
+    }
+
+    /// Check `signature` over `data`, and that it was made for `file_name`.
+    pub fn verify(
+        &self,
+        data: &[u8],
+        signature: &str,
+        file_name: &str,
+    ) -> Result<(), SigningError> {
+        let signature = minisign::SignatureBox::from_string(signature).map_err(|e| {
+            SigningError::Signature {
+                file: file_name.to_string(),
+                reason: e.to_string(),
+            }
+        })?;
+        minisign::verify(&self.0, &signature, Cursor::new(data), true, false, false).map_err(
+            |e| SigningError::SignatureMismatch {
+                file: file_name.to_string(),
+                reason: e.to_string(),
+            },
+        )?;
+
+        let trusted_comment = signature.trusted_comment().unwrap_or_default();
+        let signed_name = trusted_comment
+            .split('\t')
+            .find_map(|field| field.strip_prefix("file:"));
+        match signed_name {
+            Some(signed_name) if signed_name != file_name => Err(SigningError::SignatureMismatch {
+                file: file_name.to_string(),
+                reason: format!("signature was made for {signed_name}"),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Path of the detached signature for `artifact`.
+pub fn signature_path(artifact: &Path) -> PathBuf {
+    let mut path = artifact.as_os_str().to_owned();
+    path.push(format!(".{SIGNATURE_EXTENSION}"));
+    PathBuf::from(path)
+}
+
+/// Check `data` against the entry for `file_name` in a `SHA256SUMS` file.
+pub fn verify_checksum(checksums: &str, data: &[u8], file_name: &str) -> Result<(), SigningError> {
+    use sha2::{Digest, Sha256};
+
+    let expected = checksums
+        .lines()
+        .filter_map(|line| line.split_once("  ").or_else(|| line.split_once(" *")))
+        .find(|(_, name)| *name == file_name)
+        .map(|(digest, _)| digest.to_ascii_lowercase())
+        .ok_or_else(|| SigningError::ChecksumMissing {
+            file: file_name.to_string(),
+        })?;
+    let actual = crate::empack::content::hex::encode(Sha256::digest(data));
+    if expected != actual {
+        return Err(SigningError::ChecksumMismatch {
+            file: file_name.to_string(),
+            expected,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    include!("signing.test.rs");
+}
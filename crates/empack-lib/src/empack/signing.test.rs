@@ -0,0 +1,91 @@
+use super::*;
+
+fn keypair(password: Option<&str>) -> (String, String) {
+    let keypair = match password {
+        Some(password) => minisign::KeyPair::generate_encrypted_keypair(Some(password.to_string())),
+        None => minisign::KeyPair::generate_unencrypted_keypair(),
+    }
+    .unwrap();
+    (
+        keypair.sk.to_box(None).unwrap().into_string(),
+        keypair.pk.to_box().unwrap().into_string(),
+    )
+}
+
+#[test]
+fn signatures_verify_with_the_matching_public_key() {
+    let (secret, public) = keypair(None);
+    let signing_key = SigningKey::parse(&secret, None).unwrap();
+    let signature = signing_key.sign(b"pack bytes", "Pack-v1.0.0-client.zip", 1_792_238_645).unwrap();
+
+    let verifying_key = VerifyingKey::parse(&public).unwrap();
+    verifying_key
+        .verify(b"pack bytes", &signature, "Pack-v1.0.0-client.zip")
+        .unwrap();
+    let bare_key = public.lines().nth(1).unwrap();
+    VerifyingKey::parse(bare_key)
+        .unwrap()
+        .verify(b"pack bytes", &signature, "Pack-v1.0.0-client.zip")
+        .unwrap();
+
+    assert!(matches!(
+        verifying_key.verify(b"tampered", &signature, "Pack-v1.0.0-client.zip"),
+        Err(SigningError::SignatureMismatch { .. })
+    ));
+    assert!(matches!(
+        verifying_key.verify(b"pack bytes", &signature, "Pack-v1.0.0-server.zip"),
+        Err(SigningError::SignatureMismatch { ref reason, .. }) if reason.contains("Pack-v1.0.0-client.zip")
+    ));
+
+    let (_, other_public) = keypair(None);
+    assert!(matches!(
+        VerifyingKey::parse(&other_public)
+            .unwrap()
+            .verify(b"pack bytes", &signature, "Pack-v1.0.0-client.zip"),
+        Err(SigningError::SignatureMismatch { .. })
+    ));
+}
+
+#[test]
+fn encrypted_keys_need_a_password() {
+    let (secret, public) = keypair(Some("hunter2"));
+
+    assert!(matches!(SigningKey::parse(&secret, None), Err(SigningError::EncryptedKey)));
+    assert!(matches!(
+        SigningKey::parse(&secret, Some("wrong".to_string())),
+        Err(SigningError::SecretKey { .. })
+    ));
+
+    let signing_key = SigningKey::parse(&secret, Some("hunter2".to_string())).unwrap();
+    let signature = signing_key.sign(b"pack", "Pack.mrpack", 0).unwrap();
+    VerifyingKey::parse(&public)
+        .unwrap()
+        .verify(b"pack", &signature, "Pack.mrpack")
+        .unwrap();
+}
+
+#[test]
+fn verify_checksum_compares_the_sha256sums_entry() {
+    let checksums = "\
+2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824  Pack-v1.0.0-client.zip
+0000000000000000000000000000000000000000000000000000000000000000  Pack-v1.0.0-server.zip
+";
+
+    verify_checksum(checksums, b"hello", "Pack-v1.0.0-client.zip").unwrap();
+    assert!(matches!(
+        verify_checksum(checksums, b"hello", "Pack-v1.0.0-server.zip"),
+        Err(SigningError::ChecksumMismatch { .. })
+    ));
+    assert!(matches!(
+        verify_checksum(checksums, b"hello", "Pack.mrpack"),
+        Err(SigningError::ChecksumMissing { .. })
+    ));
+}
+
+#[test]
+fn signature_path_appends_the_extension() {
+    assert_eq!(
+        signature_path(Path::new("dist/Pack-v1.0.0-server.tar.gz")),
+        PathBuf::from("dist/Pack-v1.0.0-server.tar.gz.minisig")
+    );
+}
@@ -1,3 +1,4 @@
+use crate::application::session::{FileLock, FileLockAttempt};
 use crate::empack::builds::BuildError;
 use crate::empack::config::ConfigError;
 use crate::empack::packwiz::PackwizOps;
@@ -10,6 +11,10 @@ use thiserror::Error;
 /// If this file exists on next discovery, we know the previous operation was interrupted.
 pub(crate) const STATE_MARKER_FILE: &str = ".empack-state";
 
+/// Advisory lock file held for the whole run of a mutating command.
+/// Markers record what was happening; this lock keeps a second process out.
+pub(crate) const PROJECT_LOCK_FILE: &str = ".empack/lock";
+
 /// Result of a state transition, carrying the new state and any warnings
 /// that callers should surface through DisplayProvider.
 #[derive(Debug, Clone)]
@@ -622,6 +627,12 @@ pub enum StateError {
 
     #[error("Command execution failed: {command}")]
     CommandFailed { command: String },
+
+    #[error(
+        "Project locked by {}",
+        .pid.map_or_else(|| "another empack process".to_string(), |pid| format!("PID {pid}"))
+    )]
+    Locked { pid: Option<u32> },
 }
 
 impl<'a, P: crate::application::session::FileSystemProvider + ?Sized> PackStateManager<'a, P> {
@@ -738,6 +749,25 @@ impl<'a, P: crate::application::session::FileSystemProvider + ?Sized> PackStateM
         StateMarkerGuard::new(self.provider, self.workdir.clone(), state_label)
     }
 
+    /// Take the cross-process project lock without waiting.
+    ///
+    /// Mutating commands hold the returned lock until they finish, so a second
+    /// empack process fails with [`StateError::Locked`] instead of racing over
+    /// `pack/` and `dist/`. The lock is released when dropped or when the
+    /// process exits.
+    pub fn lock_project(&self) -> Result<FileLock, StateError> {
+        let lock_path = self.workdir.join(PROJECT_LOCK_FILE);
+        match self
+            .provider
+            .try_lock_file(&lock_path, &std::process::id().to_string())?
+        {
+            FileLockAttempt::Acquired(lock) => Ok(lock),
+            FileLockAttempt::Held { owner } => Err(StateError::Locked {
+                pid: owner.trim().parse().ok(),
+            }),
+        }
+    }
+
     /// Get paths for common modpack files
     pub fn paths(&self) -> PackPaths {
         PackPaths {
@@ -105,6 +105,11 @@ impl crate::application::session::FileSystemProvider for MockStateProvider {
         Ok(())
     }
 
+    fn append_bytes(&self, path: &Path, _content: &[u8]) -> anyhow::Result<()> {
+        self.files.borrow_mut().insert(path.to_path_buf());
+        Ok(())
+    }
+
     fn exists(&self, path: &Path) -> bool {
         self.files.borrow().contains(path)
     }
@@ -212,6 +217,16 @@ impl crate::application::session::FileSystemProvider for MockStateProvider {
         Ok(())
     }
 
+    fn try_lock_file(
+        &self,
+        _path: &Path,
+        _owner: &str,
+    ) -> anyhow::Result<crate::application::session::FileLockAttempt> {
+        Ok(crate::application::session::FileLockAttempt::Acquired(
+            crate::application::session::FileLock::new(()),
+        ))
+    }
+
 }
 
 /// Create a MockPackwizOps for state tests that stores pack files via its own in-memory map
@@ -0,0 +1,500 @@
+//! Content-addressed download store shared by every project
+//!
+//! Server jars, loader installers, direct downloads, and jars embedded in
+//! imported modpacks are kept once under the empack cache root, named by the
+//! SHA-512 of their bytes:
+//!
+//! - `objects/<sha512>` holds the bytes
+//! - `sha1/<sha1>` holds the SHA-512 name of the same object, so lookups by
+//!   either hash succeed
+//! - `urls/<sha256 of url>` holds the SHA-512 name of the last download
+//!   from a URL that came without a known hash
+//! - `refs/<project>.json` lists the objects a project has used
+//!
+//! Bytes are hashed before they are admitted, and rejected when the caller
+//! knew a different hash. Every file is written to `tmp/` and renamed into
+//! place, so a reader never sees a partial object. Updates to `refs/` and
+//! `empack cache gc` hold `store.lock`. Reads re-hash the object and drop it
+//! if it no longer matches its name. `empack cache gc` evicts objects that no
+//! project which still exists references, either through `refs/` or its
+//! `empack.lock`.
+
+use crate::application::session::{FileLock, FileLockAttempt, FileSystemProvider};
+use crate::empack::content::hex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use thiserror::Error;
+
+const OBJECTS_DIR: &str = "objects";
+const SHA1_DIR: &str = "sha1";
+const URLS_DIR: &str = "urls";
+const REFS_DIR: &str = "refs";
+const TMP_DIR: &str = "tmp";
+const LOCK_FILE: &str = "store.lock";
+
+/// How long to wait for another process to release `store.lock`.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(25);
+
+/// Temp files older than this were left by a process that died mid-write.
+const STALE_TMP_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Error)]
+pub enum StoreError {
+    #[error("{algorithm} mismatch for {source_name}: expected {expected}, got {actual}")]
+    HashMismatch {
+        source_name: String,
+        algorithm: &'static str,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("Content store I/O failed for {path}: {reason}")]
+    Io { path: PathBuf, reason: String },
+
+    #[error("Content store unavailable: {reason}")]
+    Unavailable { reason: String },
+
+    #[error("Content store is locked by process {owner}")]
+    Locked { owner: String },
+}
+
+/// A hash a download is expected to have.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContentHash {
+    Sha1(String),
+    Sha512(String),
+}
+
+impl ContentHash {
+    /// Parse a packwiz or Modrinth style `(format, hex)` pair.
+    ///
+    /// Returns `None` for formats the store is not keyed by.
+    pub fn parse(format: &str, value: &str) -> Option<Self> {
+        let value = value.trim().to_ascii_lowercase();
+        match format.to_ascii_lowercase().as_str() {
+            "sha1" => Some(Self::Sha1(value)),
+            "sha512" => Some(Self::Sha512(value)),
+            _ => None,
+        }
+    }
+
+    /// The strongest store hash in a `format -> hex` map.
+    pub fn best_of<'a>(hashes: impl IntoIterator<Item = (&'a String, &'a String)>) -> Option<Self> {
+        hashes
+            .into_iter()
+            .filter_map(|(format, value)| Self::parse(format, value))
+            .max_by_key(|hash| matches!(hash, Self::Sha512(_)))
+    }
+
+    fn algorithm(&self) -> &'static str {
+        match self {
+            Self::Sha1(_) => "sha1",
+            Self::Sha512(_) => "sha512",
+        }
+    }
+
+    fn value(&self) -> &str {
+        match self {
+            Self::Sha1(value) | Self::Sha512(value) => value,
+        }
+    }
+
+    fn of(&self, bytes: &[u8]) -> String {
+        match self {
+            Self::Sha1(_) => hex::encode(sha1::Sha1::digest(bytes)),
+            Self::Sha512(_) => hex::encode(Sha512::digest(bytes)),
+        }
+    }
+}
+
+/// Objects one project has used.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProjectRefs {
+    root: PathBuf,
+    objects: BTreeSet<String>,
+}
+
+/// What `gc` removed or, in a dry run, would remove.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct GcReport {
+    /// Projects that still exist and keep objects alive
+    pub projects: usize,
+    /// Projects whose directory or `empack.yml` is gone
+    pub forgotten_projects: usize,
+    pub removed_objects: usize,
+    pub removed_bytes: u64,
+    pub kept_objects: usize,
+}
+
+/// The store under `root`, accessed through the session filesystem.
+pub struct ContentStore<'a> {
+    filesystem: &'a dyn FileSystemProvider,
+    root: PathBuf,
+}
+
+impl<'a> ContentStore<'a> {
+    pub fn new(filesystem: &'a dyn FileSystemProvider, root: PathBuf) -> Self {
+        Self { filesystem, root }
+    }
+
+    /// The shared store under the empack cache root.
+    pub fn open(filesystem: &'a dyn FileSystemProvider) -> Result<Self, StoreError> {
+        let root = crate::platform::cache::store_dir().map_err(|e| StoreError::Unavailable {
+            reason: e.to_string(),
+        })?;
+        Ok(Self::new(filesystem, root))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn object_path(&self, sha512: &str) -> PathBuf {
+        self.root.join(OBJECTS_DIR).join(sha512)
+    }
+
+    fn sha1_path(&self, sha1: &str) -> PathBuf {
+        self.root.join(SHA1_DIR).join(sha1)
+    }
+
+    fn url_path(&self, url: &str) -> PathBuf {
+        self.root
+            .join(URLS_DIR)
+            .join(hex::encode(sha2::Sha256::digest(url.as_bytes())))
+    }
+
+    fn refs_path(&self, project_root: &Path) -> PathBuf {
+        let digest = hex::encode(sha2::Sha256::digest(
+            project_root.to_string_lossy().as_bytes(),
+        ));
+        self.root
+            .join(REFS_DIR)
+            .join(format!("{}.json", &digest[..16]))
+    }
+
+    fn io_error(path: &Path, error: impl std::fmt::Display) -> StoreError {
+        StoreError::Io {
+            path: path.to_path_buf(),
+            reason: error.to_string(),
+        }
+    }
+
+    /// SHA-512 name of the object with `hash`, if the store has one.
+    pub fn resolve(&self, hash: &ContentHash) -> Option<String> {
+        let sha512 = match hash {
+            ContentHash::Sha512(value) => value.clone(),
+            ContentHash::Sha1(value) => self.read_text(&self.sha1_path(value))?.trim().to_string(),
+        };
+        self.filesystem
+            .exists(&self.object_path(&sha512))
+            .then_some(sha512)
+    }
+
+    /// Bytes of the object with `hash`, or `None` on a miss.
+    ///
+    /// Objects only appear once fully written, so one whose bytes no longer
+    /// match its name is corrupt; it is removed and reported as a miss.
+    pub fn get(&self, hash: &ContentHash) -> Option<Vec<u8>> {
+        let sha512 = self.resolve(hash)?;
+        let path = self.object_path(&sha512);
+        let bytes = self.filesystem.read_bytes(&path).ok()?;
+        if hex::encode(Sha512::digest(&bytes)) != sha512 {
+            tracing::warn!(object = %path.display(), "removing corrupt content store object");
+            let _ = self.filesystem.remove_file(&path);
+            return None;
+        }
+        Some(bytes)
+    }
+
+    /// Hash `bytes`, check them against `expected`, and store them.
+    ///
+    /// `source_name` names the download in a mismatch error. Returns the
+    /// object's SHA-512 name.
+    pub fn admit(
+        &self,
+        bytes: &[u8],
+        expected: Option<&ContentHash>,
+        source_name: &str,
+    ) -> Result<String, StoreError> {
+        if let Some(expected) = expected {
+            let actual = expected.of(bytes);
+            if actual != expected.value() {
+                return Err(StoreError::HashMismatch {
+                    source_name: source_name.to_string(),
+                    algorithm: expected.algorithm(),
+                    expected: expected.value().to_string(),
+                    actual,
+                });
+            }
+        }
+
+        let sha512 = hex::encode(Sha512::digest(bytes));
+        let sha1 = hex::encode(sha1::Sha1::digest(bytes));
+        let object = self.object_path(&sha512);
+        if !self.filesystem.exists(&object) {
+            self.write(&object, bytes)?;
+        }
+        self.write(&self.sha1_path(&sha1), sha512.as_bytes())?;
+        Ok(sha512)
+    }
+
+    /// SHA-512 name and bytes of the last download from `url` that was
+    /// admitted with [`admit_url`](Self::admit_url).
+    pub fn get_url(&self, url: &str) -> Option<(String, Vec<u8>)> {
+        let sha512 = self.read_text(&self.url_path(url))?.trim().to_string();
+        let bytes = self.get(&ContentHash::Sha512(sha512.clone()))?;
+        Some((sha512, bytes))
+    }
+
+    /// Admit a download that came without a known hash, remembering `url` so
+    /// [`get_url`](Self::get_url) can serve it again.
+    pub fn admit_url(&self, url: &str, bytes: &[u8]) -> Result<String, StoreError> {
+        let sha512 = self.admit(bytes, None, url)?;
+        self.write(&self.url_path(url), sha512.as_bytes())?;
+        Ok(sha512)
+    }
+
+    /// Contents of a text file written by [`write`](Self::write).
+    fn read_text(&self, path: &Path) -> Option<String> {
+        String::from_utf8(self.filesystem.read_bytes(path).ok()?).ok()
+    }
+
+    /// Write `bytes` to a temp file and rename it onto `path`.
+    fn write(&self, path: &Path, bytes: &[u8]) -> Result<(), StoreError> {
+        self.create_parent(path)?;
+        let tmp = self.root.join(TMP_DIR).join(format!(
+            "{}-{}",
+            std::process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        self.create_parent(&tmp)?;
+        let written = self
+            .filesystem
+            .write_bytes(&tmp, bytes)
+            .and_then(|()| self.filesystem.rename(&tmp, path));
+        if let Err(e) = written {
+            let _ = self.filesystem.remove_file(&tmp);
+            return Err(Self::io_error(path, e));
+        }
+        Ok(())
+    }
+
+    /// Take `store.lock`, waiting up to [`LOCK_TIMEOUT`] for another process
+    /// to release it.
+    ///
+    /// The store is used from async commands, so on a multi-thread runtime the
+    /// wait runs under `block_in_place` and the worker's other tasks move on.
+    fn lock(&self) -> Result<FileLock, StoreError> {
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread => {
+                tokio::task::block_in_place(|| self.wait_for_lock())
+            }
+            _ => self.wait_for_lock(),
+        }
+    }
+
+    fn wait_for_lock(&self) -> Result<FileLock, StoreError> {
+        let path = self.root.join(LOCK_FILE);
+        let deadline = std::time::Instant::now() + LOCK_TIMEOUT;
+        loop {
+            match self
+                .filesystem
+                .try_lock_file(&path, &std::process::id().to_string())
+                .map_err(|e| Self::io_error(&path, e))?
+            {
+                FileLockAttempt::Acquired(lock) => return Ok(lock),
+                FileLockAttempt::Held { owner } if std::time::Instant::now() >= deadline => {
+                    return Err(StoreError::Locked {
+                        owner: owner.trim().to_string(),
+                    });
+                }
+                FileLockAttempt::Held { .. } => std::thread::sleep(LOCK_POLL_INTERVAL),
+            }
+        }
+    }
+
+    fn create_parent(&self, path: &Path) -> Result<(), StoreError> {
+        match path.parent() {
+            Some(parent) => self
+                .filesystem
+                .create_dir_all(parent)
+                .map_err(|e| Self::io_error(parent, e)),
+            None => Ok(()),
+        }
+    }
+
+    /// Record that the project at `project_root` uses `object`.
+    pub fn record_reference(&self, project_root: &Path, object: &str) -> Result<(), StoreError> {
+        let _lock = self.lock()?;
+        self.record_reference_locked(project_root, object)
+    }
+
+    fn record_reference_locked(&self, project_root: &Path, object: &str) -> Result<(), StoreError> {
+        let path = self.refs_path(project_root);
+        let mut refs = self
+            .read_text(&path)
+            .and_then(|text| serde_json::from_str::<ProjectRefs>(&text).ok())
+            .unwrap_or_default();
+        refs.root = project_root.to_path_buf();
+        if !refs.objects.insert(object.to_string()) && self.filesystem.exists(&path) {
+            return Ok(());
+        }
+        let json = serde_json::to_string_pretty(&refs).map_err(|e| Self::io_error(&path, e))?;
+        self.write(&path, json.as_bytes())
+    }
+
+    /// Admit `bytes` and record that the project at `project_root` uses them.
+    ///
+    /// Holds `store.lock` throughout, so `gc` cannot evict the object before
+    /// the reference exists.
+    pub fn admit_for_project(
+        &self,
+        project_root: &Path,
+        bytes: &[u8],
+        expected: Option<&ContentHash>,
+        source_name: &str,
+    ) -> Result<String, StoreError> {
+        let _lock = self.lock()?;
+        let object = self.admit(bytes, expected, source_name)?;
+        self.record_reference_locked(project_root, &object)?;
+        Ok(object)
+    }
+
+    /// [`admit_url`](Self::admit_url) and record that the project at
+    /// `project_root` uses the object.
+    pub fn admit_url_for_project(
+        &self,
+        project_root: &Path,
+        url: &str,
+        bytes: &[u8],
+    ) -> Result<String, StoreError> {
+        let _lock = self.lock()?;
+        let object = self.admit_url(url, bytes)?;
+        self.record_reference_locked(project_root, &object)?;
+        Ok(object)
+    }
+
+    /// Evict every object no surviving project references.
+    ///
+    /// A project survives while `empack.yml` exists in its root; the refs of
+    /// the others are dropped. With `dry_run`, nothing is removed.
+    pub fn gc(&self, dry_run: bool) -> Result<GcReport, StoreError> {
+        let _lock = self.lock()?;
+        let mut report = GcReport::default();
+        let mut live = BTreeSet::new();
+
+        for refs_path in self.list(REFS_DIR) {
+            let Some(refs) = self
+                .read_text(&refs_path)
+                .and_then(|text| serde_json::from_str::<ProjectRefs>(&text).ok())
+            else {
+                continue;
+            };
+            if !self.filesystem.exists(&refs.root.join("empack.yml")) {
+                report.forgotten_projects += 1;
+                if !dry_run {
+                    self.remove(&refs_path)?;
+                }
+                continue;
+            }
+
+            report.projects += 1;
+            live.extend(refs.objects);
+            if let Ok(Some(lock)) = crate::empack::lockfile::load_lock(self.filesystem, &refs.root)
+            {
+                for locked in lock.dependencies.values() {
+                    let hashes = [
+                        locked.sha512.clone().map(ContentHash::Sha512),
+                        locked.sha1.clone().map(ContentHash::Sha1),
+                    ];
+                    live.extend(hashes.iter().flatten().filter_map(|h| self.resolve(h)));
+                }
+            }
+        }
+
+        let mut kept = BTreeSet::new();
+        for object in self.list(OBJECTS_DIR) {
+            let Some(name) = object.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if live.contains(name) {
+                kept.insert(name.to_string());
+                continue;
+            }
+            report.removed_objects += 1;
+            report.removed_bytes += self
+                .filesystem
+                .file_metadata(&object)
+                .map(|metadata| metadata.len)
+                .unwrap_or(0);
+            if !dry_run {
+                self.remove(&object)?;
+            }
+        }
+        report.kept_objects = kept.len();
+
+        if !dry_run {
+            for index in self.list(SHA1_DIR).into_iter().chain(self.list(URLS_DIR)) {
+                let target = self.read_text(&index).unwrap_or_default();
+                if !kept.contains(target.trim()) {
+                    self.remove(&index)?;
+                }
+            }
+            self.remove_stale_tmp()?;
+        }
+
+        Ok(report)
+    }
+
+    /// Remove temp files left behind by writers that died.
+    ///
+    /// Live writes do not hold `store.lock`, so only old files go.
+    fn remove_stale_tmp(&self) -> Result<(), StoreError> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        for tmp in self.list(TMP_DIR) {
+            let modified = self
+                .filesystem
+                .file_metadata(&tmp)
+                .ok()
+                .and_then(|metadata| metadata.modified_unix_ms);
+            if modified.is_some_and(|ms| now.saturating_sub(ms) > STALE_TMP_AGE.as_millis() as u64)
+            {
+                self.remove(&tmp)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn list(&self, dir: &str) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = self
+            .filesystem
+            .get_file_list(&self.root.join(dir))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|path| !self.filesystem.is_directory(path))
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    fn remove(&self, path: &Path) -> Result<(), StoreError> {
+        self.filesystem
+            .remove_file(path)
+            .map_err(|e| Self::io_error(path, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("store.test.rs");
+}
@@ -0,0 +1,206 @@
+use super::*;
+use crate::application::session_mocks::{MockFileSystemProvider, mock_root};
+use crate::empack::lockfile::{EmpackLock, LockedDependency};
+use crate::primitives::ProjectType;
+
+fn store(fs: &MockFileSystemProvider) -> ContentStore<'_> {
+    ContentStore::new(fs, mock_root().join("cache/store"))
+}
+
+fn sha1_of(bytes: &[u8]) -> ContentHash {
+    ContentHash::Sha1(hex::encode(sha1::Sha1::digest(bytes)))
+}
+
+#[test]
+fn admitted_objects_are_found_by_either_hash() {
+    let fs = MockFileSystemProvider::new();
+    let store = store(&fs);
+
+    let sha512 = store.admit(b"server jar", None, "srv.jar").unwrap();
+
+    assert_eq!(store.get(&sha1_of(b"server jar")).unwrap(), b"server jar");
+    assert_eq!(store.get(&ContentHash::Sha512(sha512)).unwrap(), b"server jar");
+    assert!(store.get(&sha1_of(b"other jar")).is_none());
+}
+
+#[test]
+fn admit_rejects_bytes_that_do_not_match_the_expected_hash() {
+    let fs = MockFileSystemProvider::new();
+    let store = store(&fs);
+
+    let error = store
+        .admit(b"tampered", Some(&sha1_of(b"server jar")), "https://example.com/srv.jar")
+        .unwrap_err();
+
+    assert!(matches!(error, StoreError::HashMismatch { algorithm: "sha1", .. }));
+    assert!(error.to_string().contains("https://example.com/srv.jar"));
+    assert!(store.get(&sha1_of(b"tampered")).is_none());
+}
+
+#[test]
+fn corrupt_objects_are_dropped_on_read() {
+    let fs = MockFileSystemProvider::new();
+    let store = store(&fs);
+    let sha512 = store.admit(b"installer", None, "installer.jar").unwrap();
+
+    fs.write_bytes(&store.object_path(&sha512), b"bit rot").unwrap();
+
+    assert!(store.get(&ContentHash::Sha512(sha512.clone())).is_none());
+    assert!(!fs.exists(&store.object_path(&sha512)));
+}
+
+#[test]
+fn writes_leave_no_temp_files_behind() {
+    let fs = MockFileSystemProvider::new();
+    let store = store(&fs);
+
+    store
+        .admit_for_project(&mock_root().join("pack"), b"server jar", None, "srv.jar")
+        .unwrap();
+
+    assert!(store.list(TMP_DIR).is_empty());
+    assert_eq!(store.list(OBJECTS_DIR).len(), 1);
+}
+
+#[test]
+fn references_and_gc_wait_for_the_store_lock() {
+    let fs = MockFileSystemProvider::new();
+    let store = store(&fs);
+    let object = store.admit(b"server jar", None, "srv.jar").unwrap();
+    let project = mock_root().join("pack");
+    let refs = store.refs_path(&project);
+
+    let held = store.lock().unwrap();
+    let blocked = std::thread::scope(|scope| {
+        let fs = &fs;
+        let waiter = scope.spawn(|| {
+            ContentStore::new(fs, mock_root().join("cache/store")).record_reference(&project, &object)
+        });
+        std::thread::sleep(LOCK_POLL_INTERVAL * 4);
+        let blocked = !fs.exists(&refs);
+        drop(held);
+        waiter.join().unwrap().unwrap();
+        blocked
+    });
+
+    assert!(blocked);
+    assert!(fs.exists(&refs));
+}
+
+#[test]
+fn downloads_without_a_hash_are_found_by_url() {
+    let fs = MockFileSystemProvider::new();
+    let store = store(&fs);
+
+    let sha512 = store
+        .admit_url("https://example.com/installer.jar", b"installer")
+        .unwrap();
+
+    assert_eq!(
+        store.get_url("https://example.com/installer.jar"),
+        Some((sha512, b"installer".to_vec()))
+    );
+    assert!(store.get_url("https://example.com/other.jar").is_none());
+
+    store.gc(false).unwrap();
+    assert!(store.get_url("https://example.com/installer.jar").is_none());
+    assert!(store.list(URLS_DIR).is_empty());
+}
+
+#[test]
+fn best_of_prefers_sha512() {
+    let hashes = std::collections::HashMap::from([
+        ("sha1".to_string(), "AA".to_string()),
+        ("sha512".to_string(), "BB".to_string()),
+        ("murmur2".to_string(), "1".to_string()),
+    ]);
+
+    assert_eq!(ContentHash::best_of(&hashes), Some(ContentHash::Sha512("bb".to_string())));
+    assert_eq!(ContentHash::parse("md5", "cc"), None);
+}
+
+#[test]
+fn gc_keeps_objects_referenced_by_surviving_projects() {
+    let kept_root = mock_root().join("kept-pack");
+    let gone_root = mock_root().join("deleted-pack");
+    let fs = MockFileSystemProvider::new()
+        .with_file(kept_root.join("empack.yml"), "empack: {}\n".to_string());
+    let store = store(&fs);
+
+    let referenced = store.admit(b"referenced", None, "a").unwrap();
+    store.record_reference(&kept_root, &referenced).unwrap();
+    let locked = store.admit(b"locked mod", None, "b").unwrap();
+    let orphaned = store.admit(b"orphaned", None, "c").unwrap();
+    store.record_reference(&gone_root, &orphaned).unwrap();
+    store.admit(b"never used", None, "d").unwrap();
+
+    let lock = EmpackLock {
+        dependencies: std::collections::BTreeMap::from([(
+            "sodium".to_string(),
+            LockedDependency {
+                platform: None,
+                project_id: None,
+                version_id: None,
+                path: None,
+                project_type: ProjectType::Mod,
+                filename: "sodium.jar".to_string(),
+                url: None,
+                sha1: Some(hex::encode(sha1::Sha1::digest(b"locked mod"))),
+                sha512: None,
+            },
+        )]),
+        ..EmpackLock::default()
+    };
+    crate::empack::lockfile::write_lock(&fs, &kept_root, &lock).unwrap();
+
+    let preview = store.gc(true).unwrap();
+    assert_eq!(preview.removed_objects, 2);
+    assert!(fs.exists(&store.object_path(&orphaned)));
+
+    let report = store.gc(false).unwrap();
+    assert_eq!(
+        report,
+        GcReport {
+            projects: 1,
+            forgotten_projects: 1,
+            removed_objects: 2,
+            removed_bytes: (b"orphaned".len() + b"never used".len()) as u64,
+            kept_objects: 2,
+        }
+    );
+    assert!(store.get(&ContentHash::Sha512(referenced)).is_some());
+    assert!(store.get(&ContentHash::Sha512(locked)).is_some());
+    assert!(!fs.exists(&store.object_path(&orphaned)));
+    assert!(!fs.exists(&store.sha1_path(&hex::encode(sha1::Sha1::digest(b"orphaned")))));
+    assert!(!fs.exists(&store.refs_path(&gone_root)));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn waiting_for_the_lock_does_not_stall_the_runtime_worker() {
+    let fs = std::sync::Arc::new(MockFileSystemProvider::new());
+    let root = mock_root().join("cache/store");
+    let held = ContentStore::new(&*fs, root.clone()).lock().unwrap();
+
+    // Both tasks share the one worker; the waiter must hand it over so the
+    // release can run.
+    let waiter = tokio::spawn({
+        let fs = fs.clone();
+        let root = root.clone();
+        async move {
+            ContentStore::new(&*fs, root).admit_for_project(
+                &mock_root().join("pack"),
+                b"server jar",
+                None,
+                "srv.jar",
+            )
+        }
+    });
+    let release = tokio::spawn(async move {
+        tokio::time::sleep(LOCK_POLL_INTERVAL * 4).await;
+        drop(held);
+    });
+
+    release.await.unwrap();
+    waiter.await.unwrap().unwrap();
+    assert!(fs.exists(&ContentStore::new(&*fs, root).refs_path(&mock_root().join("pack"))));
+}
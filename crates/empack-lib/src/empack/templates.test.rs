@@ -60,6 +60,8 @@ fn test_template_installer_full_install() {
     // Verify content substitution
     let gitignore_content = std::fs::read_to_string(temp_dir.path().join(".gitignore")).unwrap();
     assert!(gitignore_content.contains("dist/"));
+    assert!(gitignore_content.contains(".empack/"));
+    assert!(gitignore_content.contains(".empack-rollback.json"));
 
     let instance_content = std::fs::read_to_string(temp_dir.path().join("templates").join("client").join("instance.cfg.template")).unwrap();
     assert!(instance_content.contains("name=Test Pack"));
@@ -0,0 +1,8 @@
+use super::*;
+
+#[test]
+fn civil_date_handles_epoch_leap_days_and_negative_days() {
+    assert_eq!(civil_date(0), (1970, 1, 1));
+    assert_eq!(civil_date(11_016), (2000, 2, 29));
+    assert_eq!(civil_date(-1), (1969, 12, 31));
+}
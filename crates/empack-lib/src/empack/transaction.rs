@@ -0,0 +1,217 @@
+//! Rollback snapshots for project-mutating commands
+//!
+//! Every command that changes the project snapshots `empack.yml`, `empack.lock`,
+//! `pack/pack.toml`, `pack/index.toml`, and every `.pw.toml` under `pack/`
+//! before they run. The snapshot is persisted next to `empack.yml` so the
+//! interrupt path can restore it even when the command never returns.
+
+use crate::application::session::{FileSystemProvider, list_relative_files};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Snapshot file written to the project root while a command runs.
+pub(crate) const ROLLBACK_FILE: &str = ".empack-rollback.json";
+
+/// Project files captured in every snapshot, relative to the project root.
+const TRACKED_FILES: [&str; 4] = [
+    "empack.yml",
+    "empack.lock",
+    "pack/pack.toml",
+    "pack/index.toml",
+];
+
+#[derive(Debug, Error)]
+pub enum TransactionError {
+    #[error("Failed to snapshot {path}: {reason}")]
+    Snapshot { path: PathBuf, reason: String },
+
+    #[error("Failed to restore {path}: {reason}")]
+    Restore { path: PathBuf, reason: String },
+
+    #[error("Failed to write rollback snapshot: {reason}")]
+    Persist { reason: String },
+}
+
+/// File contents keyed by project-relative path; `None` marks a file that
+/// did not exist when the snapshot was taken.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct Snapshot {
+    files: BTreeMap<String, Option<String>>,
+}
+
+/// An open snapshot of project state.
+///
+/// Call [`ProjectTransaction::commit`] once the command succeeds, or
+/// [`ProjectTransaction::rollback`] to put every captured file back.
+pub struct ProjectTransaction<'a> {
+    filesystem: &'a dyn FileSystemProvider,
+    workdir: PathBuf,
+    snapshot: Snapshot,
+}
+
+impl<'a> ProjectTransaction<'a> {
+    /// Capture the project files and persist the snapshot.
+    pub fn begin(
+        filesystem: &'a dyn FileSystemProvider,
+        workdir: &Path,
+    ) -> Result<Self, TransactionError> {
+        let snapshot = Snapshot {
+            files: capture_project_files(filesystem, workdir)?,
+        };
+
+        let json = serde_json::to_string(&snapshot).map_err(|e| TransactionError::Persist {
+            reason: e.to_string(),
+        })?;
+        filesystem
+            .write_file(&workdir.join(ROLLBACK_FILE), &json)
+            .map_err(|e| TransactionError::Persist {
+                reason: e.to_string(),
+            })?;
+
+        Ok(Self {
+            filesystem,
+            workdir: workdir.to_path_buf(),
+            snapshot,
+        })
+    }
+
+    /// File contents captured when the transaction began.
+    pub fn files(&self) -> &BTreeMap<String, Option<String>> {
+        &self.snapshot.files
+    }
+
+    /// Keep the command's changes and discard the snapshot.
+    pub fn commit(self) -> Result<(), TransactionError> {
+        self.filesystem
+            .remove_file(&self.workdir.join(ROLLBACK_FILE))
+            .map_err(|e| TransactionError::Persist {
+                reason: e.to_string(),
+            })
+    }
+
+    /// Restore every captured file and remove metafiles created since.
+    ///
+    /// Returns the number of files written or removed.
+    pub fn rollback(self) -> Result<usize, TransactionError> {
+        restore_snapshot(self.filesystem, &self.workdir, &self.snapshot)
+    }
+}
+
+/// Read every tracked project file, keyed by project-relative path.
+///
+/// Missing files are recorded as `None`.
+pub fn capture_project_files(
+    filesystem: &dyn FileSystemProvider,
+    workdir: &Path,
+) -> Result<BTreeMap<String, Option<String>>, TransactionError> {
+    let mut files = BTreeMap::new();
+    let mut paths: Vec<String> = TRACKED_FILES.iter().map(|p| p.to_string()).collect();
+    paths.extend(list_metafiles(filesystem, workdir));
+
+    for relative in paths {
+        let path = workdir.join(&relative);
+        let content = if filesystem.exists(&path) {
+            Some(
+                filesystem
+                    .read_to_string(&path)
+                    .map_err(|e| TransactionError::Snapshot {
+                        path: path.clone(),
+                        reason: e.to_string(),
+                    })?,
+            )
+        } else {
+            None
+        };
+        files.insert(relative, content);
+    }
+
+    Ok(files)
+}
+
+/// Restore a snapshot left behind by a command that did not finish.
+///
+/// Returns `Ok(None)` when no snapshot is pending.
+pub fn restore_pending(
+    filesystem: &dyn FileSystemProvider,
+    workdir: &Path,
+) -> Result<Option<usize>, TransactionError> {
+    let rollback_path = workdir.join(ROLLBACK_FILE);
+    if !filesystem.exists(&rollback_path) {
+        return Ok(None);
+    }
+    let content =
+        filesystem
+            .read_to_string(&rollback_path)
+            .map_err(|e| TransactionError::Restore {
+                path: rollback_path.clone(),
+                reason: e.to_string(),
+            })?;
+    let snapshot: Snapshot =
+        serde_json::from_str(&content).map_err(|e| TransactionError::Restore {
+            path: rollback_path.clone(),
+            reason: e.to_string(),
+        })?;
+    restore_snapshot(filesystem, workdir, &snapshot).map(Some)
+}
+
+fn restore_snapshot(
+    filesystem: &dyn FileSystemProvider,
+    workdir: &Path,
+    snapshot: &Snapshot,
+) -> Result<usize, TransactionError> {
+    let mut changed = 0;
+
+    for relative in list_metafiles(filesystem, workdir) {
+        if !snapshot.files.contains_key(&relative) {
+            let path = workdir.join(&relative);
+            filesystem
+                .remove_file(&path)
+                .map_err(|e| TransactionError::Restore {
+                    path: path.clone(),
+                    reason: e.to_string(),
+                })?;
+            changed += 1;
+        }
+    }
+
+    for (relative, content) in &snapshot.files {
+        let path = workdir.join(relative);
+        let current = filesystem.read_to_string(&path).ok();
+        if current.as_ref() == content.as_ref() {
+            continue;
+        }
+        let result = match content {
+            Some(content) => filesystem.write_file(&path, content),
+            None => filesystem.remove_file(&path),
+        };
+        result.map_err(|e| TransactionError::Restore {
+            path: path.clone(),
+            reason: e.to_string(),
+        })?;
+        changed += 1;
+    }
+
+    filesystem
+        .remove_file(&workdir.join(ROLLBACK_FILE))
+        .map_err(|e| TransactionError::Restore {
+            path: workdir.join(ROLLBACK_FILE),
+            reason: e.to_string(),
+        })?;
+
+    Ok(changed)
+}
+
+/// Project-relative paths of every `.pw.toml` under `pack/`.
+fn list_metafiles(filesystem: &dyn FileSystemProvider, workdir: &Path) -> Vec<String> {
+    list_relative_files(filesystem, workdir, &workdir.join("pack"))
+        .into_iter()
+        .filter(|path| path.ends_with(".pw.toml"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    include!("transaction.test.rs");
+}
@@ -0,0 +1,79 @@
+use super::*;
+use crate::application::session_mocks::{MockFileSystemProvider, mock_root};
+
+fn project_filesystem(workdir: &Path) -> MockFileSystemProvider {
+    MockFileSystemProvider::new()
+        .with_file(workdir.join("empack.yml"), "empack:\n  dependencies: {}\n".to_string())
+        .with_file(workdir.join("pack/pack.toml"), "name = \"Pack\"\n".to_string())
+        .with_file(workdir.join("pack/index.toml"), "files = []\n".to_string())
+        .with_file(
+            workdir.join("pack/mods/sodium.pw.toml"),
+            "name = \"Sodium\"\n".to_string(),
+        )
+}
+
+#[test]
+fn rollback_restores_edits_and_removes_new_metafiles() {
+    let workdir = mock_root().join("pack-project");
+    let fs = project_filesystem(&workdir);
+
+    let transaction = ProjectTransaction::begin(&fs, &workdir).expect("begin");
+    assert!(fs.exists(&workdir.join(ROLLBACK_FILE)));
+
+    fs.write_file(&workdir.join("empack.yml"), "edited\n").unwrap();
+    fs.write_file(&workdir.join("empack.lock"), "lock\n").unwrap();
+    fs.write_file(&workdir.join("pack/mods/jei.pw.toml"), "name = \"JEI\"\n")
+        .unwrap();
+    fs.remove_file(&workdir.join("pack/mods/sodium.pw.toml"))
+        .unwrap();
+
+    let restored = transaction.rollback().expect("rollback");
+
+    assert_eq!(restored, 4);
+    assert_eq!(
+        fs.read_to_string(&workdir.join("empack.yml")).unwrap(),
+        "empack:\n  dependencies: {}\n"
+    );
+    assert_eq!(
+        fs.read_to_string(&workdir.join("pack/mods/sodium.pw.toml"))
+            .unwrap(),
+        "name = \"Sodium\"\n"
+    );
+    assert!(!fs.exists(&workdir.join("empack.lock")));
+    assert!(!fs.exists(&workdir.join("pack/mods/jei.pw.toml")));
+    assert!(!fs.exists(&workdir.join(ROLLBACK_FILE)));
+}
+
+#[test]
+fn commit_keeps_changes_and_discards_snapshot() {
+    let workdir = mock_root().join("pack-project");
+    let fs = project_filesystem(&workdir);
+
+    let transaction = ProjectTransaction::begin(&fs, &workdir).expect("begin");
+    fs.write_file(&workdir.join("empack.yml"), "edited\n").unwrap();
+    transaction.commit().expect("commit");
+
+    assert_eq!(
+        fs.read_to_string(&workdir.join("empack.yml")).unwrap(),
+        "edited\n"
+    );
+    assert!(!fs.exists(&workdir.join(ROLLBACK_FILE)));
+    assert_eq!(restore_pending(&fs, &workdir).unwrap(), None);
+}
+
+#[test]
+fn restore_pending_reads_persisted_snapshot() {
+    let workdir = mock_root().join("pack-project");
+    let fs = project_filesystem(&workdir);
+
+    let transaction = ProjectTransaction::begin(&fs, &workdir).expect("begin");
+    fs.write_file(&workdir.join("pack/index.toml"), "files = [\"x\"]\n")
+        .unwrap();
+    drop(transaction);
+
+    assert_eq!(restore_pending(&fs, &workdir).unwrap(), Some(1));
+    assert_eq!(
+        fs.read_to_string(&workdir.join("pack/index.toml")).unwrap(),
+        "files = []\n"
+    );
+}
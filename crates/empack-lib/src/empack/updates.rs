@@ -0,0 +1,337 @@
+//! Newer-file discovery for resolved dependencies
+//!
+//! Reads the installed file from a packwiz `.pw.toml` and queries the
+//! project platform for the newest file compatible with the pack's
+//! Minecraft version and loader.
+
+use crate::application::session::FileSystemProvider;
+use crate::empack::parsing::ModLoader;
+use crate::networking::endpoints::{Endpoints, Service, send_with_fallback};
+use crate::primitives::{ProjectPlatform, ProjectType};
+use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
+use serde::Deserialize;
+use std::path::Path;
+use thiserror::Error;
+use tracing::trace;
+
+/// Errors raised while checking a dependency for newer files
+#[derive(Debug, Error)]
+pub enum UpdateCheckError {
+    #[error("HTTP request failed: {source}")]
+    RequestError {
+        #[from]
+        source: reqwest::Error,
+    },
+
+    #[error("{platform} API returned status {status} for project '{project_id}'")]
+    UnexpectedStatus {
+        platform: ProjectPlatform,
+        project_id: String,
+        status: u16,
+    },
+
+    #[error("API key missing for platform: {platform}")]
+    MissingApiKey { platform: ProjectPlatform },
+}
+
+/// Installed file recorded in a packwiz `.pw.toml`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledFile {
+    /// Jar or archive filename written by packwiz
+    pub filename: String,
+    /// Modrinth version ID or CurseForge file ID, when update metadata exists
+    pub version_id: Option<String>,
+}
+
+/// Newest compatible file reported by a project platform
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LatestFile {
+    /// Modrinth version ID or CurseForge file ID
+    pub version_id: String,
+    /// Human-readable version name
+    pub version_name: String,
+    /// Primary filename for the version
+    pub filename: String,
+}
+
+/// Base URLs for the platform APIs queried during update checks
+///
+/// Each list is in fallback order and must not be empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateApiBases {
+    pub modrinth: Vec<String>,
+    pub curseforge: Vec<String>,
+}
+
+impl UpdateApiBases {
+    /// Production Modrinth and CurseForge API roots
+    pub fn production() -> Self {
+        Self::from_endpoints(Endpoints::upstream())
+    }
+
+    /// The Modrinth and CurseForge API bases of `endpoints`
+    pub fn from_endpoints(endpoints: &Endpoints) -> Self {
+        let owned = |service| {
+            endpoints
+                .bases(service)
+                .into_iter()
+                .map(str::to_string)
+                .collect()
+        };
+        Self {
+            modrinth: owned(Service::ModrinthApi),
+            curseforge: owned(Service::CurseForgeApi),
+        }
+    }
+
+    pub(crate) fn modrinth_urls(&self, path: &str) -> Vec<String> {
+        join_all(&self.modrinth, path)
+    }
+
+    pub(crate) fn curseforge_urls(&self, path: &str) -> Vec<String> {
+        join_all(&self.curseforge, path)
+    }
+}
+
+fn join_all(bases: &[String], path: &str) -> Vec<String> {
+    bases
+        .iter()
+        .map(|base| format!("{}/{}", base.trim_end_matches('/'), path))
+        .collect()
+}
+
+/// Compatibility filter applied to platform file listings
+#[derive(Debug, Clone, Copy)]
+pub struct UpdateFilter<'a> {
+    pub minecraft_versions: &'a [String],
+    pub loader: Option<ModLoader>,
+    pub project_type: ProjectType,
+}
+
+/// Read the installed file from a `.pw.toml`.
+///
+/// Returns `None` when the file cannot be read or parsed.
+pub fn read_installed_file(
+    filesystem: &dyn FileSystemProvider,
+    path: &Path,
+) -> Option<InstalledFile> {
+    let content = filesystem.read_to_string(path).ok()?;
+    let toml: toml::Value = toml::from_str(&content).ok()?;
+
+    let filename = toml
+        .get("filename")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let update = toml.get("update");
+    let version_id = update
+        .and_then(|u| u.get("modrinth"))
+        .and_then(|m| m.get("version"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .or_else(|| {
+            update
+                .and_then(|u| u.get("curseforge"))
+                .and_then(|c| c.get("file-id"))
+                .and_then(|v| v.as_integer())
+                .map(|i| i.to_string())
+        });
+
+    Some(InstalledFile {
+        filename,
+        version_id,
+    })
+}
+
+/// Query the newest compatible file for a project.
+///
+/// Returns `Ok(None)` when the platform lists no file matching the filter.
+pub async fn fetch_latest_file(
+    client: &reqwest::Client,
+    api_bases: &UpdateApiBases,
+    curseforge_api_key: Option<&str>,
+    platform: ProjectPlatform,
+    project_id: &str,
+    filter: UpdateFilter<'_>,
+) -> Result<Option<LatestFile>, UpdateCheckError> {
+    match platform {
+        ProjectPlatform::Modrinth => {
+            fetch_latest_modrinth_file(client, api_bases, project_id, filter).await
+        }
+        ProjectPlatform::CurseForge => {
+            let api_key = curseforge_api_key.ok_or(UpdateCheckError::MissingApiKey {
+                platform: ProjectPlatform::CurseForge,
+            })?;
+            fetch_latest_curseforge_file(client, api_bases, api_key, project_id, filter).await
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ModrinthVersion {
+    id: String,
+    #[serde(default)]
+    version_number: String,
+    #[serde(default)]
+    date_published: String,
+    #[serde(default)]
+    files: Vec<ModrinthVersionFile>,
+}
+
+#[derive(Deserialize)]
+struct ModrinthVersionFile {
+    filename: String,
+    #[serde(default)]
+    primary: bool,
+}
+
+async fn fetch_latest_modrinth_file(
+    client: &reqwest::Client,
+    api_bases: &UpdateApiBases,
+    project_id: &str,
+    filter: UpdateFilter<'_>,
+) -> Result<Option<LatestFile>, UpdateCheckError> {
+    let mut params = Vec::new();
+    if !filter.minecraft_versions.is_empty() {
+        let versions = serde_json::to_string(filter.minecraft_versions).unwrap_or_default();
+        params.push(format!(
+            "game_versions={}",
+            utf8_percent_encode(&versions, NON_ALPHANUMERIC)
+        ));
+    }
+    if filter.project_type.uses_loader_facet()
+        && let Some(loader) = filter.loader
+    {
+        let loaders = format!("[\"{}\"]", loader.as_str());
+        params.push(format!(
+            "loaders={}",
+            utf8_percent_encode(&loaders, NON_ALPHANUMERIC)
+        ));
+    }
+
+    let mut path = format!(
+        "v2/project/{}/version",
+        utf8_percent_encode(project_id, NON_ALPHANUMERIC)
+    );
+    if !params.is_empty() {
+        path = format!("{path}?{}", params.join("&"));
+    }
+    let urls = api_bases.modrinth_urls(&path);
+    trace!("Modrinth version listing URL: {}", urls[0]);
+
+    let response = send_with_fallback(urls, |url| {
+        client.get(url).header("User-Agent", "empack/0.1.0").send()
+    })
+    .await?;
+    if !response.status().is_success() {
+        return Err(UpdateCheckError::UnexpectedStatus {
+            platform: ProjectPlatform::Modrinth,
+            project_id: project_id.to_string(),
+            status: response.status().as_u16(),
+        });
+    }
+
+    let mut versions: Vec<ModrinthVersion> = response.json().await?;
+    versions.sort_by(|a, b| b.date_published.cmp(&a.date_published));
+
+    Ok(versions.into_iter().next().map(|version| {
+        let filename = version
+            .files
+            .iter()
+            .find(|file| file.primary)
+            .or_else(|| version.files.first())
+            .map(|file| file.filename.clone())
+            .unwrap_or_default();
+        LatestFile {
+            version_id: version.id,
+            version_name: version.version_number,
+            filename,
+        }
+    }))
+}
+
+#[derive(Deserialize)]
+struct CurseForgeFilesResponse {
+    data: Vec<CurseForgeFile>,
+}
+
+#[derive(Deserialize)]
+struct CurseForgeFile {
+    id: u64,
+    #[serde(rename = "displayName", default)]
+    display_name: String,
+    #[serde(rename = "fileName", default)]
+    file_name: String,
+    #[serde(rename = "fileDate", default)]
+    file_date: String,
+}
+
+async fn fetch_latest_curseforge_file(
+    client: &reqwest::Client,
+    api_bases: &UpdateApiBases,
+    api_key: &str,
+    project_id: &str,
+    filter: UpdateFilter<'_>,
+) -> Result<Option<LatestFile>, UpdateCheckError> {
+    let mut params = vec![("pageSize", "50".to_string())];
+    if let Some(version) = filter.minecraft_versions.first() {
+        params.push(("gameVersion", version.clone()));
+    }
+    if filter.project_type.uses_loader_facet()
+        && let Some(loader) = filter.loader
+    {
+        params.push(("modLoaderType", curseforge_loader_type(loader).to_string()));
+    }
+    let query = params
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, utf8_percent_encode(v, NON_ALPHANUMERIC)))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    let urls = api_bases.curseforge_urls(&format!(
+        "v1/mods/{}/files?{}",
+        utf8_percent_encode(project_id, NON_ALPHANUMERIC),
+        query
+    ));
+    trace!("CurseForge file listing URL: {}", urls[0]);
+
+    let response = send_with_fallback(urls, |url| {
+        client
+            .get(url)
+            .header("x-api-key", api_key)
+            .header("User-Agent", "empack/0.1.0")
+            .send()
+    })
+    .await?;
+    if !response.status().is_success() {
+        return Err(UpdateCheckError::UnexpectedStatus {
+            platform: ProjectPlatform::CurseForge,
+            project_id: project_id.to_string(),
+            status: response.status().as_u16(),
+        });
+    }
+
+    let mut body: CurseForgeFilesResponse = response.json().await?;
+    body.data
+        .sort_by(|a, b| b.file_date.cmp(&a.file_date).then_with(|| b.id.cmp(&a.id)));
+
+    Ok(body.data.into_iter().next().map(|file| LatestFile {
+        version_id: file.id.to_string(),
+        version_name: file.display_name,
+        filename: file.file_name,
+    }))
+}
+
+fn curseforge_loader_type(loader: ModLoader) -> u32 {
+    match loader {
+        ModLoader::Forge => 1,
+        ModLoader::Fabric => 4,
+        ModLoader::Quilt => 5,
+        ModLoader::NeoForge => 6,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("updates.test.rs");
+}
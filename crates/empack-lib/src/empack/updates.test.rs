@@ -0,0 +1,233 @@
+use super::*;
+use crate::application::session_mocks::{MockFileSystemProvider, mock_root};
+
+fn test_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(5))
+        .build()
+        .expect("http client")
+}
+
+fn test_api_bases(url: &str) -> UpdateApiBases {
+    UpdateApiBases {
+        modrinth: vec![url.to_string()],
+        curseforge: vec![url.to_string()],
+    }
+}
+
+fn mod_filter(versions: &[String]) -> UpdateFilter<'_> {
+    UpdateFilter {
+        minecraft_versions: versions,
+        loader: Some(ModLoader::Fabric),
+        project_type: ProjectType::Mod,
+    }
+}
+
+#[test]
+fn read_installed_file_reads_modrinth_version() {
+    let path = mock_root().join("pack/mods/sodium.pw.toml");
+    let fs = MockFileSystemProvider::new().with_file(
+        path.clone(),
+        r#"name = "Sodium"
+filename = "sodium-0.6.0.jar"
+
+[update.modrinth]
+mod-id = "AANobbMI"
+version = "abc123"
+"#
+        .to_string(),
+    );
+
+    let installed = read_installed_file(&fs, &path).expect("installed file");
+    assert_eq!(installed.filename, "sodium-0.6.0.jar");
+    assert_eq!(installed.version_id.as_deref(), Some("abc123"));
+}
+
+#[test]
+fn read_installed_file_reads_curseforge_file_id() {
+    let path = mock_root().join("pack/mods/jei.pw.toml");
+    let fs = MockFileSystemProvider::new().with_file(
+        path.clone(),
+        r#"name = "JEI"
+filename = "jei.jar"
+
+[update.curseforge]
+file-id = 5101366
+project-id = 238222
+"#
+        .to_string(),
+    );
+
+    let installed = read_installed_file(&fs, &path).expect("installed file");
+    assert_eq!(installed.version_id.as_deref(), Some("5101366"));
+}
+
+#[test]
+fn read_installed_file_returns_none_for_missing_file() {
+    let fs = MockFileSystemProvider::new();
+    assert!(read_installed_file(&fs, &mock_root().join("missing.pw.toml")).is_none());
+}
+
+#[tokio::test]
+async fn fetch_latest_modrinth_file_picks_newest_published_version() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/v2/project/AANobbMI/version")
+        .match_query(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::UrlEncoded(
+                "game_versions".to_string(),
+                r#"["1.21.1"]"#.to_string(),
+            ),
+            mockito::Matcher::UrlEncoded("loaders".to_string(), r#"["fabric"]"#.to_string()),
+        ]))
+        .with_status(200)
+        .with_body(
+            r#"[
+                {"id":"old","version_number":"0.5.0","date_published":"2024-01-01T00:00:00Z","files":[{"filename":"sodium-0.5.0.jar","primary":true}]},
+                {"id":"new","version_number":"0.6.0","date_published":"2024-06-01T00:00:00Z","files":[{"filename":"sources.jar","primary":false},{"filename":"sodium-0.6.0.jar","primary":true}]}
+            ]"#,
+        )
+        .create_async()
+        .await;
+
+    let versions = vec!["1.21.1".to_string()];
+    let latest = fetch_latest_file(
+        &test_client(),
+        &test_api_bases(&server.url()),
+        None,
+        ProjectPlatform::Modrinth,
+        "AANobbMI",
+        mod_filter(&versions),
+    )
+    .await
+    .expect("latest file")
+    .expect("compatible file");
+
+    mock.assert_async().await;
+    assert_eq!(
+        latest,
+        LatestFile {
+            version_id: "new".to_string(),
+            version_name: "0.6.0".to_string(),
+            filename: "sodium-0.6.0.jar".to_string(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn fetch_latest_modrinth_file_skips_loader_for_resourcepacks() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/v2/project/faithful/version")
+        .match_query(mockito::Matcher::UrlEncoded(
+            "game_versions".to_string(),
+            r#"["1.21.1"]"#.to_string(),
+        ))
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    let versions = vec!["1.21.1".to_string()];
+    let latest = fetch_latest_file(
+        &test_client(),
+        &test_api_bases(&server.url()),
+        None,
+        ProjectPlatform::Modrinth,
+        "faithful",
+        UpdateFilter {
+            minecraft_versions: &versions,
+            loader: Some(ModLoader::Fabric),
+            project_type: ProjectType::ResourcePack,
+        },
+    )
+    .await
+    .expect("listing succeeds");
+
+    mock.assert_async().await;
+    assert_eq!(latest, None);
+}
+
+#[tokio::test]
+async fn fetch_latest_curseforge_file_filters_by_version_and_loader() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/v1/mods/238222/files")
+        .match_header("x-api-key", "test-key")
+        .match_query(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::UrlEncoded("gameVersion".to_string(), "1.21.1".to_string()),
+            mockito::Matcher::UrlEncoded("modLoaderType".to_string(), "4".to_string()),
+        ]))
+        .with_status(200)
+        .with_body(
+            r#"{"data":[
+                {"id":100,"displayName":"JEI 1.0","fileName":"jei-1.0.jar","fileDate":"2024-01-01T00:00:00Z"},
+                {"id":200,"displayName":"JEI 2.0","fileName":"jei-2.0.jar","fileDate":"2024-05-01T00:00:00Z"}
+            ]}"#,
+        )
+        .create_async()
+        .await;
+
+    let versions = vec!["1.21.1".to_string()];
+    let latest = fetch_latest_file(
+        &test_client(),
+        &test_api_bases(&server.url()),
+        Some("test-key"),
+        ProjectPlatform::CurseForge,
+        "238222",
+        mod_filter(&versions),
+    )
+    .await
+    .expect("latest file")
+    .expect("compatible file");
+
+    mock.assert_async().await;
+    assert_eq!(latest.version_id, "200");
+    assert_eq!(latest.filename, "jei-2.0.jar");
+}
+
+#[tokio::test]
+async fn fetch_latest_curseforge_file_requires_api_key() {
+    let versions = vec!["1.21.1".to_string()];
+    let result = fetch_latest_file(
+        &test_client(),
+        &test_api_bases("http://127.0.0.1:9"),
+        None,
+        ProjectPlatform::CurseForge,
+        "238222",
+        mod_filter(&versions),
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(UpdateCheckError::MissingApiKey { .. })
+    ));
+}
+
+#[tokio::test]
+async fn fetch_latest_file_surfaces_unexpected_status() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/v2/project/missing/version")
+        .match_query(mockito::Matcher::Any)
+        .with_status(404)
+        .create_async()
+        .await;
+
+    let versions = vec!["1.21.1".to_string()];
+    let result = fetch_latest_file(
+        &test_client(),
+        &test_api_bases(&server.url()),
+        None,
+        ProjectPlatform::Modrinth,
+        "missing",
+        mod_filter(&versions),
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(UpdateCheckError::UnexpectedStatus { status: 404, .. })
+    ));
+}
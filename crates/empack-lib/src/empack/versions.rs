@@ -4,6 +4,7 @@
 
 use crate::Result;
 use crate::application::session::{FileSystemProvider, NetworkProvider};
+use crate::networking::endpoints::{Service, send_with_fallback};
 use anyhow::Context;
 use semver::Version;
 use serde::{Deserialize, Serialize};
@@ -580,11 +581,12 @@ impl<'a> VersionFetcher<'a> {
             4, // 4 hour cache
             || async {
                 let client = self.network.http_client()?;
-                let url = "https://launchermeta.mojang.com/mc/game/version_manifest.json";
+                let urls = self
+                    .network
+                    .endpoints()
+                    .urls(Service::MojangMeta, "mc/game/version_manifest.json");
 
-                let response = client
-                    .get(url)
-                    .send()
+                let response = send_with_fallback(urls, |url| client.get(url).send())
                     .await
                     .context("Failed to fetch Minecraft version manifest")?;
 
@@ -685,22 +687,57 @@ impl<'a> VersionFetcher<'a> {
     /// Fetch available NeoForge versions for a specific Minecraft version
     pub async fn fetch_neoforge_loader_versions(&self, mc_version: &str) -> Result<Vec<String>> {
         let cache_key = format!("neoforge_loader_{}.json", mc_version);
-        let versions = self.fetch_cached_or_network(
-            &cache_key,
-            6, // 6 hour cache
-            || async {
-                let client = self.network.http_client()?;
+        let versions = self
+            .fetch_cached_or_network(
+                &cache_key,
+                6, // 6 hour cache
+                || async {
+                    let client = self.network.http_client()?;
+
+                    if uses_forge_style_neoforge_coordinate(mc_version) {
+                        let legacy_urls = self.network.endpoints().urls(
+                            Service::NeoForgeMaven,
+                            "api/maven/versions/releases/net/neoforged/forge",
+                        );
+
+                        let response =
+                            send_with_fallback(legacy_urls, |url| client.get(url).send()).await;
+
+                        match response {
+                            Ok(resp) if resp.status().is_success() => {
+                                if let Ok(version_data) =
+                                    resp.json::<NeoForgeVersionResponse>().await
+                                {
+                                    let filtered_versions = filter_forge_versions_by_minecraft(
+                                        &version_data.versions,
+                                        mc_version,
+                                    )?;
+
+                                    if !filtered_versions.is_empty() {
+                                        return Ok(filtered_versions);
+                                    }
+                                }
+                            }
+                            _ => {
+                                // API failed, use fallback
+                            }
+                        }
 
-                if uses_forge_style_neoforge_coordinate(mc_version) {
-                    let legacy_url =
-                        "https://maven.neoforged.net/api/maven/versions/releases/net/neoforged/forge";
+                        return Ok(Self::get_fallback_loader_versions("neoforge", mc_version));
+                    }
 
-                    let response = client.get(legacy_url).send().await;
+                    // Try NeoForge API first for 1.20.2+ and year-style releases.
+                    let urls = self.network.endpoints().urls(
+                        Service::NeoForgeMaven,
+                        "api/maven/versions/releases/net/neoforged/neoforge",
+                    );
+
+                    let response = send_with_fallback(urls, |url| client.get(url).send()).await;
 
                     match response {
                         Ok(resp) if resp.status().is_success() => {
                             if let Ok(version_data) = resp.json::<NeoForgeVersionResponse>().await {
-                                let filtered_versions = filter_forge_versions_by_minecraft(
+                                let filtered_versions = filter_neoforge_versions_by_minecraft(
                                     &version_data.versions,
                                     mc_version,
                                 )?;
@@ -715,36 +752,10 @@ impl<'a> VersionFetcher<'a> {
                         }
                     }
 
-                    return Ok(Self::get_fallback_loader_versions("neoforge", mc_version));
-                }
-
-                // Try NeoForge API first for 1.20.2+ and year-style releases.
-                let url =
-                    "https://maven.neoforged.net/api/maven/versions/releases/net/neoforged/neoforge";
-
-                let response = client.get(url).send().await;
-
-                match response {
-                    Ok(resp) if resp.status().is_success() => {
-                        if let Ok(version_data) = resp.json::<NeoForgeVersionResponse>().await {
-                            let filtered_versions = filter_neoforge_versions_by_minecraft(
-                                &version_data.versions,
-                                mc_version,
-                            )?;
-
-                            if !filtered_versions.is_empty() {
-                                return Ok(filtered_versions);
-                            }
-                        }
-                    }
-                    _ => {
-                        // API failed, use fallback
-                    }
-                }
-
-                Ok(Self::get_fallback_loader_versions("neoforge", mc_version))
-            }
-        ).await?;
+                    Ok(Self::get_fallback_loader_versions("neoforge", mc_version))
+                },
+            )
+            .await?;
 
         let sanitized = sanitize_neoforge_loader_versions(&versions, mc_version)?;
         if sanitized != versions {
@@ -788,10 +799,12 @@ impl<'a> VersionFetcher<'a> {
 
                 // Fetch maven-metadata.xml (contains ALL Forge versions)
                 // NOTE: Must use maven.minecraftforge.net, NOT files.minecraftforge.net (404)
-                let url =
-                    "https://maven.minecraftforge.net/net/minecraftforge/forge/maven-metadata.xml";
+                let urls = self.network.endpoints().urls(
+                    Service::ForgeMaven,
+                    "net/minecraftforge/forge/maven-metadata.xml",
+                );
 
-                let response = client.get(url).send().await;
+                let response = send_with_fallback(urls, |url| client.get(url).send()).await;
 
                 match response {
                     Ok(resp) if resp.status().is_success() => {
@@ -894,6 +907,19 @@ impl<'a> VersionFetcher<'a> {
     {
         let cache_path = self.cache_dir.join(cache_filename);
 
+        // Offline: any cached copy will do, and nothing falls back to defaults
+        if self.network.is_offline() {
+            return self
+                .load_from_cache(&cache_path)
+                .map(|cached_data| cached_data.versions)
+                .map_err(|_| {
+                    crate::networking::NetworkingError::Offline {
+                        resource: cache_path.display().to_string(),
+                    }
+                    .into()
+                });
+        }
+
         // Try to load from cache first
         if let Ok(cached_data) = self.load_from_cache(&cache_path)
             && !cached_data.is_expired(max_age_hours)
@@ -22,6 +22,35 @@ async fn test_version_fetcher_creation() {
     );
 }
 
+#[tokio::test]
+#[cfg(feature = "test-utils")]
+async fn test_offline_uses_expired_cache_and_never_falls_back() {
+    let network = MockNetworkProvider::new()
+        .with_offline()
+        .with_failing_http_client();
+    let filesystem = MockFileSystemProvider::new();
+    let fetcher = VersionFetcher::new(&network, &filesystem).unwrap();
+
+    let error = fetcher.fetch_minecraft_versions().await.unwrap_err();
+    assert!(
+        error.to_string().contains("minecraft_versions.json"),
+        "error should name the missing cache file: {error}"
+    );
+
+    let stale = CachedVersions {
+        versions: vec!["1.20.1".to_string()],
+        cached_at: 0,
+    };
+    fetcher
+        .write_cached_versions(&fetcher.cache_dir.join("minecraft_versions.json"), &stale)
+        .unwrap();
+    assert_eq!(
+        fetcher.fetch_minecraft_versions().await.unwrap(),
+        vec!["1.20.1".to_string()]
+    );
+    assert_eq!(*network.client_calls.lock().unwrap(), 0);
+}
+
 #[test]
 fn test_cached_versions_expiry() {
     let versions = vec!["1.21.4".to_string(), "1.21.1".to_string()];
@@ -144,6 +144,7 @@ where
             if let Some(dir) = &marker_dir {
                 let marker = dir.join(empack::state::STATE_MARKER_FILE);
                 let _ = std::fs::remove_file(marker);
+                application::session::restore_process_interrupt_snapshot(dir);
             }
 
             std::process::exit(130)
@@ -157,19 +158,32 @@ pub(crate) mod test_support {
     use std::ffi::OsString;
     use std::sync::OnceLock;
 
-    const CLI_ENV_VARS: [&str; 20] = [
+    const CLI_ENV_VARS: [&str; 34] = [
         "EMPACK_WORKDIR",
         "EMPACK_CPU_JOBS",
         "EMPACK_NET_TIMEOUT",
         "EMPACK_ID_MODRINTH",
         "EMPACK_KEY_MODRINTH",
         "EMPACK_KEY_CURSEFORGE",
+        "EMPACK_SIGNING_KEY",
+        "EMPACK_SIGNING_KEY_PASSWORD",
         "EMPACK_LOG_LEVEL",
         "EMPACK_LOG_FORMAT",
         "EMPACK_LOG_OUTPUT",
         "EMPACK_COLOR",
         "EMPACK_YES",
         "EMPACK_DRY_RUN",
+        "EMPACK_NO_ROLLBACK",
+        "EMPACK_WAIT_LOCK",
+        "EMPACK_OFFLINE",
+        "EMPACK_MODRINTH_API_URL",
+        "EMPACK_CURSEFORGE_API_URL",
+        "EMPACK_MODRINTH_CDN_URL",
+        "EMPACK_FORGE_MAVEN_URL",
+        "EMPACK_NEOFORGE_MAVEN_URL",
+        "EMPACK_FABRIC_MAVEN_URL",
+        "EMPACK_QUILT_MAVEN_URL",
+        "EMPACK_MOJANG_META_URL",
         "EMPACK_MODLOADER",
         "EMPACK_MC_VERSION",
         "EMPACK_AUTHOR",
@@ -178,6 +192,7 @@ pub(crate) mod test_support {
         "EMPACK_PACK_VERSION",
         "EMPACK_DATAPACK_FOLDER",
         "EMPACK_GAME_VERSIONS",
+        "SOURCE_DATE_EPOCH",
     ];
 
     pub struct CliEnvGuard {
@@ -12,6 +12,11 @@ use super::NetworkingError;
 /// Default cache TTL (Time To Live) - 5 minutes
 const DEFAULT_CACHE_TTL_SECS: u64 = 300;
 
+/// How long an expired entry stays on disk - 30 days. Expired entries still
+/// answer `--offline` requests and ETag revalidation. Offline runs keep every
+/// entry, however old.
+const STALE_RETENTION_SECS: u64 = 30 * 24 * 60 * 60;
+
 /// Cached HTTP response with ETag support
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CachedResponse {
@@ -38,6 +43,7 @@ pub struct HttpCache {
     cache: Arc<RwLock<HashMap<String, CachedResponse>>>,
     cache_dir: PathBuf,
     default_ttl: Duration,
+    prune_stale: bool,
 }
 
 impl HttpCache {
@@ -47,6 +53,7 @@ impl HttpCache {
             cache: Arc::new(RwLock::new(HashMap::new())),
             cache_dir,
             default_ttl: Duration::from_secs(DEFAULT_CACHE_TTL_SECS),
+            prune_stale: true,
         }
     }
 
@@ -56,9 +63,17 @@ impl HttpCache {
             cache: Arc::new(RwLock::new(HashMap::new())),
             cache_dir,
             default_ttl: ttl,
+            prune_stale: true,
         }
     }
 
+    /// Keep entries expired beyond the retention window when loading, so
+    /// `--offline` can serve them regardless of age.
+    pub fn keeping_stale_entries(mut self) -> Self {
+        self.prune_stale = false;
+        self
+    }
+
     /// Get cache directory path
     pub fn cache_dir(&self) -> &PathBuf {
         &self.cache_dir
@@ -94,24 +109,24 @@ impl HttpCache {
                 message: format!("Failed to parse cache file: {}", e),
             })?;
 
-        // Load into memory, filtering out expired entries
+        // Load into memory, dropping entries expired beyond the retention window
         let mut cache = self.cache.write().await;
-        let now = SystemTime::now();
-        let mut valid_count = 0;
-        let mut expired_count = 0;
+        let cutoff = SystemTime::now() - Duration::from_secs(STALE_RETENTION_SECS);
+        let mut kept_count = 0;
+        let mut dropped_count = 0;
 
         for (url, entry) in loaded_cache {
-            if entry.expires > now {
+            if !self.prune_stale || entry.expires > cutoff {
                 cache.insert(url, entry);
-                valid_count += 1;
+                kept_count += 1;
             } else {
-                expired_count += 1;
+                dropped_count += 1;
             }
         }
 
         debug!(
-            "Loaded cache from disk: {} valid entries, {} expired entries removed",
-            valid_count, expired_count
+            "Loaded cache from disk: {} entries, {} long-expired entries removed",
+            kept_count, dropped_count
         );
 
         Ok(())
@@ -187,15 +187,28 @@ async fn test_disk_persistence() {
 }
 
 #[tokio::test]
-async fn test_disk_persistence_filters_expired() {
+async fn test_disk_persistence_keeps_recently_expired() {
     let temp_dir = TempDir::new().unwrap();
     let cache_dir = temp_dir.path().to_path_buf();
 
-    // Create cache with expired and valid entries
+    // Create cache with long-expired, recently expired, and valid entries
     {
         let cache = HttpCache::new(cache_dir.clone());
 
-        // Add expired entry
+        // Add entry expired beyond the retention window
+        cache
+            .put(
+                "https://example.com/ancient".to_string(),
+                CachedResponse {
+                    data: b"ancient".to_vec(),
+                    etag: None,
+                    expires: SystemTime::now() - Duration::from_secs(STALE_RETENTION_SECS + 60),
+                    status: 200,
+                },
+            )
+            .await;
+
+        // Add recently expired entry
         cache
             .put(
                 "https://example.com/expired".to_string(),
@@ -224,14 +237,24 @@ async fn test_disk_persistence_filters_expired() {
         cache.save_to_disk().await.unwrap();
     }
 
-    // Load from disk - should only get valid entry
+    // Load from disk - expired entries stay for offline use until retention ends
     {
-        let cache = HttpCache::new(cache_dir);
+        let cache = HttpCache::new(cache_dir.clone());
         cache.load_from_disk().await.unwrap();
 
-        assert_eq!(cache.len().await, 1);
+        assert_eq!(cache.len().await, 2);
         assert!(cache.get("https://example.com/valid").await.is_some());
-        assert!(cache.get("https://example.com/expired").await.is_none());
+        assert!(cache.get("https://example.com/expired").await.unwrap().is_expired());
+        assert!(cache.get("https://example.com/ancient").await.is_none());
+    }
+
+    // Offline runs keep every entry, however long ago it expired
+    {
+        let cache = HttpCache::new(cache_dir).keeping_stale_entries();
+        cache.load_from_disk().await.unwrap();
+
+        assert_eq!(cache.len().await, 3);
+        assert!(cache.get("https://example.com/ancient").await.is_some());
     }
 }
 
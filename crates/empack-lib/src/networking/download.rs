@@ -0,0 +1,517 @@
+//! Resumable, parallel file downloads
+//!
+//! Each download streams to `<dest>.part` and is renamed onto `dest` once the
+//! body is complete, so `dest` never holds a truncated file. The response's
+//! strong `ETag`, or else its `Last-Modified`, is kept in
+//! `<dest>.part.validator`. An interrupted attempt leaves both behind; the
+//! next attempt, in this run or a later one, asks for the rest with an HTTP
+//! `Range` request guarded by `If-Range`, so a file that changed on the
+//! server is sent whole and restarts the download. A `.part` file without a
+//! validator cannot be checked and is discarded. A server that ignores the
+//! range sends the whole file again, which restarts it too.
+//!
+//! Batches run at most `jobs` downloads at once, and every request first
+//! waits for the rate budget of its host. Progress is reported in bytes, per
+//! file and, for batches, in total.
+//!
+//! URLs under a configured service are tried on each of its bases in turn
+//! (see [`Endpoints`]); the next base takes over only once a base has used up
+//! its attempts.
+
+use crate::application::session::{FileSystemProvider, Session};
+use crate::display::providers::{ProgressProvider, ProgressTracker};
+use crate::networking::endpoints::Endpoints;
+use crate::networking::rate_budget::HostBudgetRegistry;
+use futures_util::stream::{self, StreamExt};
+use reqwest::header::{
+    ACCEPT_ENCODING, CONTENT_RANGE, ETAG, HeaderMap, IF_RANGE, LAST_MODIFIED, RANGE,
+};
+use reqwest::{Client, StatusCode};
+use std::cell::Cell;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use thiserror::Error;
+
+/// Extension appended to `dest` while a download is in progress.
+pub const PART_EXTENSION: &str = "part";
+
+/// Extension appended to the `.part` path for the file holding its validator.
+pub const VALIDATOR_EXTENSION: &str = "validator";
+
+const MAX_ATTEMPTS: u32 = 3;
+/// Default wait before the first retry of a download.
+pub const RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Error)]
+pub enum DownloadError {
+    #[error("HTTP GET {url} returned status {status}")]
+    Status { url: String, status: StatusCode },
+
+    #[error("HTTP GET {url} failed after {attempts} attempts: {reason}")]
+    Exhausted {
+        url: String,
+        attempts: u32,
+        reason: String,
+    },
+
+    #[error("Failed to write download to {path}: {reason}")]
+    Io { path: PathBuf, reason: String },
+}
+
+/// One file to download.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Download {
+    pub url: String,
+    pub dest: PathBuf,
+    /// Name shown on the progress bar
+    pub label: String,
+}
+
+impl Download {
+    /// Download `url` to `dest`, labelled with the file name of `dest`.
+    pub fn new(url: impl Into<String>, dest: impl Into<PathBuf>) -> Self {
+        let dest = dest.into();
+        let label = dest
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Self {
+            url: url.into(),
+            dest,
+            label,
+        }
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+}
+
+/// Path of the in-progress download for `dest`.
+pub fn part_path(dest: &Path) -> PathBuf {
+    let mut path = dest.as_os_str().to_owned();
+    path.push(format!(".{PART_EXTENSION}"));
+    PathBuf::from(path)
+}
+
+/// Where the validator of the `.part` file at `part` is kept.
+pub fn validator_path(part: &Path) -> PathBuf {
+    let mut path = part.as_os_str().to_owned();
+    path.push(format!(".{VALIDATOR_EXTENSION}"));
+    PathBuf::from(path)
+}
+
+/// The `If-Range` validator a response allows resuming against: its strong
+/// `ETag`, or else its `Last-Modified` date.
+///
+/// Weak ETags are not allowed in `If-Range`.
+fn response_validator(headers: &HeaderMap) -> Option<String> {
+    let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+    header(ETAG)
+        .filter(|etag| !etag.starts_with("W/"))
+        .or_else(|| header(LAST_MODIFIED))
+        .map(str::to_string)
+}
+
+/// Byte totals of a whole batch, shared by its downloads.
+struct BatchProgress {
+    tracker: Box<dyn ProgressTracker>,
+    length: Cell<u64>,
+    position: Cell<u64>,
+}
+
+/// Byte progress of one download, mirrored into its batch.
+struct FileProgress<'a> {
+    tracker: Box<dyn ProgressTracker>,
+    batch: Option<&'a BatchProgress>,
+    length: Cell<u64>,
+    position: Cell<u64>,
+}
+
+impl<'a> FileProgress<'a> {
+    fn new(tracker: Box<dyn ProgressTracker>, batch: Option<&'a BatchProgress>) -> Self {
+        Self {
+            tracker,
+            batch,
+            length: Cell::new(0),
+            position: Cell::new(0),
+        }
+    }
+
+    fn set_length(&self, length: u64) {
+        let previous = self.length.replace(length);
+        self.tracker.set_length(length);
+        if let Some(batch) = self.batch {
+            batch.length.set(batch.length.get() - previous + length);
+            batch.tracker.set_length(batch.length.get());
+        }
+    }
+
+    fn set_position(&self, position: u64) {
+        let previous = self.position.replace(position);
+        self.tracker.set_position(position);
+        if let Some(batch) = self.batch {
+            batch
+                .position
+                .set(batch.position.get() - previous + position);
+            batch.tracker.set_position(batch.position.get());
+        }
+    }
+}
+
+/// How one request ended, short of a fatal error.
+enum Attempt {
+    Complete(u64),
+    Interrupted(String),
+}
+
+/// Streams downloads to disk through the session filesystem.
+pub struct DownloadEngine<'a> {
+    filesystem: &'a dyn FileSystemProvider,
+    client: Client,
+    budgets: &'a HostBudgetRegistry,
+    endpoints: &'a Endpoints,
+    jobs: usize,
+    idle_timeout: Duration,
+    retry_base_delay: Duration,
+}
+
+impl<'a> DownloadEngine<'a> {
+    pub fn new(
+        filesystem: &'a dyn FileSystemProvider,
+        client: Client,
+        budgets: &'a HostBudgetRegistry,
+    ) -> Self {
+        Self {
+            filesystem,
+            client,
+            budgets,
+            endpoints: Endpoints::upstream(),
+            jobs: 1,
+            idle_timeout: Duration::from_secs(30),
+            retry_base_delay: RETRY_BASE_DELAY,
+        }
+    }
+
+    /// An engine using the session's filesystem, HTTP client, rate budgets,
+    /// and endpoints, running `--cpu-jobs` downloads at once.
+    ///
+    /// `--net-timeout` bounds how long a download may stall, not how long it
+    /// may take.
+    pub fn for_session(session: &'a dyn Session) -> crate::Result<Self> {
+        let app_config = session.config().app_config();
+        let (jobs, timeout) = (app_config.cpu_jobs, app_config.net_timeout);
+        Ok(Self::new(
+            session.filesystem(),
+            session.network().http_client()?,
+            session.network().rate_budgets(),
+        )
+        .with_endpoints(session.network().endpoints())
+        .with_jobs(jobs)
+        .with_idle_timeout(Duration::from_secs(timeout))
+        .with_retry_base_delay(session.network().retry_base_delay()))
+    }
+
+    pub fn with_endpoints(mut self, endpoints: &'a Endpoints) -> Self {
+        self.endpoints = endpoints;
+        self
+    }
+
+    pub fn with_jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs.max(1);
+        self
+    }
+
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Wait before the first retry; each later retry waits twice as long.
+    pub fn with_retry_base_delay(mut self, delay: Duration) -> Self {
+        self.retry_base_delay = delay;
+        self
+    }
+
+    /// Download one file and return its size.
+    pub async fn fetch(
+        &self,
+        download: &Download,
+        progress: &dyn ProgressProvider,
+    ) -> Result<u64, DownloadError> {
+        let file = FileProgress::new(progress.bytes(0, &download.label), None);
+        self.fetch_tracked(download, &file).await
+    }
+
+    /// Download every file, at most `jobs` at once.
+    ///
+    /// Results are in the order of `downloads`; one failure does not stop
+    /// the others.
+    pub async fn fetch_all(
+        &self,
+        downloads: &[Download],
+        progress: &dyn ProgressProvider,
+    ) -> Vec<Result<u64, DownloadError>> {
+        let multi = progress.multi();
+        let batch = BatchProgress {
+            tracker: multi.add_bytes_bar(0, &format!("Downloading {} files", downloads.len())),
+            length: Cell::new(0),
+            position: Cell::new(0),
+        };
+
+        let results = stream::iter(downloads)
+            .map(|download| {
+                let file = FileProgress::new(multi.add_bytes_bar(0, &download.label), Some(&batch));
+                async move { self.fetch_tracked(download, &file).await }
+            })
+            .buffered(self.jobs)
+            .collect::<Vec<_>>()
+            .await;
+
+        batch.tracker.finish_clear();
+        results
+    }
+
+    async fn fetch_tracked(
+        &self,
+        download: &Download,
+        progress: &FileProgress<'_>,
+    ) -> Result<u64, DownloadError> {
+        let mut urls = self.endpoints.rebase(&download.url).into_iter().peekable();
+        let result = loop {
+            let url = urls.next().expect("rebase returns at least one URL");
+            let result = self.fetch_with_retries(download, &url, progress).await;
+            match result {
+                Err(DownloadError::Exhausted { ref reason, .. }) if urls.peek().is_some() => {
+                    tracing::warn!(url, %reason, "endpoint failed; trying the next one");
+                }
+                result => break result,
+            }
+        };
+        match &result {
+            Ok(_) => progress.tracker.finish_clear(),
+            Err(_) => progress.tracker.abandon(&download.label),
+        }
+        result
+    }
+
+    /// Retry until the file is complete.
+    ///
+    /// Only attempts that fail without extending the `.part` file past its
+    /// largest size so far count towards the attempt limit.
+    async fn fetch_with_retries(
+        &self,
+        download: &Download,
+        url: &str,
+        progress: &FileProgress<'_>,
+    ) -> Result<u64, DownloadError> {
+        let part = part_path(&download.dest);
+        if let Some(parent) = download.dest.parent() {
+            self.filesystem
+                .create_dir_all(parent)
+                .map_err(|e| io_error(parent, e))?;
+        }
+
+        let mut failures = 0;
+        let mut furthest = self.part_len(&part);
+        loop {
+            let reason = match self.attempt(url, &part, progress).await? {
+                Attempt::Complete(len) => {
+                    self.filesystem
+                        .rename(&part, &download.dest)
+                        .map_err(|e| io_error(&download.dest, e))?;
+                    self.forget_validator(&part)?;
+                    return Ok(len);
+                }
+                Attempt::Interrupted(reason) => reason,
+            };
+
+            let len = self.part_len(&part);
+            if len > furthest {
+                furthest = len;
+            } else {
+                failures += 1;
+            }
+            if failures >= MAX_ATTEMPTS {
+                return Err(DownloadError::Exhausted {
+                    url: url.to_string(),
+                    attempts: failures,
+                    reason,
+                });
+            }
+            tracing::debug!(url, resume_at = len, %reason, "retrying download");
+            let delay = self.retry_base_delay * (1 << failures.saturating_sub(1));
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    fn part_len(&self, part: &Path) -> u64 {
+        if !self.filesystem.exists(part) {
+            return 0;
+        }
+        self.filesystem
+            .file_metadata(part)
+            .map(|metadata| metadata.len)
+            .unwrap_or(0)
+    }
+
+    /// One request, resuming from the end of `part` when its validator is
+    /// known.
+    async fn attempt(
+        &self,
+        url: &str,
+        part: &Path,
+        progress: &FileProgress<'_>,
+    ) -> Result<Attempt, DownloadError> {
+        let mut offset = self.part_len(part);
+        let validator = self
+            .filesystem
+            .read_to_string(&validator_path(part))
+            .ok()
+            .filter(|validator| !validator.is_empty());
+        if offset > 0 && validator.is_none() {
+            // Without a validator the server cannot tell whether the bytes
+            // already on disk belong to the file it would send now.
+            self.truncate(part)?;
+            offset = 0;
+        }
+        let budget = self.budgets.for_url(url);
+        if let Some(budget) = &budget {
+            tokio::time::sleep(budget.acquire()).await;
+        }
+
+        // Ranges address the encoded body, so ask for it unencoded.
+        let mut request = self.client.get(url).header(ACCEPT_ENCODING, "identity");
+        if let Some(validator) = validator.as_deref().filter(|_| offset > 0) {
+            request = request
+                .header(RANGE, format!("bytes={offset}-"))
+                .header(IF_RANGE, validator);
+        }
+        let mut response = match tokio::time::timeout(self.idle_timeout, request.send()).await {
+            Ok(Ok(response)) => response,
+            Ok(Err(e)) => return Ok(Attempt::Interrupted(e.to_string())),
+            Err(_) => return Ok(Attempt::Interrupted("request timed out".to_string())),
+        };
+        let status = response.status();
+        if let Some(budget) = &budget {
+            budget.record_response(response.headers(), status);
+        }
+
+        let content_range = response
+            .headers()
+            .get(CONTENT_RANGE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let mut written = match status {
+            StatusCode::PARTIAL_CONTENT
+                if offset > 0
+                    && content_range
+                        .as_deref()
+                        .is_some_and(|range| range.starts_with(&format!("bytes {offset}-"))) =>
+            {
+                offset
+            }
+            StatusCode::RANGE_NOT_SATISFIABLE if offset > 0 => {
+                // The part file already holds the whole body, or more than it.
+                let total = content_range
+                    .as_deref()
+                    .and_then(|range| range.strip_prefix("bytes */"))
+                    .and_then(|total| total.parse::<u64>().ok());
+                if total == Some(offset) {
+                    progress.set_length(offset);
+                    progress.set_position(offset);
+                    return Ok(Attempt::Complete(offset));
+                }
+                self.truncate(part)?;
+                return Ok(Attempt::Interrupted(format!(
+                    "server rejected resuming at byte {offset}"
+                )));
+            }
+            status if status.is_success() => {
+                self.truncate(part)?;
+                self.remember_validator(part, response.headers())?;
+                0
+            }
+            status if status.is_client_error() => {
+                return Err(DownloadError::Status {
+                    url: url.to_string(),
+                    status,
+                });
+            }
+            status => return Ok(Attempt::Interrupted(format!("status {status}"))),
+        };
+
+        let length = response.content_length().map(|len| written + len);
+        progress.set_length(length.unwrap_or(0));
+        progress.set_position(written);
+
+        loop {
+            match tokio::time::timeout(self.idle_timeout, response.chunk()).await {
+                Ok(Ok(Some(chunk))) => {
+                    self.filesystem
+                        .append_bytes(part, &chunk)
+                        .map_err(|e| io_error(part, e))?;
+                    written += chunk.len() as u64;
+                    progress.set_position(written);
+                }
+                Ok(Ok(None)) => break,
+                Ok(Err(e)) => return Ok(Attempt::Interrupted(e.to_string())),
+                Err(_) => {
+                    return Ok(Attempt::Interrupted(format!(
+                        "stalled after {written} bytes"
+                    )));
+                }
+            }
+        }
+
+        match length {
+            Some(length) if written < length => Ok(Attempt::Interrupted(format!(
+                "connection closed after {written} of {length} bytes"
+            ))),
+            _ => Ok(Attempt::Complete(written)),
+        }
+    }
+
+    fn truncate(&self, part: &Path) -> Result<(), DownloadError> {
+        self.filesystem
+            .write_bytes(part, &[])
+            .map_err(|e| io_error(part, e))
+    }
+
+    /// Keep the validator of a response that starts `part` afresh, or drop
+    /// the old one when the response has none.
+    fn remember_validator(&self, part: &Path, headers: &HeaderMap) -> Result<(), DownloadError> {
+        match response_validator(headers) {
+            Some(validator) => {
+                let path = validator_path(part);
+                self.filesystem
+                    .write_file(&path, &validator)
+                    .map_err(|e| io_error(&path, e))
+            }
+            None => self.forget_validator(part),
+        }
+    }
+
+    fn forget_validator(&self, part: &Path) -> Result<(), DownloadError> {
+        let path = validator_path(part);
+        if !self.filesystem.exists(&path) {
+            return Ok(());
+        }
+        self.filesystem
+            .remove_file(&path)
+            .map_err(|e| io_error(&path, e))
+    }
+}
+
+fn io_error(path: &Path, error: impl std::fmt::Display) -> DownloadError {
+    DownloadError::Io {
+        path: path.to_path_buf(),
+        reason: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("download.test.rs");
+}
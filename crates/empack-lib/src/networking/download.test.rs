@@ -0,0 +1,380 @@
+use super::*;
+use crate::application::session_mocks::{MockCommandSession, MockFileSystemProvider, mock_root};
+use crate::display::DisplayProvider;
+use crate::networking::endpoints::Service;
+use crate::networking::rate_budget::RateBudget;
+use reqwest::header::HeaderMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+fn engine<'a>(
+    fs: &'a MockFileSystemProvider,
+    budgets: &'a HostBudgetRegistry,
+) -> DownloadEngine<'a> {
+    DownloadEngine::new(fs, Client::new(), budgets).with_retry_base_delay(Duration::ZERO)
+}
+
+fn progress() -> Box<dyn ProgressProvider> {
+    MockCommandSession::new().display_provider.progress()
+}
+
+#[tokio::test]
+async fn streams_to_dest_and_removes_the_part_file() {
+    let mut server = mockito::Server::new_async().await;
+    let _jar = server
+        .mock("GET", "/srv.jar")
+        .match_header("accept-encoding", "identity")
+        .with_body("server jar")
+        .create_async()
+        .await;
+    let fs = MockFileSystemProvider::new();
+    let budgets = HostBudgetRegistry::empty();
+    let dest = mock_root().join("cache").join("srv.jar");
+
+    let len = engine(&fs, &budgets)
+        .fetch(
+            &Download::new(format!("{}/srv.jar", server.url()), &dest),
+            &*progress(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(len, 10);
+    assert_eq!(fs.read_bytes(&dest).unwrap(), b"server jar");
+    assert!(!fs.exists(&part_path(&dest)));
+}
+
+#[tokio::test]
+async fn resumes_a_part_file_with_a_range_request() {
+    let mut server = mockito::Server::new_async().await;
+    let rest = server
+        .mock("GET", "/installer.jar")
+        .match_header("range", "bytes=6-")
+        .match_header("if-range", "\"v1\"")
+        .with_status(206)
+        .with_header("content-range", "bytes 6-10/11")
+        .with_body("world")
+        .expect(1)
+        .create_async()
+        .await;
+    let fs = MockFileSystemProvider::new();
+    let budgets = HostBudgetRegistry::empty();
+    let dest = mock_root().join("cache").join("installer.jar");
+    fs.write_bytes(&part_path(&dest), b"hello ").unwrap();
+    fs.write_file(&validator_path(&part_path(&dest)), "\"v1\"")
+        .unwrap();
+
+    let len = engine(&fs, &budgets)
+        .fetch(
+            &Download::new(format!("{}/installer.jar", server.url()), &dest),
+            &*progress(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(len, 11);
+    assert_eq!(fs.read_bytes(&dest).unwrap(), b"hello world");
+    assert!(!fs.exists(&validator_path(&part_path(&dest))));
+    rest.assert_async().await;
+}
+
+#[tokio::test]
+async fn discards_a_part_file_without_a_validator() {
+    let mut server = mockito::Server::new_async().await;
+    let full = server
+        .mock("GET", "/installer.jar")
+        .match_header("range", mockito::Matcher::Missing)
+        .with_body("hello world")
+        .expect(1)
+        .create_async()
+        .await;
+    let fs = MockFileSystemProvider::new();
+    let budgets = HostBudgetRegistry::empty();
+    let dest = mock_root().join("cache").join("installer.jar");
+    fs.write_bytes(&part_path(&dest), b"stale ").unwrap();
+
+    engine(&fs, &budgets)
+        .fetch(
+            &Download::new(format!("{}/installer.jar", server.url()), &dest),
+            &*progress(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(fs.read_bytes(&dest).unwrap(), b"hello world");
+    full.assert_async().await;
+}
+
+#[tokio::test]
+async fn restarts_when_the_file_changed_since_the_part_was_written() {
+    let mut server = mockito::Server::new_async().await;
+    // A failed If-Range makes the server send the new file whole
+    let _changed = server
+        .mock("GET", "/installer.jar")
+        .match_header("if-range", "Mon, 01 Jan 2024 00:00:00 GMT")
+        .with_header("etag", "W/\"weak\"")
+        .with_header("last-modified", "Tue, 02 Jan 2024 00:00:00 GMT")
+        .with_body("new file")
+        .create_async()
+        .await;
+    let fs = MockFileSystemProvider::new();
+    let budgets = HostBudgetRegistry::empty();
+    let dest = mock_root().join("cache").join("installer.jar");
+    let part = part_path(&dest);
+    fs.write_bytes(&part, b"old ").unwrap();
+    fs.write_file(&validator_path(&part), "Mon, 01 Jan 2024 00:00:00 GMT")
+        .unwrap();
+
+    engine(&fs, &budgets)
+        .fetch(
+            &Download::new(format!("{}/installer.jar", server.url()), &dest),
+            &*progress(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(fs.read_bytes(&dest).unwrap(), b"new file");
+    assert!(!fs.exists(&validator_path(&part)));
+}
+
+#[test]
+fn response_validator_prefers_a_strong_etag() {
+    let mut headers = HeaderMap::new();
+    assert_eq!(response_validator(&headers), None);
+
+    headers.insert("last-modified", "Tue, 02 Jan 2024 00:00:00 GMT".parse().unwrap());
+    headers.insert("etag", "W/\"weak\"".parse().unwrap());
+    assert_eq!(
+        response_validator(&headers).as_deref(),
+        Some("Tue, 02 Jan 2024 00:00:00 GMT")
+    );
+
+    headers.insert("etag", "\"strong\"".parse().unwrap());
+    assert_eq!(response_validator(&headers).as_deref(), Some("\"strong\""));
+}
+
+#[tokio::test]
+async fn restarts_when_the_server_ignores_the_range() {
+    let mut server = mockito::Server::new_async().await;
+    let _full = server
+        .mock("GET", "/installer.jar")
+        .with_body("fresh bytes")
+        .create_async()
+        .await;
+    let fs = MockFileSystemProvider::new();
+    let budgets = HostBudgetRegistry::empty();
+    let dest = mock_root().join("cache").join("installer.jar");
+    fs.write_bytes(&part_path(&dest), b"stale").unwrap();
+
+    engine(&fs, &budgets)
+        .fetch(
+            &Download::new(format!("{}/installer.jar", server.url()), &dest),
+            &*progress(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(fs.read_bytes(&dest).unwrap(), b"fresh bytes");
+}
+
+#[tokio::test]
+async fn completes_a_part_file_the_server_says_is_whole() {
+    let mut server = mockito::Server::new_async().await;
+    let _done = server
+        .mock("GET", "/installer.jar")
+        .match_header("range", "bytes=11-")
+        .with_status(416)
+        .with_header("content-range", "bytes */11")
+        .create_async()
+        .await;
+    let fs = MockFileSystemProvider::new();
+    let budgets = HostBudgetRegistry::empty();
+    let dest = mock_root().join("cache").join("installer.jar");
+    fs.write_bytes(&part_path(&dest), b"hello world").unwrap();
+    fs.write_file(&validator_path(&part_path(&dest)), "\"v1\"")
+        .unwrap();
+
+    engine(&fs, &budgets)
+        .fetch(
+            &Download::new(format!("{}/installer.jar", server.url()), &dest),
+            &*progress(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(fs.read_bytes(&dest).unwrap(), b"hello world");
+}
+
+#[tokio::test]
+async fn client_errors_fail_without_retrying() {
+    let mut server = mockito::Server::new_async().await;
+    let missing = server
+        .mock("GET", "/missing.jar")
+        .with_status(404)
+        .expect(1)
+        .create_async()
+        .await;
+    let fs = MockFileSystemProvider::new();
+    let budgets = HostBudgetRegistry::empty();
+    let dest = mock_root().join("cache").join("missing.jar");
+
+    let error = engine(&fs, &budgets)
+        .fetch(
+            &Download::new(format!("{}/missing.jar", server.url()), &dest),
+            &*progress(),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        DownloadError::Status {
+            status: StatusCode::NOT_FOUND,
+            ..
+        }
+    ));
+    assert!(!fs.exists(&dest));
+    missing.assert_async().await;
+}
+
+#[tokio::test]
+async fn server_errors_are_retried_up_to_the_attempt_limit() {
+    let mut server = mockito::Server::new_async().await;
+    let flaky = server
+        .mock("GET", "/flaky.jar")
+        .with_status(503)
+        .expect(MAX_ATTEMPTS as usize)
+        .create_async()
+        .await;
+    let fs = MockFileSystemProvider::new();
+    let budgets = HostBudgetRegistry::empty();
+    let dest = mock_root().join("cache").join("flaky.jar");
+
+    let error = engine(&fs, &budgets)
+        .fetch(
+            &Download::new(format!("{}/flaky.jar", server.url()), &dest),
+            &*progress(),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        DownloadError::Exhausted {
+            attempts: MAX_ATTEMPTS,
+            ..
+        }
+    ));
+    flaky.assert_async().await;
+}
+
+#[tokio::test]
+async fn falls_back_to_the_next_base_once_retries_are_exhausted() {
+    let mut broken = mockito::Server::new_async().await;
+    let mut mirror = mockito::Server::new_async().await;
+    let down = broken
+        .mock("GET", "/maven/srv.jar")
+        .with_status(502)
+        .expect(MAX_ATTEMPTS as usize)
+        .create_async()
+        .await;
+    let _jar = mirror
+        .mock("GET", "/maven/srv.jar")
+        .with_body("server jar")
+        .create_async()
+        .await;
+    let endpoints = Endpoints::default().with_bases(
+        Service::ForgeMaven,
+        [format!("{}/maven", broken.url()), format!("{}/maven", mirror.url())],
+    );
+    let fs = MockFileSystemProvider::new();
+    let budgets = HostBudgetRegistry::empty();
+    let dest = mock_root().join("cache").join("srv.jar");
+
+    engine(&fs, &budgets)
+        .with_endpoints(&endpoints)
+        .fetch(
+            &Download::new(format!("{}/srv.jar", Service::ForgeMaven.upstream()), &dest),
+            &*progress(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(fs.read_bytes(&dest).unwrap(), b"server jar");
+    down.assert_async().await;
+}
+
+/// Counts how often the engine consults and feeds the budget.
+#[derive(Default)]
+struct CountingBudget {
+    acquired: AtomicU32,
+    recorded: AtomicU32,
+}
+
+impl RateBudget for CountingBudget {
+    fn record_response(&self, _headers: &HeaderMap, _status: StatusCode) {
+        self.recorded.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn acquire(&self) -> Duration {
+        self.acquired.fetch_add(1, Ordering::Relaxed);
+        Duration::ZERO
+    }
+
+    fn is_exhausted(&self) -> bool {
+        false
+    }
+}
+
+#[tokio::test]
+async fn fetch_all_keeps_order_isolates_failures_and_uses_the_host_budget() {
+    let mut server = mockito::Server::new_async().await;
+    for (path, body) in [("/a.jar", "aaa"), ("/b.jar", "bb"), ("/c.jar", "c")] {
+        server
+            .mock("GET", path)
+            .with_body(body)
+            .create_async()
+            .await;
+    }
+    server
+        .mock("GET", "/gone.jar")
+        .with_status(410)
+        .create_async()
+        .await;
+    let budget = Arc::new(CountingBudget::default());
+    let budgets = HostBudgetRegistry::with_budgets(HashMap::from([(
+        "127.0.0.1".to_string(),
+        budget.clone() as Arc<dyn RateBudget>,
+    )]));
+    let fs = MockFileSystemProvider::new();
+    let dir = mock_root().join("cache");
+    let downloads = ["a.jar", "gone.jar", "b.jar", "c.jar"]
+        .map(|name| Download::new(format!("{}/{name}", server.url()), dir.join(name)));
+
+    let results = engine(&fs, &budgets)
+        .with_jobs(2)
+        .fetch_all(&downloads, &*progress())
+        .await;
+
+    let sizes = results
+        .iter()
+        .map(|result| result.as_ref().ok().copied())
+        .collect::<Vec<_>>();
+    assert_eq!(sizes, [Some(3), None, Some(2), Some(1)]);
+    assert_eq!(fs.read_bytes(&dir.join("c.jar")).unwrap(), b"c");
+    assert_eq!(budget.acquired.load(Ordering::Relaxed), 4);
+    assert_eq!(budget.recorded.load(Ordering::Relaxed), 4);
+}
+
+#[test]
+fn retries_back_off_unless_the_caller_overrides_the_delay() {
+    let fs = MockFileSystemProvider::new();
+    let budgets = HostBudgetRegistry::empty();
+
+    assert_eq!(
+        DownloadEngine::new(&fs, Client::new(), &budgets).retry_base_delay,
+        RETRY_BASE_DELAY
+    );
+    assert_eq!(engine(&fs, &budgets).retry_base_delay, Duration::ZERO);
+}
@@ -0,0 +1,200 @@
+//! Base URLs of the remote services empack talks to
+//!
+//! Every service has an ordered list of base URLs, its public upstream unless
+//! configured otherwise. A request goes to the first base; when that one
+//! gives no answer, a 5xx, or a 429, the same path is tried on the next.
+//!
+//! URLs are built against upstream or configured bases alike, so links that
+//! arrive from elsewhere, such as Modrinth CDN URLs in a modpack manifest or
+//! download URLs in a Mojang version manifest, are moved onto the configured
+//! bases by [`Endpoints::rebase`]. A mirror therefore has to serve the same
+//! paths as the service it stands in for.
+
+use crate::primitives::ProjectPlatform;
+use reqwest::{Response, StatusCode};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::future::Future;
+
+/// A remote service with a configurable base URL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Service {
+    ModrinthApi,
+    CurseForgeApi,
+    ModrinthCdn,
+    ForgeMaven,
+    NeoForgeMaven,
+    FabricMaven,
+    QuiltMaven,
+    MojangMeta,
+}
+
+impl Service {
+    pub const ALL: [Self; 8] = [
+        Self::ModrinthApi,
+        Self::CurseForgeApi,
+        Self::ModrinthCdn,
+        Self::ForgeMaven,
+        Self::NeoForgeMaven,
+        Self::FabricMaven,
+        Self::QuiltMaven,
+        Self::MojangMeta,
+    ];
+
+    /// The public base URL of the service.
+    pub fn upstream(self) -> &'static str {
+        match self {
+            Self::ModrinthApi => ProjectPlatform::Modrinth.api_base_url(),
+            Self::CurseForgeApi => ProjectPlatform::CurseForge.api_base_url(),
+            Self::ModrinthCdn => "https://cdn.modrinth.com",
+            Self::ForgeMaven => "https://maven.minecraftforge.net",
+            Self::NeoForgeMaven => "https://maven.neoforged.net",
+            Self::FabricMaven => "https://maven.fabricmc.net",
+            Self::QuiltMaven => "https://maven.quiltmc.org",
+            Self::MojangMeta => "https://piston-meta.mojang.com",
+        }
+    }
+}
+
+/// Base URLs per service, in fallback order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Endpoints {
+    overrides: BTreeMap<Service, Vec<String>>,
+}
+
+static UPSTREAM: Endpoints = Endpoints {
+    overrides: BTreeMap::new(),
+};
+
+impl Endpoints {
+    /// Every service at its upstream.
+    pub fn upstream() -> &'static Self {
+        &UPSTREAM
+    }
+
+    /// Use `bases`, in fallback order, for `service`.
+    ///
+    /// An empty list restores the upstream.
+    pub fn with_bases<I, S>(mut self, service: Service, bases: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let bases: Vec<String> = bases
+            .into_iter()
+            .map(|base| base.into().trim_end_matches('/').to_string())
+            .collect();
+        if bases.is_empty() {
+            self.overrides.remove(&service);
+        } else {
+            self.overrides.insert(service, bases);
+        }
+        self
+    }
+
+    /// Base URLs of `service`, in fallback order.
+    pub fn bases(&self, service: Service) -> Vec<&str> {
+        match self.overrides.get(&service) {
+            Some(bases) => bases.iter().map(String::as_str).collect(),
+            None => vec![service.upstream()],
+        }
+    }
+
+    /// The base URL of `service` that is tried first.
+    pub fn base(&self, service: Service) -> &str {
+        self.overrides
+            .get(&service)
+            .and_then(|bases| bases.first())
+            .map_or(service.upstream(), String::as_str)
+    }
+
+    /// `path` under every base of `service`, in fallback order.
+    pub fn urls(&self, service: Service, path: &str) -> Vec<String> {
+        self.bases(service)
+            .into_iter()
+            .map(|base| format!("{}/{}", base, path.trim_start_matches('/')))
+            .collect()
+    }
+
+    /// Candidate URLs for `url`, in fallback order.
+    ///
+    /// A URL under the upstream or a configured base of a service is moved
+    /// onto each of that service's bases, matching the longest base. Any
+    /// other URL is returned unchanged.
+    pub fn rebase(&self, url: &str) -> Vec<String> {
+        let matched = Service::ALL
+            .into_iter()
+            .flat_map(|service| {
+                std::iter::once(service.upstream())
+                    .chain(self.bases(service))
+                    .filter_map(move |base| strip_base(url, base).map(|rest| (service, base, rest)))
+            })
+            .max_by_key(|(_, base, _)| base.len());
+
+        match matched {
+            Some((service, _, rest)) => self
+                .bases(service)
+                .into_iter()
+                .map(|base| format!("{base}{rest}"))
+                .collect(),
+            None => vec![url.to_string()],
+        }
+    }
+
+    /// Send a request for `url`, falling back through its candidates.
+    pub async fn send<F, Fut, E>(&self, url: &str, send: F) -> Result<Response, E>
+    where
+        F: FnMut(String) -> Fut,
+        Fut: Future<Output = Result<Response, E>>,
+        E: Display,
+    {
+        send_with_fallback(self.rebase(url), send).await
+    }
+}
+
+/// The rest of `url` after `base`, if `url` lies under it.
+fn strip_base<'u>(url: &'u str, base: &str) -> Option<&'u str> {
+    url.strip_prefix(base)
+        .filter(|rest| rest.is_empty() || rest.starts_with(['/', '?']))
+}
+
+/// Whether a response means the endpoint, not the request, failed.
+pub fn is_endpoint_failure(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Send the request `send` builds for each of `urls` in turn.
+///
+/// Returns the first answer that is not an endpoint failure, or the last
+/// attempt's result when every URL fails. `urls` must not be empty.
+pub async fn send_with_fallback<F, Fut, E>(urls: Vec<String>, mut send: F) -> Result<Response, E>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<Response, E>>,
+    E: Display,
+{
+    let mut urls = urls.into_iter().peekable();
+    loop {
+        let url = urls
+            .next()
+            .expect("send_with_fallback needs at least one URL");
+        let result = send(url.clone()).await;
+        if urls.peek().is_none() {
+            return result;
+        }
+        match &result {
+            Ok(response) if !is_endpoint_failure(response.status()) => return result,
+            Ok(response) => {
+                tracing::warn!(url, status = %response.status(), "endpoint failed; trying the next one");
+            }
+            Err(error) => {
+                tracing::warn!(url, %error, "endpoint failed; trying the next one");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("endpoints.test.rs");
+}
@@ -0,0 +1,105 @@
+use super::*;
+
+fn mirrored() -> Endpoints {
+    Endpoints::default()
+        .with_bases(
+            Service::ModrinthApi,
+            ["https://mirror.corp/modrinth/", "https://api.modrinth.com"],
+        )
+        .with_bases(Service::ModrinthCdn, ["https://mirror.corp/modrinth/cdn"])
+}
+
+#[test]
+fn upstream_endpoints_leave_urls_alone() {
+    let endpoints = Endpoints::upstream();
+
+    assert_eq!(endpoints.base(Service::MojangMeta), "https://piston-meta.mojang.com");
+    assert_eq!(
+        endpoints.rebase("https://api.modrinth.com/v2/project/sodium"),
+        ["https://api.modrinth.com/v2/project/sodium"]
+    );
+    assert_eq!(
+        endpoints.rebase("https://example.com/pack.zip"),
+        ["https://example.com/pack.zip"]
+    );
+}
+
+#[test]
+fn rebase_moves_upstream_and_configured_urls_onto_every_base() {
+    let endpoints = mirrored();
+
+    assert_eq!(
+        endpoints.urls(Service::ModrinthApi, "/v2/search?query=jei"),
+        [
+            "https://mirror.corp/modrinth/v2/search?query=jei",
+            "https://api.modrinth.com/v2/search?query=jei",
+        ]
+    );
+    assert_eq!(
+        endpoints.rebase("https://mirror.corp/modrinth/v2/project/sodium"),
+        endpoints.rebase("https://api.modrinth.com/v2/project/sodium"),
+    );
+    assert_eq!(
+        endpoints.rebase("https://cdn.modrinth.com/data/AANobbMI/versions/1/sodium.jar"),
+        ["https://mirror.corp/modrinth/cdn/data/AANobbMI/versions/1/sodium.jar"]
+    );
+    // The longest base wins, so CDN paths under the API mirror stay CDN paths
+    assert_eq!(
+        endpoints.rebase("https://mirror.corp/modrinth/cdn/data/x.jar"),
+        ["https://mirror.corp/modrinth/cdn/data/x.jar"]
+    );
+    // Only whole path segments match
+    assert_eq!(
+        endpoints.rebase("https://api.modrinth.com.evil/v2"),
+        ["https://api.modrinth.com.evil/v2"]
+    );
+}
+
+#[test]
+fn empty_bases_restore_the_upstream() {
+    let endpoints = mirrored().with_bases(Service::ModrinthApi, Vec::<String>::new());
+
+    assert_eq!(endpoints.bases(Service::ModrinthApi), ["https://api.modrinth.com"]);
+}
+
+#[tokio::test]
+async fn send_falls_back_on_endpoint_failures_only() {
+    let mut broken = mockito::Server::new_async().await;
+    let mut healthy = mockito::Server::new_async().await;
+    let _unavailable = broken
+        .mock("GET", "/v2/project/sodium")
+        .with_status(503)
+        .create_async()
+        .await;
+    let _missing = broken
+        .mock("GET", "/v2/project/nope")
+        .with_status(404)
+        .create_async()
+        .await;
+    let found = healthy
+        .mock("GET", "/v2/project/sodium")
+        .with_body("{}")
+        .expect(1)
+        .create_async()
+        .await;
+    let endpoints = Endpoints::default()
+        .with_bases(Service::ModrinthApi, [broken.url(), healthy.url()]);
+    let client = reqwest::Client::new();
+
+    let response = endpoints
+        .send(&format!("{}/v2/project/sodium", broken.url()), |url| {
+            client.get(url).send()
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    found.assert_async().await;
+
+    let response = endpoints
+        .send(&format!("{}/v2/project/nope", broken.url()), |url| {
+            client.get(url).send()
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
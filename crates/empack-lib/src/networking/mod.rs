@@ -7,6 +7,8 @@ use tracing::trace;
 use crate::platform::SystemResources;
 
 pub mod cache;
+pub mod download;
+pub mod endpoints;
 pub mod rate_budget;
 pub mod rate_limit;
 
@@ -48,6 +50,9 @@ pub enum NetworkingError {
 
     #[error("Rate limit error: {message}")]
     RateLimitError { message: String },
+
+    #[error("Offline mode: {resource} is not cached")]
+    Offline { resource: String },
 }
 
 /// Resource-aware networking configuration
@@ -36,6 +36,10 @@ pub fn jar_cache_dir() -> Result<PathBuf> {
     Ok(cache_root()?.join("jars"))
 }
 
+pub fn store_dir() -> Result<PathBuf> {
+    Ok(cache_root()?.join("store"))
+}
+
 pub fn restricted_builds_cache_dir() -> Result<PathBuf> {
     Ok(cache_root()?.join("restricted-builds"))
 }
@@ -62,6 +62,10 @@ pub enum BuildTarget {
     ClientFull,
     /// Build a non-redistributable server (embeds content)
     ServerFull,
+    /// Build a CurseForge-compatible modpack zip ('manifest.json' + overrides)
+    Curseforge,
+    /// Build an importable Prism Launcher instance zip
+    Prism,
 }
 
 impl fmt::Display for BuildTarget {
@@ -72,6 +76,8 @@ impl fmt::Display for BuildTarget {
             BuildTarget::Server => write!(f, "server"),
             BuildTarget::ClientFull => write!(f, "client-full"),
             BuildTarget::ServerFull => write!(f, "server-full"),
+            BuildTarget::Curseforge => write!(f, "curseforge"),
+            BuildTarget::Prism => write!(f, "prism"),
         }
     }
 }
@@ -86,6 +92,8 @@ impl std::str::FromStr for BuildTarget {
             "server" => Ok(BuildTarget::Server),
             "client-full" => Ok(BuildTarget::ClientFull),
             "server-full" => Ok(BuildTarget::ServerFull),
+            "curseforge" => Ok(BuildTarget::Curseforge),
+            "prism" => Ok(BuildTarget::Prism),
             _ => Err(format!("Invalid build target: {}", s)),
         }
     }
@@ -239,13 +247,15 @@ impl BuildTarget {
             BuildTarget::Server => 12,
             BuildTarget::ClientFull => 13,
             BuildTarget::ServerFull => 14,
+            BuildTarget::Curseforge => 15,
+            BuildTarget::Prism => 16,
         }
     }
 
     /// Expand the narrow internal "core distribution" helper target set.
     ///
-    /// This is not the user-facing CLI `all` expansion, which resolves to all
-    /// five build targets in `application/commands.rs`.
+    /// This is not the user-facing CLI `all` expansion, which resolves to every
+    /// build target in `application/commands.rs`.
     pub fn expand_all() -> Vec<BuildTarget> {
         vec![
             BuildTarget::Mrpack,
@@ -33,6 +33,16 @@ pub enum LogOutput {
     Stdout,
 }
 
+/// Command output modes for user-facing results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Human-readable status text on stdout
+    Text,
+    /// One JSON result document on stdout; status text moves to stderr
+    Json,
+}
+
 /// Log levels for structured logging
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum LogLevel {
@@ -225,6 +235,7 @@ impl ValueEnum for LogFormat {
 impl_fromstr_for_value_enum!(LogLevel, "invalid log level");
 impl_fromstr_for_value_enum!(LogFormat, "invalid log format");
 impl_fromstr_for_value_enum!(LogOutput, "invalid log output stream");
+impl_fromstr_for_value_enum!(OutputFormat, "invalid output format");
 
 #[cfg(test)]
 mod empack_tests {
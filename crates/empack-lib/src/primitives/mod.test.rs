@@ -70,6 +70,7 @@ macro_rules! test_fromstr_aliases {
 test_enum_completeness!(LogLevel, test_log_level_completeness);
 test_enum_completeness!(LogFormat, test_log_format_completeness);
 test_enum_completeness!(LogOutput, test_log_output_completeness);
+test_enum_completeness!(OutputFormat, test_output_format_completeness);
 test_enum_completeness!(
     TerminalCapsDetectIntent,
     test_tty_caps_detect_intent_completeness
@@ -115,6 +116,12 @@ test_fromstr_aliases!(
     [("stderr", LogOutput::Stderr), ("stdout", LogOutput::Stdout),]
 );
 
+test_fromstr_aliases!(
+    OutputFormat,
+    test_output_format_aliases,
+    [("text", OutputFormat::Text), ("json", OutputFormat::Json),]
+);
+
 test_fromstr_aliases!(
     TerminalCapsDetectIntent,
     test_tty_caps_detect_intent_aliases,
@@ -860,7 +860,7 @@ pub fn write_local_mrpack(
     loader_id: &str,
     loader_version: &str,
 ) -> anyhow::Result<()> {
-    use empack_lib::empack::archive::{ArchiveFormat, create_archive};
+    use empack_lib::empack::archive::{ArchiveFormat, MIN_SOURCE_DATE_EPOCH, create_archive};
 
     let source_dir = tempfile::TempDir::new()?;
     let mut dependencies = serde_json::Map::new();
@@ -892,7 +892,12 @@ pub fn write_local_mrpack(
         std::fs::create_dir_all(parent)?;
     }
 
-    create_archive(source_dir.path(), archive_path, ArchiveFormat::Zip)
-        .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    create_archive(
+        source_dir.path(),
+        archive_path,
+        ArchiveFormat::Zip,
+        MIN_SOURCE_DATE_EPOCH,
+    )
+    .map_err(|e| anyhow::anyhow!(e.to_string()))?;
     Ok(())
 }
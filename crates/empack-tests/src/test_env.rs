@@ -10,7 +10,10 @@ use empack_lib::application::session_mocks::{
     MockCommandSession, MockConfigProvider, MockFileSystemProvider, MockInteractiveProvider,
     MockNetworkProvider as LibMockNetworkProvider, MockProcessProvider, mock_root,
 };
-use empack_lib::empack::search::{ProjectInfo, ProjectResolverTrait, SearchError};
+use empack_lib::empack::content::{SideEnv, SideRequirement};
+use empack_lib::empack::search::{
+    CandidateFilters, ProjectInfo, ProjectResolverTrait, SearchError,
+};
 use empack_lib::primitives::ProjectPlatform;
 use empack_lib::terminal::TerminalCapabilities;
 use reqwest::Client;
@@ -295,6 +298,38 @@ impl ProjectResolverTrait for MockProjectResolver {
 
         Box::pin(async move { result })
     }
+
+    fn search_candidates(
+        &self,
+        title: &str,
+        _project_type: &str,
+        _filters: &CandidateFilters,
+        _min_confidence: u8,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<ProjectInfo>, SearchError>> + Send + '_>> {
+        let result = if let Some(project_info) = self.search_results.get(title) {
+            Ok(vec![project_info.clone()])
+        } else {
+            Err(SearchError::NoResults {
+                query: title.to_string(),
+            })
+        };
+
+        Box::pin(async move { result })
+    }
+
+    fn fetch_side_env(
+        &self,
+        _platform: ProjectPlatform,
+        _project_id: &str,
+        _file_id: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<SideEnv, SearchError>> + Send + '_>> {
+        Box::pin(async move {
+            Ok(SideEnv {
+                client: SideRequirement::Unknown,
+                server: SideRequirement::Unknown,
+            })
+        })
+    }
 }
 
 #[cfg(test)]
@@ -262,6 +262,50 @@ fn e2e_missing_tracked_local_dependency_validation_exits_two() {
     );
 }
 
+#[test]
+fn e2e_locked_sync_with_stale_lock_exits_two() {
+    let project = TestProject::workflow_fixture("exit-stale-lock", "fabric", "1.21.1");
+
+    std::fs::write(
+        project.dir().join("empack.yml"),
+        r#"empack:
+  dependencies:
+    sodium:
+      status: resolved
+      title: Sodium
+      platform: modrinth
+      project_id: AANobbMI
+      type: mod
+  minecraft_version: "1.21.1"
+  loader: fabric
+  name: "exit-stale-lock"
+  author: "Workflow Test"
+  version: "1.0.0"
+"#,
+    )
+    .expect("write empack.yml");
+    std::fs::write(project.dir().join("empack.lock"), "version = 1\n")
+        .expect("write empty empack.lock");
+
+    let output = cargo_empack_cmd(project.dir())
+        .args(["sync", "--locked"])
+        .output()
+        .expect("spawn locked sync command");
+
+    assert_eq!(
+        output.status.code(),
+        Some(EmpackExitCode::Usage.as_i32()),
+        "unexpected output:\n{}",
+        combined_output(&output)
+    );
+
+    let combined = combined_output(&output);
+    assert!(
+        combined.contains("sodium is not in empack.lock"),
+        "expected lock disagreement in output:\n{combined}"
+    );
+}
+
 #[test]
 fn e2e_tracked_local_parent_dir_validation_exits_two() {
     let project =
@@ -0,0 +1,83 @@
+use empack_tests::e2e::TestProject;
+
+fn write_list_fixture(project: &TestProject) {
+    std::fs::write(
+        project.dir().join("empack.yml"),
+        r#"empack:
+  dependencies:
+    sodium:
+      status: resolved
+      title: Sodium
+      platform: modrinth
+      project_id: AANobbMI
+      type: mod
+    jei:
+      status: resolved
+      title: Just Enough Items
+      platform: curseforge
+      project_id: "238222"
+      type: mod
+      version: "5101366"
+  minecraft_version: "1.21.1"
+  loader: fabric
+  name: "list-pack"
+  author: "Workflow Test"
+  version: "1.0.0"
+"#,
+    )
+    .expect("write empack.yml");
+    std::fs::create_dir_all(project.dir().join("pack").join("mods")).expect("create mods dir");
+    std::fs::write(
+        project
+            .dir()
+            .join("pack")
+            .join("mods")
+            .join("sodium.pw.toml"),
+        "name = \"Sodium\"\nfilename = \"sodium.jar\"\n",
+    )
+    .expect("write sodium.pw.toml");
+}
+
+#[test]
+fn e2e_list_reports_dependencies_and_metafiles() {
+    let project = TestProject::workflow_fixture("list-pack", "fabric", "1.21.1");
+    write_list_fixture(&project);
+
+    let output = project.cmd().arg("list").output().expect("spawn failed");
+    assert!(
+        output.status.success(),
+        "empack list failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Dependencies (2)"), "stdout: {stdout}");
+    assert!(stdout.contains("AANobbMI"), "stdout: {stdout}");
+    assert!(stdout.contains("238222"), "stdout: {stdout}");
+    assert!(
+        stdout.contains("1 dependency without a .pw.toml"),
+        "stdout: {stdout}"
+    );
+}
+
+#[test]
+fn e2e_list_filters_by_platform() {
+    let project = TestProject::workflow_fixture("list-pack", "fabric", "1.21.1");
+    write_list_fixture(&project);
+
+    let output = project
+        .cmd()
+        .args(["ls", "--platform", "curseforge"])
+        .output()
+        .expect("spawn failed");
+    assert!(
+        output.status.success(),
+        "empack ls failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Dependencies (1)"), "stdout: {stdout}");
+    assert!(stdout.contains("238222"), "stdout: {stdout}");
+    assert!(!stdout.contains("AANobbMI"), "stdout: {stdout}");
+}
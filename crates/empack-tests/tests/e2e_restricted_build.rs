@@ -104,7 +104,15 @@ fn e2e_build_all_restricted_at_mrpack_stops_before_later_targets() {
 
     let pending = assert_pending_restricted_build(
         project.dir(),
-        &["mrpack", "client", "server", "client-full", "server-full"],
+        &[
+            "mrpack",
+            "client",
+            "server",
+            "client-full",
+            "server-full",
+            "curseforge",
+            "prism",
+        ],
         &["BeeFix-1.20-1.0.7.jar"],
     );
     assert_eq!(
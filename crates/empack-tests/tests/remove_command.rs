@@ -200,6 +200,23 @@ async fn e2e_remove_dependencies_removes_orphans() -> Result<()> {
             workdir.join("empack.yml"),
             remove_project_config().to_string(),
         )
+        .with_file(
+            mods_dir.join("sodium.pw.toml"),
+            r#"
+name = "Sodium"
+filename = "sodium.jar"
+
+[update]
+[update.modrinth]
+mod-id = "AANobbMI"
+version = "v1.0.0"
+
+[deps]
+leaf-a = "*"
+leaf-b = "*"
+"#
+            .to_string(),
+        )
         .with_file(
             mods_dir.join("leaf-a.pw.toml"),
             r#"
@@ -210,9 +227,6 @@ filename = "leaf-a.jar"
 [update.modrinth]
 mod-id = "leaf-a"
 version = "v1.0.0"
-
-[deps]
-sodium = "*"
 "#
             .to_string(),
         )
@@ -226,9 +240,6 @@ filename = "leaf-b.jar"
 [update.modrinth]
 mod-id = "leaf-b"
 version = "v1.0.0"
-
-[deps]
-sodium = "*"
 "#
             .to_string(),
         )
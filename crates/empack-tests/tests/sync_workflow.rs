@@ -47,7 +47,8 @@ async fn test_sync_workflow_full() -> Result<()> {
 
     Display::init_or_get(TerminalCapabilities::minimal());
 
-    let sync_result = execute_command_with_session(Commands::Sync {}, &session).await;
+    let sync_result =
+        execute_command_with_session(Commands::Sync { locked: false }, &session).await;
     assert!(sync_result.is_ok(), "sync command failed: {sync_result:?}");
 
     let packwiz_calls = session
@@ -97,7 +98,8 @@ async fn test_sync_dry_run_no_modifications() -> Result<()> {
 
     Display::init_or_get(TerminalCapabilities::minimal());
 
-    let sync_result = execute_command_with_session(Commands::Sync {}, &session).await;
+    let sync_result =
+        execute_command_with_session(Commands::Sync { locked: false }, &session).await;
     assert!(
         sync_result.is_ok(),
         "dry-run sync command failed: {sync_result:?}"
@@ -144,7 +146,8 @@ async fn test_sync_normalized_installed_names_noop() -> Result<()> {
 
     Display::init_or_get(TerminalCapabilities::minimal());
 
-    let sync_result = execute_command_with_session(Commands::Sync {}, &session).await;
+    let sync_result =
+        execute_command_with_session(Commands::Sync { locked: false }, &session).await;
     assert!(
         sync_result.is_ok(),
         "slug-matching installed names should produce a no-op sync: {sync_result:?}"